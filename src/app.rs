@@ -1,18 +1,143 @@
-use crate::core::hotkey::hotkey_from_config;
-use crate::core::window::is_window_valid;
+use crate::automation::ocr_models::test_load;
+use crate::core::click_guard;
+use crate::core::clock::to_local;
+use crate::core::file_logger::file_logger;
+use crate::core::frame_recorder::FrameRecorder;
+use crate::core::hotkey::{HotkeyRegistrar, MouseButtonWatcher};
+use crate::core::launch_args::{auto_start_failed, mark_auto_start_failed, LaunchArgs};
+use crate::core::overlay_window::OverlayWindow;
+use crate::core::remote_control::{RemoteCommand, RemoteControl, ToolState};
+use crate::core::scheduler::{describe_next, next_due, Scheduler};
+use crate::core::session_summary::SessionSummary;
+use crate::core::sounds::{play_sound, sounds};
+use crate::core::window::{
+    find_game_window, get_client_rect_in_screen_coords, get_client_size, get_cursor_pos,
+    get_window_title, is_game_or_helper_focused, is_window_valid, primary_monitor_size,
+    set_click_through,
+};
+use crate::core::worker::{run_journal, JournalEntry, LogEntry, LogLevel};
 use crate::settings::{
-    AppSettings, HotkeyConfig, HotkeyModifiers, NamedMacro, MAX_CUSTOM_MACROS,
+    AppSettings, HotkeyActionId, HotkeyKey, MacroId, NamedMacro, OverlayAnchor, OverlayPlacement,
+    ScheduleAction, SettingsSaver, StartMode, MAX_CUSTOM_MACROS,
 };
+use crate::tools::anti_afk::AntiAfkTool;
+use crate::tools::auto_relog::AutoRelogTool;
 use crate::tools::collection_filler::CollectionFillerTool;
 use crate::tools::custom_macro::CustomMacroTool;
 use crate::tools::image_clicker::ImageClickerTool;
-use crate::tools::r#trait::Tool;
+use crate::tools::loot_clicker::LootClickerTool;
+use crate::tools::pixel_trigger::PixelTriggerTool;
+use crate::tools::r#trait::{RelogEvent, StopReason, Tool, ToolsChange};
+use crate::tools::rebuffer::RebufferTool;
+use crate::tools::rotation::RotationTool;
+use crate::tools::stat_logger::StatLoggerTool;
+use crate::tools::undo::UndoStack;
+use crate::ui::app_header::{HeaderContext, ProfileAction};
+use crate::ui::log_panel::LogReportContext;
+use crate::ui::macro_picker::{
+    render_macro_picker, MacroEntry, MacroPickerAction, MACRO_TAB_LIMIT,
+};
+use crate::ui::overlay::{
+    overlay_size, render_overlay_toolbar, OverlayAction, OverlayButton, OverlayStatus,
+};
+use crate::ui::running_panel::{render_running_panel, RunningPanelAction, RunningTool};
+use crate::ui::statistics::{render_statistics, StatisticsAction};
 use eframe::egui;
-use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
-use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, HotKeyState};
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
 use std::collections::HashSet;
 use windows::Win32::Foundation::HWND;
 
+// Log source of app-level notices (settings, profiles, undo)
+const APP_LOG_SOURCE: &str = "App";
+
+/// A built-in tool: its tab name, a fresh instance and its "Show in overlay" flag
+struct BuiltinTool {
+    name: &'static str,
+    build: fn() -> Box<dyn Tool>,
+    show_in_overlay: fn(&AppSettings) -> bool,
+}
+
+// The built-in tools, in `tools` order
+const BUILTIN_TOOLS: &[BuiltinTool] = &[
+    BuiltinTool {
+        name: "Image Clicker",
+        build: || Box::new(ImageClickerTool::default()),
+        show_in_overlay: |settings| settings.accept_item.show_in_overlay,
+    },
+    BuiltinTool {
+        name: "Collection Filler",
+        build: || Box::new(CollectionFillerTool::default()),
+        show_in_overlay: |settings| settings.collection_filler.show_in_overlay,
+    },
+    BuiltinTool {
+        name: "Anti-AFK",
+        build: || Box::new(AntiAfkTool::default()),
+        show_in_overlay: |settings| settings.anti_afk.show_in_overlay,
+    },
+    BuiltinTool {
+        name: "Pixel Trigger",
+        build: || Box::new(PixelTriggerTool::default()),
+        show_in_overlay: |settings| settings.pixel_trigger.show_in_overlay,
+    },
+    BuiltinTool {
+        name: "Rebuffer",
+        build: || Box::new(RebufferTool::default()),
+        show_in_overlay: |settings| settings.rebuffer.show_in_overlay,
+    },
+    BuiltinTool {
+        name: "Skill Rotation",
+        build: || Box::new(RotationTool::default()),
+        show_in_overlay: |settings| settings.rotation.show_in_overlay,
+    },
+    BuiltinTool {
+        name: "Auto-Relog",
+        build: || Box::new(AutoRelogTool::default()),
+        show_in_overlay: |settings| settings.auto_relog.show_in_overlay,
+    },
+    BuiltinTool {
+        name: "Loot Clicker",
+        build: || Box::new(LootClickerTool::default()),
+        show_in_overlay: |settings| settings.loot_clicker.show_in_overlay,
+    },
+    BuiltinTool {
+        name: "Stat Logger",
+        build: || Box::new(StatLoggerTool::default()),
+        show_in_overlay: |settings| settings.stat_logger.show_in_overlay,
+    },
+];
+
+/// Position of the built-in tool called `name` in `tools` (a typo fails the build)
+const fn builtin_tool(name: &str) -> usize {
+    let mut idx = 0;
+    while idx < BUILTIN_TOOLS.len() {
+        let (a, b) = (BUILTIN_TOOLS[idx].name.as_bytes(), name.as_bytes());
+        if a.len() == b.len() {
+            let mut i = 0;
+            while i < a.len() && a[i] == b[i] {
+                i += 1;
+            }
+            if i == a.len() {
+                return idx;
+            }
+        }
+        idx += 1;
+    }
+    panic!("unknown built-in tool");
+}
+
+const IMAGE_CLICKER_TOOL: usize = builtin_tool("Image Clicker");
+const COLLECTION_FILLER_TOOL: usize = builtin_tool("Collection Filler");
+const AUTO_RELOG_TOOL: usize = builtin_tool("Auto-Relog");
+// Custom macro tools follow, in `custom_macros` order
+const FIRST_MACRO_TOOL: usize = BUILTIN_TOOLS.len();
+
+// How long a sent overlay move may take to land before the position counts as dragged
+const OVERLAY_MOVE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+// Longest the cursor can sit in the failsafe corner before the tools are stopped
+const FAILSAFE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
 // Macro to toggle a tool with mutual exclusion
 
 pub struct CabalHelperApp {
@@ -25,8 +150,11 @@ pub struct CabalHelperApp {
     // Mapping of tool indices to their names (for dynamic macro naming)
     tool_names: Vec<String>,
 
-    // UI State
-    selected_tab: String,
+    // UI State: index into `tools` of the tab being shown
+    selected_tool: usize,
+    // Macros, most recently selected first (orders the macro list)
+    recent_macros: Vec<MacroId>,
+    show_macro_picker: bool,
 
     // Game context
     game_hwnd: Option<HWND>,
@@ -34,75 +162,253 @@ pub struct CabalHelperApp {
 
     // Overlay state
     is_overlay_mode: bool,
+    // Tool buttons and "running" rows the overlay was last sized for
+    overlay_button_count: usize,
+    overlay_running_rows: usize,
+    // Last position sent to the overlay window (pixels), and when
+    overlay_target: Option<(i32, i32)>,
+    overlay_move_sent: Option<std::time::Instant>,
+    // The user dragged the overlay since it was (re)placed
+    overlay_dragged: bool,
+    // This app's own window, and whether it currently lets clicks through
+    own_hwnd: Option<HWND>,
+    click_through_applied: bool,
     show_log_panel: bool,
     show_help_window: bool,
-    capturing_emergency_hotkey: bool,
-    hotkey_manager: Option<GlobalHotKeyManager>,
-    registered_hotkey: Option<HotKey>,
-    registered_hotkey_config: HotkeyConfig,
+    show_stats_window: bool,
+    capturing_hotkey: Option<HotkeyActionId>,
+    hotkey_registrar: HotkeyRegistrar,
     hotkey_error: Option<String>,
-    hotkey_capture_suspended: bool,
+    mouse_watcher: MouseButtonWatcher,
 
     // Optimization state
     last_window_check: std::time::Instant,
 
     last_window_always_on_top: bool,
+
+    // Region preview drawn over the game for the selected tool
+    region_overlay: Option<OverlayWindow>,
+
+    // Last failed profile/import/export operation (shown in the header)
+    settings_error: Option<String>,
+
+    // Result of the last OCR model "Test load" (ok, line)
+    ocr_model_report: Vec<(bool, String)>,
+
+    // App-level messages (e.g. settings migration) shown above the tool log
+    app_notices: Vec<LogEntry>,
+
+    // Shown above everything until dismissed (e.g. corrupt settings file, failed --start)
+    settings_warning: Option<String>,
+
+    // Window title and the profiles it matched when more than one did
+    profile_choice: Option<(String, Vec<String>)>,
+
+    // Debounced writes of `settings`
+    settings_saver: SettingsSaver,
+    // Set once the user agreed to close despite a failed save
+    close_confirmed: bool,
+    // The window was closed by the user (not e.g. a shutdown), so the recap is shown too
+    closed_from_window: bool,
+
+    // Finished runs of this session, written out (and shown) on exit
+    session: SessionSummary,
+    // Screenshots taken while tools run, into this session's folder
+    frame_recorder: FrameRecorder,
+    // Localhost HTTP listener; its commands are carried out in `update`
+    remote_control: RemoteControl,
+    // Fires `settings.schedule` entries, and the "Next scheduled" line for the header
+    scheduler: Scheduler,
+    next_scheduled: Option<String>,
+
+    // Session-only undo for destructive edits, and the toast offering it
+    undo_stack: UndoStack,
+    undo_toast: Option<(String, std::time::Instant)>,
+
+    // Tools the auto-relog stopped for its reconnect, started again once it is done
+    relog_stopped_tools: Vec<usize>,
+    // Tools paused because the game client changed size under them
+    resize_hold: Option<ResizeHold>,
+    // Runs the last session left unfinished (crash, reboot), offered for resuming
+    unfinished_runs: Vec<JournalEntry>,
+
+    // Command line, applied on the first frame (needs the egui context)
+    pending_launch: Option<LaunchArgs>,
+    // Tool or macro named by --start, started once the game is connected
+    pending_launch_start: Option<String>,
+}
+
+/// Running tools paused by the window check: their pixel positions were worked out
+/// for a client of `from` pixels, but it is now `to`
+struct ResizeHold {
+    from: (i32, i32),
+    to: (i32, i32),
+    tools: Vec<usize>,
+    // The ones the hold paused itself, resumed if the window goes back to their size
+    paused: Vec<usize>,
+}
+
+impl ResizeHold {
+    /// "Window size changed 1280x720 → 1920x1080 — paused"
+    fn message(&self) -> String {
+        tr!(
+            "resize.paused",
+            from = format!("{}x{}", self.from.0, self.from.1),
+            to = format!("{}x{}", self.to.0, self.to.1)
+        )
+    }
 }
 
 impl Default for CabalHelperApp {
     fn default() -> Self {
         // Load settings
-        let settings = AppSettings::load();
+        let loaded = AppSettings::load();
+        let settings = loaded.settings;
+        crate::i18n::set_language(settings.global.language);
+        let app_notices = loaded
+            .notices
+            .iter()
+            .map(|notice| LogEntry::new(APP_LOG_SOURCE, LogLevel::Info, notice))
+            .collect();
+        let settings_warning = loaded.warning;
 
-        let hotkey_manager = GlobalHotKeyManager::new().ok();
-        let mut registered_hotkey: Option<HotKey> = None;
-        let registered_hotkey_config = settings.emergency_stop_hotkey.clone();
-        let mut hotkey_error: Option<String> = None;
+        let mut hotkey_registrar = HotkeyRegistrar::new();
+        hotkey_registrar.sync(&settings.hotkey_bindings());
+        let hotkey_error = hotkey_registrar.error().map(str::to_string);
+        let settings_saver = SettingsSaver::new(&settings);
 
-        if let Some(manager) = hotkey_manager.as_ref() {
-            if let Some(hotkey) = hotkey_from_config(&settings.emergency_stop_hotkey) {
-                if let Err(err) = manager.register(hotkey.clone()) {
-                    hotkey_error = Some(format!("Hotkey registration failed: {:?}", err));
-                } else {
-                    registered_hotkey = Some(hotkey);
-                }
-            }
-        } else {
-            hotkey_error = Some("Global hotkey manager unavailable".to_string());
-        }
+        // Read what the last session left in the run journal before this one starts writing it
+        let unfinished_runs = run_journal().unfinished();
+        run_journal().enable();
 
         // Build tools dynamically
         let (tools, tool_names) = Self::build_tools(&settings);
 
-        // Set initial tab to first tool
-        let selected_tab = tool_names
-            .get(0)
-            .cloned()
-            .unwrap_or_else(|| "Image Clicker".to_string());
-
         Self {
             settings,
             tools,
             tool_names,
-            selected_tab,
+            selected_tool: IMAGE_CLICKER_TOOL,
+            recent_macros: Vec::new(),
+            show_macro_picker: false,
             game_hwnd: None,
-            status_message: "Ready".to_string(),
+            status_message: tr!("header.ready").to_string(),
             is_overlay_mode: false,
+            overlay_button_count: 0,
+            overlay_running_rows: 0,
+            overlay_target: None,
+            overlay_move_sent: None,
+            overlay_dragged: false,
+            own_hwnd: None,
+            click_through_applied: false,
             show_log_panel: false,
             show_help_window: false,
-            capturing_emergency_hotkey: false,
-            hotkey_manager,
-            registered_hotkey,
-            registered_hotkey_config,
+            show_stats_window: false,
+            capturing_hotkey: None,
+            hotkey_registrar,
             hotkey_error,
-            hotkey_capture_suspended: false,
+            mouse_watcher: MouseButtonWatcher::default(),
             last_window_check: std::time::Instant::now(),
             last_window_always_on_top: false,
+            region_overlay: None,
+            settings_error: None,
+            ocr_model_report: Vec::new(),
+            app_notices,
+            settings_warning,
+            profile_choice: None,
+            settings_saver,
+            close_confirmed: false,
+            closed_from_window: false,
+            session: SessionSummary::new(std::time::SystemTime::now()),
+            frame_recorder: FrameRecorder::new(std::time::SystemTime::now()),
+            remote_control: RemoteControl::default(),
+            scheduler: Scheduler::default(),
+            next_scheduled: None,
+            undo_stack: UndoStack::default(),
+            undo_toast: None,
+            relog_stopped_tools: Vec::new(),
+            resize_hold: None,
+            unfinished_runs,
+            pending_launch: None,
+            pending_launch_start: None,
         }
     }
 }
 
 impl CabalHelperApp {
+    /// App that carries out `launch` on its first frame
+    /// (the saved start mode applies unless it names one)
+    pub fn with_launch_args(launch: LaunchArgs) -> Self {
+        let mut app = Self::default();
+        app.pending_launch = Some(launch);
+        app
+    }
+
+    /// First frame: select the profile, connect and bring the window up as requested.
+    /// Overlay and Minimized are meant for unattended launches, so like --connect they
+    /// also connect to the game when it is already running
+    fn apply_launch_args(&mut self, ctx: &egui::Context, launch: LaunchArgs) {
+        if let Some(error) = launch.error {
+            self.report_launch_failure(error);
+        }
+
+        if let Some(requested) = &launch.profile {
+            let profile = self
+                .settings
+                .profile_names()
+                .into_iter()
+                .find(|name| name.eq_ignore_ascii_case(requested));
+            match profile {
+                Some(name) => {
+                    if name != self.settings.active_profile {
+                        self.apply_profile_action(ProfileAction::Switch(name.clone()));
+                    }
+                    self.push_notice(
+                        LogLevel::Info,
+                        &format!("Profile \"{}\" selected from the command line", name),
+                    );
+                }
+                None => self.report_launch_failure(format!(
+                    "--profile: there is no profile named \"{}\"",
+                    requested
+                )),
+            }
+        }
+
+        let start_mode = launch.start_mode.unwrap_or(self.settings.global.start_mode);
+        if (launch.connect || start_mode != StartMode::Normal) && self.game_hwnd.is_none() {
+            if let Some((hwnd, title)) = find_game_window() {
+                self.game_hwnd = Some(hwnd);
+                self.status_message = title;
+                // An explicit --profile wins over the title patterns
+                if launch.profile.is_none() {
+                    self.auto_select_profile(hwnd);
+                }
+            } else if launch.connect {
+                self.status_message = tr!("header.no_window").to_string();
+                self.report_launch_failure("--connect: the game window was not found".to_string());
+            }
+        }
+
+        if let Some(name) = launch.start {
+            if auto_start_failed() {
+                // Not with the wrong profile or without the game it was meant for
+                self.report_launch_failure(format!(
+                    "--start: \"{}\" was not started because of the error above",
+                    name
+                ));
+            } else {
+                self.pending_launch_start = Some(name);
+            }
+        }
+
+        match start_mode {
+            StartMode::Overlay => self.enter_overlay_mode(ctx),
+            StartMode::Minimized => ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true)),
+            StartMode::Normal => {}
+        }
+    }
+
     fn ensure_unique_macro_names(&mut self) {
         let mut used: HashSet<String> = HashSet::new();
 
@@ -124,103 +430,1192 @@ impl CabalHelperApp {
 
             if candidate != original {
                 named_macro.name = candidate.clone();
-                if self.selected_tab == original {
-                    self.selected_tab = candidate.clone();
-                }
             }
 
             used.insert(candidate);
         }
     }
 
-    fn sync_hotkey_capture_state(&mut self) {
-        if self.capturing_emergency_hotkey {
-            if !self.hotkey_capture_suspended {
-                if let (Some(manager), Some(hotkey)) =
-                    (self.hotkey_manager.as_ref(), self.registered_hotkey.as_ref())
-                {
-                    let _ = manager.unregister(hotkey.clone());
-                }
-                self.registered_hotkey = None;
-                self.registered_hotkey_config = HotkeyConfig {
-                    key: None,
-                    modifiers: HotkeyModifiers::default(),
-                };
-                self.hotkey_capture_suspended = true;
+    /// Keep OS hotkey registrations in line with the settings; none are active while capturing
+    fn sync_hotkeys(&mut self) {
+        if self.capturing_hotkey.is_some() {
+            self.hotkey_registrar.suspend();
+        } else {
+            self.hotkey_registrar.sync(&self.settings.hotkey_bindings());
+        }
+        self.hotkey_error = self.hotkey_registrar.error().map(str::to_string);
+    }
+
+    /// The emergency stop can be limited to when the game (or this window) has focus,
+    /// so Esc in a browser on another monitor doesn't end an overnight run
+    fn emergency_stop_allowed(&self) -> bool {
+        !self.settings.global.emergency_stop_focus_only || is_game_or_helper_focused(self.game_hwnd)
+    }
+
+    /// Stop the tool at `idx` if it runs, otherwise stop every tool and start it
+    fn toggle_tool(&mut self, idx: usize) {
+        if self.tools[idx].is_running() {
+            self.tools[idx].stop(StopReason::User);
+            return;
+        }
+        for tool in &mut self.tools {
+            tool.stop(StopReason::OtherToolStarted);
+        }
+        self.tools[idx].start(&self.settings, self.game_hwnd);
+
+        // Switch to this tool's tab
+        self.select_tool(idx);
+    }
+
+    /// Ctrl+Tab / Ctrl+Shift+Tab cycle the tabs, Ctrl+1..9 jump to one
+    fn handle_tab_shortcuts(&mut self, ctx: &egui::Context) {
+        let count = self.tools.len();
+        let ctrl_shift = egui::Modifiers::CTRL | egui::Modifiers::SHIFT;
+        // Shift first: the plain Ctrl pattern would also match Ctrl+Shift
+        let target = if ctx.input_mut(|i| i.consume_key(ctrl_shift, egui::Key::Tab)) {
+            Some((self.selected_tool + count - 1) % count)
+        } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::CTRL, egui::Key::Tab)) {
+            Some((self.selected_tool + 1) % count)
+        } else {
+            const DIGITS: [egui::Key; 9] = [
+                egui::Key::Num1,
+                egui::Key::Num2,
+                egui::Key::Num3,
+                egui::Key::Num4,
+                egui::Key::Num5,
+                egui::Key::Num6,
+                egui::Key::Num7,
+                egui::Key::Num8,
+                egui::Key::Num9,
+            ];
+            DIGITS
+                .iter()
+                .position(|&key| ctx.input_mut(|i| i.consume_key(egui::Modifiers::CTRL, key)))
+                .filter(|&idx| idx < count)
+        };
+
+        if let Some(idx) = target {
+            self.select_tool(idx);
+            ctx.request_repaint();
+        }
+    }
+
+    /// Show the tab of the tool at `idx`, remembering macros as recently used
+    fn select_tool(&mut self, idx: usize) {
+        self.selected_tool = idx;
+        let selected = idx
+            .checked_sub(FIRST_MACRO_TOOL)
+            .and_then(|macro_index| self.settings.custom_macros.get(macro_index));
+        if let Some(named_macro) = selected {
+            self.recent_macros.retain(|&id| id != named_macro.id);
+            self.recent_macros.insert(0, named_macro.id);
+        }
+    }
+
+    /// Switch to the compact always-on-top toolbar
+    fn enter_overlay_mode(&mut self, ctx: &egui::Context) {
+        self.flush_settings();
+        self.is_overlay_mode = true;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
+            egui::WindowLevel::AlwaysOnTop,
+        ));
+
+        // Dynamic overlay sizing: one button per tool shown in the overlay
+        self.overlay_button_count = self.overlay_tool_indices().len();
+        self.overlay_running_rows = 0;
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(overlay_size(
+            self.overlay_button_count,
+            self.overlay_running_rows,
+        )));
+
+        // Placed by `sync_overlay_position` from the next frame on
+        self.overlay_target = None;
+        self.overlay_move_sent = None;
+        self.overlay_dragged = false;
+    }
+
+    /// Keep the overlay at its placement, following the game window when it moves.
+    /// A drag switches the placement to Manual at wherever the window was dropped
+    fn sync_overlay_position(&mut self, ctx: &egui::Context) {
+        let Some(client_rect) = self.game_hwnd.and_then(get_client_rect_in_screen_coords) else {
+            return;
+        };
+        // Viewport positions are in points, the game window in pixels
+        let (ppp, outer_rect) = ctx.input(|i| {
+            (
+                i.viewport().native_pixels_per_point.unwrap_or(1.0),
+                i.viewport().outer_rect,
+            )
+        });
+        let current = outer_rect.map(|rect| {
+            (
+                (rect.min.x * ppp).round() as i32,
+                (rect.min.y * ppp).round() as i32,
+            )
+        });
+
+        let settled = match (current, self.overlay_target) {
+            (Some((cx, cy)), Some((tx, ty))) => (cx - tx).abs() <= 1 && (cy - ty).abs() <= 1,
+            _ => false,
+        };
+        if settled {
+            self.overlay_move_sent = None;
+        }
+        let move_pending = self
+            .overlay_move_sent
+            .is_some_and(|sent| sent.elapsed() < OVERLAY_MOVE_TIMEOUT);
+
+        if self.overlay_dragged && !settled && !move_pending {
+            if let Some((cx, cy)) = current {
+                self.settings.global.overlay_position =
+                    OverlayPlacement::Manual(cx - client_rect.0, cy - client_rect.1);
+                self.overlay_target = current;
+                return;
             }
-        } else if self.hotkey_capture_suspended {
-            self.hotkey_capture_suspended = false;
         }
+
+        let size = overlay_size(self.overlay_button_count, self.overlay_running_rows) * ppp;
+        let target = self
+            .settings
+            .global
+            .overlay_position
+            .screen_position(client_rect, (size.x.round() as i32, size.y.round() as i32));
+        if self.overlay_target != Some(target) {
+            self.overlay_target = Some(target);
+            self.overlay_move_sent = Some(std::time::Instant::now());
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(egui::pos2(
+                target.0 as f32 / ppp,
+                target.1 as f32 / ppp,
+            )));
+        }
+    }
+
+    /// True when a working hotkey can still bring the mouse back to a click-through overlay
+    fn click_through_escape_bound(&self) -> bool {
+        [
+            HotkeyActionId::ToggleOverlay,
+            HotkeyActionId::ToggleClickThrough,
+        ]
+        .into_iter()
+        .any(|action| {
+            let config = self.settings.hotkey(action);
+            (config.key.is_some() || config.mouse_button.is_some())
+                && !self.hotkey_registrar.problems().contains_key(&action)
+        })
+    }
+
+    /// Let clicks through the overlay while the setting is on; never outside overlay mode
+    fn sync_click_through(&mut self) {
+        let wanted = self.is_overlay_mode
+            && self.settings.global.overlay_click_through
+            && self.click_through_escape_bound();
+        if wanted == self.click_through_applied {
+            return;
+        }
+        if let Some(hwnd) = self.own_hwnd {
+            set_click_through(hwnd, wanted);
+            self.click_through_applied = wanted;
+        }
+    }
+
+    /// Native handle of the eframe window
+    fn viewport_hwnd(frame: &eframe::Frame) -> Option<HWND> {
+        match frame.window_handle().ok()?.as_raw() {
+            RawWindowHandle::Win32(handle) => Some(HWND(handle.hwnd.get())),
+            _ => None,
+        }
+    }
+
+    /// Back from the overlay toolbar to the full window
+    fn leave_overlay_mode(&mut self, ctx: &egui::Context) {
+        self.is_overlay_mode = false;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(true));
+        let level = if self.settings.global.always_on_top {
+            egui::WindowLevel::AlwaysOnTop
+        } else {
+            egui::WindowLevel::Normal
+        };
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+        self.last_window_always_on_top = self.settings.global.always_on_top;
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize([760.0, 620.0].into()));
     }
+
     /// Build tools dynamically: hardcoded tools + one tool per custom macro
     fn build_tools(settings: &AppSettings) -> (Vec<Box<dyn Tool>>, Vec<String>) {
         let mut tools: Vec<Box<dyn Tool>> = Vec::new();
         let mut names: Vec<String> = Vec::new();
 
-        // Hardcoded tools
-        tools.push(Box::new(ImageClickerTool::default()));
-        names.push("Image Clicker".to_string());
-
-        tools.push(Box::new(CollectionFillerTool::default()));
-        names.push("Collection Filler".to_string());
+        for builtin in BUILTIN_TOOLS {
+            tools.push((builtin.build)());
+            names.push(builtin.name.to_string());
+        }
 
         // Dynamic custom macro tools (single universal macro type)
-        for (idx, named_macro) in settings.custom_macros.iter().enumerate() {
-            tools.push(Box::new(CustomMacroTool::new(idx)));
+        for named_macro in &settings.custom_macros {
+            tools.push(Box::new(CustomMacroTool::new(
+                named_macro.id,
+                &named_macro.name,
+            )));
             names.push(named_macro.name.clone());
         }
 
         (tools, names)
     }
 
-    /// Rebuild tools after settings change (e.g., adding/deleting a macro)
+    /// Switch/create/clone/rename/delete settings profiles
+    fn apply_profile_action(&mut self, action: ProfileAction) {
+        let replaces_settings = !matches!(action, ProfileAction::Rename(_));
+        if replaces_settings {
+            for tool in &mut self.tools {
+                tool.stop(StopReason::SettingsReplaced);
+            }
+        }
+
+        let result = match action {
+            ProfileAction::Switch(name) => self.settings.switch_profile(&name),
+            ProfileAction::New(name) => self.settings.new_profile(&name),
+            ProfileAction::Clone(name) => self.settings.clone_profile(&name),
+            ProfileAction::Rename(name) => self.settings.rename_active_profile(&name),
+            ProfileAction::Delete => self.settings.delete_active_profile(),
+        };
+
+        match result {
+            Ok(()) => {
+                self.settings_error = None;
+                if replaces_settings {
+                    // Fresh tool instances: no worker or calibration survives the switch
+                    self.rebuild_tools();
+                }
+                self.flush_settings();
+            }
+            Err(err) => self.settings_error = Some(err),
+        }
+    }
+
+    /// Switch to the profile whose title pattern matches the connected window
+    /// Several matches are left to the user instead of guessing
+    fn auto_select_profile(&mut self, hwnd: HWND) {
+        let Some(title) = get_window_title(hwnd) else {
+            return;
+        };
+        let mut matches = self.settings.profiles_matching_title(&title);
+        match matches.len() {
+            0 => {}
+            1 => {
+                let name = matches.remove(0);
+                if name != self.settings.active_profile {
+                    self.apply_profile_action(ProfileAction::Switch(name.clone()));
+                }
+                self.push_notice(
+                    LogLevel::Info,
+                    &format!("Profile \"{}\" selected for window \"{}\"", name, title),
+                );
+            }
+            _ => self.profile_choice = Some((title, matches)),
+        }
+    }
+
+    /// Ask which profile to use when several patterns matched the window title
+    fn render_profile_choice(&mut self, ctx: &egui::Context) {
+        let Some((title, matches)) = self.profile_choice.clone() else {
+            return;
+        };
+
+        let mut chosen = None;
+        let mut keep_open = true;
+        egui::Window::new(tr!("profile_choice.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label(tr!("profile_choice.prompt", title = title));
+                for name in &matches {
+                    if ui.button(name).clicked() {
+                        chosen = Some(name.clone());
+                    }
+                }
+                if ui.button(tr!("profile_choice.keep")).clicked() {
+                    keep_open = false;
+                }
+            });
+
+        if let Some(name) = chosen {
+            if name != self.settings.active_profile {
+                self.apply_profile_action(ProfileAction::Switch(name.clone()));
+            }
+            self.push_notice(
+                LogLevel::Info,
+                &format!("Profile \"{}\" chosen for window \"{}\"", name, title),
+            );
+            keep_open = false;
+        }
+        if !keep_open {
+            self.profile_choice = None;
+        }
+    }
+
+    /// Save every profile to a user-chosen file
+    fn export_settings(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Settings", &["json"])
+            .set_title("Export Settings")
+            .set_file_name("cabalhelper_settings_export.json")
+            .save_file()
+        else {
+            return;
+        };
+
+        let client_size = self.game_hwnd.and_then(get_client_size);
+        match self.settings.export_to(&path, client_size) {
+            Ok(()) => {
+                self.settings_error = None;
+                self.push_notice(
+                    LogLevel::Info,
+                    &format!("Settings exported to {}", path.display()),
+                );
+            }
+            Err(err) => self.settings_error = Some(format!("Export failed: {}", err)),
+        }
+    }
+
+    /// Replace all settings with an exported file (after confirmation)
+    fn import_settings(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Settings", &["json"])
+            .set_title("Import Settings")
+            .pick_file()
+        else {
+            return;
+        };
+
+        let (metadata, mut imported) = match AppSettings::read_export(&path) {
+            Ok(result) => result,
+            Err(err) => {
+                self.settings_error = Some(format!("Import failed: {}", err));
+                return;
+            }
+        };
+
+        let mut description = format!(
+            "Replace all current settings and profiles with {}?",
+            path.display()
+        );
+        let exported_size = metadata.as_ref().and_then(|m| m.client_size);
+        let current_size = self.game_hwnd.and_then(get_client_size);
+        if let (Some((ew, eh)), Some((cw, ch))) = (exported_size, current_size) {
+            if (ew, eh) != (cw, ch) {
+                description.push_str(&format!(
+                    "\n\nThe export was calibrated at {}x{} but the game is now {}x{}. \
+                     Calibrations may need to be redone.",
+                    ew, eh, cw, ch
+                ));
+            }
+        }
+        let confirmed = rfd::MessageDialog::new()
+            .set_level(rfd::MessageLevel::Warning)
+            .set_title("Import Settings")
+            .set_description(description)
+            .set_buttons(rfd::MessageButtons::YesNo)
+            .show()
+            == rfd::MessageDialogResult::Yes;
+        if !confirmed {
+            return;
+        }
+
+        for tool in &mut self.tools {
+            tool.stop(StopReason::SettingsReplaced);
+        }
+        let corrections = imported.validate_and_clamp();
+        self.settings = imported;
+        for correction in corrections {
+            self.push_notice(
+                LogLevel::Warn,
+                &format!("Imported settings adjusted: {}", correction),
+            );
+        }
+        self.rebuild_tools();
+        self.settings_error = None;
+        self.push_notice(
+            LogLevel::Info,
+            &format!("Settings imported from {}", path.display()),
+        );
+        self.flush_settings();
+    }
+
+    /// Stop the other tools while the auto-relog reconnects, and start them again after
+    fn handle_relog_events(&mut self) {
+        while let Some(event) = self.tools[AUTO_RELOG_TOOL].take_relog_event() {
+            match event {
+                RelogEvent::Disconnected => {
+                    let running: Vec<usize> = (0..self.tools.len())
+                        .filter(|&idx| idx != AUTO_RELOG_TOOL && self.tools[idx].is_running())
+                        .collect();
+                    for &idx in &running {
+                        self.tools[idx].stop(StopReason::Disconnected);
+                    }
+                    if !running.is_empty() {
+                        let names: Vec<&str> = running
+                            .iter()
+                            .map(|&idx| self.tool_names[idx].as_str())
+                            .collect();
+                        self.push_notice(
+                            LogLevel::Warn,
+                            &format!("Auto-Relog: disconnected, stopped {}", names.join(", ")),
+                        );
+                    }
+                    self.relog_stopped_tools = running;
+                }
+                RelogEvent::Reconnected => {
+                    let stopped = std::mem::take(&mut self.relog_stopped_tools);
+                    if !self.settings.auto_relog.resume_previous_tool || stopped.is_empty() {
+                        continue;
+                    }
+                    for &idx in &stopped {
+                        if let Some(tool) = self.tools.get_mut(idx) {
+                            tool.start(&self.settings, self.game_hwnd);
+                        }
+                    }
+                    let names: Vec<&str> = stopped
+                        .iter()
+                        .filter_map(|&idx| self.tool_names.get(idx))
+                        .map(String::as_str)
+                        .collect();
+                    self.push_notice(
+                        LogLevel::Info,
+                        &format!("Auto-Relog: reconnected, restarted {}", names.join(", ")),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Add an app-level line to the log panel
+    fn push_notice(&mut self, level: LogLevel, text: &str) {
+        self.app_notices
+            .push(LogEntry::new(APP_LOG_SOURCE, level, text));
+    }
+
+    /// A command-line request that could not be carried out: logged, shown until
+    /// dismissed and remembered for the exit code
+    fn report_launch_failure(&mut self, message: String) {
+        self.push_notice(LogLevel::Error, &message);
+        mark_auto_start_failed();
+        self.settings_warning = Some(match self.settings_warning.take() {
+            Some(warning) => format!("{}\n{}", warning, message),
+            None => message,
+        });
+    }
+
+    /// Start the tool or macro named by --start (now that the game is connected)
+    fn start_launch_tool(&mut self, name: &str) {
+        let Some(idx) = self.tool_index(name) else {
+            self.report_launch_failure(format!(
+                "--start: there is no tool or macro named \"{}\"",
+                name
+            ));
+            return;
+        };
+
+        if !self.tools[idx].is_running() {
+            self.toggle_tool(idx);
+        }
+        if self.tools[idx].is_running() {
+            self.push_notice(
+                LogLevel::Info,
+                &format!("Started \"{}\" from the command line", self.tool_names[idx]),
+            );
+        } else {
+            // e.g. missing calibration or an empty macro; the tool's status says which
+            let status = self.tools[idx].get_status();
+            self.report_launch_failure(format!(
+                "--start: \"{}\" did not start: {}",
+                self.tool_names[idx], status
+            ));
+        }
+    }
+
+    /// Tab of the tool or macro called `name` (any case)
+    fn tool_index(&self, name: &str) -> Option<usize> {
+        self.tool_names
+            .iter()
+            .position(|tool_name| tool_name.eq_ignore_ascii_case(name))
+    }
+
+    /// Carry out what arrived over the remote control endpoint, like the matching buttons,
+    /// and publish the tool states it serves
+    fn handle_remote_commands(&mut self) {
+        while let Some(request) = self.remote_control.try_recv() {
+            let result = match &request.command {
+                RemoteCommand::StopAll => {
+                    for tool in &mut self.tools {
+                        tool.stop(StopReason::RemoteControl);
+                    }
+                    Ok("All tools stopped".to_string())
+                }
+                RemoteCommand::Stop(name) => match self.tool_index(name) {
+                    Some(idx) => {
+                        self.tools[idx].stop(StopReason::RemoteControl);
+                        Ok(format!("Stopped \"{}\"", self.tool_names[idx]))
+                    }
+                    None => Err(format!("There is no tool or macro named \"{}\"", name)),
+                },
+                RemoteCommand::Start(name) => match self.tool_index(name) {
+                    Some(idx) => {
+                        // Only the named tool: Auto-Relog, Anti-AFK and the rest keep running
+                        if !self.tools[idx].is_running() {
+                            self.tools[idx].start(&self.settings, self.game_hwnd);
+                        }
+                        if self.tools[idx].is_running() {
+                            Ok(format!("Started \"{}\"", self.tool_names[idx]))
+                        } else {
+                            // e.g. not connected or not calibrated; the tool's status says which
+                            Err(format!(
+                                "\"{}\" did not start: {}",
+                                self.tool_names[idx],
+                                self.tools[idx].get_status()
+                            ))
+                        }
+                    }
+                    None => Err(format!("There is no tool or macro named \"{}\"", name)),
+                },
+            };
+            match &result {
+                Ok(message) => self.push_notice(LogLevel::Info, &format!("Remote: {}", message)),
+                Err(error) => self.push_notice(LogLevel::Warn, &format!("Remote: {}", error)),
+            }
+            request.reply(result);
+        }
+
+        if self.remote_control.is_listening() {
+            let states = self
+                .tools
+                .iter()
+                .zip(&self.tool_names)
+                .map(|(tool, name)| ToolState {
+                    name: name.clone(),
+                    running: tool.is_running(),
+                    paused: tool.is_paused(),
+                    status: tool.get_status(),
+                })
+                .collect();
+            self.remote_control.publish(states);
+        }
+    }
+
+    /// Start or stop what the schedule says is due this minute and refresh the "Next
+    /// scheduled" line. Unlike the tool's own button, a scheduled start leaves the other
+    /// tools running, so several entries due in the same minute all start
+    fn run_schedule(&mut self) {
+        let Some(now) = to_local(std::time::SystemTime::now()) else {
+            return;
+        };
+        self.settings.sync_schedule_targets();
+        for entry_idx in self.scheduler.due(&self.settings.schedule, &now) {
+            let entry = self.settings.schedule[entry_idx].clone();
+            let idx = match entry.macro_id {
+                Some(id) => self
+                    .settings
+                    .macro_index(id)
+                    .map(|macro_index| FIRST_MACRO_TOOL + macro_index),
+                None => self.tool_index(&entry.target),
+            };
+            let Some(idx) = idx else {
+                let problem = if entry.macro_id.is_some() {
+                    format!("the macro \"{}\" no longer exists", entry.target)
+                } else {
+                    format!("there is no tool or macro named \"{}\"", entry.target)
+                };
+                self.push_notice(LogLevel::Warn, &format!("Schedule: {}", problem));
+                continue;
+            };
+            match entry.action {
+                ScheduleAction::Start => {
+                    if !self.tools[idx].is_running() {
+                        self.tools[idx].start(&self.settings, self.game_hwnd);
+                    }
+                    if self.tools[idx].is_running() {
+                        self.push_notice(
+                            LogLevel::Info,
+                            &format!("Schedule: started \"{}\"", self.tool_names[idx]),
+                        );
+                    } else {
+                        // e.g. not connected or not calibrated; the tool's status says which
+                        let status = self.tools[idx].get_status();
+                        self.push_notice(
+                            LogLevel::Warn,
+                            &format!(
+                                "Schedule: \"{}\" did not start: {}",
+                                self.tool_names[idx], status
+                            ),
+                        );
+                    }
+                }
+                ScheduleAction::Stop => {
+                    self.tools[idx].stop(StopReason::Schedule);
+                    self.push_notice(
+                        LogLevel::Info,
+                        &format!("Schedule: stopped \"{}\"", self.tool_names[idx]),
+                    );
+                }
+            }
+        }
+
+        self.next_scheduled = next_due(&self.settings.schedule, &now)
+            .map(|(idx, ahead)| describe_next(&self.settings.schedule[idx], &now, ahead));
+    }
+
+    /// Pause running tools whose calibrated positions were worked out for another client size
+    fn hold_resized_tools(&mut self, size: (i32, i32)) {
+        let previous = self.resize_hold.take();
+        let mut held = Vec::new();
+        let mut paused = Vec::new();
+        let mut from = None;
+        let mut newly_paused = false;
+        for (idx, tool) in self.tools.iter_mut().enumerate() {
+            let Some(started_at) = tool.run_client_size() else {
+                continue;
+            };
+            if started_at == size {
+                continue;
+            }
+            if !tool.is_paused() {
+                tool.set_paused(true);
+                newly_paused = true;
+                paused.push(idx);
+            } else if previous
+                .as_ref()
+                .is_some_and(|hold| hold.paused.contains(&idx))
+            {
+                paused.push(idx);
+            }
+            held.push(idx);
+            from.get_or_insert(started_at);
+        }
+
+        // The window is back at the size these started at: undo the hold's pause
+        let resumed: Vec<usize> = previous
+            .as_ref()
+            .map(|hold| {
+                hold.paused
+                    .iter()
+                    .copied()
+                    .filter(|idx| !held.contains(idx))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut names = Vec::new();
+        for &idx in &resumed {
+            if let Some(tool) = self.tools.get_mut(idx) {
+                if tool.is_running() && tool.is_paused() {
+                    tool.set_paused(false);
+                    names.push(self.tool_names[idx].as_str());
+                }
+            }
+        }
+        if !names.is_empty() {
+            let message = format!(
+                "Window size back to {}x{}, resumed ({})",
+                size.0,
+                size.1,
+                names.join(", ")
+            );
+            self.push_notice(LogLevel::Info, &message);
+        }
+
+        let Some(from) = from else {
+            return;
+        };
+
+        let hold = ResizeHold {
+            from: previous.as_ref().map_or(from, |hold| hold.from),
+            to: size,
+            tools: held,
+            paused,
+        };
+        if newly_paused {
+            let names: Vec<&str> = hold
+                .tools
+                .iter()
+                .map(|&idx| self.tool_names[idx].as_str())
+                .collect();
+            let message = hold.message();
+            self.push_notice(
+                LogLevel::Warn,
+                &format!("{} ({})", message, names.join(", ")),
+            );
+            self.status_message = message;
+        }
+        self.resize_hold = Some(hold);
+    }
+
+    /// Restart the held tools: calibrations are stored relative to the client, so a fresh
+    /// start works out their pixel positions again at the new size
+    fn rescale_and_resume(&mut self) {
+        let Some(hold) = self.resize_hold.take() else {
+            return;
+        };
+        for &idx in &hold.tools {
+            if let Some(tool) = self.tools.get_mut(idx) {
+                if tool.is_running() {
+                    tool.stop(StopReason::WindowResized);
+                    tool.start(&self.settings, self.game_hwnd);
+                }
+            }
+        }
+        self.push_notice(
+            LogLevel::Info,
+            &format!(
+                "Calibrations rescaled to {}x{}, tools restarted",
+                hold.to.0, hold.to.1
+            ),
+        );
+    }
+
+    /// Bar under the header while tools are held for a window-size change
+    fn render_resize_hold(&mut self, ctx: &egui::Context) {
+        let Some(hold) = &self.resize_hold else {
+            return;
+        };
+        // Stopped or resumed by hand in the meantime
+        if !hold.tools.iter().any(|&idx| {
+            self.tools
+                .get(idx)
+                .is_some_and(|tool| tool.is_running() && tool.is_paused())
+        }) {
+            self.resize_hold = None;
+            return;
+        }
+
+        let message = hold.message();
+        let mut rescale = false;
+        let mut stop = false;
+        egui::TopBottomPanel::top("resize_hold").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.colored_label(egui::Color32::from_rgb(230, 180, 80), message);
+                if ui
+                    .button(tr!("resize.rescale"))
+                    .on_hover_text(tr!("resize.rescale_hint"))
+                    .clicked()
+                {
+                    rescale = true;
+                }
+                if ui.button(tr!("resize.stop")).clicked() {
+                    stop = true;
+                }
+            });
+        });
+        if rescale {
+            self.rescale_and_resume();
+        } else if stop {
+            if let Some(hold) = self.resize_hold.take() {
+                for &idx in &hold.tools {
+                    if let Some(tool) = self.tools.get_mut(idx) {
+                        tool.stop(StopReason::WindowResized);
+                    }
+                }
+            }
+        }
+    }
+
+    /// "A previous run of 'Reroll Epaulet' did not finish" bar with Resume / Dismiss
+    fn render_unfinished_runs(&mut self, ctx: &egui::Context) {
+        let Some(entry) = self.unfinished_runs.first() else {
+            return;
+        };
+        let message = tr!(
+            "journal.unfinished",
+            name = entry.name,
+            count = entry.iterations,
+            time = to_local(entry.started_at())
+                .map(|time| format!("{} {}", time.date(), time.clock()))
+                .unwrap_or_default()
+        );
+        let mut resume = false;
+        let mut dismiss = false;
+        egui::TopBottomPanel::top("unfinished_run").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.colored_label(egui::Color32::from_rgb(230, 180, 80), message);
+                if ui
+                    .button(tr!("journal.resume"))
+                    .on_hover_text(tr!("journal.resume_hint"))
+                    .clicked()
+                {
+                    resume = true;
+                }
+                if ui.button(tr!("journal.dismiss")).clicked() {
+                    dismiss = true;
+                }
+            });
+        });
+        if resume {
+            let entry = self.unfinished_runs.remove(0);
+            self.resume_unfinished_run(entry);
+        } else if dismiss {
+            self.unfinished_runs.remove(0);
+        }
+    }
+
+    /// Start the tool or macro again, counting on from where the journal left it
+    fn resume_unfinished_run(&mut self, entry: JournalEntry) {
+        let Some(idx) = self.tool_index(&entry.name) else {
+            self.push_notice(
+                LogLevel::Warn,
+                &format!(
+                    "Resume: there is no tool or macro named \"{}\" any more",
+                    entry.name
+                ),
+            );
+            return;
+        };
+        self.push_notice(
+            LogLevel::Info,
+            &format!(
+                "Resume: \"{}\" from {} iterations",
+                self.tool_names[idx], entry.iterations
+            ),
+        );
+        self.tools[idx].seed_next_run(entry);
+        if !self.tools[idx].is_running() {
+            self.toggle_tool(idx);
+        }
+    }
+
+    /// Restore the most recently deleted/cleared data
+    fn undo_last(&mut self) {
+        let Some(entry) = self.undo_stack.pop() else {
+            return;
+        };
+        let description = entry.description();
+        let (level, message) = match entry.restore(&mut self.settings) {
+            Ok(tools_change) => {
+                if let Some(change) = tools_change {
+                    self.apply_tools_change(change);
+                }
+                self.settings_saver.mark_dirty();
+                (LogLevel::Info, format!("Undone: {}", description))
+            }
+            Err(err) => (LogLevel::Error, format!("Undo failed: {}", err)),
+        };
+        self.push_notice(level, &message);
+        self.undo_toast = Some((message, std::time::Instant::now()));
+    }
+
+    /// Bottom toast after a destructive edit, with an Undo button while there is something to undo
+    fn render_undo_toast(&mut self, ctx: &egui::Context) {
+        const TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(8);
+
+        let Some((message, shown_at)) = self.undo_toast.clone() else {
+            return;
+        };
+        if shown_at.elapsed() > TOAST_DURATION {
+            self.undo_toast = None;
+            return;
+        }
+
+        let mut undo_clicked = false;
+        egui::TopBottomPanel::bottom("undo_toast").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(message);
+                if !self.undo_stack.is_empty()
+                    && ui
+                        .button(tr!("undo.button"))
+                        .on_hover_text("Ctrl+Z")
+                        .clicked()
+                {
+                    undo_clicked = true;
+                }
+            });
+        });
+        if undo_clicked {
+            self.undo_last();
+        }
+        ctx.request_repaint_after(std::time::Duration::from_millis(500));
+    }
+
+    /// Write pending settings changes now, reporting failures in the header
+    fn flush_settings(&mut self) {
+        if let Err(err) = self.settings_saver.flush(&self.settings) {
+            self.settings_error = Some(format!("Save failed: {}", err));
+        }
+    }
+
+    /// Save before the window closes; if that fails, ask before throwing the changes away
+    fn handle_close_request(&mut self, ctx: &egui::Context) {
+        if self.close_confirmed {
+            return;
+        }
+        let Err(err) = self.settings_saver.flush(&self.settings) else {
+            self.closed_from_window = true;
+            return;
+        };
+        self.settings_error = Some(format!("Save failed: {}", err));
+
+        let close_anyway = rfd::MessageDialog::new()
+            .set_level(rfd::MessageLevel::Warning)
+            .set_title("Unsaved Settings")
+            .set_description(format!(
+                "Your settings could not be saved:\n{}\n\nClose anyway and lose the unsaved changes?",
+                err
+            ))
+            .set_buttons(rfd::MessageButtons::YesNo)
+            .show()
+            == rfd::MessageDialogResult::Yes;
+        if close_anyway {
+            self.close_confirmed = true;
+            self.closed_from_window = true;
+        } else {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        }
+    }
+
+    /// Replace every tool with a fresh instance after the settings were swapped out
+    /// (profile switch, import). Running workers are stopped first
     fn rebuild_tools(&mut self) {
+        for tool in &mut self.tools {
+            tool.stop(StopReason::SettingsReplaced);
+        }
+        self.collect_usage_stats();
         let (tools, names) = Self::build_tools(&self.settings);
         self.tools = tools;
         self.tool_names = names;
+        self.relog_stopped_tools.clear();
+        self.resize_hold = None;
 
-        // Ensure selected tab still exists
-        if !self.tool_names.contains(&self.selected_tab) {
-            self.selected_tab = self
-                .tool_names
-                .get(0)
-                .cloned()
-                .unwrap_or_else(|| "Image Clicker".to_string());
+        // Another profile may have fewer macros
+        if self.selected_tool >= self.tools.len() {
+            self.selected_tool = IMAGE_CLICKER_TOOL;
         }
+        self.forget_deleted_recent_macros();
     }
 
-    fn sync_tool_names_from_settings(&mut self) {
-        let mut names: Vec<String> = Vec::with_capacity(2 + self.settings.custom_macros.len());
-        names.push("Image Clicker".to_string());
-        names.push("Collection Filler".to_string());
-        for named_macro in &self.settings.custom_macros {
-            names.push(named_macro.name.clone());
+    /// Drop deleted macros from the recently used list
+    fn forget_deleted_recent_macros(&mut self) {
+        let settings = &self.settings;
+        self.recent_macros
+            .retain(|&id| settings.macro_index(id).is_some());
+    }
+
+    /// Mirror an added/removed macro in the tool list, leaving the other tools
+    /// (and their running workers) untouched
+    fn apply_tools_change(&mut self, change: ToolsChange) {
+        // Held tab indices would point at other tools; the next window check rebuilds it
+        self.resize_hold = None;
+        match change {
+            ToolsChange::MacroInserted(index) => {
+                let pos = FIRST_MACRO_TOOL + index;
+                let Some(named_macro) = self.settings.custom_macros.get(index) else {
+                    self.rebuild_tools();
+                    return;
+                };
+                if pos > self.tools.len() {
+                    self.rebuild_tools();
+                    return;
+                }
+                self.tools.insert(
+                    pos,
+                    Box::new(CustomMacroTool::new(named_macro.id, &named_macro.name)),
+                );
+                self.tool_names.insert(pos, named_macro.name.clone());
+                if self.selected_tool >= pos {
+                    self.selected_tool += 1;
+                }
+            }
+            ToolsChange::MacroRemoved(index) => {
+                let pos = FIRST_MACRO_TOOL + index;
+                if pos >= self.tools.len() {
+                    self.rebuild_tools();
+                    return;
+                }
+                let mut removed = self.tools.remove(pos);
+                removed.stop(StopReason::User);
+                let removed_name = self.tool_names.remove(pos);
+                Self::record_finished_run(
+                    &mut self.settings,
+                    &mut self.session,
+                    removed.as_mut(),
+                    &removed_name,
+                );
+                if self.selected_tool > pos {
+                    self.selected_tool -= 1;
+                } else if self.selected_tool == pos {
+                    // The macro that took its place, or the one before it when it was last
+                    self.selected_tool = pos.min(self.tools.len() - 1);
+                }
+                self.forget_deleted_recent_macros();
+            }
+            ToolsChange::MacrosSwapped(a, b) => {
+                let (pos_a, pos_b) = (FIRST_MACRO_TOOL + a, FIRST_MACRO_TOOL + b);
+                if pos_a.max(pos_b) >= self.tools.len() {
+                    self.rebuild_tools();
+                    return;
+                }
+                self.tools.swap(pos_a, pos_b);
+                self.tool_names.swap(pos_a, pos_b);
+                // The selection follows the moved tab
+                if self.selected_tool == pos_a {
+                    self.selected_tool = pos_b;
+                } else if self.selected_tool == pos_b {
+                    self.selected_tool = pos_a;
+                }
+            }
         }
 
-        if names == self.tool_names {
-            return;
+        if self.tools.len() != FIRST_MACRO_TOOL + self.settings.custom_macros.len() {
+            self.rebuild_tools();
         }
+    }
 
-        let selected_index = self
-            .tool_names
-            .iter()
-            .position(|name| name == &self.selected_tab);
-        self.tool_names = names;
-        if let Some(idx) = selected_index {
-            if let Some(new_name) = self.tool_names.get(idx) {
-                self.selected_tab = new_name.clone();
+    /// The searchable macro list under the tab strip
+    fn render_macro_picker_panel(&mut self, ui: &mut egui::Ui) {
+        let entries: Vec<MacroEntry> = (FIRST_MACRO_TOOL..self.tools.len())
+            .zip(&self.settings.custom_macros)
+            .map(|(idx, named_macro)| MacroEntry {
+                id: named_macro.id,
+                macro_index: idx - FIRST_MACRO_TOOL,
+                name: self.tool_names[idx].as_str(),
+                is_running: self.tools[idx].is_running(),
+                is_paused: self.tools[idx].is_paused(),
+            })
+            .collect();
+        let selected = self.selected_tool.checked_sub(FIRST_MACRO_TOOL);
+
+        let action = egui::Frame::none()
+            .fill(egui::Color32::from_rgb(22, 22, 24))
+            .rounding(egui::Rounding::same(6.0))
+            .inner_margin(egui::Margin::same(8.0))
+            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(40, 40, 40)))
+            .show(ui, |ui| {
+                render_macro_picker(ui, &entries, &self.recent_macros, selected)
+            })
+            .inner;
+
+        match action {
+            MacroPickerAction::Select(macro_index) => {
+                self.select_tool(FIRST_MACRO_TOOL + macro_index);
+                self.show_macro_picker = false;
             }
+            MacroPickerAction::Close => self.show_macro_picker = false,
+            MacroPickerAction::None => {}
+        }
+    }
+
+    /// Every tool whose worker is running, for the "what's running" strip
+    fn running_tools(&self) -> Vec<RunningTool> {
+        self.tools
+            .iter()
+            .zip(&self.tool_names)
+            .enumerate()
+            .filter(|(_, (tool, _))| tool.is_running())
+            .map(|(idx, (tool, name))| RunningTool {
+                tool_index: idx,
+                name: name.as_str(),
+                status: tool.get_status(),
+                elapsed: tool.get_stats().elapsed(),
+                is_paused: tool.is_paused(),
+            })
+            .collect()
+    }
+
+    /// Fold finished runs into the lifetime statistics (written by the debounced saver)
+    fn collect_usage_stats(&mut self) {
+        for (tool, name) in self.tools.iter_mut().zip(&self.tool_names) {
+            Self::record_finished_run(&mut self.settings, &mut self.session, tool.as_mut(), name);
+        }
+    }
+
+    /// Count a tool's ended run in the lifetime stats and the session summary
+    fn record_finished_run(
+        settings: &mut AppSettings,
+        session: &mut SessionSummary,
+        tool: &mut dyn Tool,
+        name: &str,
+    ) {
+        if let Some(run) = tool.take_finished_run() {
+            settings.global.usage_stats.record_run(name, &run);
+            session.record_run(name, &run);
+        }
+    }
+
+    /// Append the session recap to its file, and show it when the user closed the window
+    fn finish_session(&self) {
+        if self.session.is_empty() {
+            return;
+        }
+        let text = self.session.text(std::time::SystemTime::now());
+        let saved = match self.session.write(&text) {
+            Ok(path) => format!("Saved to {}", path.display()),
+            Err(err) => format!("Could not save the summary: {}", err),
+        };
+        if self.closed_from_window {
+            rfd::MessageDialog::new()
+                .set_level(rfd::MessageLevel::Info)
+                .set_title("Session Summary")
+                .set_description(format!("{}\n{}", text, saved))
+                .set_buttons(rfd::MessageButtons::Ok)
+                .show();
+        }
+    }
+
+    /// Lifetime statistics in their own window, like Help
+    fn render_stats_window(&mut self, ctx: &egui::Context) {
+        if !self.show_stats_window {
+            return;
+        }
+        let stats_viewport_id = egui::ViewportId::from_hash_of("stats_window");
+        let stats_builder = egui::ViewportBuilder::default()
+            .with_title(tr!("window.statistics"))
+            .with_inner_size([420.0, 520.0])
+            .with_min_inner_size([320.0, 300.0])
+            .with_resizable(true);
+        let (close_requested, action) =
+            ctx.show_viewport_immediate(stats_viewport_id, stats_builder, |ctx, _class| {
+                let close_requested = ctx.input(|i| i.viewport().close_requested());
+                let action = egui::CentralPanel::default()
+                    .show(ctx, |ui| {
+                        egui::ScrollArea::vertical()
+                            .auto_shrink([false, false])
+                            .show(ui, |ui| {
+                                render_statistics(
+                                    ui,
+                                    &self.settings.global.usage_stats,
+                                    &self.tool_names,
+                                )
+                            })
+                            .inner
+                    })
+                    .inner;
+                (close_requested, action)
+            });
+
+        if let StatisticsAction::Reset(name) = action {
+            self.settings.global.usage_stats.tools.remove(&name);
+        }
+        if close_requested {
+            self.show_stats_window = false;
+        }
+    }
+
+    fn sync_tool_names_from_settings(&mut self) {
+        let mut names: Vec<String> =
+            Vec::with_capacity(FIRST_MACRO_TOOL + self.settings.custom_macros.len());
+        names.extend(BUILTIN_TOOLS.iter().map(|builtin| builtin.name.to_string()));
+        for named_macro in &self.settings.custom_macros {
+            names.push(named_macro.name.clone());
+        }
+
+        if names != self.tool_names {
+            self.tool_names = names;
         }
     }
 
     fn tool_visible_in_overlay(&self, idx: usize) -> bool {
-        match idx {
-            0 => self.settings.accept_item.show_in_overlay,
-            1 => self.settings.collection_filler.show_in_overlay,
-            _ => self
+        match BUILTIN_TOOLS.get(idx) {
+            Some(builtin) => (builtin.show_in_overlay)(&self.settings),
+            None => self
                 .settings
                 .custom_macros
-                .get(idx - 2)
+                .get(idx - FIRST_MACRO_TOOL)
                 .map(|macro_settings| macro_settings.show_in_overlay)
                 .unwrap_or(true),
         }
@@ -232,59 +1627,90 @@ impl CabalHelperApp {
             .collect()
     }
 
-    fn sync_hotkey_registration(&mut self) {
-        if self.capturing_emergency_hotkey {
-            return;
-        }
-        if self.settings.emergency_stop_hotkey == self.registered_hotkey_config {
+    /// Keep calibrations alive for tools whose UI isn't drawn this frame (other tabs, overlay mode)
+    fn tick_background_calibrations(&mut self, ctx: &egui::Context) {
+        let Some(hwnd) = self.game_hwnd else {
             return;
+        };
+        let rendered_idx = if self.is_overlay_mode {
+            None
+        } else {
+            Some(self.selected_tool)
+        };
+
+        for (idx, tool) in self.tools.iter_mut().enumerate() {
+            if Some(idx) != rendered_idx && tool.is_calibrating() {
+                tool.update_calibration(&mut self.settings, hwnd);
+                // Clicks are polled, so keep frames coming while waiting for one
+                ctx.request_repaint();
+            }
         }
+    }
+
+    /// Show the selected tool's calibrated regions over the game, following the window
+    fn sync_region_overlay(&mut self) {
+        let preview = self.game_hwnd.and_then(|hwnd| {
+            let shapes = self
+                .tools
+                .get(self.selected_tool)?
+                .region_preview(&self.settings, hwnd)?;
+            let client_rect = get_client_rect_in_screen_coords(hwnd)?;
+            Some((shapes, client_rect))
+        });
 
-        let Some(manager) = self.hotkey_manager.as_ref() else {
-            self.hotkey_error = Some("Global hotkey manager unavailable".to_string());
-            self.settings.emergency_stop_hotkey = self.registered_hotkey_config.clone();
+        let Some((shapes, client_rect)) = preview else {
+            // Dropping the window erases everything it drew
+            self.region_overlay = None;
             return;
         };
 
-        let old_config = self.registered_hotkey_config.clone();
-        let old_hotkey = self.registered_hotkey.clone();
-
-        if let Some(hotkey) = &old_hotkey {
-            let _ = manager.unregister(hotkey.clone());
+        if self.region_overlay.is_none() {
+            self.region_overlay = OverlayWindow::new();
         }
-
-        if let Some(hotkey) = hotkey_from_config(&self.settings.emergency_stop_hotkey) {
-            match manager.register(hotkey.clone()) {
-                Ok(()) => {
-                    self.registered_hotkey = Some(hotkey);
-                    self.registered_hotkey_config = self.settings.emergency_stop_hotkey.clone();
-                    self.hotkey_error = None;
-                }
-                Err(err) => {
-                    self.hotkey_error = Some(format!("Hotkey registration failed: {:?}", err));
-                    self.settings.emergency_stop_hotkey = old_config.clone();
-                    self.registered_hotkey_config = old_config;
-                    self.registered_hotkey = None;
-                    if let Some(old) = old_hotkey {
-                        if manager.register(old.clone()).is_ok() {
-                            self.registered_hotkey = Some(old);
-                        }
-                    }
-                }
-            }
-        } else {
-            self.registered_hotkey = None;
-            self.registered_hotkey_config = self.settings.emergency_stop_hotkey.clone();
-            self.hotkey_error = None;
+        if let Some(overlay) = self.region_overlay.as_mut() {
+            overlay.move_to(client_rect);
+            overlay.set_shapes(shapes);
         }
     }
 }
 
 impl eframe::App for CabalHelperApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Runs still going end here; stopping them first gets them into the statistics
+        for tool in &mut self.tools {
+            tool.stop(StopReason::User);
+        }
+        self.collect_usage_stats();
+        // Close requests already flushed; this catches any other way out (and the runs above)
+        let _ = self.settings_saver.flush(&self.settings);
+        file_logger().flush();
+        self.frame_recorder.stop();
+        self.remote_control.shutdown();
+        self.finish_session();
+    }
+
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         const LOG_PANEL_WIDTH: f32 = 280.0;
         const MIN_WINDOW_WIDTH: f32 = 400.0;
 
+        // Picked up from the header, a profile switch or an import alike
+        crate::i18n::set_language(self.settings.global.language);
+
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.handle_close_request(ctx);
+        }
+
+        file_logger().configure(
+            self.settings.global.log_to_file,
+            self.settings.global.log_retention_days,
+        );
+        sounds().configure(&self.settings.global.event_sounds);
+        click_guard::configure(self.settings.global.click_guard_warn_only);
+
+        if let Some(launch) = self.pending_launch.take() {
+            self.apply_launch_args(ctx, launch);
+        }
+
         // Adaptive repaint rate based on mode
         let repaint_interval = if self.is_overlay_mode {
             std::time::Duration::from_millis(100) // 10 FPS for overlay
@@ -293,61 +1719,245 @@ impl eframe::App for CabalHelperApp {
         };
         ctx.request_repaint_after(repaint_interval);
 
-        if !self.is_overlay_mode && self.last_window_always_on_top != self.settings.always_on_top {
-            let level = if self.settings.always_on_top {
+        if !self.is_overlay_mode
+            && self.last_window_always_on_top != self.settings.global.always_on_top
+        {
+            let level = if self.settings.global.always_on_top {
                 egui::WindowLevel::AlwaysOnTop
             } else {
                 egui::WindowLevel::Normal
             };
             ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
-            self.last_window_always_on_top = self.settings.always_on_top;
+            self.last_window_always_on_top = self.settings.global.always_on_top;
         }
 
-        self.sync_hotkey_capture_state();
+        self.sync_hotkeys();
 
-        // Emergency stop on global hotkey
-        if let Some(hotkey) = &self.registered_hotkey {
-            let hotkey_id = hotkey.id();
-            let mut triggered = false;
-            while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
-                if event.id == hotkey_id && event.state == HotKeyState::Pressed {
-                    triggered = true;
+        // Global hotkeys and mouse side buttons
+        let mut triggered: Vec<HotkeyActionId> = Vec::new();
+        while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+            if event.state != HotKeyState::Pressed {
+                continue;
+            }
+            triggered.extend(self.hotkey_registrar.action_for(event.id));
+        }
+        for button in self.mouse_watcher.poll() {
+            triggered.extend(self.hotkey_registrar.action_for_mouse(button));
+        }
+        let mut emergency_stop = false;
+        for action in triggered {
+            match action {
+                HotkeyActionId::EmergencyStop => emergency_stop = true,
+                HotkeyActionId::PauseResume => {
+                    // Pause everything unless every running tool already is, then resume them
+                    let pause = self
+                        .tools
+                        .iter()
+                        .any(|tool| tool.is_running() && !tool.is_paused());
+                    for tool in &mut self.tools {
+                        if tool.is_running() {
+                            tool.set_paused(pause);
+                        }
+                    }
+                    ctx.request_repaint();
+                }
+                HotkeyActionId::ToggleImageClicker => {
+                    self.toggle_tool(IMAGE_CLICKER_TOOL);
+                    ctx.request_repaint();
+                }
+                HotkeyActionId::ToggleCollectionFiller => {
+                    self.toggle_tool(COLLECTION_FILLER_TOOL);
+                    ctx.request_repaint();
+                }
+                HotkeyActionId::OneShotMacro(id) => {
+                    let Some(macro_index) = self.settings.macro_index(id) else {
+                        self.push_notice(LogLevel::Warn, tr!("hotkey.one_shot_missing"));
+                        continue;
+                    };
+                    let idx = FIRST_MACRO_TOOL + macro_index;
+                    if idx < self.tools.len() {
+                        if !self.tools[idx].is_running() {
+                            for tool in &mut self.tools {
+                                tool.stop(StopReason::OtherToolStarted);
+                            }
+                        }
+                        self.tools[idx].start_one_shot(&self.settings, self.game_hwnd);
+                        ctx.request_repaint();
+                    }
+                }
+                HotkeyActionId::ToggleOverlay => {
+                    if self.is_overlay_mode {
+                        self.leave_overlay_mode(ctx);
+                    } else {
+                        self.enter_overlay_mode(ctx);
+                    }
+                    ctx.request_repaint();
+                }
+                HotkeyActionId::ToggleClickThrough => {
+                    self.settings.global.overlay_click_through =
+                        !self.settings.global.overlay_click_through;
+                    ctx.request_repaint();
                 }
             }
-            if triggered {
+        }
+        if self.own_hwnd.is_none() {
+            self.own_hwnd = Self::viewport_hwnd(frame);
+        }
+        self.sync_click_through();
+        // Esc also cancels calibration; don't let that stop running tools too
+        let esc_cancels_calibration = self.settings.hotkey(HotkeyActionId::EmergencyStop).key
+            == Some(HotkeyKey::Escape)
+            && self.tools.iter().any(|tool| tool.is_calibrating());
+        if emergency_stop && !esc_cancels_calibration && self.emergency_stop_allowed() {
+            for tool in &mut self.tools {
+                tool.stop(StopReason::EmergencyHotkey);
+            }
+            ctx.request_repaint();
+        }
+
+        // Corner failsafe: the cursor is only polled here, so keep frames coming while
+        // anything runs. Deliberate, so it ignores "only when the game is focused".
+        if self.settings.global.corner_failsafe.enabled
+            && self.tools.iter().any(|tool| tool.is_running())
+        {
+            ctx.request_repaint_after(FAILSAFE_POLL_INTERVAL);
+            let failsafe = self.settings.global.corner_failsafe;
+            if let Some((x, y)) =
+                get_cursor_pos().filter(|&pos| failsafe.triggered(pos, primary_monitor_size()))
+            {
                 for tool in &mut self.tools {
-                    tool.stop();
+                    tool.stop(StopReason::CornerFailsafe);
                 }
+                self.push_notice(
+                    LogLevel::Warn,
+                    &format!(
+                        "Corner failsafe: cursor at ({}, {}), all tools stopped",
+                        x, y
+                    ),
+                );
                 ctx.request_repaint();
             }
         }
 
-        // Periodic check if window is still valid
+        self.handle_relog_events();
+
+        self.remote_control
+            .sync(&self.settings.global.remote_control, ctx);
+        self.handle_remote_commands();
+
+        self.run_schedule();
+
+        // Frames are only taken while something runs against the connected game
+        let record = self.settings.global.frame_recorder.enabled
+            && self.tools.iter().any(|tool| tool.is_running());
+        self.frame_recorder.sync(
+            self.game_hwnd.filter(|_| record),
+            self.settings.global.frame_recorder,
+        );
+
+        // Periodic check if window is still valid (and still the size tools started at)
         if self.last_window_check.elapsed() > std::time::Duration::from_secs(2) {
             if let Some(hwnd) = self.game_hwnd {
                 if !is_window_valid(hwnd) {
                     self.game_hwnd = None;
-                    self.status_message = "Connection Lost".to_string();
+                    self.status_message = tr!("header.connection_lost").to_string();
+                    self.flush_settings();
+                } else if let Some(size) = get_client_size(hwnd) {
+                    self.hold_resized_tools(size);
                 }
             }
             self.last_window_check = std::time::Instant::now();
         }
 
+        // Waits for the profile too when several matched the window title
+        if self.game_hwnd.is_some() && self.profile_choice.is_none() {
+            if let Some(name) = self.pending_launch_start.take() {
+                self.start_launch_tool(&name);
+                ctx.request_repaint();
+            }
+        }
+
+        self.tick_background_calibrations(ctx);
+
+        // Ctrl+Z undoes the last destructive edit (text fields keep their own undo)
+        let text_focused = ctx.memory(|m| m.focused().is_some());
+        if !text_focused && ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z))
+        {
+            self.undo_last();
+        }
+        if !text_focused && !self.is_overlay_mode {
+            self.handle_tab_shortcuts(ctx);
+        }
+
         let mut panel = egui::CentralPanel::default();
         if self.is_overlay_mode {
             panel = panel.frame(egui::Frame::none());
         }
 
+        if !self.is_overlay_mode {
+            if let Some(warning) = self.settings_warning.clone() {
+                egui::TopBottomPanel::top("settings_warning").show(ctx, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.colored_label(egui::Color32::from_rgb(255, 80, 80), warning);
+                        if ui.button(tr!("common.dismiss")).clicked() {
+                            self.settings_warning = None;
+                        }
+                    });
+                });
+            }
+        }
+
+        if !self.is_overlay_mode {
+            self.render_unfinished_runs(ctx);
+            self.render_resize_hold(ctx);
+            self.render_undo_toast(ctx);
+        }
+
         if !self.is_overlay_mode && self.show_log_panel {
-            let (log_snapshot, is_running) = self
-                .tool_names
-                .iter()
-                .position(|name| name == &self.selected_tab)
-                .and_then(|idx| self.tools.get(idx))
-                .map(|tool| (tool.get_log(), tool.is_running()))
-                .unwrap_or_default();
+            // Every tool's log in one timeline; the panel filters it
+            let mut log_snapshot: Vec<LogEntry> = self.app_notices.clone();
+            for tool in &self.tools {
+                log_snapshot.extend(tool.get_log());
+            }
+            log_snapshot.sort_by_key(|entry| entry.time);
+            let is_running = self.tools.iter().any(|tool| tool.is_running());
+            let mut log_sources = vec![APP_LOG_SOURCE.to_string()];
+            log_sources.extend(self.tool_names.iter().cloned());
+            let report_context = LogReportContext {
+                window: self.game_hwnd.and_then(|hwnd| {
+                    let (width, height) = get_client_size(hwnd)?;
+                    Some((self.status_message.clone(), width, height))
+                }),
+                active_tool: self
+                    .tool_names
+                    .get(self.selected_tool)
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+
+            crate::ui::log_panel::render_log_panel(
+                ctx,
+                &log_snapshot,
+                &log_sources,
+                &report_context,
+                is_running,
+                &mut self.settings.global.log_to_file,
+                &mut self.settings.global.log_retention_days,
+            );
+        }
 
-            crate::ui::log_panel::render_log_panel(ctx, &log_snapshot, is_running);
+        if !self.is_overlay_mode {
+            let running = self.running_tools();
+            if !running.is_empty() {
+                let action = egui::TopBottomPanel::bottom("running_panel")
+                    .show(ctx, |ui| render_running_panel(ui, &running))
+                    .inner;
+                if let RunningPanelAction::Stop(idx) = action {
+                    self.tools[idx].stop(StopReason::User);
+                }
+                // Keep the elapsed times ticking
+                ctx.request_repaint_after(std::time::Duration::from_secs(1));
+            }
         }
 
         panel.show(ctx, |ui| {
@@ -355,105 +1965,117 @@ impl eframe::App for CabalHelperApp {
                 // Overlay View
                 let response = ui.allocate_response(ui.available_size(), egui::Sense::drag());
                 if response.dragged() {
+                    self.overlay_dragged = true;
                     ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
                 }
+                self.sync_overlay_position(ctx);
 
                 ui.allocate_ui_at_rect(response.rect, |ui| {
-                    // Collect button states and actions first
-                    let mut tool_to_toggle: Option<usize> = None;
-                    let overlay_indices = self.overlay_tool_indices();
-
-                    // Horizontal layout - tight fit with borders
-                    ui.horizontal(|ui| {
-                        ui.style_mut().spacing.item_spacing = egui::vec2(0.0, 0.0);
-
-                        // Tool buttons with borders
-                        for idx in overlay_indices {
-                            let tool = &self.tools[idx];
-                            let is_running = tool.is_running();
-                            let name = self.tool_names.get(idx).map(|n| n.as_str()).unwrap_or("");
-                            let btn_text: String = name.chars().take(2).collect();
-                            let btn = egui::Button::new(
-                                egui::RichText::new(btn_text).size(16.0).strong().color(
-                                    if is_running {
-                                        egui::Color32::GREEN
-                                    } else {
-                                        egui::Color32::WHITE
-                                    },
-                                ),
-                            )
-                            .min_size(egui::vec2(36.0, 36.0))
-                            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 60, 60)));
-
-                            if ui.add(btn).clicked() {
-                                tool_to_toggle = Some(idx);
-                            }
-                        }
+                    let buttons: Vec<OverlayButton> = self
+                        .overlay_tool_indices()
+                        .into_iter()
+                        .map(|idx| OverlayButton {
+                            tool_index: idx,
+                            name: self.tool_names.get(idx).map(|n| n.as_str()).unwrap_or(""),
+                            is_running: self.tools[idx].is_running(),
+                            is_paused: self.tools[idx].is_paused(),
+                            is_calibrating: self.tools[idx].is_calibrating(),
+                        })
+                        .collect();
+                    let button_count = buttons.len();
+                    let status_idx = (0..self.tools.len())
+                        .find(|&idx| self.tools[idx].is_running())
+                        .unwrap_or(self.selected_tool);
+                    let status = OverlayStatus {
+                        name: self.tool_names[status_idx].as_str(),
+                        text: self.tools[status_idx].get_status(),
+                        is_running: self.tools[status_idx].is_running(),
+                    };
+
+                    // With several tools running, list each (the status line covers just one)
+                    let mut running = self.running_tools();
+                    if running.len() < 2 {
+                        running.clear();
+                    }
+                    let running_rows = running.len();
 
-                        // Settings button with border
-                        let btn = egui::Button::new(
-                            egui::RichText::new("⚙")
-                                .size(12.0)
-                                .color(egui::Color32::from_rgb(150, 150, 150)),
-                        )
-                        .min_size(egui::vec2(24.0, 36.0))
-                        .fill(egui::Color32::from_rgba_premultiplied(40, 40, 40, 180))
-                        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 60, 60)));
-
-                        if ui.add(btn).clicked() {
-                            self.is_overlay_mode = false;
-                            ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(true));
-                            let level = if self.settings.always_on_top {
-                                egui::WindowLevel::AlwaysOnTop
-                            } else {
-                                egui::WindowLevel::Normal
+                    let placement = self.settings.global.overlay_position;
+
+                    match render_overlay_toolbar(ui, &buttons, &status, &running, placement) {
+                        OverlayAction::ToggleTool(idx) => {
+                            self.toggle_tool(idx);
+                            ctx.request_repaint();
+                        }
+                        OverlayAction::CyclePlacement => {
+                            self.settings.global.overlay_position = match placement {
+                                OverlayPlacement::AutoSnap(anchor) => {
+                                    OverlayPlacement::AutoSnap(anchor.next())
+                                }
+                                OverlayPlacement::Manual(..) => {
+                                    OverlayPlacement::AutoSnap(OverlayAnchor::TopCenter)
+                                }
                             };
-                            ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
-                            self.last_window_always_on_top = self.settings.always_on_top;
-                            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(
-                                [760.0, 620.0].into(),
-                            ));
+                            self.overlay_dragged = false;
                         }
-                    });
-
-                    // Apply the toggle action after UI rendering
-                    if let Some(idx) = tool_to_toggle {
-                        let is_running = self.tools[idx].is_running();
-                        if is_running {
-                            self.tools[idx].stop();
-                        } else {
-                            // Stop all tools first
-                            for tool in &mut self.tools {
-                                tool.stop();
+                        OverlayAction::ExitOverlay => self.leave_overlay_mode(ctx),
+                        OverlayAction::StopTool(idx) => self.tools[idx].stop(StopReason::User),
+                        OverlayAction::None => {
+                            // "Show in overlay" flags and running tools change while the toolbar is up
+                            if button_count != self.overlay_button_count
+                                || running_rows != self.overlay_running_rows
+                            {
+                                self.overlay_button_count = button_count;
+                                self.overlay_running_rows = running_rows;
+                                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(
+                                    overlay_size(button_count, running_rows),
+                                ));
                             }
-                            // Start the requested tool
-                            self.tools[idx].start(&self.settings, self.game_hwnd);
-
-                            // Switch to this tool's tab
-                            self.selected_tab = self.tool_names[idx].clone();
                         }
-                        ctx.request_repaint();
                     }
                 });
             } else {
                 // Normal View
+                let active_profile = self.settings.active_profile.clone();
+                let profile_names = self.settings.profile_names();
+                let hotkey_rows: Vec<_> = self
+                    .settings
+                    .hotkey_bindings()
+                    .into_iter()
+                    .map(|(id, config)| (id, self.settings.hotkey_action_label(id), config))
+                    .collect();
+                let frame_recorder_error = self.frame_recorder.error();
                 let action = crate::ui::app_header::render_header(
                     ui,
+                    &mut self.settings,
                     &mut self.game_hwnd,
                     &mut self.status_message,
-                    &mut self.settings.always_on_top,
-                    &mut self.settings.emergency_stop_hotkey,
-                    &mut self.capturing_emergency_hotkey,
-                    self.hotkey_error.as_deref(),
+                    &mut self.capturing_hotkey,
+                    HeaderContext {
+                        ocr_model_report: &self.ocr_model_report,
+                        frame_recorder_error: frame_recorder_error.as_deref(),
+                        remote_listening: self.remote_control.is_listening(),
+                        remote_error: self.remote_control.error(),
+                        tool_names: &self.tool_names,
+                        next_scheduled: self.next_scheduled.as_deref(),
+                        hotkey_rows: &hotkey_rows,
+                        hotkey_problems: self.hotkey_registrar.problems(),
+                        hotkey_error: self.hotkey_error.as_deref(),
+                        active_profile: &active_profile,
+                        profile_names: &profile_names,
+                        unsaved_changes: self.settings_saver.is_dirty(),
+                        settings_error: self.settings_error.as_deref(),
+                    },
                 );
-                self.sync_hotkey_capture_state();
+                self.sync_hotkeys();
 
                 match action {
                     crate::ui::app_header::HeaderAction::Connect(hwnd) => {
                         self.game_hwnd = Some(hwnd);
+                        self.auto_select_profile(hwnd);
                     }
                     crate::ui::app_header::HeaderAction::Disconnect => {
                         self.game_hwnd = None;
+                        self.flush_settings();
                     }
                     crate::ui::app_header::HeaderAction::ToggleLog => {
                         let inner_rect = ctx.input(|i| i.viewport().inner_rect);
@@ -478,42 +2100,67 @@ impl eframe::App for CabalHelperApp {
                         ));
                     }
                     crate::ui::app_header::HeaderAction::ToggleOverlay => {
-                        self.is_overlay_mode = true;
-                        ctx.send_viewport_cmd(egui::ViewportCommand::Decorations(false));
-                        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
-                            egui::WindowLevel::AlwaysOnTop,
-                        ));
-
-                        // Dynamic overlay sizing
-                        let num_tools = self.overlay_tool_indices().len();
-                        let overlay_width = (num_tools as f32 * 36.0) + 24.0; // 36px per tool + 24px settings button
-                        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(
-                            [overlay_width, 36.0].into(),
-                        ));
-
-                        // Initial positioning: top-center of game window (one-time only)
-                        if let Some(game_hwnd) = self.game_hwnd {
-                            if let Some((x, y, w, _h)) =
-                                crate::core::window::get_client_rect_in_screen_coords(game_hwnd)
-                            {
-                                let target_x = x + (w / 2) - (overlay_width as i32 / 2);
-                                let target_y = y as f32;
-                                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(
-                                    [target_x as f32, target_y].into(),
-                                ));
-                            }
+                        self.enter_overlay_mode(ctx);
+                    }
+                    crate::ui::app_header::HeaderAction::SetHotkey(hotkey_action, config) => {
+                        self.settings.set_hotkey(hotkey_action, config);
+                        self.sync_hotkeys();
+                    }
+                    crate::ui::app_header::HeaderAction::Profile(profile_action) => {
+                        self.apply_profile_action(profile_action);
+                    }
+                    crate::ui::app_header::HeaderAction::SaveNow => {
+                        match self.settings_saver.save_now(&self.settings) {
+                            Ok(()) => self.settings_error = None,
+                            Err(err) => self.settings_error = Some(format!("Save failed: {}", err)),
                         }
                     }
+                    crate::ui::app_header::HeaderAction::ExportSettings => {
+                        self.export_settings();
+                    }
+                    crate::ui::app_header::HeaderAction::ImportSettings => {
+                        self.import_settings();
+                    }
                     crate::ui::app_header::HeaderAction::Help => {
                         self.show_help_window = true;
                     }
+                    crate::ui::app_header::HeaderAction::Statistics => {
+                        self.show_stats_window = true;
+                    }
+                    crate::ui::app_header::HeaderAction::TestSound(event) => {
+                        let sound = self
+                            .settings
+                            .global
+                            .event_sounds
+                            .get(&event)
+                            .cloned()
+                            .unwrap_or_default();
+                        if let Err(e) = play_sound(&sound) {
+                            self.push_notice(LogLevel::Warn, &e);
+                        }
+                    }
+                    crate::ui::app_header::HeaderAction::TestOcrModels => {
+                        self.ocr_model_report = test_load(&self.settings.global.ocr_models);
+                        for (ok, line) in self.ocr_model_report.clone() {
+                            let level = if ok { LogLevel::Info } else { LogLevel::Warn };
+                            self.push_notice(level, &line);
+                        }
+                    }
+                    crate::ui::app_header::HeaderAction::OpenFrameFolder => {
+                        if let Err(err) = self.frame_recorder.open_dir() {
+                            self.push_notice(LogLevel::Warn, &err);
+                        }
+                    }
                     crate::ui::app_header::HeaderAction::None => {}
                 }
 
+                self.render_profile_choice(ctx);
+                self.render_stats_window(ctx);
+
                 if self.show_help_window {
                     let help_viewport_id = egui::ViewportId::from_hash_of("help_window");
                     let help_builder = egui::ViewportBuilder::default()
-                        .with_title("Help")
+                        .with_title(tr!("window.help"))
                         .with_inner_size([640.0, 620.0])
                         .with_min_inner_size([520.0, 420.0])
                         .with_resizable(true);
@@ -558,8 +2205,23 @@ impl eframe::App for CabalHelperApp {
                                 se: 0.0,
                             };
 
-                            for (_idx, name) in self.tool_names.iter().enumerate() {
-                                let is_selected = self.selected_tab == *name;
+                            // Many macros: only the selected one keeps a tab, the rest are in the list
+                            let macro_count = self.settings.custom_macros.len();
+                            let use_macro_picker = macro_count > MACRO_TAB_LIMIT;
+                            let visible_tabs: Vec<usize> = (0..self.tools.len())
+                                .filter(|&idx| {
+                                    !use_macro_picker
+                                        || idx < FIRST_MACRO_TOOL
+                                        || idx == self.selected_tool
+                                })
+                                .collect();
+
+                            let mut tab_move: Option<(usize, usize)> = None;
+                            let mut clicked_tab: Option<usize> = None;
+                            for idx in visible_tabs {
+                                let name = &self.tool_names[idx];
+                                let is_selected = idx == self.selected_tool;
+                                let is_paused = self.tools[idx].is_paused();
                                 let (text_color, bg, stroke) = if is_selected {
                                     (
                                         egui::Color32::WHITE,
@@ -574,8 +2236,13 @@ impl eframe::App for CabalHelperApp {
                                     )
                                 };
 
+                                let label = if is_paused {
+                                    format!("⏸ {}", name)
+                                } else {
+                                    name.clone()
+                                };
                                 let btn = egui::Button::new(
-                                    egui::RichText::new(name)
+                                    egui::RichText::new(label)
                                         .size(13.0)
                                         .color(text_color)
                                         .strong(),
@@ -586,8 +2253,80 @@ impl eframe::App for CabalHelperApp {
                                 .rounding(tab_rounding)
                                 .min_size(egui::vec2(0.0, 30.0));
 
-                                if ui.add(btn).clicked() {
-                                    self.selected_tab = name.clone();
+                                let mut response = ui.add(btn);
+                                if is_paused {
+                                    response = response.on_hover_text(tr!("overlay.paused"));
+                                }
+                                if response.clicked() {
+                                    clicked_tab = Some(idx);
+                                }
+
+                                // Macro tabs: rename inline or move (built-in tabs stay in front)
+                                if let Some(macro_index) = idx.checked_sub(FIRST_MACRO_TOOL) {
+                                    response.context_menu(|ui| {
+                                        if let Some(named_macro) =
+                                            self.settings.custom_macros.get_mut(macro_index)
+                                        {
+                                            ui.label(tr!("tabs.name"));
+                                            ui.text_edit_singleline(&mut named_macro.name);
+                                            ui.separator();
+                                        }
+                                        let move_left = ui.add_enabled(
+                                            macro_index > 0,
+                                            egui::Button::new(tr!("tabs.move_left")),
+                                        );
+                                        if move_left.clicked() {
+                                            tab_move = Some((macro_index, macro_index - 1));
+                                            ui.close_menu();
+                                        }
+                                        let move_right = ui.add_enabled(
+                                            macro_index + 1 < macro_count,
+                                            egui::Button::new(tr!("tabs.move_right")),
+                                        );
+                                        if move_right.clicked() {
+                                            tab_move = Some((macro_index, macro_index + 1));
+                                            ui.close_menu();
+                                        }
+                                    });
+                                }
+                            }
+
+                            if let Some(idx) = clicked_tab {
+                                self.select_tool(idx);
+                            }
+                            if let Some((from, to)) = tab_move {
+                                self.settings.custom_macros.swap(from, to);
+                                self.apply_tools_change(ToolsChange::MacrosSwapped(from, to));
+                                self.settings_saver.mark_dirty();
+                            }
+
+                            if use_macro_picker {
+                                let (text_color, bg) = if self.show_macro_picker {
+                                    (egui::Color32::WHITE, egui::Color32::from_rgb(35, 35, 38))
+                                } else {
+                                    (
+                                        egui::Color32::from_rgb(170, 170, 170),
+                                        egui::Color32::from_rgb(22, 22, 24),
+                                    )
+                                };
+                                let btn = egui::Button::new(
+                                    egui::RichText::new(tr!(
+                                        "tabs.macro_list",
+                                        count = macro_count
+                                    ))
+                                    .size(13.0)
+                                    .color(text_color)
+                                    .strong(),
+                                )
+                                .frame(true)
+                                .fill(bg)
+                                .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(40, 40, 40)))
+                                .rounding(tab_rounding)
+                                .min_size(egui::vec2(0.0, 30.0));
+                                let response =
+                                    ui.add(btn).on_hover_text(tr!("tabs.macro_list_hint"));
+                                if response.clicked() {
+                                    self.show_macro_picker = !self.show_macro_picker;
                                 }
                             }
 
@@ -622,14 +2361,20 @@ impl eframe::App for CabalHelperApp {
                                     self.settings
                                         .custom_macros
                                         .push(NamedMacro::new(candidate.clone()));
-                                    self.rebuild_tools();
-                                    self.selected_tab = candidate;
-                                    self.settings.auto_save();
+                                    self.apply_tools_change(ToolsChange::MacroInserted(
+                                        self.settings.custom_macros.len() - 1,
+                                    ));
+                                    self.select_tool(self.tools.len() - 1);
+                                    self.settings_saver.mark_dirty();
                                 }
                             }
                         });
                     });
 
+                if self.show_macro_picker && self.settings.custom_macros.len() > MACRO_TAB_LIMIT {
+                    self.render_macro_picker_panel(ui);
+                }
+
                 ui.add_space(4.0);
 
                 // --- Main Content Area ---
@@ -643,20 +2388,25 @@ impl eframe::App for CabalHelperApp {
                          egui::ScrollArea::vertical()
                             .auto_shrink([false, false]) // Expand to fill
                             .show(ui, |ui| {
-                            // Find the selected tool by name and update it
-                            if let Some(idx) = self
-                                .tool_names
-                                .iter()
-                                .position(|name| name == &self.selected_tab)
-                            {
-                                if let Some(tool) = self.tools.get_mut(idx) {
-                                    tool.update(
-                                        ctx,
-                                        ui,
-                                        &mut self.settings,
-                                        self.game_hwnd,
-                                        self.hotkey_error.as_deref(),
-                                    );
+                            // Update the selected tool
+                            if let Some(tool) = self.tools.get_mut(self.selected_tool) {
+                                tool.update(
+                                    ctx,
+                                    ui,
+                                    &mut self.settings,
+                                    self.game_hwnd,
+                                    self.hotkey_error.as_deref(),
+                                );
+                                for entry in tool.take_undo_entries() {
+                                    self.undo_toast = Some((
+                                        entry.description(),
+                                        std::time::Instant::now(),
+                                    ));
+                                    self.undo_stack.push(entry);
+                                }
+                                if let Some(change) = tool.take_tools_change() {
+                                    self.apply_tools_change(change);
+                                    ctx.request_repaint();
                                 }
                             }
                         });
@@ -664,19 +2414,25 @@ impl eframe::App for CabalHelperApp {
 
                 self.ensure_unique_macro_names();
                 self.sync_tool_names_from_settings();
-                self.sync_hotkey_registration();
+                self.sync_hotkeys();
 
-                // Check if macro count changed (e.g., macro was deleted)
-                // We need to rebuild tools to stay in sync
-                // 2 hardcoded (Image Clicker, Collection Filler) + N Custom macros
-                let expected_tool_count = 2 + self.settings.custom_macros.len();
+                // Safety net: macro list edits arrive as a `ToolsChange`, anything else
+                // that changes the macro count falls back to a full rebuild
+                let expected_tool_count = FIRST_MACRO_TOOL + self.settings.custom_macros.len();
                 if self.tools.len() != expected_tool_count {
                     self.rebuild_tools();
                 }
-
-                // Auto-save settings after tool updates
-                self.settings.auto_save();
             }
         });
+
+        self.collect_usage_stats();
+
+        // Auto-save settings after tool updates (debounced)
+        self.settings_saver.detect_changes(&self.settings);
+        if let Err(err) = self.settings_saver.tick(&self.settings) {
+            self.settings_error = Some(format!("Save failed: {}", err));
+        }
+
+        self.sync_region_overlay();
     }
 }
@@ -1,22 +1,96 @@
 use crate::core::coords::denormalize_rect;
+use crate::core::error::HelperError;
+use crate::core::throttle::ActionThrottle;
 use crate::core::window::get_client_rect_in_screen_coords;
-use crate::settings::NormRect;
+use crate::core::worker::{push_log_entry, LogLevel, SharedLog};
+use crate::settings::{NormRect, TemplateMatchMode};
+use image::GrayImage;
 use rustautogui::{MatchMode, RustAutoGui};
+use std::collections::HashMap;
 use windows::Win32::Foundation::HWND;
 
+/// A window moving by more than this (in pixels) re-stores the templates
+const WINDOW_MOVE_THRESHOLD_PX: i32 = 3;
+
+/// Our own copy of a stored template, for searches that share one screen capture
+/// (and to store it again in `gui` when the window moves)
+pub struct StoredTemplate {
+    pub image: GrayImage,
+    // Client-relative search region in pixels; None searches the whole client area
+    pub region: Option<(i32, i32, i32, i32)>,
+    path: String,
+    area: Option<NormRect>,
+    mode: TemplateMatchMode,
+}
+
 /// Automation context that encapsulates common automation setup
 pub struct AutomationContext {
     pub gui: RustAutoGui,
     pub game_hwnd: HWND,
+    // Every click / key press of the run waits on this first
+    pub throttle: ActionThrottle,
+    // Same templates as stored in `gui`, by alias
+    pub templates: HashMap<String, StoredTemplate>,
+    // Client area origin on screen when the templates were stored; rustautogui keeps
+    // their search regions in screen coordinates
+    client_origin: Option<(i32, i32)>,
 }
 
 impl AutomationContext {
     /// Create a new automation context
-    pub fn new(game_hwnd: HWND) -> Result<Self, String> {
-        let gui = RustAutoGui::new(false)
-            .map_err(|e| format!("Failed to initialize RustAutoGui: {}", e))?;
+    pub fn new(game_hwnd: HWND, throttle: ActionThrottle) -> Result<Self, HelperError> {
+        let gui = RustAutoGui::new(false).map_err(|e| HelperError::CaptureFailed {
+            os_error: format!("failed to initialize RustAutoGui: {}", e),
+        })?;
 
-        Ok(Self { gui, game_hwnd })
+        Ok(Self {
+            gui,
+            game_hwnd,
+            throttle,
+            templates: HashMap::new(),
+            client_origin: get_client_rect_in_screen_coords(game_hwnd)
+                .map(|(left, top, _, _)| (left, top)),
+        })
+    }
+
+    /// Check whether the game window moved since the templates were stored and, if so,
+    /// store the ones with a search region again so rustautogui searches the new spot.
+    /// Call it once per loop iteration; a move is logged.
+    pub fn refresh(&mut self, log: &SharedLog) {
+        let Some((left, top, _, _)) = get_client_rect_in_screen_coords(self.game_hwnd) else {
+            return;
+        };
+        let Some((old_left, old_top)) = self.client_origin else {
+            self.client_origin = Some((left, top));
+            return;
+        };
+        let (dx, dy) = (left - old_left, top - old_top);
+        if dx.abs() <= WINDOW_MOVE_THRESHOLD_PX && dy.abs() <= WINDOW_MOVE_THRESHOLD_PX {
+            return;
+        }
+        self.client_origin = Some((left, top));
+
+        let regional: Vec<(String, String, Option<NormRect>, TemplateMatchMode)> = self
+            .templates
+            .iter()
+            .filter(|(_, template)| template.area.is_some())
+            .map(|(alias, t)| (alias.clone(), t.path.clone(), t.area, t.mode))
+            .collect();
+        push_log_entry(
+            log,
+            LogLevel::Info,
+            &format!(
+                "Game window moved by ({}, {}), updating {} template region(s)",
+                dx,
+                dy,
+                regional.len()
+            ),
+        );
+        for (alias, path, area, mode) in regional {
+            if let Err(e) = self.store_template(&path, area, &alias, mode) {
+                push_log_entry(log, LogLevel::Warn, &e.to_string());
+            }
+        }
     }
 
     /// Convert normalized window-relative area to screen region
@@ -32,23 +106,55 @@ impl AutomationContext {
         ))
     }
 
-    /// Store a template with a window-relative region
+    /// Store a template with a window-relative region, searched with `mode`
     pub fn store_template(
         &mut self,
         path: &str,
         window_relative_region: Option<NormRect>,
         alias: &str,
-    ) -> Result<(), String> {
+        mode: TemplateMatchMode,
+    ) -> Result<(), HelperError> {
         let screen_region = match window_relative_region {
-            Some(region) => Some(
-                self.to_screen_region(region)
-                    .ok_or_else(|| "Failed to convert region".to_string())?,
-            ),
+            Some(region) => Some(self.to_screen_region(region).ok_or_else(|| {
+                HelperError::InvalidRegion {
+                    region: denormalize_rect(
+                        self.game_hwnd,
+                        region.0,
+                        region.1,
+                        region.2,
+                        region.3,
+                    )
+                    .unwrap_or((0, 0, 0, 0)),
+                }
+            })?),
             None => None,
         };
+        let load_failed = |e: &dyn std::fmt::Display| HelperError::TemplateLoad {
+            path: path.to_string(),
+            source: e.to_string(),
+        };
 
+        let match_mode = match mode {
+            TemplateMatchMode::Segmented => MatchMode::Segmented,
+            TemplateMatchMode::Fft => MatchMode::FFT,
+        };
         self.gui
-            .store_template_from_file(path, screen_region, MatchMode::Segmented, alias)
-            .map_err(|e| format!("Failed to load template '{}': {}", alias, e))
+            .store_template_from_file(path, screen_region, match_mode, alias)
+            .map_err(|e| load_failed(&e))?;
+
+        let image = image::open(path).map_err(|e| load_failed(&e))?.to_luma8();
+        let region = window_relative_region
+            .and_then(|area| denormalize_rect(self.game_hwnd, area.0, area.1, area.2, area.3));
+        self.templates.insert(
+            alias.to_string(),
+            StoredTemplate {
+                image,
+                region,
+                path: path.to_string(),
+                area: window_relative_region,
+                mode,
+            },
+        );
+        Ok(())
     }
 }
@@ -1,32 +1,463 @@
+use crate::automation::context::AutomationContext;
+use crate::core::error::HelperError;
+use crate::core::screen_capture::capture_window_region;
+use crate::core::window::{get_client_rect_in_screen_coords, get_client_size};
+use image::{imageops, DynamicImage, GrayImage, RgbaImage};
 use rustautogui::RustAutoGui;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use windows::Win32::Foundation::HWND;
+
+/// How often `wait_for_template_gone` searches again
+const GONE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Matches closer than this (in pixels) are one hit unless the caller picks a radius
+pub const DEFAULT_DEDUPE_RADIUS: f32 = 5.0;
+
+/// One hit of a template search, in screen coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemplateMatch {
+    pub x: u32,
+    pub y: u32,
+    /// Match confidence (higher is better)
+    pub score: f64,
+}
+
+impl TemplateMatch {
+    pub fn pos(&self) -> (u32, u32) {
+        (self.x, self.y)
+    }
+}
 
 /// Find red dots (or any stored template) on screen using a pre-stored template
-/// Returns a list of (x, y) positions in screen coordinates
+/// Returns the matches best first, with hits within `dedupe_radius` pixels of a better one
+/// dropped (`None` uses DEFAULT_DEDUPE_RADIUS)
 pub fn find_stored_template(
     gui: &mut RustAutoGui,
     alias: &str,
-    precision: f32
-) -> Option<Vec<(u32, u32)>> {
-    
+    precision: f32,
+    dedupe_radius: Option<f32>,
+) -> Option<Vec<TemplateMatch>> {
     match gui.find_stored_image_on_screen(precision, alias) {
         Ok(Some(matches)) => {
-            let filtered: Vec<(u32, u32)> = matches.iter()
-                .map(|(x, y, _score)| (*x, *y))
+            let matches: Vec<TemplateMatch> = matches
+                .iter()
+                .map(|&(x, y, score)| TemplateMatch { x, y, score })
                 .collect();
-            
-            if filtered.is_empty() {
+            let deduped = dedupe_matches(matches, dedupe_radius.unwrap_or(DEFAULT_DEDUPE_RADIUS));
+
+            if deduped.is_empty() {
                 None
             } else {
-                Some(filtered)
+                Some(deduped)
+            }
+        }
+        Ok(None) => None,
+        Err(_) => None,
+    }
+}
+
+/// Matches per alias from one capture of the game window
+#[derive(Debug, Default)]
+pub struct TemplateScan {
+    matches: HashMap<String, Vec<TemplateMatch>>,
+}
+
+impl TemplateScan {
+    /// The alias' matches best first; empty when nothing was found
+    pub fn take(&mut self, alias: &str) -> Vec<TemplateMatch> {
+        self.matches.remove(alias).unwrap_or_default()
+    }
+
+    /// Record matches found some other way (e.g. `find_color_regions`)
+    pub fn insert(&mut self, alias: &str, matches: Vec<TemplateMatch>) {
+        self.matches.insert(alias.to_string(), matches);
+    }
+}
+
+/// Search several stored templates against a single capture of the game window.
+/// `find_stored_template` captures the screen on every call, so probing three aliases
+/// in a row costs three captures; this captures the area covering all their regions
+/// once and matches each template inside its own region.
+/// A failed capture or an unknown alias gives no matches, like `find_stored_template`.
+pub fn scan_stored_templates(
+    ctx: &AutomationContext,
+    aliases: &[&str],
+    precision: f32,
+    dedupe_radius: Option<f32>,
+) -> TemplateScan {
+    let Some((client_w, client_h)) = get_client_size(ctx.game_hwnd) else {
+        return TemplateScan::default();
+    };
+    let Some((client_left, client_top, _, _)) = get_client_rect_in_screen_coords(ctx.game_hwnd)
+    else {
+        return TemplateScan::default();
+    };
+
+    let queries: Vec<(&str, &GrayImage, (i32, i32, i32, i32))> = aliases
+        .iter()
+        .filter_map(|&alias| {
+            let template = ctx.templates.get(alias)?;
+            let region = template.region.unwrap_or((0, 0, client_w, client_h));
+            Some((alias, &template.image, region))
+        })
+        .collect();
+    if queries.is_empty() {
+        return TemplateScan::default();
+    }
+
+    // One capture covering every region
+    let left = queries.iter().map(|q| q.2 .0).min().unwrap_or(0);
+    let top = queries.iter().map(|q| q.2 .1).min().unwrap_or(0);
+    let right = queries.iter().map(|q| q.2 .0 + q.2 .2).max().unwrap_or(0);
+    let bottom = queries.iter().map(|q| q.2 .1 + q.2 .3).max().unwrap_or(0);
+    let (width, height) = ((right - left).max(1) as u32, (bottom - top).max(1) as u32);
+    let Ok(frame) = capture_window_region(ctx.game_hwnd, (left, top, width as i32, height as i32))
+    else {
+        return TemplateScan::default();
+    };
+    let mut frame = DynamicImage::ImageRgba8(frame).to_luma8();
+    // Windows Graphics Capture returns physical pixels; bring a DPI-scaled frame back
+    // to client pixels so the templates match at their own size
+    if frame.dimensions() != (width, height) {
+        frame = imageops::resize(&frame, width, height, imageops::FilterType::Triangle);
+    }
+
+    let radius = dedupe_radius.unwrap_or(DEFAULT_DEDUPE_RADIUS);
+    match_in_frame(
+        &frame,
+        (left, top),
+        (client_left, client_top),
+        queries,
+        precision,
+        radius,
+    )
+}
+
+/// Match each `(alias, template, client region)` inside its region of `frame`, a capture
+/// whose top-left is at client position `frame_origin`. Matches are template centres in
+/// screen coordinates (`client_origin` is the client area's top-left on screen).
+fn match_in_frame(
+    frame: &GrayImage,
+    frame_origin: (i32, i32),
+    client_origin: (i32, i32),
+    queries: Vec<(&str, &GrayImage, (i32, i32, i32, i32))>,
+    precision: f32,
+    radius: f32,
+) -> TemplateScan {
+    let (left, top) = frame_origin;
+    let (client_left, client_top) = client_origin;
+    let mut scan = TemplateScan::default();
+    for (alias, template, (x, y, w, h)) in queries {
+        let (crop_x, crop_y) = ((x - left).max(0) as u32, (y - top).max(0) as u32);
+        let area =
+            imageops::crop_imm(frame, crop_x, crop_y, w.max(0) as u32, h.max(0) as u32).to_image();
+        let (half_w, half_h) = (template.width() / 2, template.height() / 2);
+        // Report the template centre in screen coordinates, as rustautogui does
+        let matches = match_template(&area, template, precision as f64)
+            .into_iter()
+            .map(|(mx, my, score)| TemplateMatch {
+                x: (client_left + x) as u32 + mx + half_w,
+                y: (client_top + y) as u32 + my + half_h,
+                score,
+            })
+            .collect();
+        scan.insert(alias, dedupe_matches(matches, radius));
+    }
+    scan
+}
+
+/// Zero-mean normalized cross-correlation of `template` at every position in `image`.
+/// Returns the top-left corner and score of each position scoring at least `min_score`.
+fn match_template(image: &GrayImage, template: &GrayImage, min_score: f64) -> Vec<(u32, u32, f64)> {
+    let (image_w, image_h) = image.dimensions();
+    let (template_w, template_h) = template.dimensions();
+    if template_w == 0 || template_h == 0 || template_w > image_w || template_h > image_h {
+        return Vec::new();
+    }
+
+    let n = (template_w * template_h) as f64;
+    let template_mean = template.as_raw().iter().map(|&p| p as f64).sum::<f64>() / n;
+    let template_dev: Vec<f64> = template
+        .as_raw()
+        .iter()
+        .map(|&p| p as f64 - template_mean)
+        .collect();
+    let template_norm: f64 = template_dev.iter().map(|d| d * d).sum();
+    // A flat template correlates equally with everything
+    if template_norm == 0.0 {
+        return Vec::new();
+    }
+
+    // Summed-area tables give each window's sum and sum of squares in O(1)
+    let stride = image_w as usize + 1;
+    let mut sums = vec![0.0f64; stride * (image_h as usize + 1)];
+    let mut squares = sums.clone();
+    for y in 0..image_h as usize {
+        let (mut row_sum, mut row_squares) = (0.0, 0.0);
+        for x in 0..image_w as usize {
+            let p = image.as_raw()[y * image_w as usize + x] as f64;
+            row_sum += p;
+            row_squares += p * p;
+            sums[(y + 1) * stride + x + 1] = sums[y * stride + x + 1] + row_sum;
+            squares[(y + 1) * stride + x + 1] = squares[y * stride + x + 1] + row_squares;
+        }
+    }
+    let window = |table: &[f64], x: usize, y: usize| {
+        let (x1, y1) = (x + template_w as usize, y + template_h as usize);
+        table[y1 * stride + x1] - table[y * stride + x1] - table[y1 * stride + x]
+            + table[y * stride + x]
+    };
+
+    let mut found = Vec::new();
+    for y in 0..=(image_h - template_h) as usize {
+        for x in 0..=(image_w - template_w) as usize {
+            let sum = window(&sums, x, y);
+            let image_norm = window(&squares, x, y) - sum * sum / n;
+            if image_norm <= f64::EPSILON {
+                continue;
+            }
+            // The template deviations sum to zero, so the window mean drops out
+            let cross: f64 = template_dev
+                .chunks(template_w as usize)
+                .enumerate()
+                .map(|(row, devs)| {
+                    let start = (y + row) * image_w as usize + x;
+                    image.as_raw()[start..start + template_w as usize]
+                        .iter()
+                        .zip(devs)
+                        .map(|(&p, d)| p as f64 * d)
+                        .sum::<f64>()
+                })
+                .sum();
+            let score = cross / (template_norm * image_norm).sqrt();
+            if score >= min_score {
+                found.push((x as u32, y as u32, score));
             }
-        },
-        Ok(None) => {
-            None
-        },
-        Err(_) => {
-            None
         }
     }
+    found
+}
+
+/// Find blobs of `target_rgb` in a client-relative `region` of the window without a
+/// template: pixels within `tolerance` of the target on every channel are grouped into
+/// 8-connected blobs, and blobs of at least `min_blob_size` pixels are returned as their
+/// centroids in window (client) coordinates, largest blob first.
+pub fn find_color_regions(
+    hwnd: HWND,
+    region: (i32, i32, i32, i32),
+    target_rgb: [u8; 3],
+    tolerance: u8,
+    min_blob_size: u32,
+) -> Result<Vec<(i32, i32)>, HelperError> {
+    let (x, y, w, h) = region;
+    let frame = capture_window_region(hwnd, region)?;
+    // The capture is in physical pixels; scale the centroids back to client pixels
+    let scale_x = w as f32 / frame.width().max(1) as f32;
+    let scale_y = h as f32 / frame.height().max(1) as f32;
+    Ok(color_blobs(&frame, target_rgb, tolerance, min_blob_size)
+        .into_iter()
+        .map(|(cx, cy)| {
+            (
+                x + (cx * scale_x).round() as i32,
+                y + (cy * scale_y).round() as i32,
+            )
+        })
+        .collect())
+}
+
+/// Find text labels drawn in any of `colors` (color, per-channel tolerance) in a
+/// client-relative `region`, from one capture: matching blobs of at least
+/// `min_blob_size` pixels (the letters) are merged into one label while they are
+/// within `merge_distance` client pixels of each other on the same line. Returns
+/// the label centers in window (client) coordinates, top to bottom.
+pub fn find_color_labels(
+    hwnd: HWND,
+    region: (i32, i32, i32, i32),
+    colors: &[([u8; 3], u8)],
+    min_blob_size: u32,
+    merge_distance: f32,
+) -> Result<Vec<(i32, i32)>, HelperError> {
+    let (x, y, w, h) = region;
+    let frame = capture_window_region(hwnd, region)?;
+    let scale_x = w as f32 / frame.width().max(1) as f32;
+    let scale_y = h as f32 / frame.height().max(1) as f32;
+    let letters: Vec<(f32, f32)> = blobs_where(&frame, min_blob_size, |pixel| {
+        colors
+            .iter()
+            .any(|&(target, tolerance)| color_matches(pixel, target, tolerance))
+    })
+    .into_iter()
+    .map(|(cx, cy)| (cx * scale_x, cy * scale_y))
+    .collect();
+    Ok(cluster_labels(&letters, merge_distance)
+        .into_iter()
+        .map(|(cx, cy)| (x + cx.round() as i32, y + cy.round() as i32))
+        .collect())
+}
+
+/// Color of one client-relative pixel of the window, read in the background like
+/// `find_color_regions`
+pub fn read_pixel(hwnd: HWND, x: i32, y: i32) -> Result<[u8; 3], HelperError> {
+    let frame = capture_window_region(hwnd, (x, y, 1, 1))?;
+    let [r, g, b, _] = frame
+        .get_pixel_checked(0, 0)
+        .ok_or(HelperError::InvalidRegion {
+            region: (x, y, 1, 1),
+        })?
+        .0;
+    Ok([r, g, b])
+}
+
+/// Whether each channel of `pixel` is within `tolerance` of `target`
+pub fn color_matches(pixel: [u8; 3], target: [u8; 3], tolerance: u8) -> bool {
+    pixel
+        .iter()
+        .zip(target)
+        .all(|(&channel, wanted)| channel.abs_diff(wanted) <= tolerance)
+}
+
+/// Centroids of the 8-connected blobs of matching pixels, largest first
+fn color_blobs(
+    image: &RgbaImage,
+    target: [u8; 3],
+    tolerance: u8,
+    min_blob_size: u32,
+) -> Vec<(f32, f32)> {
+    blobs_where(image, min_blob_size, |pixel| {
+        color_matches(pixel, target, tolerance)
+    })
+}
+
+/// Centroids of the 8-connected blobs of pixels accepted by `is_match`, largest first
+fn blobs_where(
+    image: &RgbaImage,
+    min_blob_size: u32,
+    is_match: impl Fn([u8; 3]) -> bool,
+) -> Vec<(f32, f32)> {
+    let (width, height) = image.dimensions();
+    let index = |x: u32, y: u32| (y * width + x) as usize;
+    let mut matching: Vec<bool> = image
+        .pixels()
+        .map(|p| is_match([p.0[0], p.0[1], p.0[2]]))
+        .collect();
+
+    let mut blobs: Vec<(u32, f32, f32)> = Vec::new();
+    let mut stack = Vec::new();
+    for start_y in 0..height {
+        for start_x in 0..width {
+            if !matching[index(start_x, start_y)] {
+                continue;
+            }
+            // Flood fill, clearing pixels as they join the blob
+            matching[index(start_x, start_y)] = false;
+            stack.push((start_x, start_y));
+            let (mut size, mut sum_x, mut sum_y) = (0u32, 0u64, 0u64);
+            while let Some((x, y)) = stack.pop() {
+                size += 1;
+                sum_x += x as u64;
+                sum_y += y as u64;
+                for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                    for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                        if matching[index(nx, ny)] {
+                            matching[index(nx, ny)] = false;
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+            }
+            if size >= min_blob_size.max(1) {
+                blobs.push((size, sum_x as f32 / size as f32, sum_y as f32 / size as f32));
+            }
+        }
+    }
+
+    blobs.sort_by(|a, b| b.0.cmp(&a.0));
+    blobs.into_iter().map(|(_, x, y)| (x, y)).collect()
+}
+
+/// Group letter centroids into labels: two letters belong to the same label when they
+/// are at most `merge_distance` apart horizontally and half that vertically (one line
+/// of text), directly or through other letters. Returns the label centers, top to
+/// bottom and left to right.
+fn cluster_labels(letters: &[(f32, f32)], merge_distance: f32) -> Vec<(f32, f32)> {
+    // Union-find over the letters
+    let mut parent: Vec<usize> = (0..letters.len()).collect();
+    for (a, &(ax, ay)) in letters.iter().enumerate() {
+        for (b, &(bx, by)) in letters.iter().enumerate().skip(a + 1) {
+            let (dx, dy) = ((ax - bx).abs(), (ay - by).abs());
+            if dx <= merge_distance && dy <= merge_distance / 2.0 {
+                let (ra, rb) = (label_root(&mut parent, a), label_root(&mut parent, b));
+                parent[ra] = rb;
+            }
+        }
+    }
+
+    let mut sums: HashMap<usize, (f32, f32, u32)> = HashMap::new();
+    for (i, &(x, y)) in letters.iter().enumerate() {
+        let sum = sums
+            .entry(label_root(&mut parent, i))
+            .or_insert((0.0, 0.0, 0));
+        sum.0 += x;
+        sum.1 += y;
+        sum.2 += 1;
+    }
+    let mut labels: Vec<(f32, f32)> = sums
+        .into_values()
+        .map(|(x, y, count)| (x / count as f32, y / count as f32))
+        .collect();
+    labels.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.total_cmp(&b.0)));
+    labels
+}
+
+/// Representative letter of `i`'s label in `cluster_labels`
+fn label_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Search for a stored template until it no longer matches (a dialog closed, a loading
+/// screen ended). Returns true once it is gone; false when `timeout_ms` passes with it
+/// still on screen or `running` is cleared.
+pub fn wait_for_template_gone(
+    gui: &mut RustAutoGui,
+    alias: &str,
+    precision: f32,
+    timeout_ms: u64,
+    running: &AtomicBool,
+) -> bool {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    while running.load(Ordering::Relaxed) {
+        if find_stored_template(gui, alias, precision, None).is_none() {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(GONE_POLL_INTERVAL);
+    }
+    false
+}
+
+/// Merge matches of the same spot: sorted best first, each match is kept only if no
+/// better one lies within `radius` pixels. The template matcher reports the same dot
+/// a few pixels apart, which would otherwise be clicked several times.
+pub fn dedupe_matches(mut matches: Vec<TemplateMatch>, radius: f32) -> Vec<TemplateMatch> {
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let mut kept: Vec<TemplateMatch> = Vec::with_capacity(matches.len());
+    for candidate in matches {
+        if !kept
+            .iter()
+            .any(|better| is_position_near(better.pos(), candidate.pos(), radius))
+        {
+            kept.push(candidate);
+        }
+    }
+    kept
 }
 
 /// Check if a position is near another position (within threshold pixels)
@@ -39,15 +470,15 @@ pub fn is_position_near(pos1: (u32, u32), pos2: (u32, u32), threshold: f32) -> b
 /// Filter detected positions by color, keeping only red dots
 /// This solves the grayscale detection issue where grey dots are detected as red dots
 pub fn filter_red_dots(
-    positions: Vec<(u32, u32)>,
+    matches: Vec<TemplateMatch>,
     min_red: u8,
     red_dominance: u8
-) -> Vec<(u32, u32)> {
+) -> Vec<TemplateMatch> {
     use crate::core::window::get_pixel_color;
     
-    positions.into_iter()
-        .filter(|(x, y)| {
-            if let Some((r, g, b)) = get_pixel_color(*x as i32, *y as i32) {
+    matches.into_iter()
+        .filter(|m| {
+            if let Some((r, g, b)) = get_pixel_color(m.x as i32, m.y as i32) {
                 // Check if pixel is red:
                 // 1. Red channel must be above minimum threshold
                 // 2. Red must be significantly brighter than green and blue
@@ -59,3 +490,168 @@ pub fn filter_red_dots(
         .collect()
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(x: u32, y: u32, score: f64) -> TemplateMatch {
+        TemplateMatch { x, y, score }
+    }
+
+    /// A 3x3 cross on a flat background
+    fn cross() -> GrayImage {
+        GrayImage::from_fn(3, 3, |x, y| {
+            image::Luma([if x == 1 || y == 1 { 220 } else { 30 }])
+        })
+    }
+
+    #[test]
+    fn test_match_template_finds_every_copy() {
+        let mut image = GrayImage::from_pixel(20, 12, image::Luma([30]));
+        imageops::replace(&mut image, &cross(), 2, 3);
+        imageops::replace(&mut image, &cross(), 14, 7);
+
+        let found: Vec<(u32, u32)> = match_template(&image, &cross(), 0.95)
+            .into_iter()
+            .map(|(x, y, score)| {
+                assert!(score > 0.999);
+                (x, y)
+            })
+            .collect();
+        assert_eq!(found, vec![(2, 3), (14, 7)]);
+    }
+
+    #[test]
+    fn test_match_in_frame_keeps_each_template_in_its_region() {
+        // Frame captured from client (10, 20); crosses at frame (2, 3) and (14, 7)
+        let mut frame = GrayImage::from_pixel(20, 12, image::Luma([30]));
+        imageops::replace(&mut frame, &cross(), 2, 3);
+        imageops::replace(&mut frame, &cross(), 14, 7);
+        let template = cross();
+        let queries = vec![
+            ("left", &template, (10, 20, 10, 12)),
+            ("right", &template, (20, 20, 10, 12)),
+            ("empty", &template, (10, 30, 5, 2)),
+        ];
+
+        let mut scan = match_in_frame(&frame, (10, 20), (100, 200), queries, 0.95, 5.0);
+        let mut positions = |alias: &str| -> Vec<(u32, u32)> {
+            scan.take(alias).iter().map(TemplateMatch::pos).collect()
+        };
+        // Screen = client origin + client position of the match + half the template
+        assert_eq!(positions("left"), vec![(113, 224)]);
+        assert_eq!(positions("right"), vec![(125, 228)]);
+        assert!(positions("empty").is_empty());
+    }
+
+    #[test]
+    fn test_match_template_skips_flat_input() {
+        let flat = GrayImage::from_pixel(3, 3, image::Luma([120]));
+        let image = GrayImage::from_pixel(10, 10, image::Luma([120]));
+
+        // Neither a flat template nor a flat image area counts as a match
+        assert!(match_template(&image, &flat, 0.5).is_empty());
+        assert!(match_template(&image, &cross(), 0.5).is_empty());
+        // A template larger than the area can't match
+        assert!(match_template(&cross(), &image, 0.5).is_empty());
+    }
+
+    const RED: image::Rgba<u8> = image::Rgba([210, 30, 30, 255]);
+
+    fn paint(image: &mut RgbaImage, pixels: &[(u32, u32)], color: image::Rgba<u8>) {
+        for &(x, y) in pixels {
+            image.put_pixel(x, y, color);
+        }
+    }
+
+    #[test]
+    fn test_color_blobs_merge_touching_pixels() {
+        let mut image = RgbaImage::from_pixel(12, 8, image::Rgba([40, 40, 40, 255]));
+        // A 2x2 square and a diagonal neighbour: one 5-pixel blob
+        paint(&mut image, &[(1, 1), (2, 1), (1, 2), (2, 2), (3, 3)], RED);
+        // A separate 3-pixel line
+        paint(&mut image, &[(9, 5), (10, 5), (11, 5)], RED);
+        // A single stray pixel, below the minimum size
+        paint(&mut image, &[(6, 0)], RED);
+
+        let blobs = color_blobs(&image, [210, 30, 30], 0, 2);
+        assert_eq!(blobs.len(), 2);
+        // Largest first, centroid of the five pixels
+        assert_eq!(blobs[0], (9.0 / 5.0, 9.0 / 5.0));
+        assert_eq!(blobs[1], (10.0, 5.0));
+    }
+
+    #[test]
+    fn test_color_tolerance_is_per_channel() {
+        let target = [200, 40, 40];
+        assert!(color_matches([200, 40, 40], target, 0));
+        assert!(color_matches([180, 60, 20], target, 20));
+        // One channel past the tolerance is enough to reject
+        assert!(!color_matches([180, 61, 40], target, 20));
+        // No wrap-around at the ends of the range
+        assert!(color_matches([255, 0, 0], [250, 5, 5], 5));
+        assert!(!color_matches([0, 0, 0], [255, 255, 255], 254));
+
+        // Shaded pixels join the blob only within the tolerance
+        let mut image = RgbaImage::from_pixel(4, 1, image::Rgba([0, 0, 0, 255]));
+        paint(
+            &mut image,
+            &[(0, 0), (1, 0)],
+            image::Rgba([200, 40, 40, 255]),
+        );
+        paint(&mut image, &[(2, 0)], image::Rgba([185, 50, 35, 255]));
+        assert_eq!(color_blobs(&image, target, 10, 1), vec![(0.5, 0.0)]);
+        assert_eq!(color_blobs(&image, target, 15, 1), vec![(1.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_cluster_labels_joins_letters_of_one_line() {
+        let letters = [
+            // "Sword": five letters 6 px apart
+            (10.0, 20.0),
+            (16.0, 20.0),
+            (22.0, 21.0),
+            (28.0, 20.0),
+            (34.0, 19.0),
+            // Another label on the next line
+            (12.0, 40.0),
+            (18.0, 40.0),
+            // Far to the right on the first line
+            (90.0, 20.0),
+        ];
+        let labels = cluster_labels(&letters, 8.0);
+        assert_eq!(labels, vec![(22.0, 20.0), (90.0, 20.0), (15.0, 40.0)]);
+
+        // Too far apart to be one word
+        assert_eq!(cluster_labels(&letters[..2], 5.0).len(), 2);
+        assert!(cluster_labels(&[], 8.0).is_empty());
+    }
+
+    #[test]
+    fn test_dedupe_keeps_best_of_each_cluster() {
+        let matches = vec![
+            hit(100, 100, 0.91),
+            hit(102, 101, 0.97),
+            hit(101, 99, 0.93),
+            hit(300, 50, 0.88),
+            hit(303, 52, 0.90),
+        ];
+        let deduped = dedupe_matches(matches, DEFAULT_DEDUPE_RADIUS);
+        assert_eq!(deduped, vec![hit(102, 101, 0.97), hit(303, 52, 0.90)]);
+    }
+
+    #[test]
+    fn test_dedupe_orders_by_score_and_respects_radius() {
+        let matches = vec![hit(0, 0, 0.80), hit(10, 0, 0.95), hit(20, 0, 0.85)];
+
+        // 10 px apart: separate hits, best first
+        let deduped = dedupe_matches(matches.clone(), 5.0);
+        assert_eq!(
+            deduped,
+            vec![hit(10, 0, 0.95), hit(20, 0, 0.85), hit(0, 0, 0.80)]
+        );
+
+        // A wider radius folds both neighbours into the best match
+        assert_eq!(dedupe_matches(matches, 10.0), vec![hit(10, 0, 0.95)]);
+    }
+}
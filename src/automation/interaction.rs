@@ -1,6 +1,8 @@
+use crate::core::click_guard::allow_screen_click;
 use crate::core::coords::{denormalize_point, denormalize_rect};
-use crate::core::window::client_to_screen_coords;
-use crate::settings::{NormPoint, NormRect};
+use crate::core::input::{click_at_position_post, scroll_at_position_post};
+use crate::core::window::{client_to_screen_coords, screen_to_window_coords};
+use crate::settings::{NormPoint, NormRect, ScrollDirection};
 use rustautogui::RustAutoGui;
 use std::thread;
 use std::time::Duration;
@@ -13,8 +15,11 @@ pub fn delay_ms(ms: u64) {
     }
 }
 
-/// Click at screen coordinates (with retry logic from Python version)
-pub fn click_at_screen(gui: &mut RustAutoGui, x: u32, y: u32) {
+/// Click at screen coordinates (with retry logic from Python version), if they lie over the game
+pub fn click_at_screen(gui: &mut RustAutoGui, game_hwnd: HWND, x: u32, y: u32) {
+    if !allow_screen_click(game_hwnd, x as i32, y as i32) {
+        return;
+    }
     // Python does 2 click attempts with 50ms delay
     for attempt in 0..2 {
         // Move mouse to position (screen coordinates)
@@ -43,7 +48,10 @@ pub fn click_at_screen(gui: &mut RustAutoGui, x: u32, y: u32) {
 }
 
 /// Right click at screen coordinates (with retry logic from Python version)
-pub fn right_click_at_screen(gui: &mut RustAutoGui, x: u32, y: u32) {
+pub fn right_click_at_screen(gui: &mut RustAutoGui, game_hwnd: HWND, x: u32, y: u32) {
+    if !allow_screen_click(game_hwnd, x as i32, y as i32) {
+        return;
+    }
     // Python does 2 click attempts with 50ms delay
     for attempt in 0..2 {
         // Move mouse to position (screen coordinates)
@@ -72,7 +80,10 @@ pub fn right_click_at_screen(gui: &mut RustAutoGui, x: u32, y: u32) {
 }
 
 /// Middle click at screen coordinates (with retry logic from Python version)
-pub fn middle_click_at_screen(gui: &mut RustAutoGui, x: u32, y: u32) {
+pub fn middle_click_at_screen(gui: &mut RustAutoGui, game_hwnd: HWND, x: u32, y: u32) {
+    if !allow_screen_click(game_hwnd, x as i32, y as i32) {
+        return;
+    }
     // Python does 2 click attempts with 50ms delay
     for attempt in 0..2 {
         // Move mouse to position (screen coordinates)
@@ -110,12 +121,68 @@ pub fn click_at_window_pos(gui: &mut RustAutoGui, game_hwnd: HWND, pos: NormPoin
         Some(coords) => coords,
         None => return false,
     };
-    click_at_screen(gui, screen_x as u32, screen_y as u32);
+    click_at_screen(gui, game_hwnd, screen_x as u32, screen_y as u32);
     true
 }
 
-/// Scroll in a specific area (normalized window-relative coordinates)
-pub fn scroll_in_area(gui: &mut RustAutoGui, game_hwnd: HWND, area: NormRect, amount: i32) {
+/// Click at screen coordinates without moving the mouse (converts to client coords, posts messages)
+pub fn background_click_at_screen(game_hwnd: HWND, x: u32, y: u32) -> bool {
+    let (client_x, client_y) = match screen_to_window_coords(game_hwnd, x as i32, y as i32) {
+        Some(coords) => coords,
+        None => return false,
+    };
+    click_at_position_post(game_hwnd, client_x, client_y)
+}
+
+/// Click at normalized window-relative coordinates without moving the mouse
+pub fn background_click_at_window_pos(game_hwnd: HWND, pos: NormPoint) -> bool {
+    let (rel_x, rel_y) = match denormalize_point(game_hwnd, pos.0, pos.1) {
+        Some(coords) => coords,
+        None => return false,
+    };
+    click_at_position_post(game_hwnd, rel_x, rel_y)
+}
+
+/// Scroll in a specific area using WM_MOUSEWHEEL messages (no physical mouse movement)
+/// Returns false if the messages could not be delivered, so callers can fall back to `scroll_in_area`
+pub fn background_scroll_in_area(
+    game_hwnd: HWND,
+    area: NormRect,
+    direction: ScrollDirection,
+    ticks: u32,
+    delta_per_tick: u32,
+) -> bool {
+    let (left, top, width, height) =
+        match denormalize_rect(game_hwnd, area.0, area.1, area.2, area.3) {
+            Some(rect) => rect,
+            None => return false,
+        };
+    let (screen_x, screen_y) =
+        match client_to_screen_coords(game_hwnd, left + width / 2, top + height / 2) {
+            Some(coords) => coords,
+            None => return false,
+        };
+
+    // WM_MOUSEWHEEL deltas are positive away from the user, i.e. scrolling up
+    let delta = delta_per_tick.min(i16::MAX as u32) as i16;
+    let wheel_delta = match direction {
+        ScrollDirection::Up => delta,
+        ScrollDirection::Down => -delta,
+    };
+    scroll_at_position_post(game_hwnd, screen_x, screen_y, wheel_delta, ticks)
+}
+
+/// Scroll in a specific area (normalized window-relative coordinates) with the physical
+/// mouse: `ticks` wheel notches of `delta_per_tick` each. `ScrollDirection::Up` moves the
+/// list towards its first rows. The tick count is not capped; callers pick it.
+pub fn scroll_in_area(
+    gui: &mut RustAutoGui,
+    game_hwnd: HWND,
+    area: NormRect,
+    direction: ScrollDirection,
+    ticks: u32,
+    delta_per_tick: u32,
+) {
     let (left, top, width, height) =
         match denormalize_rect(game_hwnd, area.0, area.1, area.2, area.3) {
             Some(rect) => rect,
@@ -134,15 +201,10 @@ pub fn scroll_in_area(gui: &mut RustAutoGui, game_hwnd: HWND, area: NormRect, am
     }
     delay_ms(20);
 
-    // Scroll (reduced from 20 to 5 ticks since game only processes ~1 unit anyway)
-    let scroll_ticks = if amount.abs() > 5 { 5 } else { amount.abs() };
-    if amount < 0 {
-        for _ in 0..scroll_ticks {
-            let _ = gui.scroll_up(120);
-        }
-    } else {
-        for _ in 0..scroll_ticks {
-            let _ = gui.scroll_down(120);
-        }
+    for _ in 0..ticks {
+        let _ = match direction {
+            ScrollDirection::Up => gui.scroll_up(delta_per_tick),
+            ScrollDirection::Down => gui.scroll_down(delta_per_tick),
+        };
     }
 }
@@ -0,0 +1,707 @@
+// Runs a custom macro's action list on a worker thread, for the GUI tool and headless mode alike
+use crate::automation::context::AutomationContext;
+use crate::automation::detection::wait_for_template_gone;
+use crate::automation::ocr_models::{build_engine, read_region_text, OcrPreprocess};
+use crate::core::coords::{denormalize_point, denormalize_rect};
+use crate::core::error::HelperError;
+use crate::core::hotkey::{hotkey_key_label, hotkey_key_to_vk};
+use crate::core::input::{click_at_position, key_press_post};
+use crate::core::sounds::{sounds, Event};
+use crate::core::throttle::ActionThrottle;
+use crate::core::window::{client_to_screen_coords, get_window_title, is_window_valid};
+use crate::core::worker::{push_log_entry, LogLevel, SharedLog, SharedStats, StatusSender, Worker};
+use crate::settings::{
+    ActionRateLimit, ComparisonMode, CustomMacroSettings, MacroAction, OcrDecodeMode,
+    OcrModelSettings, OcrNameMatchMode, TemplateMatchMode,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use windows::Win32::Foundation::HWND;
+
+/// How a run ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroOutcome {
+    /// Every iteration ran
+    Completed,
+    /// An OCR search matched its target (or an alternative)
+    MatchFound {
+        stat: String,
+        value: i32,
+    },
+    /// Stopped from outside (Stop button, hotkey, Ctrl+C)
+    Stopped,
+    Failed(String),
+}
+
+fn format_ocr_display(text: &str) -> String {
+    let mut display = text.replace("\r\n", "\\n");
+    display = display.replace('\r', "\\n");
+    display = display.replace('\n', "\\n");
+    display
+}
+
+// A faster speed never shortens a delay below this (shorter delays are kept as they are)
+const MIN_SCALED_DELAY_MS: u64 = 10;
+
+/// `ms` at the macro's speed multiplier: halved at 2x, doubled at 0.5x
+pub fn scaled_delay_ms(ms: u64, speed_multiplier: f32) -> u64 {
+    if speed_multiplier == 1.0 || ms == 0 {
+        return ms;
+    }
+    let scaled = (ms as f64 / f64::from(speed_multiplier)).round() as u64;
+    scaled.max(MIN_SCALED_DELAY_MS.min(ms))
+}
+
+/// Template alias of the "wait until gone" action at `idx`
+fn wait_gone_alias(idx: usize) -> String {
+    format!("wait_gone_{}", idx)
+}
+
+/// Run one click, key press, text or delay action against `ctx.game_hwnd`; shared by the
+/// custom macro and the rebuffer. OCR searches and image waits need the macro's own state,
+/// so they are left to `MacroRun`.
+pub fn run_input_action(
+    ctx: &mut AutomationContext,
+    idx: usize,
+    action: &MacroAction,
+    running: &AtomicBool,
+    status: &StatusSender,
+    log: &SharedLog,
+    stats: &SharedStats,
+) {
+    let game_hwnd = ctx.game_hwnd;
+    match action {
+        MacroAction::Click {
+            coordinate,
+            button,
+            click_method,
+            use_mouse_movement: _,
+        } => {
+            if let Some((x, y)) = coordinate {
+                let (client_x, client_y) = match denormalize_point(game_hwnd, *x, *y) {
+                    Some(pos) => pos,
+                    None => {
+                        status.set("Invalid click position");
+                        return;
+                    }
+                };
+                let btn_text = match button {
+                    crate::settings::MouseButton::Left => {
+                        tr!("custom_macro.button_left")
+                    }
+                    crate::settings::MouseButton::Right => {
+                        tr!("custom_macro.button_right")
+                    }
+                    crate::settings::MouseButton::Middle => {
+                        tr!("custom_macro.button_middle")
+                    }
+                };
+                status.set(tr!(
+                    "status.clicking_at",
+                    button = btn_text,
+                    x = client_x,
+                    y = client_y
+                ));
+
+                ctx.throttle.wait();
+                match click_method {
+                    crate::settings::ClickMethod::SendMessage => {
+                        // Direct click without mouse movement (default)
+                        match button {
+                            crate::settings::MouseButton::Left => {
+                                click_at_position(game_hwnd, client_x, client_y);
+                            }
+                            crate::settings::MouseButton::Right => {
+                                use crate::core::input::right_click_at_position;
+                                right_click_at_position(game_hwnd, client_x, client_y);
+                            }
+                            crate::settings::MouseButton::Middle => {
+                                use crate::core::input::middle_click_at_position;
+                                middle_click_at_position(game_hwnd, client_x, client_y);
+                            }
+                        }
+                    }
+                    crate::settings::ClickMethod::MouseMovement => {
+                        ctx.refresh(log);
+                        // Use screen coordinates with mouse movement
+                        let (screen_x, screen_y) =
+                            match client_to_screen_coords(game_hwnd, client_x, client_y) {
+                                Some(pos) => pos,
+                                None => {
+                                    status.set("Failed to convert to screen coords");
+                                    return;
+                                }
+                            };
+                        match button {
+                            crate::settings::MouseButton::Left => {
+                                use crate::automation::interaction::click_at_screen;
+                                click_at_screen(
+                                    &mut ctx.gui,
+                                    game_hwnd,
+                                    screen_x as u32,
+                                    screen_y as u32,
+                                );
+                            }
+                            crate::settings::MouseButton::Right => {
+                                use crate::automation::interaction::right_click_at_screen;
+                                right_click_at_screen(
+                                    &mut ctx.gui,
+                                    game_hwnd,
+                                    screen_x as u32,
+                                    screen_y as u32,
+                                );
+                            }
+                            crate::settings::MouseButton::Middle => {
+                                use crate::automation::interaction::middle_click_at_screen;
+                                middle_click_at_screen(
+                                    &mut ctx.gui,
+                                    game_hwnd,
+                                    screen_x as u32,
+                                    screen_y as u32,
+                                );
+                            }
+                        }
+                    }
+                }
+            } else {
+                status.set(tr!("status.action_position_unset", action = idx + 1));
+            }
+        }
+        MacroAction::PressKey { key } => {
+            status.set(tr!("status.pressing_key", key = hotkey_key_label(*key)));
+            ctx.throttle.wait();
+            if !key_press_post(game_hwnd, hotkey_key_to_vk(*key)) {
+                let error = "Key press could not be sent to the game window";
+                status.set(error);
+                stats.lock().unwrap().set_error(error);
+            }
+        }
+        MacroAction::TypeText { text } => {
+            status.set(tr!("status.typing", text = text));
+            ctx.throttle.wait();
+            if let Err(e) = ctx.gui.keyboard_input(text) {
+                status.set(format!("Keyboard error: {:?}", e));
+                stats
+                    .lock()
+                    .unwrap()
+                    .set_error(&format!("Keyboard error: {:?}", e));
+            }
+        }
+        MacroAction::Delay { milliseconds } => {
+            status.set(tr!("status.waiting_ms", ms = milliseconds));
+            Worker::sleep_while_running(running, *milliseconds);
+        }
+        MacroAction::OcrSearch { .. } | MacroAction::WaitForImageGone { .. } => {}
+    }
+}
+
+/// One run of a macro: what to run, against which window
+pub struct MacroRun {
+    pub settings: CustomMacroSettings,
+    pub game_hwnd: HWND,
+    /// Game clients to run the whole macro on one after another instead of `game_hwnd`
+    pub clients: Vec<HWND>,
+    pub rate_limit: ActionRateLimit,
+    pub ocr_models: OcrModelSettings,
+    /// Overrides the loop settings: one iteration per pending press, then stop
+    pub one_shot: Option<Arc<AtomicU32>>,
+}
+
+impl MacroRun {
+    /// The task body for `Worker::start`: runs until the actions are done, an OCR search
+    /// matches, something fails or `running` is cleared; clears `running` when it ends
+    pub fn run(
+        self,
+        running: Arc<AtomicBool>,
+        paused: Arc<AtomicBool>,
+        status: StatusSender,
+        log: SharedLog,
+        stats: SharedStats,
+    ) -> MacroOutcome {
+        if self.clients.is_empty() {
+            // A resumed run starts at the iteration it had reached
+            let first_iteration = u32::try_from(stats.lock().unwrap().iterations).unwrap_or(0);
+            let outcome = self.run_client(
+                self.game_hwnd,
+                first_iteration,
+                Arc::clone(&running),
+                paused,
+                status,
+                log,
+                stats,
+            );
+            running.store(false, Ordering::Relaxed);
+            return outcome;
+        }
+
+        let total = self.clients.len();
+        let mut failed = 0;
+        let mut skipped = 0;
+        for (idx, &hwnd) in self.clients.iter().enumerate() {
+            if Worker::checkpoint(&running, &paused).is_break() {
+                break;
+            }
+            let client = format!("Client {}/{}", idx + 1, total);
+            if !is_window_valid(hwnd) {
+                skipped += 1;
+                push_log_entry(
+                    &log,
+                    LogLevel::Warn,
+                    &format!("{}: window closed, skipped", client),
+                );
+                continue;
+            }
+            let title = get_window_title(hwnd).unwrap_or_default();
+            Worker::push_log(&log, &format!("{}: {}", client, title));
+
+            let outcome = self.run_client(
+                hwnd,
+                0,
+                Arc::clone(&running),
+                Arc::clone(&paused),
+                status.prefixed(&format!("{} - ", client)),
+                Arc::clone(&log),
+                Arc::clone(&stats),
+            );
+            match &outcome {
+                MacroOutcome::Completed => {
+                    Worker::push_log(&log, &format!("{}: finished", client));
+                }
+                MacroOutcome::Failed(error) => {
+                    failed += 1;
+                    push_log_entry(
+                        &log,
+                        LogLevel::Warn,
+                        &format!("{}: failed ({})", client, error),
+                    );
+                }
+                // A match or the Stop button ends the whole batch
+                MacroOutcome::MatchFound { .. } | MacroOutcome::Stopped => {
+                    running.store(false, Ordering::Relaxed);
+                    return outcome;
+                }
+            }
+        }
+
+        let stopped = !running.swap(false, Ordering::Relaxed);
+        let summary = format!(
+            "Clients done: {} ok, {} failed, {} skipped",
+            total - failed - skipped,
+            failed,
+            skipped
+        );
+        Worker::push_log(&log, &summary);
+        if stopped {
+            status.set("Stopped by user");
+            MacroOutcome::Stopped
+        } else if failed + skipped > 0 {
+            status.set(summary.clone());
+            MacroOutcome::Failed(summary)
+        } else {
+            status.set(summary);
+            MacroOutcome::Completed
+        }
+    }
+
+    /// The whole macro against one game window. Ends with the returned outcome and leaves
+    /// `running` alone, so a batch can go on to the next client; the caller clears it
+    fn run_client(
+        &self,
+        game_hwnd: HWND,
+        first_iteration: u32,
+        running: Arc<AtomicBool>,
+        paused: Arc<AtomicBool>,
+        status: StatusSender,
+        log: SharedLog,
+        stats: SharedStats,
+    ) -> MacroOutcome {
+        let MacroRun {
+            settings,
+            rate_limit,
+            ocr_models,
+            one_shot,
+            ..
+        } = self;
+
+        use crate::core::ocr_parser::{matches_target, parse_ocr_result};
+        use ocrs::{DecodeMethod, OcrEngine};
+
+        // Ends the run with an error; also used for setup failures before the first iteration
+        let fail = |error: String| {
+            status.set(error.clone());
+            push_log_entry(&log, LogLevel::Error, &error);
+            stats.lock().unwrap().set_error(&error);
+            MacroOutcome::Failed(error)
+        };
+        // `fail` that keeps the typed cause in the stats
+        let fail_with = |message: String, error: HelperError| {
+            let outcome = fail(message);
+            stats.lock().unwrap().helper_error = Some(error);
+            outcome
+        };
+
+        let throttle = ActionThrottle::new(*rate_limit, Arc::clone(&log));
+        let mut ctx = match AutomationContext::new(game_hwnd, throttle) {
+            Ok(c) => c,
+            Err(e) => return fail_with(e.status(), e),
+        };
+
+        // Images of the "wait until gone" actions
+        for (idx, action) in settings.actions.iter().enumerate() {
+            if let MacroAction::WaitForImageGone {
+                image_path, region, ..
+            } = action
+            {
+                if let Err(e) = ctx.store_template(
+                    image_path,
+                    *region,
+                    &wait_gone_alias(idx),
+                    TemplateMatchMode::default(),
+                ) {
+                    return fail_with(format!("Error: Action {}: {}", idx + 1, e), e);
+                }
+            }
+        }
+
+        // Initialize OCR engine only if needed
+        let has_ocr_actions = settings
+            .actions
+            .iter()
+            .any(|a| matches!(a, MacroAction::OcrSearch { .. }));
+        let mut ocr_engine: Option<OcrEngine> = None;
+
+        if has_ocr_actions {
+            status.set(tr!("status.loading_ocr"));
+
+            // Determine decode configuration from first OCR action
+            let mut decode_mode_cfg = OcrDecodeMode::Greedy;
+            let mut beam_width_cfg: u32 = 10;
+            for a in &settings.actions {
+                if let MacroAction::OcrSearch {
+                    decode_mode,
+                    beam_width,
+                    ..
+                } = a
+                {
+                    decode_mode_cfg = *decode_mode;
+                    beam_width_cfg = *beam_width;
+                    break;
+                }
+            }
+
+            let dm = match decode_mode_cfg {
+                OcrDecodeMode::Greedy => DecodeMethod::Greedy,
+                OcrDecodeMode::BeamSearch => {
+                    let width = beam_width_cfg.max(2);
+                    DecodeMethod::BeamSearch { width }
+                }
+            };
+
+            let engine = match build_engine(ocr_models, dm, &status, &log) {
+                Ok(engine) => engine,
+                Err(e) => return fail_with(e.status(), e),
+            };
+
+            ocr_engine = Some(engine);
+        }
+
+        let speed = settings.speed_multiplier;
+        if speed != 1.0 {
+            Worker::push_log(&log, &format!("Delays run at {}x speed", speed));
+        }
+
+        let mut iteration = first_iteration;
+        let mut ocr_counts: HashMap<String, u32> = HashMap::new();
+        // Why the run ended early, if it did
+        let mut match_found: Option<(String, i32)> = None;
+        let mut failure: Option<String> = None;
+
+        loop {
+            if Worker::checkpoint(&running, &paused).is_break() {
+                break;
+            }
+
+            // Determine if we should exit based on loop settings
+            if let Some(pending) = one_shot {
+                if pending
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                    .is_err()
+                {
+                    break;
+                }
+                status.set("One-shot running...");
+            } else if settings.loop_enabled {
+                if !settings.infinite_loop && iteration >= settings.loop_count {
+                    break;
+                }
+                if settings.infinite_loop {
+                    status.set(tr!("status.loop_infinite", iteration = iteration + 1));
+                } else {
+                    status.set(tr!(
+                        "status.loop_count",
+                        iteration = iteration + 1,
+                        total = settings.loop_count
+                    ));
+                }
+            } else {
+                if iteration >= 1 {
+                    break;
+                }
+            }
+
+            for (idx, action) in settings.actions.iter().enumerate() {
+                if Worker::checkpoint(&running, &paused).is_break() {
+                    break;
+                }
+                stats.lock().unwrap().add_items(1);
+
+                match action {
+                    MacroAction::Delay { milliseconds } if speed != 1.0 => {
+                        let ms = scaled_delay_ms(*milliseconds, speed);
+                        status.set(tr!(
+                            "status.waiting_ms_scaled",
+                            ms = ms,
+                            base = milliseconds,
+                            speed = speed
+                        ));
+                        Worker::sleep_while_running(&running, ms);
+                    }
+                    MacroAction::Click { .. }
+                    | MacroAction::PressKey { .. }
+                    | MacroAction::TypeText { .. }
+                    | MacroAction::Delay { .. } => {
+                        run_input_action(&mut ctx, idx, action, &running, &status, &log, &stats);
+                    }
+                    MacroAction::WaitForImageGone {
+                        min_confidence,
+                        timeout_ms,
+                        ..
+                    } => {
+                        ctx.refresh(&log);
+                        status.set(tr!("status.waiting_image_gone", action = idx + 1));
+                        let gone = wait_for_template_gone(
+                            &mut ctx.gui,
+                            &wait_gone_alias(idx),
+                            *min_confidence,
+                            *timeout_ms,
+                            &running,
+                        );
+                        if !gone && running.load(Ordering::Relaxed) {
+                            failure = Some(tr!(
+                                "status.image_still_visible",
+                                action = idx + 1,
+                                ms = timeout_ms
+                            ));
+                            break;
+                        }
+                    }
+                    MacroAction::OcrSearch {
+                        ocr_region,
+                        scale_factor,
+                        invert_colors,
+                        grayscale,
+                        target_stat,
+                        target_value,
+                        comparison,
+                        name_match_mode,
+                        alt_targets,
+                        ..
+                    } => {
+                        if ocr_engine.is_none() {
+                            failure = Some("OCR engine not initialized".to_string());
+                            break;
+                        }
+
+                        let region = if let Some(region) = ocr_region {
+                            match denormalize_rect(
+                                game_hwnd, region.0, region.1, region.2, region.3,
+                            ) {
+                                Some(rect) => rect,
+                                None => {
+                                    failure = Some(tr!(
+                                        "status.action_ocr_region_invalid",
+                                        action = idx + 1
+                                    ));
+                                    break;
+                                }
+                            }
+                        } else {
+                            failure = Some(tr!("status.action_ocr_region_unset", action = idx + 1));
+                            break;
+                        };
+
+                        let engine = ocr_engine.as_ref().unwrap();
+
+                        let preprocess = OcrPreprocess {
+                            scale_factor: *scale_factor,
+                            invert_colors: *invert_colors,
+                            grayscale: *grayscale,
+                        };
+                        match read_region_text(engine, game_hwnd, region, preprocess) {
+                            Ok(text) => {
+                                {
+                                    let counter = ocr_counts.entry(text.clone()).or_insert(0);
+                                    *counter += 1;
+                                }
+
+                                Worker::push_log(&log, &format_ocr_display(&text));
+
+                                if let Some((detected_stat, detected_value)) =
+                                    parse_ocr_result(&text)
+                                {
+                                    let normalize_contains = |s: &str| -> String {
+                                        s.chars()
+                                            .filter(|c| c.is_ascii_alphanumeric())
+                                            .flat_map(|c| c.to_lowercase())
+                                            .collect()
+                                    };
+
+                                    let matches_config =
+                                        |stat: &str,
+                                         value: i32,
+                                         comparison: ComparisonMode,
+                                         name_match_mode: OcrNameMatchMode|
+                                         -> bool {
+                                            if stat.trim().is_empty() {
+                                                return false;
+                                            }
+                                            match name_match_mode {
+                                                OcrNameMatchMode::Exact => matches_target(
+                                                    &detected_stat,
+                                                    detected_value,
+                                                    stat,
+                                                    value,
+                                                    comparison,
+                                                ),
+                                                OcrNameMatchMode::Contains => {
+                                                    let detected =
+                                                        normalize_contains(&detected_stat);
+                                                    let target = normalize_contains(stat);
+                                                    if target.is_empty() {
+                                                        false
+                                                    } else if !detected.contains(&target) {
+                                                        false
+                                                    } else {
+                                                        match comparison {
+                                                            ComparisonMode::Equals => {
+                                                                detected_value == value
+                                                            }
+                                                            ComparisonMode::GreaterThanOrEqual => {
+                                                                detected_value >= value
+                                                            }
+                                                            ComparisonMode::LessThanOrEqual => {
+                                                                detected_value <= value
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        };
+
+                                    let mut matched = matches_config(
+                                        target_stat,
+                                        *target_value,
+                                        *comparison,
+                                        *name_match_mode,
+                                    );
+                                    if !matched {
+                                        for alt in alt_targets.iter() {
+                                            if alt.delay_ms > 0 {
+                                                Worker::sleep_while_running(
+                                                    &running,
+                                                    scaled_delay_ms(alt.delay_ms, speed),
+                                                );
+                                            }
+                                            if matches_config(
+                                                &alt.target_stat,
+                                                alt.target_value,
+                                                alt.comparison,
+                                                alt.name_match_mode,
+                                            ) {
+                                                matched = true;
+                                                break;
+                                            }
+                                        }
+                                    }
+
+                                    if matched {
+                                        status.set(format!(
+                                            "MATCH FOUND! {} {}",
+                                            detected_stat, detected_value
+                                        ));
+                                        match_found = Some((detected_stat.clone(), detected_value));
+                                        stats.lock().unwrap().add_match();
+                                        sounds().play(Event::MatchFound);
+                                        break;
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                status.set(e.status());
+                                stats.lock().unwrap().record_error(&e);
+                            }
+                        }
+                    }
+                }
+            }
+
+            iteration += 1;
+            stats.lock().unwrap().bump_iteration();
+            if failure.is_some() || match_found.is_some() {
+                break;
+            }
+        }
+
+        let outcome = if let Some(error) = failure {
+            fail(error)
+        } else if let Some((stat, value)) = match_found {
+            status.set("Stopped (match found)");
+            MacroOutcome::MatchFound { stat, value }
+        } else if running.load(Ordering::Relaxed) {
+            if one_shot.is_some() {
+                let actions_run = settings.actions.len() * iteration as usize;
+                status.set(tr!("status.one_shot_done", actions = actions_run));
+            } else {
+                status.set("Macro completed!");
+                sounds().play(Event::MacroCompleted);
+            }
+            MacroOutcome::Completed
+        } else {
+            status.set("Stopped by user");
+            MacroOutcome::Stopped
+        };
+
+        if !ocr_counts.is_empty() {
+            let mut ranking: Vec<(String, u32)> = ocr_counts.into_iter().collect();
+            ranking.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+            if let Ok(mut log) = log.lock() {
+                log.clear();
+            }
+
+            Worker::push_log(&log, "OCR SUMMARY (most frequent to least):");
+            for (key, value) in ranking {
+                Worker::push_log(&log, &format!("{} x{}", format_ocr_display(&key), value));
+            }
+        }
+
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled_delay_respects_the_floor() {
+        assert_eq!(scaled_delay_ms(500, 1.0), 500);
+        assert_eq!(scaled_delay_ms(500, 2.0), 250);
+        assert_eq!(scaled_delay_ms(500, 0.5), 1000);
+        // Sped up, a delay stops at the floor; one already below it is left alone
+        assert_eq!(scaled_delay_ms(30, 4.0), MIN_SCALED_DELAY_MS);
+        assert_eq!(scaled_delay_ms(5, 4.0), 5);
+        assert_eq!(scaled_delay_ms(0, 0.25), 0);
+    }
+}
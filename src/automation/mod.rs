@@ -1,4 +1,6 @@
 // Automation module - shared automation primitives
 pub mod detection;
 pub mod interaction;
+pub mod macro_runner;
+pub mod ocr_models;
 pub mod context;
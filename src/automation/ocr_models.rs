@@ -0,0 +1,231 @@
+// OCR models: the files chosen in the settings, or the ones built into the exe
+use crate::core::error::HelperError;
+use crate::core::screen_capture::capture_window_region;
+use crate::core::worker::{push_log_entry, LogLevel, SharedLog, StatusSender, Worker};
+use crate::settings::OcrModelSettings;
+use ocrs::{DecodeMethod, ImageSource, OcrEngine, OcrEngineParams};
+use std::time::{Duration, Instant};
+use windows::Win32::Foundation::HWND;
+
+const DETECTION_MODEL_BYTES: &[u8] = include_bytes!("../models/text-detection.rten");
+const RECOGNITION_MODEL_BYTES: &[u8] = include_bytes!("../models/text-recognition.rten");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcrModelKind {
+    Detection,
+    Recognition,
+}
+
+impl OcrModelKind {
+    pub const ALL: [OcrModelKind; 2] = [OcrModelKind::Detection, OcrModelKind::Recognition];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OcrModelKind::Detection => "detection",
+            OcrModelKind::Recognition => "recognition",
+        }
+    }
+
+    fn embedded_bytes(self) -> &'static [u8] {
+        match self {
+            OcrModelKind::Detection => DETECTION_MODEL_BYTES,
+            OcrModelKind::Recognition => RECOGNITION_MODEL_BYTES,
+        }
+    }
+
+    fn custom_path(self, settings: &OcrModelSettings) -> &str {
+        match self {
+            OcrModelKind::Detection => settings.detection_model_path.trim(),
+            OcrModelKind::Recognition => settings.recognition_model_path.trim(),
+        }
+    }
+}
+
+/// A model ready for the OCR engine, with where it came from
+pub struct LoadedModel {
+    pub model: rten::Model,
+    // The file path, or "built-in"
+    pub source: String,
+    // Why the file from the settings wasn't used, when one is set
+    pub fallback_reason: Option<String>,
+    pub load_time: Duration,
+}
+
+/// Load the model file set in `settings`, falling back to the built-in model when
+/// none is set or it can't be loaded. Errs only when the built-in model fails too.
+pub fn load_model(
+    kind: OcrModelKind,
+    settings: &OcrModelSettings,
+) -> Result<LoadedModel, HelperError> {
+    let path = kind.custom_path(settings);
+    let mut fallback_reason = None;
+    if !path.is_empty() {
+        let started = Instant::now();
+        match load_model_file(path) {
+            Ok(model) => {
+                return Ok(LoadedModel {
+                    model,
+                    source: path.to_string(),
+                    fallback_reason: None,
+                    load_time: started.elapsed(),
+                })
+            }
+            Err(e) => {
+                fallback_reason = Some(format!(
+                    "Could not load the {} model {}: {}",
+                    kind.name(),
+                    path,
+                    e
+                ))
+            }
+        }
+    }
+
+    let started = Instant::now();
+    let model = rten::Model::load(kind.embedded_bytes().to_vec())
+        .map_err(|e| HelperError::OcrEngine(format!("built-in {} model: {:?}", kind.name(), e)))?;
+    Ok(LoadedModel {
+        model,
+        source: "built-in".to_string(),
+        fallback_reason,
+        load_time: started.elapsed(),
+    })
+}
+
+fn load_model_file(path: &str) -> Result<rten::Model, String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    rten::Model::load(bytes).map_err(|e| format!("{:?}", e))
+}
+
+/// OCR engine with both models of `settings`, shared by the OCR tools. A model file
+/// that can't be loaded is replaced by the built-in one, with a warning in the status
+/// line and the log.
+pub fn build_engine(
+    settings: &OcrModelSettings,
+    decode_method: DecodeMethod,
+    status: &StatusSender,
+    log: &SharedLog,
+) -> Result<OcrEngine, HelperError> {
+    let load = |kind: OcrModelKind| {
+        let loaded = load_model(kind, settings)?;
+        if let Some(reason) = &loaded.fallback_reason {
+            let warning = format!("{} - using the built-in model", reason);
+            status.set(warning.clone());
+            push_log_entry(log, LogLevel::Warn, &warning);
+        }
+        Worker::push_log(
+            log,
+            &format!("OCR {} model: {}", kind.name(), loaded.source),
+        );
+        Ok::<_, HelperError>(loaded.model)
+    };
+    let detection_model = load(OcrModelKind::Detection)?;
+    let recognition_model = load(OcrModelKind::Recognition)?;
+
+    OcrEngine::new(OcrEngineParams {
+        detection_model: Some(detection_model),
+        recognition_model: Some(recognition_model),
+        decode_method,
+        ..Default::default()
+    })
+    .map_err(|e| HelperError::OcrEngine(format!("{:?}", e)))
+}
+
+/// How a captured region is prepared before it is read
+#[derive(Debug, Clone, Copy)]
+pub struct OcrPreprocess {
+    pub scale_factor: u32,
+    pub invert_colors: bool,
+    pub grayscale: bool,
+}
+
+/// Capture the client-relative `region` of the window and read its text
+pub fn read_region_text(
+    engine: &OcrEngine,
+    hwnd: HWND,
+    region: (i32, i32, i32, i32),
+    preprocess: OcrPreprocess,
+) -> Result<String, HelperError> {
+    let img = capture_window_region(hwnd, region)?;
+    let mut processed_img = image::DynamicImage::ImageRgba8(img);
+
+    if preprocess.invert_colors {
+        processed_img.invert();
+    }
+
+    if preprocess.grayscale {
+        processed_img = image::DynamicImage::ImageLuma8(processed_img.to_luma8());
+    }
+
+    if preprocess.scale_factor > 1 {
+        let (w, h) = (processed_img.width(), processed_img.height());
+        processed_img = processed_img.resize(
+            w * preprocess.scale_factor,
+            h * preprocess.scale_factor,
+            image::imageops::FilterType::Lanczos3,
+        );
+    }
+
+    let rgb_img = processed_img.into_rgb8();
+    let (width, height) = rgb_img.dimensions();
+    let img_source = ImageSource::from_bytes(rgb_img.as_raw(), (width, height))
+        .map_err(|e| HelperError::OcrEngine(format!("image: {:?}", e)))?;
+    let ocr_input = engine
+        .prepare_input(img_source)
+        .map_err(|e| HelperError::OcrEngine(format!("prep: {:?}", e)))?;
+    engine
+        .get_text(&ocr_input)
+        .map_err(|e| HelperError::OcrEngine(format!("{:?}", e)))
+}
+
+/// "Test load" in the settings: one line per model with its source and load time,
+/// flagged when the model file from the settings could not be used
+pub fn test_load(settings: &OcrModelSettings) -> Vec<(bool, String)> {
+    OcrModelKind::ALL
+        .into_iter()
+        .flat_map(|kind| match load_model(kind, settings) {
+            Ok(loaded) => {
+                let mut lines: Vec<(bool, String)> = loaded
+                    .fallback_reason
+                    .map(|reason| (false, reason))
+                    .into_iter()
+                    .collect();
+                lines.push((
+                    true,
+                    format!(
+                        "OCR {} model: {} loaded in {} ms",
+                        kind.name(),
+                        loaded.source,
+                        loaded.load_time.as_millis()
+                    ),
+                ));
+                lines
+            }
+            Err(error) => vec![(false, error.to_string())],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_model_file_falls_back_to_built_in() {
+        let settings = OcrModelSettings {
+            detection_model_path: "no_such_dir/detection.rten".to_string(),
+            recognition_model_path: String::new(),
+        };
+
+        let detection = load_model(OcrModelKind::Detection, &settings).unwrap();
+        assert_eq!(detection.source, "built-in");
+        assert!(detection
+            .fallback_reason
+            .is_some_and(|reason| reason.contains("no_such_dir/detection.rten")));
+
+        // No path set: built-in without a warning
+        let recognition = load_model(OcrModelKind::Recognition, &settings).unwrap();
+        assert_eq!(recognition.source, "built-in");
+        assert!(recognition.fallback_reason.is_none());
+    }
+}
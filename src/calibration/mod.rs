@@ -1,25 +1,100 @@
 // Calibration module - shared calibration logic for all tools
 use crate::core::coords::{normalize_point, normalize_rect};
-use crate::core::input::is_left_mouse_down;
+use crate::core::input::{is_key_down, is_left_mouse_down, is_right_mouse_down};
+use crate::core::overlay_window::{OverlayShape, OverlayWindow};
+use crate::core::screen_capture::capture_window_region;
 use crate::core::window::{
-    get_client_rect_in_screen_coords, get_cursor_pos, get_window_under_cursor,
-    is_game_window_or_child, screen_to_window_coords,
+    client_to_screen_coords, get_client_rect_in_screen_coords, get_client_size, get_cursor_pos,
+    get_pixel_color, get_window_under_cursor, is_game_window_or_child, screen_to_window_coords,
 };
+use image::RgbaImage;
+use std::time::{Duration, Instant};
 use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    VIRTUAL_KEY, VK_CONTROL, VK_DOWN, VK_ESCAPE, VK_LEFT, VK_RETURN, VK_RIGHT, VK_SHIFT, VK_UP,
+};
+
+// Keys watched while nudging a point: Left, Right, Up, Down, Enter, Esc
+const NUDGE_KEYS: [VIRTUAL_KEY; 6] = [VK_LEFT, VK_RIGHT, VK_UP, VK_DOWN, VK_RETURN, VK_ESCAPE];
+
+/// Point being fine-tuned with the arrow keys after the calibration click
+struct PointAdjust {
+    clicked: (i32, i32),
+    current: (i32, i32),
+}
+
+const SELECTION_COLOR: (u8, u8, u8) = (255, 60, 60);
+
+// Magnifier captures a (2r+1)px square around the cursor, at most every interval
+const MAGNIFIER_RADIUS: i32 = 12;
+const MAGNIFIER_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Zoom source shown in the UI while calibrating
+pub struct MagnifierFrame {
+    pub image: RgbaImage,
+    pub center: (i32, i32), // client coordinates of the marked pixel
+    pub marker: (u32, u32), // marked pixel inside `image`
+}
+
+/// Cursor position in client coordinates, if it is over the game window
+fn cursor_in_game(game_hwnd: HWND) -> Option<(i32, i32)> {
+    let (screen_x, screen_y) = get_cursor_pos()?;
+
+    if let Some((left, top, width, height)) = get_client_rect_in_screen_coords(game_hwnd) {
+        let right = left + width;
+        let bottom = top + height;
+        if screen_x >= left && screen_x < right && screen_y >= top && screen_y < bottom {
+            return screen_to_window_coords(game_hwnd, screen_x, screen_y);
+        }
+    }
+
+    if let Some(cursor_hwnd) = get_window_under_cursor() {
+        if is_game_window_or_child(cursor_hwnd, game_hwnd) {
+            return screen_to_window_coords(game_hwnd, screen_x, screen_y);
+        }
+    }
+
+    None
+}
+
+/// Right mouse button or Esc, either of which cancels calibration. While a point is
+/// being nudged Esc keeps the click instead, so only the right button cancels
+fn is_cancel_down(adjusting: bool) -> bool {
+    is_right_mouse_down() || (!adjusting && is_key_down(VK_ESCAPE))
+}
 
 /// Result of a calibration operation
 #[derive(Debug, Clone)]
 pub enum CalibrationResult {
     Point(f32, f32),
-    Area(f32, f32, f32, f32), // left, top, width, height (normalized)
+    Area(f32, f32, f32, f32),    // left, top, width, height (normalized)
+    Pixel(f32, f32, u8, u8, u8), // normalized x, y and the sampled r, g, b
+    Cancelled,                   // right click, or Esc before a point was clicked
 }
 
 /// Manages calibration state and logic
 pub struct CalibrationManager {
     active: bool,
-    is_area: bool, // true for area calibration, false for point
+    is_area: bool,  // true for area calibration, false for point
+    is_pixel: bool, // point calibration that also samples the color
     area_start: Option<(i32, i32)>,
+    // Live selection (left, top, width, height in client pixels) between the area clicks
+    selection: Option<(i32, i32, i32, i32)>,
+    grid_size: i32,
     last_left_down: bool,
+    last_cancel_down: bool,
+
+    // Arrow-key nudging after a point click (opt-out per tool)
+    nudge_points: bool,
+    adjust: Option<PointAdjust>,
+    last_nudge_keys: [bool; 6],
+
+    // Click-through window showing the selection (created on demand)
+    overlay: Option<OverlayWindow>,
+
+    // Zoomed preview around the cursor
+    magnifier: Option<MagnifierFrame>,
+    last_magnifier_capture: Option<Instant>,
 }
 
 impl Default for CalibrationManager {
@@ -27,8 +102,18 @@ impl Default for CalibrationManager {
         Self {
             active: false,
             is_area: false,
+            is_pixel: false,
             area_start: None,
+            selection: None,
+            grid_size: 5,
             last_left_down: false,
+            last_cancel_down: false,
+            nudge_points: true,
+            adjust: None,
+            last_nudge_keys: [false; 6],
+            overlay: None,
+            magnifier: None,
+            last_magnifier_capture: None,
         }
     }
 }
@@ -38,26 +123,53 @@ impl CalibrationManager {
         Self::default()
     }
 
+    /// Enable or skip the arrow-key adjust step (when skipped, points return on click)
+    pub fn set_nudge_points(&mut self, enabled: bool) {
+        self.nudge_points = enabled;
+    }
+
     /// Start calibrating a point (single click)
     pub fn start_point(&mut self) {
+        self.clear_overlay();
         self.active = true;
         self.is_area = false;
+        self.is_pixel = false;
         self.area_start = None;
+        self.selection = None;
+        self.adjust = None;
+        self.magnifier = None;
         self.last_left_down = false;
+        self.last_cancel_down = is_cancel_down(false);
     }
 
     /// Start calibrating an area (click top-left, then bottom-right)
     pub fn start_area(&mut self) {
+        self.clear_overlay();
         self.active = true;
         self.is_area = true;
+        self.is_pixel = false;
         self.area_start = None;
+        self.selection = None;
+        self.adjust = None;
+        self.magnifier = None;
         self.last_left_down = false;
+        self.last_cancel_down = is_cancel_down(false);
+    }
+
+    /// Start calibrating a pixel (single click, records position and color)
+    pub fn start_pixel(&mut self) {
+        self.start_point();
+        self.is_pixel = true;
     }
 
     /// Cancel current calibration
     pub fn cancel(&mut self) {
+        self.clear_overlay();
         self.active = false;
         self.area_start = None;
+        self.selection = None;
+        self.adjust = None;
+        self.magnifier = None;
         self.last_left_down = false;
     }
 
@@ -71,10 +183,220 @@ impl CalibrationManager {
         self.is_area && self.area_start.is_some()
     }
 
+    /// Size of the area being selected (between the two clicks), in client pixels
+    pub fn selection_size(&self) -> Option<(i32, i32)> {
+        self.area_start?;
+        self.selection.map(|(_, _, width, height)| (width, height))
+    }
+
+    /// Live status text while selecting an area ("Selection 120x35 ...")
+    pub fn selection_status(&self) -> Option<String> {
+        let (width, height) = self.selection_size()?;
+        Some(format!(
+            "Selection {}x{} (Shift: square, Ctrl: {}px grid)",
+            width, height, self.grid_size
+        ))
+    }
+
+    /// Grid that Ctrl snaps area corners to
+    pub fn set_grid_size(&mut self, px: i32) {
+        self.grid_size = px.max(1);
+    }
+
+    /// Check if a clicked point is being fine-tuned with the arrow keys
+    pub fn is_adjusting(&self) -> bool {
+        self.adjust.is_some()
+    }
+
     /// Main update loop for calibration
     /// Handles mouse clicks and returns result if calibration finished this frame
     pub fn update(&mut self, game_hwnd: HWND) -> Option<CalibrationResult> {
-        self.handle_clicks(game_hwnd)
+        if self.active {
+            // Right click or Esc aborts from inside the game
+            let cancel_down = is_cancel_down(self.adjust.is_some());
+            let cancel_pressed = cancel_down && !self.last_cancel_down;
+            self.last_cancel_down = cancel_down;
+            if cancel_pressed {
+                self.cancel();
+                return Some(CalibrationResult::Cancelled);
+            }
+
+            self.refresh_magnifier(game_hwnd);
+        }
+
+        let result = if self.adjust.is_some() {
+            self.handle_nudge(game_hwnd)
+        } else {
+            self.handle_clicks(game_hwnd)
+        };
+
+        if !self.active {
+            self.magnifier = None;
+        }
+        result
+    }
+
+    /// Latest zoomed capture around the cursor (only while calibrating)
+    pub fn magnifier(&self) -> Option<&MagnifierFrame> {
+        self.magnifier.as_ref()
+    }
+
+    /// Capture a small square around the cursor (or the nudged point)
+    /// Keeps the previous frame while the cursor is outside the game window
+    fn refresh_magnifier(&mut self, game_hwnd: HWND) {
+        if self
+            .last_magnifier_capture
+            .is_some_and(|last| last.elapsed() < MAGNIFIER_INTERVAL)
+        {
+            return;
+        }
+        self.last_magnifier_capture = Some(Instant::now());
+
+        let center = match &self.adjust {
+            Some(adjust) => Some(adjust.current),
+            None => cursor_in_game(game_hwnd),
+        };
+        let Some((cx, cy)) = center else {
+            return;
+        };
+        let Some((width, height)) = get_client_size(game_hwnd) else {
+            return;
+        };
+
+        let size = MAGNIFIER_RADIUS * 2 + 1;
+        if width < size || height < size {
+            return;
+        }
+        let left = (cx - MAGNIFIER_RADIUS).clamp(0, width - size);
+        let top = (cy - MAGNIFIER_RADIUS).clamp(0, height - size);
+
+        if let Ok(image) = capture_window_region(game_hwnd, (left, top, size, size)) {
+            // The capture may be DPI-scaled, so map the marker into image pixels
+            let marker_x = ((cx - left) as u32 * image.width()) / size as u32;
+            let marker_y = ((cy - top) as u32 * image.height()) / size as u32;
+            self.magnifier = Some(MagnifierFrame {
+                image,
+                center: (cx, cy),
+                marker: (
+                    marker_x.min(image.width().saturating_sub(1)),
+                    marker_y.min(image.height().saturating_sub(1)),
+                ),
+            });
+        }
+    }
+
+    /// Show (or move) the selection rectangle on screen (left, top, right, bottom)
+    pub fn update_overlay_rect(&mut self, rect: (i32, i32, i32, i32)) {
+        let (left, top, right, bottom) = rect;
+        let (width, height) = (right - left, bottom - top);
+        if width <= 0 || height <= 0 {
+            return;
+        }
+
+        if self.overlay.is_none() {
+            self.overlay = OverlayWindow::new();
+        }
+        if let Some(overlay) = self.overlay.as_mut() {
+            // The window is exactly the selection, so the border hugs its edges
+            overlay.move_to((left, top, width, height));
+            overlay.set_shapes(vec![OverlayShape {
+                rect: (0, 0, width, height),
+                color: SELECTION_COLOR,
+                label: None,
+            }]);
+        }
+    }
+
+    /// Remove the selection rectangle, if any
+    pub fn clear_overlay(&mut self) {
+        self.overlay = None;
+    }
+
+    /// Arrow keys move the clicked point (Shift = 10px), Enter accepts, Esc keeps the click
+    fn handle_nudge(&mut self, game_hwnd: HWND) -> Option<CalibrationResult> {
+        let mut down = [false; 6];
+        for (state, key) in down.iter_mut().zip(NUDGE_KEYS) {
+            *state = is_key_down(key);
+        }
+        let pressed: [bool; 6] = std::array::from_fn(|idx| down[idx] && !self.last_nudge_keys[idx]);
+        self.last_nudge_keys = down;
+
+        let adjust = self.adjust.as_mut()?;
+        let finished = if pressed[4] {
+            Some(adjust.current)
+        } else if pressed[5] {
+            Some(adjust.clicked)
+        } else {
+            None
+        };
+
+        if let Some((x, y)) = finished {
+            self.adjust = None;
+            self.active = false;
+            self.clear_overlay();
+            return normalize_point(game_hwnd, x, y)
+                .map(|(nx, ny)| CalibrationResult::Point(nx, ny));
+        }
+
+        let step = if is_key_down(VK_SHIFT) { 10 } else { 1 };
+        let (mut x, mut y) = adjust.current;
+        if pressed[0] {
+            x -= step;
+        }
+        if pressed[1] {
+            x += step;
+        }
+        if pressed[2] {
+            y -= step;
+        }
+        if pressed[3] {
+            y += step;
+        }
+        if let Some((width, height)) = get_client_size(game_hwnd) {
+            x = x.clamp(0, (width - 1).max(0));
+            y = y.clamp(0, (height - 1).max(0));
+        }
+        adjust.current = (x, y);
+
+        // Small marker around the current pixel
+        if let Some((sx, sy)) = client_to_screen_coords(game_hwnd, x, y) {
+            self.update_overlay_rect((sx - 3, sy - 3, sx + 4, sy + 4));
+        }
+
+        None
+    }
+
+    /// Rectangle between two corners; Ctrl snaps both to the grid, Shift makes it square
+    fn constrain_selection(
+        &self,
+        game_hwnd: HWND,
+        start: (i32, i32),
+        end: (i32, i32),
+    ) -> (i32, i32, i32, i32) {
+        let ((mut x1, mut y1), (mut x2, mut y2)) = (start, end);
+
+        if is_key_down(VK_CONTROL) && self.grid_size > 1 {
+            let grid = self.grid_size as f32;
+            let snap = |v: i32| (v as f32 / grid).round() as i32 * self.grid_size;
+            (x1, y1, x2, y2) = (snap(x1), snap(y1), snap(x2), snap(y2));
+        }
+
+        if is_key_down(VK_SHIFT) {
+            let side = (x2 - x1).abs().max((y2 - y1).abs());
+            x2 = if x2 < x1 { x1 - side } else { x1 + side };
+            y2 = if y2 < y1 { y1 - side } else { y1 + side };
+        }
+
+        if let Some((width, height)) = get_client_size(game_hwnd) {
+            x1 = x1.clamp(0, width);
+            x2 = x2.clamp(0, width);
+            y1 = y1.clamp(0, height);
+            y2 = y2.clamp(0, height);
+        }
+
+        let left = x1.min(x2);
+        let top = y1.min(y2);
+        (left, top, (x2 - x1).abs(), (y2 - y1).abs())
     }
 
     /// Handle mouse clicks and return calibration result if complete
@@ -84,25 +406,20 @@ impl CalibrationManager {
             return None;
         }
 
-        let cursor_in_game = || -> Option<(i32, i32)> {
-            let (screen_x, screen_y) = get_cursor_pos()?;
-
-            if let Some((left, top, width, height)) = get_client_rect_in_screen_coords(game_hwnd) {
-                let right = left + width;
-                let bottom = top + height;
-                if screen_x >= left && screen_x < right && screen_y >= top && screen_y < bottom {
-                    return screen_to_window_coords(game_hwnd, screen_x, screen_y);
-                }
-            }
-
-            if let Some(cursor_hwnd) = get_window_under_cursor() {
-                if is_game_window_or_child(cursor_hwnd, game_hwnd) {
-                    return screen_to_window_coords(game_hwnd, screen_x, screen_y);
+        // Live selection rectangle between the two area clicks
+        if let Some(start) = self.area_start {
+            if let Some(end) = cursor_in_game(game_hwnd) {
+                let (left, top, width, height) = self.constrain_selection(game_hwnd, start, end);
+                self.selection = Some((left, top, width, height));
+                let corners = (
+                    client_to_screen_coords(game_hwnd, left, top),
+                    client_to_screen_coords(game_hwnd, left + width, top + height),
+                );
+                if let (Some((left, top)), Some((right, bottom))) = corners {
+                    self.update_overlay_rect((left, top, right + 1, bottom + 1));
                 }
             }
-
-            None
-        };
+        }
 
         let is_down = is_left_mouse_down();
         if !is_down {
@@ -115,15 +432,15 @@ impl CalibrationManager {
         self.last_left_down = true;
 
         if self.is_area {
-            if let Some((x, y)) = cursor_in_game() {
-                if let Some((x1, y1)) = self.area_start {
-                    let left = x1.min(x);
-                    let top = y1.min(y);
-                    let width = (x1.max(x) - left).abs();
-                    let height = (y1.max(y) - top).abs();
+            if let Some((x, y)) = cursor_in_game(game_hwnd) {
+                if let Some(start) = self.area_start {
+                    let (left, top, width, height) =
+                        self.constrain_selection(game_hwnd, start, (x, y));
 
                     self.active = false;
                     self.area_start = None;
+                    self.selection = None;
+                    self.clear_overlay();
                     if let Some((nl, nt, nw, nh)) =
                         normalize_rect(game_hwnd, left, top, width, height)
                     {
@@ -138,7 +455,28 @@ impl CalibrationManager {
             return None;
         }
 
-        if let Some((x, y)) = cursor_in_game() {
+        if let Some((x, y)) = cursor_in_game(game_hwnd) {
+            if self.is_pixel {
+                self.active = false;
+                let (screen_x, screen_y) = client_to_screen_coords(game_hwnd, x, y)?;
+                let (r, g, b) = get_pixel_color(screen_x, screen_y)?;
+                let (nx, ny) = normalize_point(game_hwnd, x, y)?;
+                return Some(CalibrationResult::Pixel(nx, ny, r, g, b));
+            }
+
+            if self.nudge_points {
+                // Stay active until Enter/Esc (or a right click); arrow keys adjust the point
+                self.adjust = Some(PointAdjust {
+                    clicked: (x, y),
+                    current: (x, y),
+                });
+                // Keys already held at click time don't count as presses
+                for (state, key) in self.last_nudge_keys.iter_mut().zip(NUDGE_KEYS) {
+                    *state = is_key_down(key);
+                }
+                return None;
+            }
+
             self.active = false;
             if let Some((nx, ny)) = normalize_point(game_hwnd, x, y) {
                 return Some(CalibrationResult::Point(nx, ny));
@@ -0,0 +1,144 @@
+// Last check before every automated click: does the target actually lie on the game?
+use crate::core::window::{get_client_size, is_game_at_screen_point, screen_to_window_coords};
+use crate::core::worker::{push_log_entry, LogLevel, SharedLog, SharedStats};
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use windows::Win32::Foundation::HWND;
+
+// Log rejected clicks but send them anyway (set from `AppSettings::click_guard_warn_only`)
+static WARN_ONLY: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // Log and stats of the run on this thread, attached by the Worker
+    static RUN: RefCell<Option<(SharedLog, SharedStats)>> = const { RefCell::new(None) };
+}
+
+/// Why a click was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClickRejection {
+    // Client coordinates past the game's current client size (a stale calibration)
+    OutsideClient { x: i32, y: i32, size: (i32, i32) },
+    // The screen point is covered by another window, or the game has no client area
+    NotOverGame { screen_x: i32, screen_y: i32 },
+}
+
+impl fmt::Display for ClickRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClickRejection::OutsideClient { x, y, size } => write!(
+                f,
+                "Click at ({}, {}) is outside the {}x{} game window",
+                x, y, size.0, size.1
+            ),
+            ClickRejection::NotOverGame { screen_x, screen_y } => write!(
+                f,
+                "Click at screen ({}, {}) is not over the game window",
+                screen_x, screen_y
+            ),
+        }
+    }
+}
+
+/// Switch between skipping bad clicks and only warning about them
+pub fn configure(warn_only: bool) {
+    WARN_ONLY.store(warn_only, Ordering::Relaxed);
+}
+
+/// Report rejected clicks on this thread to a run's log and stats
+pub fn attach_run(log: SharedLog, stats: SharedStats) {
+    RUN.with(|run| *run.borrow_mut() = Some((log, stats)));
+}
+
+fn within(x: i32, y: i32, (width, height): (i32, i32)) -> bool {
+    (0..width).contains(&x) && (0..height).contains(&y)
+}
+
+fn check_client(game_hwnd: HWND, x: i32, y: i32) -> Result<(), ClickRejection> {
+    match get_client_size(game_hwnd) {
+        Some(size) if within(x, y, size) => Ok(()),
+        Some(size) => Err(ClickRejection::OutsideClient { x, y, size }),
+        None => Err(ClickRejection::OutsideClient { x, y, size: (0, 0) }),
+    }
+}
+
+fn check_screen(game_hwnd: HWND, screen_x: i32, screen_y: i32) -> Result<(), ClickRejection> {
+    let (x, y) = screen_to_window_coords(game_hwnd, screen_x, screen_y)
+        .ok_or(ClickRejection::NotOverGame { screen_x, screen_y })?;
+    check_client(game_hwnd, x, y)?;
+    if is_game_at_screen_point(game_hwnd, screen_x, screen_y) {
+        Ok(())
+    } else {
+        Err(ClickRejection::NotOverGame { screen_x, screen_y })
+    }
+}
+
+/// Log a rejection and count the skipped click; returns whether the click should still go out
+fn allow(check: Result<(), ClickRejection>) -> bool {
+    let Err(rejection) = check else {
+        return true;
+    };
+    let warn_only = WARN_ONLY.load(Ordering::Relaxed);
+    RUN.with(|run| {
+        if let Some((log, stats)) = run.borrow().as_ref() {
+            let message = if warn_only {
+                format!("{} (sent anyway)", rejection)
+            } else {
+                stats.lock().unwrap().skipped_clicks += 1;
+                format!("{}, skipped", rejection)
+            };
+            push_log_entry(log, LogLevel::Warn, &message);
+        }
+    });
+    warn_only
+}
+
+/// Guard for clicks sent as window messages at client coordinates
+pub fn allow_client_click(game_hwnd: HWND, x: i32, y: i32) -> bool {
+    allow(check_client(game_hwnd, x, y))
+}
+
+/// Guard for physical clicks: the point must also be over the game, not the helper or another app
+pub fn allow_screen_click(game_hwnd: HWND, screen_x: i32, screen_y: i32) -> bool {
+    allow(check_screen(game_hwnd, screen_x, screen_y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::worker::{LogBuffer, WorkerStats};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_within_client_size() {
+        assert!(within(0, 0, (800, 600)));
+        assert!(within(799, 599, (800, 600)));
+        assert!(!within(800, 300, (800, 600)));
+        assert!(!within(400, -1, (800, 600)));
+        assert!(!within(0, 0, (0, 0)));
+    }
+
+    #[test]
+    fn test_rejected_click_is_counted() {
+        // No test turns warn-only on, so the click is skipped
+        let log: SharedLog = Arc::new(Mutex::new(LogBuffer::new("Test")));
+        let stats: SharedStats = Arc::new(Mutex::new(WorkerStats::default()));
+        attach_run(log, Arc::clone(&stats));
+
+        assert!(allow(Ok(())));
+        assert!(!allow(Err(ClickRejection::OutsideClient {
+            x: 1500,
+            y: 20,
+            size: (1280, 720),
+        })));
+        assert_eq!(stats.lock().unwrap().skipped_clicks, 1);
+        assert_eq!(
+            ClickRejection::NotOverGame {
+                screen_x: 10,
+                screen_y: -5
+            }
+            .to_string(),
+            "Click at screen (10, -5) is not over the game window"
+        );
+    }
+}
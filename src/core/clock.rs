@@ -0,0 +1,124 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use windows::Win32::Foundation::{FILETIME, SYSTEMTIME};
+use windows::Win32::System::Time::{FileTimeToSystemTime, SystemTimeToTzSpecificLocalTime};
+
+// 100ns intervals between 1601-01-01 (FILETIME epoch) and 1970-01-01
+const UNIX_EPOCH_AS_FILETIME: u64 = 116_444_736_000_000_000;
+
+/// Wall-clock time in the user's time zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTime {
+    pub year: u16,
+    pub month: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16,
+    // 0 = Sunday
+    pub weekday: u16,
+}
+
+impl LocalTime {
+    /// HH:MM:SS
+    pub fn clock(&self) -> String {
+        format!("{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+
+    /// YYYY-MM-DD
+    pub fn date(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Convert to local time using the Windows time zone settings
+pub fn to_local(time: SystemTime) -> Option<LocalTime> {
+    let ticks = filetime_ticks(time.duration_since(UNIX_EPOCH).ok()?)?;
+    let filetime = FILETIME {
+        dwLowDateTime: ticks as u32,
+        dwHighDateTime: (ticks >> 32) as u32,
+    };
+    let mut utc = SYSTEMTIME::default();
+    let mut local = SYSTEMTIME::default();
+    unsafe {
+        if FileTimeToSystemTime(&filetime, &mut utc).is_err() {
+            return None;
+        }
+        if SystemTimeToTzSpecificLocalTime(None, &utc, &mut local).is_err() {
+            return None;
+        }
+    }
+    Some(LocalTime {
+        year: local.wYear,
+        month: local.wMonth,
+        day: local.wDay,
+        hour: local.wHour,
+        minute: local.wMinute,
+        second: local.wSecond,
+        weekday: local.wDayOfWeek,
+    })
+}
+
+fn filetime_ticks(since_unix_epoch: Duration) -> Option<u64> {
+    let ticks = u64::try_from(since_unix_epoch.as_nanos() / 100).ok()?;
+    ticks.checked_add(UNIX_EPOCH_AS_FILETIME)
+}
+
+/// Countdown as m:ss, rounded up so it reads 0:00 exactly when the wait is over
+pub fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// Run time as HH:MM:SS (hours keep counting past a day)
+pub fn format_hms(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filetime_ticks() {
+        assert_eq!(filetime_ticks(Duration::ZERO), Some(UNIX_EPOCH_AS_FILETIME));
+        assert_eq!(
+            filetime_ticks(Duration::from_secs(1)),
+            Some(UNIX_EPOCH_AS_FILETIME + 10_000_000)
+        );
+    }
+
+    #[test]
+    fn test_clock_and_date_format() {
+        let time = LocalTime {
+            year: 2024,
+            month: 3,
+            day: 7,
+            hour: 9,
+            minute: 5,
+            second: 0,
+            weekday: 4,
+        };
+        assert_eq!(time.clock(), "09:05:00");
+        assert_eq!(time.date(), "2024-03-07");
+    }
+
+    #[test]
+    fn test_format_countdown_rounds_up() {
+        assert_eq!(format_countdown(Duration::ZERO), "0:00");
+        assert_eq!(format_countdown(Duration::from_millis(1)), "0:01");
+        assert_eq!(format_countdown(Duration::from_secs(29 * 60 + 5)), "29:05");
+    }
+
+    #[test]
+    fn test_format_hms() {
+        assert_eq!(format_hms(Duration::ZERO), "00:00:00");
+        assert_eq!(format_hms(Duration::from_secs(42 * 60 + 13)), "00:42:13");
+        assert_eq!(format_hms(Duration::from_secs(26 * 3600 + 5)), "26:00:05");
+    }
+}
@@ -0,0 +1,131 @@
+// Errors of the capture, detection, OCR and input layers, with the wording the UI shows
+use crate::core::click_guard::ClickRejection;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HelperError {
+    // No game window to work with
+    WindowNotFound,
+    // The window handle went stale (game closed or restarted)
+    WindowInvalid,
+    CaptureFailed { os_error: String },
+    TemplateLoad { path: String, source: String },
+    FileWrite { path: String, source: String },
+    OcrEngine(String),
+    // Client-relative (x, y, width, height) that can't be captured or searched
+    InvalidRegion { region: (i32, i32, i32, i32) },
+    InputRejected(ClickRejection),
+}
+
+impl fmt::Display for HelperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HelperError::WindowNotFound => write!(f, "Game window not found"),
+            HelperError::WindowInvalid => write!(f, "Game window is no longer valid"),
+            HelperError::CaptureFailed { os_error } => {
+                write!(f, "Screen capture failed: {}", os_error)
+            }
+            HelperError::TemplateLoad { path, source } => {
+                write!(f, "Failed to load template {}: {}", path, source)
+            }
+            HelperError::FileWrite { path, source } => {
+                write!(f, "Failed to save {}: {}", path, source)
+            }
+            HelperError::OcrEngine(detail) => write!(f, "OCR engine error: {}", detail),
+            HelperError::InvalidRegion {
+                region: (x, y, width, height),
+            } => write!(f, "Invalid region ({}, {}) {}x{}", x, y, width, height),
+            HelperError::InputRejected(rejection) => write!(f, "Input rejected: {}", rejection),
+        }
+    }
+}
+
+impl From<ClickRejection> for HelperError {
+    fn from(rejection: ClickRejection) -> Self {
+        HelperError::InputRejected(rejection)
+    }
+}
+
+impl HelperError {
+    /// Status line for a tool that hit this error (every tool words it the same way)
+    pub fn status(&self) -> String {
+        format!("Error: {}", self)
+    }
+
+    /// What the user can do about it, shown on hover
+    pub fn hint(&self) -> &'static str {
+        match self {
+            HelperError::WindowNotFound | HelperError::WindowInvalid => {
+                tr!("error.hint.window")
+            }
+            HelperError::CaptureFailed { .. } => tr!("error.hint.capture"),
+            HelperError::TemplateLoad { .. } => tr!("error.hint.template"),
+            HelperError::FileWrite { .. } => tr!("error.hint.file_write"),
+            HelperError::OcrEngine(_) => tr!("error.hint.ocr"),
+            HelperError::InvalidRegion { .. } | HelperError::InputRejected(_) => {
+                tr!("error.hint.recalibrate")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_of_every_variant() {
+        let cases = [
+            (HelperError::WindowNotFound, "Game window not found"),
+            (HelperError::WindowInvalid, "Game window is no longer valid"),
+            (
+                HelperError::CaptureFailed {
+                    os_error: "0x80070005".to_string(),
+                },
+                "Screen capture failed: 0x80070005",
+            ),
+            (
+                HelperError::TemplateLoad {
+                    path: "red-dot.png".to_string(),
+                    source: "file not found".to_string(),
+                },
+                "Failed to load template red-dot.png: file not found",
+            ),
+            (
+                HelperError::FileWrite {
+                    path: "templates/dialog.png".to_string(),
+                    source: "access denied".to_string(),
+                },
+                "Failed to save templates/dialog.png: access denied",
+            ),
+            (
+                HelperError::OcrEngine("no models".to_string()),
+                "OCR engine error: no models",
+            ),
+            (
+                HelperError::InvalidRegion {
+                    region: (10, 20, 0, 5),
+                },
+                "Invalid region (10, 20) 0x5",
+            ),
+            (
+                HelperError::InputRejected(ClickRejection::OutsideClient {
+                    x: 1500,
+                    y: 20,
+                    size: (1280, 720),
+                }),
+                "Input rejected: Click at (1500, 20) is outside the 1280x720 game window",
+            ),
+        ];
+        for (error, text) in cases {
+            assert_eq!(error.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_status_is_an_error_line() {
+        let status = HelperError::WindowNotFound.status();
+        assert_eq!(status, "Error: Game window not found");
+        assert!(crate::ui::status::is_error_status(&status));
+    }
+}
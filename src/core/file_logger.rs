@@ -0,0 +1,220 @@
+// Optional on-disk copy of the worker logs, one file per day
+use crate::core::clock::to_local;
+use crate::core::paths;
+use crate::core::worker::{LogEntry, LogLevel};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+const FILE_PREFIX: &str = "cabalhelper-";
+const FILE_SUFFIX: &str = ".log";
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+struct OpenLogFile {
+    date: String,
+    writer: BufWriter<File>,
+}
+
+pub struct FileLogger {
+    dir: PathBuf,
+    enabled: AtomicBool,
+    retention_days: AtomicU32,
+    file: Mutex<Option<OpenLogFile>>,
+    // Why the current file couldn't be opened, shown in the log panel
+    error: Mutex<Option<String>>,
+}
+
+/// Shared logger writing to `paths::logs_dir()`; disabled until `configure` turns it on
+pub fn file_logger() -> &'static FileLogger {
+    static LOGGER: OnceLock<FileLogger> = OnceLock::new();
+    LOGGER.get_or_init(|| FileLogger::new(paths::logs_dir()))
+}
+
+impl FileLogger {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            enabled: AtomicBool::new(false),
+            retention_days: AtomicU32::new(1),
+            file: Mutex::new(None),
+            error: Mutex::new(None),
+        }
+    }
+
+    /// Turn file logging on or off and set how many days of files to keep
+    pub fn configure(&self, enabled: bool, retention_days: u32) {
+        self.retention_days.store(retention_days, Ordering::Relaxed);
+        let was_enabled = self.enabled.swap(enabled, Ordering::Relaxed);
+        if was_enabled && !enabled {
+            // Dropping the writer flushes it
+            *self.file.lock().unwrap() = None;
+            *self.error.lock().unwrap() = None;
+        }
+    }
+
+    /// Append one entry, tagged with the tool it came from.
+    /// Errors are flushed right away so they survive a crash.
+    pub fn append(&self, entry: &LogEntry) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        let local = to_local(entry.time);
+        let date = local
+            .map(|time| time.date())
+            .unwrap_or_else(|| "undated".to_string());
+        let clock = local
+            .map(|time| time.clock())
+            .unwrap_or_else(|| "--:--:--".to_string());
+
+        let mut file = self.file.lock().unwrap();
+        let is_current = matches!(file.as_ref(), Some(open) if open.date == date);
+        if !is_current {
+            // New day (or first entry): rotate and drop files past the retention period
+            *file = self.open(&date);
+            prune_old_logs(
+                &self.dir,
+                self.retention_days.load(Ordering::Relaxed),
+                SystemTime::now(),
+            );
+        }
+        let Some(open) = file.as_mut() else {
+            return;
+        };
+        let _ = writeln!(
+            open.writer,
+            "{} {:<5} [{}] {}",
+            clock,
+            entry.level.label(),
+            entry.source,
+            entry.text
+        );
+        if entry.level == LogLevel::Error {
+            let _ = open.writer.flush();
+        }
+    }
+
+    /// Write out buffered lines (e.g. on exit)
+    pub fn flush(&self) {
+        if let Some(open) = self.file.lock().unwrap().as_mut() {
+            let _ = open.writer.flush();
+        }
+    }
+
+    /// Show the log folder in Explorer (creating it if needed)
+    pub fn open_dir(&self) -> Result<(), String> {
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| {
+                std::process::Command::new("explorer")
+                    .arg(&self.dir)
+                    .spawn()
+            })
+            .map(|_| ())
+            .map_err(|err| format!("Can't open {}: {}", self.dir.display(), err))
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+
+    fn open(&self, date: &str) -> Option<OpenLogFile> {
+        let path = self.dir.join(log_file_name(date));
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|_| OpenOptions::new().create(true).append(true).open(&path));
+        match result {
+            Ok(file) => {
+                *self.error.lock().unwrap() = None;
+                Some(OpenLogFile {
+                    date: date.to_string(),
+                    writer: BufWriter::new(file),
+                })
+            }
+            Err(err) => {
+                *self.error.lock().unwrap() =
+                    Some(format!("Can't write {}: {}", path.display(), err));
+                None
+            }
+        }
+    }
+}
+
+fn log_file_name(date: &str) -> String {
+    format!("{}{}{}", FILE_PREFIX, date, FILE_SUFFIX)
+}
+
+/// Delete our log files last written more than `retention_days` before `now`
+fn prune_old_logs(dir: &Path, retention_days: u32, now: SystemTime) {
+    let Some(cutoff) = now.checked_sub(Duration::from_secs(
+        u64::from(retention_days) * SECS_PER_DAY,
+    )) else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.starts_with(FILE_PREFIX) || !name.ends_with(FILE_SUFFIX) {
+            continue;
+        }
+        let modified = entry.metadata().and_then(|meta| meta.modified());
+        if matches!(modified, Ok(time) if time < cutoff) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("cabalhelper_logs_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_appends_tagged_lines_only_when_enabled() {
+        let dir = temp_log_dir("append");
+        let logger = FileLogger::new(dir.clone());
+        logger.append(&LogEntry::new("Tool", LogLevel::Info, "before enabling"));
+        assert!(!dir.exists());
+
+        logger.configure(true, 7);
+        logger.append(&LogEntry::new("Tool", LogLevel::Info, "started"));
+        logger.append(&LogEntry::new("Tool", LogLevel::Error, "failed"));
+
+        let files: Vec<_> = fs::read_dir(&dir).unwrap().flatten().collect();
+        assert_eq!(files.len(), 1);
+        let contents = fs::read_to_string(files[0].path()).unwrap();
+        assert!(contents.contains("INFO  [Tool] started"));
+        assert!(contents.contains("ERROR [Tool] failed"));
+        assert!(!contents.contains("before enabling"));
+
+        logger.configure(false, 7);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_prune_removes_only_old_log_files() {
+        let dir = temp_log_dir("prune");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(log_file_name("2020-01-01")), "old").unwrap();
+        fs::write(dir.join("notes.txt"), "keep").unwrap();
+
+        prune_old_logs(&dir, 7, SystemTime::now());
+        assert!(dir.join(log_file_name("2020-01-01")).exists());
+
+        let in_ten_days = SystemTime::now() + Duration::from_secs(10 * SECS_PER_DAY);
+        prune_old_logs(&dir, 7, in_ten_days);
+        assert!(!dir.join(log_file_name("2020-01-01")).exists());
+        assert!(dir.join("notes.txt").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,200 @@
+// Periodic screenshots of the game while tools run, for seeing afterwards what a macro did
+use crate::core::clock::to_local;
+use crate::core::error::HelperError;
+use crate::core::paths;
+use crate::core::screen_capture::capture_window_region;
+use crate::core::window::get_client_size;
+use crate::core::worker::Worker;
+use crate::settings::FrameRecorderSettings;
+use image::{imageops, DynamicImage};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use windows::Win32::Foundation::HWND;
+
+const FILE_PREFIX: &str = "frame_";
+const FILE_SUFFIX: &str = ".jpg";
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Background capture thread owned by the app; `sync` starts and stops it as tools do
+pub struct FrameRecorder {
+    // This session's folder, created with the first frame
+    session_dir: PathBuf,
+    // Window the current thread records
+    active: Option<HWND>,
+    // Read by the thread before each frame, so edits apply without a restart
+    settings: Arc<Mutex<FrameRecorderSettings>>,
+    // Stop flag of the current thread (each start gets its own)
+    running: Arc<AtomicBool>,
+    // Why the last frame couldn't be saved, shown in the settings section
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl FrameRecorder {
+    pub fn new(started_at: SystemTime) -> Self {
+        Self {
+            session_dir: paths::frames_dir().join(session_folder_name(started_at)),
+            active: None,
+            settings: Arc::new(Mutex::new(FrameRecorderSettings::default())),
+            running: Arc::new(AtomicBool::new(false)),
+            error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Record `hwnd` with `settings`, or stop when `hwnd` is None.
+    /// Restarts the thread only when the window changed.
+    pub fn sync(&mut self, hwnd: Option<HWND>, settings: FrameRecorderSettings) {
+        *self.settings.lock().unwrap() = settings;
+        if hwnd == self.active {
+            return;
+        }
+        self.stop();
+        if let Some(hwnd) = hwnd {
+            self.start(hwnd);
+        }
+    }
+
+    /// Let the thread finish its current frame and exit (not waited for)
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        self.active = None;
+    }
+
+    /// Show this session's frames in Explorer (creating the folder if needed)
+    pub fn open_dir(&self) -> Result<(), String> {
+        fs::create_dir_all(&self.session_dir)
+            .and_then(|_| {
+                std::process::Command::new("explorer")
+                    .arg(&self.session_dir)
+                    .spawn()
+            })
+            .map(|_| ())
+            .map_err(|err| format!("Can't open {}: {}", self.session_dir.display(), err))
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+
+    fn start(&mut self, hwnd: HWND) {
+        let running = Arc::new(AtomicBool::new(true));
+        self.running = Arc::clone(&running);
+        self.active = Some(hwnd);
+        let dir = self.session_dir.clone();
+        let shared_settings = Arc::clone(&self.settings);
+        let error = Arc::clone(&self.error);
+
+        std::thread::spawn(move || {
+            while running.load(Ordering::Relaxed) {
+                let settings = *shared_settings.lock().unwrap();
+                let result = save_frame(hwnd, &dir, settings.scale_percent);
+                if result.is_ok() {
+                    let max_bytes = u64::from(settings.max_total_mb) * BYTES_PER_MB;
+                    prune_frames(&dir, settings.max_files as usize, max_bytes);
+                }
+                *error.lock().unwrap() = result.err().map(|e| e.to_string());
+                Worker::sleep_while_running(&running, settings.interval_secs * 1000);
+            }
+        });
+    }
+}
+
+/// `<date>_<HH-MM-SS>` of the session start
+fn session_folder_name(started_at: SystemTime) -> String {
+    to_local(started_at)
+        .map(|time| format!("{}_{}", time.date(), time.clock().replace(':', "-")))
+        .unwrap_or_else(|| "undated".to_string())
+}
+
+/// Capture the whole client area, shrink it to `scale_percent` and save it as a JPEG
+/// named after the current time
+fn save_frame(hwnd: HWND, dir: &Path, scale_percent: u32) -> Result<(), HelperError> {
+    let (width, height) = get_client_size(hwnd).ok_or(HelperError::WindowInvalid)?;
+    let image = capture_window_region(hwnd, (0, 0, width, height))?;
+    let scaled = imageops::resize(
+        &image,
+        (image.width() * scale_percent / 100).max(1),
+        (image.height() * scale_percent / 100).max(1),
+        imageops::FilterType::Triangle,
+    );
+
+    let write_failed = |path: &Path, e: &dyn std::fmt::Display| HelperError::FileWrite {
+        path: path.display().to_string(),
+        source: e.to_string(),
+    };
+    fs::create_dir_all(dir).map_err(|e| write_failed(dir, &e))?;
+    let clock = to_local(SystemTime::now())
+        .map(|time| time.clock().replace(':', "-"))
+        .unwrap_or_else(|| "undated".to_string());
+    let path = dir.join(format!("{}{}{}", FILE_PREFIX, clock, FILE_SUFFIX));
+    // JPEG has no alpha channel
+    DynamicImage::ImageRgba8(scaled)
+        .to_rgb8()
+        .save(&path)
+        .map_err(|e| write_failed(&path, &e))
+}
+
+/// Delete our frames in `dir`, oldest first, until at most `max_files` remain and they
+/// take at most `max_bytes`
+fn prune_frames(dir: &Path, max_files: usize, max_bytes: u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut frames: Vec<(SystemTime, PathBuf, u64)> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX)
+        })
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            Some((meta.modified().ok()?, entry.path(), meta.len()))
+        })
+        .collect();
+    // Names break ties between frames written within the same clock tick
+    frames.sort();
+
+    let mut total: u64 = frames.iter().map(|(_, _, size)| size).sum();
+    let mut count = frames.len();
+    for (_, path, size) in frames {
+        if count <= max_files && total <= max_bytes {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            count -= 1;
+            total -= size;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_keeps_the_newest_frames_within_both_caps() {
+        let dir = std::env::temp_dir().join(format!("cabalhelper_frames_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let frame = |n: u32| dir.join(format!("{}00-00-0{}{}", FILE_PREFIX, n, FILE_SUFFIX));
+        for n in 1..=4 {
+            fs::write(frame(n), vec![0u8; 100]).unwrap();
+        }
+        fs::write(dir.join("notes.txt"), vec![0u8; 1000]).unwrap();
+
+        prune_frames(&dir, 3, 10_000);
+        assert!(!frame(1).exists());
+        assert!(frame(2).exists());
+
+        // 300 bytes left, only two fit under the size cap
+        prune_frames(&dir, 3, 250);
+        assert!(!frame(2).exists());
+        assert!(frame(3).exists() && frame(4).exists());
+        assert!(dir.join("notes.txt").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
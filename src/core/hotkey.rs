@@ -1,8 +1,17 @@
-use crate::settings::{HotkeyConfig, HotkeyKey, HotkeyModifiers};
+use crate::core::input::key_state;
+use crate::settings::{
+    HotkeyActionId, HotkeyConfig, HotkeyKey, HotkeyModifiers, HotkeyMouseButton,
+};
 use eframe::egui;
 use global_hotkey::hotkey::{Code, HotKey, Modifiers};
+use global_hotkey::GlobalHotKeyManager;
+use std::collections::HashMap;
+use windows::Win32::UI::Input::KeyboardAndMouse::{VIRTUAL_KEY, VK_XBUTTON1, VK_XBUTTON2};
 
 pub fn hotkey_label(config: &HotkeyConfig) -> String {
+    if let Some(button) = config.mouse_button {
+        return mouse_button_label(button).to_string();
+    }
     let Some(key) = config.key else {
         return "Disabled".to_string();
     };
@@ -25,6 +34,9 @@ pub fn hotkey_label(config: &HotkeyConfig) -> String {
 }
 
 pub fn hotkey_from_config(config: &HotkeyConfig) -> Option<HotKey> {
+    if config.mouse_button.is_some() {
+        return None;
+    }
     let key = config.key?;
     let code = hotkey_key_to_code(key);
     let modifiers = hotkey_modifiers_to_code(config.modifiers);
@@ -35,7 +47,21 @@ pub fn hotkey_from_config(config: &HotkeyConfig) -> Option<HotKey> {
     }
 }
 
-fn hotkey_key_label(key: HotkeyKey) -> &'static str {
+fn mouse_button_label(button: HotkeyMouseButton) -> &'static str {
+    match button {
+        HotkeyMouseButton::XButton1 => "Mouse 4",
+        HotkeyMouseButton::XButton2 => "Mouse 5",
+    }
+}
+
+fn mouse_button_to_vk(button: HotkeyMouseButton) -> VIRTUAL_KEY {
+    match button {
+        HotkeyMouseButton::XButton1 => VK_XBUTTON1,
+        HotkeyMouseButton::XButton2 => VK_XBUTTON2,
+    }
+}
+
+pub fn hotkey_key_label(key: HotkeyKey) -> &'static str {
     match key {
         HotkeyKey::A => "A",
         HotkeyKey::B => "B",
@@ -192,6 +218,23 @@ pub fn try_capture_hotkey(ctx: &egui::Context) -> Option<HotkeyConfig> {
     let modifiers = ctx.input(|i| i.modifiers);
     let events = ctx.input(|i| i.events.clone());
     for event in events {
+        if let egui::Event::PointerButton {
+            button: button @ (egui::PointerButton::Extra1 | egui::PointerButton::Extra2),
+            pressed: true,
+            ..
+        } = event
+        {
+            let mouse_button = if button == egui::PointerButton::Extra1 {
+                HotkeyMouseButton::XButton1
+            } else {
+                HotkeyMouseButton::XButton2
+            };
+            return Some(HotkeyConfig {
+                key: None,
+                modifiers: HotkeyModifiers::default(),
+                mouse_button: Some(mouse_button),
+            });
+        }
         if let egui::Event::Key {
             key,
             pressed: true,
@@ -207,6 +250,7 @@ pub fn try_capture_hotkey(ctx: &egui::Context) -> Option<HotkeyConfig> {
                         shift: modifiers.shift,
                         meta: modifiers.command,
                     },
+                    mouse_button: None,
                 });
             }
         }
@@ -214,6 +258,76 @@ pub fn try_capture_hotkey(ctx: &egui::Context) -> Option<HotkeyConfig> {
     None
 }
 
+/// Virtual-key code of `key`, for sending it to the game window
+pub fn hotkey_key_to_vk(key: HotkeyKey) -> VIRTUAL_KEY {
+    use windows::Win32::UI::Input::KeyboardAndMouse::*;
+    match key {
+        HotkeyKey::A => VK_A,
+        HotkeyKey::B => VK_B,
+        HotkeyKey::C => VK_C,
+        HotkeyKey::D => VK_D,
+        HotkeyKey::E => VK_E,
+        HotkeyKey::F => VK_F,
+        HotkeyKey::G => VK_G,
+        HotkeyKey::H => VK_H,
+        HotkeyKey::I => VK_I,
+        HotkeyKey::J => VK_J,
+        HotkeyKey::K => VK_K,
+        HotkeyKey::L => VK_L,
+        HotkeyKey::M => VK_M,
+        HotkeyKey::N => VK_N,
+        HotkeyKey::O => VK_O,
+        HotkeyKey::P => VK_P,
+        HotkeyKey::Q => VK_Q,
+        HotkeyKey::R => VK_R,
+        HotkeyKey::S => VK_S,
+        HotkeyKey::T => VK_T,
+        HotkeyKey::U => VK_U,
+        HotkeyKey::V => VK_V,
+        HotkeyKey::W => VK_W,
+        HotkeyKey::X => VK_X,
+        HotkeyKey::Y => VK_Y,
+        HotkeyKey::Z => VK_Z,
+        HotkeyKey::Digit0 => VK_0,
+        HotkeyKey::Digit1 => VK_1,
+        HotkeyKey::Digit2 => VK_2,
+        HotkeyKey::Digit3 => VK_3,
+        HotkeyKey::Digit4 => VK_4,
+        HotkeyKey::Digit5 => VK_5,
+        HotkeyKey::Digit6 => VK_6,
+        HotkeyKey::Digit7 => VK_7,
+        HotkeyKey::Digit8 => VK_8,
+        HotkeyKey::Digit9 => VK_9,
+        HotkeyKey::F1 => VK_F1,
+        HotkeyKey::F2 => VK_F2,
+        HotkeyKey::F3 => VK_F3,
+        HotkeyKey::F4 => VK_F4,
+        HotkeyKey::F5 => VK_F5,
+        HotkeyKey::F6 => VK_F6,
+        HotkeyKey::F7 => VK_F7,
+        HotkeyKey::F8 => VK_F8,
+        HotkeyKey::F9 => VK_F9,
+        HotkeyKey::F10 => VK_F10,
+        HotkeyKey::F11 => VK_F11,
+        HotkeyKey::F12 => VK_F12,
+        HotkeyKey::Escape => VK_ESCAPE,
+        HotkeyKey::Space => VK_SPACE,
+        HotkeyKey::Enter => VK_RETURN,
+        HotkeyKey::Tab => VK_TAB,
+        HotkeyKey::Backspace => VK_BACK,
+        HotkeyKey::Insert => VK_INSERT,
+        HotkeyKey::Delete => VK_DELETE,
+        HotkeyKey::Home => VK_HOME,
+        HotkeyKey::End => VK_END,
+        HotkeyKey::PageUp => VK_PRIOR,
+        HotkeyKey::PageDown => VK_NEXT,
+        HotkeyKey::ArrowUp => VK_UP,
+        HotkeyKey::ArrowDown => VK_DOWN,
+        HotkeyKey::ArrowLeft => VK_LEFT,
+        HotkeyKey::ArrowRight => VK_RIGHT,
+    }
+}
+
 fn egui_key_to_hotkey_key(key: egui::Key) -> Option<HotkeyKey> {
     match key {
         egui::Key::Escape => Some(HotkeyKey::Escape),
@@ -282,3 +396,273 @@ fn egui_key_to_hotkey_key(key: egui::Key) -> Option<HotkeyKey> {
         _ => None,
     }
 }
+
+/// Whether two bindings fire on the same key or button press (disabled bindings never clash)
+pub fn same_hotkey(a: &HotkeyConfig, b: &HotkeyConfig) -> bool {
+    if a.mouse_button.is_some() || b.mouse_button.is_some() {
+        return a.mouse_button == b.mouse_button;
+    }
+    a.key.is_some() && a.key == b.key && a.modifiers == b.modifiers
+}
+
+/// Reports each press of a mouse side button once. global_hotkey only covers the keyboard,
+/// so these are polled from the UI loop instead.
+#[derive(Default)]
+pub struct MouseButtonWatcher {
+    held: [bool; 2],
+}
+
+impl MouseButtonWatcher {
+    pub fn poll(&mut self) -> Vec<HotkeyMouseButton> {
+        self.update(HotkeyMouseButton::ALL.map(|button| key_state(mouse_button_to_vk(button))))
+    }
+
+    // `states` holds (held now, pressed since the last poll) in `HotkeyMouseButton::ALL` order
+    fn update(&mut self, states: [(bool, bool); 2]) -> Vec<HotkeyMouseButton> {
+        let mut pressed = Vec::new();
+        for (idx, (held, clicked)) in states.into_iter().enumerate() {
+            // A click shorter than the poll interval only shows up in `clicked`
+            if !self.held[idx] && (held || clicked) {
+                pressed.push(HotkeyMouseButton::ALL[idx]);
+            }
+            self.held[idx] = held;
+        }
+        pressed
+    }
+}
+
+/// Why an action's binding is not registered with the OS
+#[derive(Debug, Clone, PartialEq)]
+pub enum HotkeyProblem {
+    /// Same combination as this action, which keeps it
+    Conflict(HotkeyActionId),
+    /// The OS refused it, usually because another program holds the combination
+    Registration { config: HotkeyConfig, error: String },
+}
+
+impl HotkeyProblem {
+    pub fn message(&self) -> String {
+        match self {
+            HotkeyProblem::Conflict(other) => {
+                format!("Already used by {}; change one of them", other.label())
+            }
+            HotkeyProblem::Registration { error, .. } => {
+                format!(
+                    "Windows refused this hotkey (another app may use it): {}",
+                    error
+                )
+            }
+        }
+    }
+}
+
+/// Keeps the OS global hotkey registrations in line with the configured bindings
+pub struct HotkeyRegistrar {
+    manager: Option<GlobalHotKeyManager>,
+    registered: HashMap<HotkeyActionId, (HotkeyConfig, HotKey)>,
+    // Polled by `MouseButtonWatcher`; rebuilt on every sync
+    mouse_bindings: HashMap<HotkeyActionId, HotkeyMouseButton>,
+    problems: HashMap<HotkeyActionId, HotkeyProblem>,
+    error: Option<String>,
+}
+
+impl HotkeyRegistrar {
+    pub fn new() -> Self {
+        let manager = GlobalHotKeyManager::new().ok();
+        let error = manager
+            .is_none()
+            .then(|| "Global hotkey manager unavailable".to_string());
+        Self {
+            manager,
+            registered: HashMap::new(),
+            mouse_bindings: HashMap::new(),
+            problems: HashMap::new(),
+            error,
+        }
+    }
+
+    /// Register changed bindings. A binding that duplicates one already registered for another
+    /// action, or that the OS refuses, stays unregistered with its problem recorded until it changes.
+    pub fn sync(&mut self, bindings: &[(HotkeyActionId, HotkeyConfig)]) {
+        // None for actions that no longer exist (e.g. a deleted macro)
+        let wanted = |action: HotkeyActionId| {
+            bindings
+                .iter()
+                .find(|(id, _)| *id == action)
+                .map(|(_, config)| config)
+        };
+
+        // Conflicts are re-checked below; OS refusals are only retried once the binding changes
+        self.problems.retain(|action, problem| match problem {
+            HotkeyProblem::Conflict(_) => false,
+            HotkeyProblem::Registration { config, .. } => wanted(*action) == Some(&*config),
+        });
+
+        // Mouse buttons need no OS registration; the first action bound to a button keeps it
+        self.mouse_bindings.clear();
+        for (action, config) in bindings {
+            let Some(button) = config.mouse_button else {
+                continue;
+            };
+            if let Some(other) = self
+                .mouse_bindings
+                .iter()
+                .find(|(_, bound)| **bound == button)
+                .map(|(id, _)| *id)
+            {
+                self.problems
+                    .insert(*action, HotkeyProblem::Conflict(other));
+            } else {
+                self.mouse_bindings.insert(*action, button);
+            }
+        }
+
+        let Some(manager) = self.manager.as_ref() else {
+            return;
+        };
+        self.registered.retain(|action, (config, hotkey)| {
+            let keep = wanted(*action) == Some(&*config);
+            if !keep {
+                let _ = manager.unregister(*hotkey);
+            }
+            keep
+        });
+
+        for (action, config) in bindings {
+            let action = *action;
+            if self.registered.contains_key(&action) || self.problems.contains_key(&action) {
+                continue;
+            }
+            let Some(hotkey) = hotkey_from_config(config) else {
+                continue;
+            };
+            if let Some(other) = self
+                .registered
+                .iter()
+                .find(|(_, (registered, _))| same_hotkey(registered, config))
+                .map(|(id, _)| *id)
+            {
+                self.problems.insert(action, HotkeyProblem::Conflict(other));
+                continue;
+            }
+            match manager.register(hotkey) {
+                Ok(()) => {
+                    self.registered.insert(action, (config.clone(), hotkey));
+                }
+                Err(err) => {
+                    let config = config.clone();
+                    let error = format!("{:?}", err);
+                    self.problems
+                        .insert(action, HotkeyProblem::Registration { config, error });
+                }
+            }
+        }
+    }
+
+    /// Drop every registration, e.g. while a new binding is captured so the
+    /// old one doesn't fire. The next `sync` registers them again.
+    pub fn suspend(&mut self) {
+        if let Some(manager) = self.manager.as_ref() {
+            for (_, (_, hotkey)) in self.registered.drain() {
+                let _ = manager.unregister(hotkey);
+            }
+        }
+        self.mouse_bindings.clear();
+        self.problems.clear();
+    }
+
+    pub fn action_for(&self, hotkey_id: u32) -> Option<HotkeyActionId> {
+        self.registered
+            .iter()
+            .find(|(_, (_, hotkey))| hotkey.id() == hotkey_id)
+            .map(|(action, _)| *action)
+    }
+
+    pub fn action_for_mouse(&self, button: HotkeyMouseButton) -> Option<HotkeyActionId> {
+        self.mouse_bindings
+            .iter()
+            .find(|(_, bound)| **bound == button)
+            .map(|(action, _)| *action)
+    }
+
+    /// Bindings that are configured but not active, by action
+    pub fn problems(&self) -> &HashMap<HotkeyActionId, HotkeyProblem> {
+        &self.problems
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(key: Option<HotkeyKey>, ctrl: bool) -> HotkeyConfig {
+        HotkeyConfig {
+            key,
+            modifiers: HotkeyModifiers {
+                ctrl,
+                ..HotkeyModifiers::default()
+            },
+            mouse_button: None,
+        }
+    }
+
+    #[test]
+    fn test_same_hotkey_compares_key_and_modifiers() {
+        let ctrl_f5 = binding(Some(HotkeyKey::F5), true);
+        assert!(same_hotkey(&ctrl_f5, &binding(Some(HotkeyKey::F5), true)));
+        assert!(!same_hotkey(&ctrl_f5, &binding(Some(HotkeyKey::F5), false)));
+        assert!(!same_hotkey(&ctrl_f5, &binding(Some(HotkeyKey::F7), true)));
+    }
+
+    #[test]
+    fn test_disabled_bindings_never_clash() {
+        assert!(!same_hotkey(&binding(None, false), &binding(None, false)));
+        assert!(!same_hotkey(&binding(None, true), &binding(None, true)));
+    }
+
+    #[test]
+    fn test_mouse_bindings_clash_only_with_the_same_button() {
+        let side = HotkeyConfig {
+            mouse_button: Some(HotkeyMouseButton::XButton1),
+            ..binding(None, false)
+        };
+        let other_side = HotkeyConfig {
+            mouse_button: Some(HotkeyMouseButton::XButton2),
+            ..binding(None, false)
+        };
+        assert!(same_hotkey(&side, &side.clone()));
+        assert!(!same_hotkey(&side, &other_side));
+        assert!(!same_hotkey(&side, &binding(Some(HotkeyKey::F5), false)));
+    }
+
+    #[test]
+    fn test_mouse_watcher_fires_once_per_press() {
+        let mut watcher = MouseButtonWatcher::default();
+        let idle = (false, false);
+
+        // Held across several polls
+        assert_eq!(
+            watcher.update([(true, true), idle]),
+            vec![HotkeyMouseButton::XButton1]
+        );
+        assert!(watcher.update([(true, false), idle]).is_empty());
+        assert!(watcher.update([idle, idle]).is_empty());
+
+        // Pressed and released between two polls
+        assert_eq!(
+            watcher.update([idle, (false, true)]),
+            vec![HotkeyMouseButton::XButton2]
+        );
+        assert!(watcher.update([idle, idle]).is_empty());
+
+        // A new press after a release fires again
+        assert_eq!(
+            watcher.update([(true, true), idle]),
+            vec![HotkeyMouseButton::XButton1]
+        );
+    }
+}
@@ -1,9 +1,12 @@
+use crate::core::click_guard::allow_client_click;
 use windows::{
     Win32::Foundation::{HWND, LPARAM, WPARAM},
-    Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState,
+    Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, MapVirtualKeyA, MAPVK_VK_TO_VSC, VIRTUAL_KEY,
+    },
     Win32::UI::WindowsAndMessaging::{
-        SendMessageA, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_RBUTTONDOWN,
-        WM_RBUTTONUP,
+        PostMessageA, SendMessageA, WM_KEYDOWN, WM_KEYUP, WM_LBUTTONDOWN, WM_LBUTTONUP,
+        WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_RBUTTONDOWN, WM_RBUTTONUP,
     },
 };
 
@@ -14,6 +17,9 @@ const MK_MBUTTON: u32 = 0x0010;
 
 /// Click at coordinates using SendMessage (direct click, frees up mouse)
 pub fn click_at_position(hwnd: HWND, x: i32, y: i32) -> bool {
+    if !allow_client_click(hwnd, x, y) {
+        return false;
+    }
     unsafe {
         // Create lParam: low word = x, high word = y
         let lparam_value = ((y as u32) << 16) | (x as u32 & 0xFFFF);
@@ -27,8 +33,56 @@ pub fn click_at_position(hwnd: HWND, x: i32, y: i32) -> bool {
     }
 }
 
+/// Click at coordinates using PostMessage (queued, does not wait for the game to process it)
+pub fn click_at_position_post(hwnd: HWND, x: i32, y: i32) -> bool {
+    if !allow_client_click(hwnd, x, y) {
+        return false;
+    }
+    unsafe {
+        // Create lParam: low word = x, high word = y
+        let lparam_value = ((y as u32) << 16) | (x as u32 & 0xFFFF);
+        let lparam = LPARAM(lparam_value as isize);
+
+        // Post mouse down and up messages
+        PostMessageA(hwnd, WM_LBUTTONDOWN, WPARAM(MK_LBUTTON as usize), lparam).is_ok()
+            && PostMessageA(hwnd, WM_LBUTTONUP, WPARAM(0), lparam).is_ok()
+    }
+}
+
+/// Scroll the mouse wheel using PostMessage (no physical mouse movement)
+/// Coordinates are in screen space (WM_MOUSEWHEEL convention).
+/// `wheel_delta` is sent `ticks` times: positive rolls away from the user (scroll up),
+/// negative towards the user (scroll down); 120 is one notch.
+pub fn scroll_at_position_post(
+    hwnd: HWND,
+    screen_x: i32,
+    screen_y: i32,
+    wheel_delta: i16,
+    ticks: u32,
+) -> bool {
+    unsafe {
+        // Create lParam: low word = x, high word = y
+        let lparam_value = ((screen_y as u32) << 16) | (screen_x as u32 & 0xFFFF);
+        let lparam = LPARAM(lparam_value as isize);
+
+        // High word of wParam carries the signed wheel delta
+        let wparam = WPARAM(((wheel_delta as u16 as u32) << 16) as usize);
+
+        for _ in 0..ticks {
+            if PostMessageA(hwnd, WM_MOUSEWHEEL, wparam, lparam).is_err() {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 /// Right click at coordinates using SendMessage (direct click, frees up mouse)
 pub fn right_click_at_position(hwnd: HWND, x: i32, y: i32) -> bool {
+    if !allow_client_click(hwnd, x, y) {
+        return false;
+    }
     unsafe {
         // Create lParam: low word = x, high word = y
         let lparam_value = ((y as u32) << 16) | (x as u32 & 0xFFFF);
@@ -44,6 +98,9 @@ pub fn right_click_at_position(hwnd: HWND, x: i32, y: i32) -> bool {
 
 /// Middle click at coordinates using SendMessage (direct click, frees up mouse)
 pub fn middle_click_at_position(hwnd: HWND, x: i32, y: i32) -> bool {
+    if !allow_client_click(hwnd, x, y) {
+        return false;
+    }
     unsafe {
         // Create lParam: low word = x, high word = y
         let lparam_value = ((y as u32) << 16) | (x as u32 & 0xFFFF);
@@ -57,6 +114,32 @@ pub fn middle_click_at_position(hwnd: HWND, x: i32, y: i32) -> bool {
     }
 }
 
+/// Press and release `key` using PostMessage (the game does not need focus)
+pub fn key_press_post(hwnd: HWND, key: VIRTUAL_KEY) -> bool {
+    unsafe {
+        // lParam: repeat count 1, scan code in bits 16-23; key up also sets the
+        // previous-state and transition bits
+        let scan_code = MapVirtualKeyA(key.0 as u32, MAPVK_VK_TO_VSC);
+        let down = 1 | (scan_code << 16);
+        let up = down | 0xC000_0000;
+        let wparam = WPARAM(key.0 as usize);
+
+        PostMessageA(hwnd, WM_KEYDOWN, wparam, LPARAM(down as isize)).is_ok()
+            && PostMessageA(hwnd, WM_KEYUP, wparam, LPARAM(up as i32 as isize)).is_ok()
+    }
+}
+
+/// Move the cursor one pixel right of (x, y) and back using PostMessage (no physical movement)
+pub fn mouse_wiggle_post(hwnd: HWND, x: i32, y: i32) -> bool {
+    unsafe {
+        // Create lParam: low word = x, high word = y
+        let lparam = |x: i32| LPARAM((((y as u32) << 16) | (x as u32 & 0xFFFF)) as isize);
+
+        PostMessageA(hwnd, WM_MOUSEMOVE, WPARAM(0), lparam(x + 1)).is_ok()
+            && PostMessageA(hwnd, WM_MOUSEMOVE, WPARAM(0), lparam(x)).is_ok()
+    }
+}
+
 /// Check if left mouse button is currently down
 pub fn is_left_mouse_down() -> bool {
     unsafe {
@@ -64,3 +147,27 @@ pub fn is_left_mouse_down() -> bool {
         (key_state as u16) & 0x8000 != 0
     }
 }
+
+/// Check if right mouse button is currently down
+pub fn is_right_mouse_down() -> bool {
+    unsafe {
+        let key_state = GetAsyncKeyState(0x02); // VK_RBUTTON
+        (key_state as u16) & 0x8000 != 0
+    }
+}
+
+/// Check if a key is currently down (global, works while the game has focus)
+pub fn is_key_down(key: VIRTUAL_KEY) -> bool {
+    unsafe {
+        let key_state = GetAsyncKeyState(key.0 as i32);
+        (key_state as u16) & 0x8000 != 0
+    }
+}
+
+/// Key state for edge detection: (held now, pressed since the previous call)
+pub fn key_state(key: VIRTUAL_KEY) -> (bool, bool) {
+    unsafe {
+        let key_state = GetAsyncKeyState(key.0 as i32) as u16;
+        (key_state & 0x8000 != 0, key_state & 0x0001 != 0)
+    }
+}
@@ -0,0 +1,170 @@
+// Command-line options for scripted launches: profile, connection and a tool to start,
+// or a macro run without the window (--headless)
+use crate::settings::StartMode;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Exit code when `--profile`, `--connect` or `--start` could not be carried out
+/// (reported once the window is closed, so the reason stays readable until then)
+pub const AUTO_START_FAILED_EXIT_CODE: i32 = 2;
+
+static AUTO_START_FAILED: AtomicBool = AtomicBool::new(false);
+
+/// What the command line asked for at launch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchArgs {
+    /// `--overlay` / `--minimized`
+    pub start_mode: Option<StartMode>,
+    /// `--profile <name>`
+    pub profile: Option<String>,
+    /// `--connect`
+    pub connect: bool,
+    /// `--start <tool or macro name>`, run once connected
+    pub start: Option<String>,
+    /// `--headless`: no window, just the macro named by `run`
+    pub headless: bool,
+    /// `--run <macro name>` (headless)
+    pub run: Option<String>,
+    /// `--max-minutes <N>`: headless runs stop after this long
+    pub max_minutes: Option<u64>,
+    /// An option given without its value (or with one that is not a number)
+    pub error: Option<String>,
+}
+
+impl LaunchArgs {
+    /// Options after the program name; `--name value` and `--name=value` both work,
+    /// and anything unknown (e.g. `--portable`) is left to whoever reads it
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Self {
+        let args: Vec<String> = args.into_iter().collect();
+        let mut launch = LaunchArgs {
+            start_mode: StartMode::from_args(args.iter().cloned()),
+            ..Default::default()
+        };
+
+        let mut iter = args.into_iter().peekable();
+        while let Some(arg) = iter.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            match name.as_str() {
+                "--connect" => launch.connect = true,
+                "--headless" => launch.headless = true,
+                "--profile" | "--start" | "--run" | "--max-minutes" => {
+                    // A following option is not a value: `--profile --connect` lacks the name
+                    let value = inline_value
+                        .or_else(|| iter.next_if(|next| !next.starts_with("--")))
+                        .filter(|value| !value.trim().is_empty());
+                    let Some(value) = value else {
+                        let what = if name == "--max-minutes" {
+                            "a number"
+                        } else {
+                            "a name"
+                        };
+                        launch.error = Some(format!("{} needs {}", name, what));
+                        continue;
+                    };
+                    match name.as_str() {
+                        "--profile" => launch.profile = Some(value),
+                        "--start" => launch.start = Some(value),
+                        "--run" => launch.run = Some(value),
+                        _ => match value.trim().parse::<u64>() {
+                            Ok(minutes) if minutes > 0 => launch.max_minutes = Some(minutes),
+                            _ => {
+                                launch.error = Some(format!(
+                                    "--max-minutes needs a whole number, not {}",
+                                    value
+                                ))
+                            }
+                        },
+                    }
+                }
+                _ => {}
+            }
+        }
+        launch
+    }
+}
+
+/// Remember that a requested auto-start failed (decides the exit code)
+pub fn mark_auto_start_failed() {
+    AUTO_START_FAILED.store(true, Ordering::Relaxed);
+}
+
+pub fn auto_start_failed() -> bool {
+    AUTO_START_FAILED.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_profile_connect_and_start() {
+        let launch = LaunchArgs::parse(args(&[
+            "--profile",
+            "Alt",
+            "--connect",
+            "--start",
+            "Reroll Epaulet",
+            "--minimized",
+        ]));
+        assert_eq!(launch.profile.as_deref(), Some("Alt"));
+        assert!(launch.connect);
+        assert_eq!(launch.start.as_deref(), Some("Reroll Epaulet"));
+        assert_eq!(launch.start_mode, Some(StartMode::Minimized));
+        assert_eq!(launch.error, None);
+    }
+
+    #[test]
+    fn test_parse_inline_values_and_unknown_flags() {
+        let launch = LaunchArgs::parse(args(&["--portable", "--start=Image Clicker"]));
+        assert_eq!(launch.start.as_deref(), Some("Image Clicker"));
+        assert_eq!(launch.profile, None);
+        assert!(!launch.connect);
+        assert_eq!(launch.start_mode, None);
+
+        assert_eq!(LaunchArgs::parse(args(&[])), LaunchArgs::default());
+    }
+
+    #[test]
+    fn test_parse_missing_value_is_an_error() {
+        let launch = LaunchArgs::parse(args(&["--profile", "--connect"]));
+        assert_eq!(launch.profile, None);
+        assert!(launch.connect);
+        assert_eq!(launch.error.as_deref(), Some("--profile needs a name"));
+
+        let launch = LaunchArgs::parse(args(&["--start"]));
+        assert_eq!(launch.error.as_deref(), Some("--start needs a name"));
+    }
+
+    #[test]
+    fn test_parse_headless_run() {
+        let launch = LaunchArgs::parse(args(&[
+            "--headless",
+            "--run",
+            "Reroll Epaulet",
+            "--max-minutes",
+            "90",
+        ]));
+        assert!(launch.headless);
+        assert_eq!(launch.run.as_deref(), Some("Reroll Epaulet"));
+        assert_eq!(launch.max_minutes, Some(90));
+        assert_eq!(launch.error, None);
+
+        let launch = LaunchArgs::parse(args(&["--headless", "--max-minutes=soon"]));
+        assert_eq!(launch.max_minutes, None);
+        assert_eq!(
+            launch.error.as_deref(),
+            Some("--max-minutes needs a whole number, not soon")
+        );
+        let launch = LaunchArgs::parse(args(&["--max-minutes"]));
+        assert_eq!(
+            launch.error.as_deref(),
+            Some("--max-minutes needs a number")
+        );
+    }
+}
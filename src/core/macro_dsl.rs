@@ -0,0 +1,677 @@
+// Plain-text macro scripts: one action per line, coordinates in client pixels.
+//
+//   # comment
+//   click 512 340 right mouse
+//   key F1
+//   type "hello"
+//   delay 200
+//   ocr region 10 20 150 40 target "crit dmg" >= 15 exact scale 3 invert color beam 5
+//   or "hp" >= 100 after 200
+//   wait_gone "dialog.png" region 0 0 800 600 confidence 0.85 timeout 5000
+use crate::settings::{
+    ClickMethod, ComparisonMode, HotkeyKey, MacroAction, MouseButton, NormPoint, NormRect,
+    OcrAltTarget, OcrDecodeMode, OcrNameMatchMode,
+};
+use std::str::FromStr;
+
+// Same defaults as the builder's "Add" buttons
+const DEFAULT_OCR_SCALE: u32 = 2;
+const DEFAULT_BEAM_WIDTH: u32 = 10;
+const DEFAULT_ALT_DELAY_MS: u64 = 100;
+const DEFAULT_WAIT_CONFIDENCE: f32 = 0.85;
+const DEFAULT_WAIT_TIMEOUT_MS: u64 = 5000;
+
+/// Turn a script into macro actions. `client_size` is the game client the pixel
+/// coordinates refer to. Errors name the first bad line ("line 3: ...")
+pub fn parse_script(script: &str, client_size: (i32, i32)) -> Result<Vec<MacroAction>, String> {
+    if client_size.0 <= 0 || client_size.1 <= 0 {
+        return Err("the game window has no size".to_string());
+    }
+    let mut actions = Vec::new();
+    for (idx, line) in script.lines().enumerate() {
+        let tokens = tokenize(line).map_err(|err| format!("line {}: {}", idx + 1, err))?;
+        if tokens.is_empty() {
+            continue;
+        }
+        parse_line(Tokens::new(tokens), client_size, &mut actions)
+            .map_err(|err| format!("line {}: {}", idx + 1, err))?;
+    }
+    Ok(actions)
+}
+
+/// Write actions as a script that `parse_script` reads back to the same actions
+pub fn to_script(actions: &[MacroAction], client_size: (i32, i32)) -> String {
+    let mut script = String::new();
+    for action in actions {
+        match action {
+            MacroAction::Click {
+                coordinate,
+                button,
+                click_method,
+                use_mouse_movement: _,
+            } => {
+                script.push_str("click");
+                if let Some(point) = coordinate {
+                    let (x, y) = point_to_pixels(*point, client_size);
+                    script.push_str(&format!(" {} {}", x, y));
+                }
+                match button {
+                    MouseButton::Left => {}
+                    MouseButton::Right => script.push_str(" right"),
+                    MouseButton::Middle => script.push_str(" middle"),
+                }
+                if *click_method == ClickMethod::MouseMovement {
+                    script.push_str(" mouse");
+                }
+            }
+            MacroAction::PressKey { key } => {
+                script.push_str(&format!("key {}", key_name(*key)));
+            }
+            MacroAction::TypeText { text } => {
+                script.push_str(&format!("type {}", quote(text)));
+            }
+            MacroAction::Delay { milliseconds } => {
+                script.push_str(&format!("delay {}", milliseconds));
+            }
+            MacroAction::OcrSearch {
+                ocr_region,
+                scale_factor,
+                invert_colors,
+                grayscale,
+                decode_mode,
+                beam_width,
+                target_stat,
+                target_value,
+                comparison,
+                name_match_mode,
+                alt_targets,
+            } => {
+                script.push_str("ocr");
+                if let Some(rect) = ocr_region {
+                    script.push_str(&region_text(*rect, client_size));
+                }
+                script.push_str(&format!(
+                    " target {} {} {}",
+                    quote(target_stat),
+                    comparison_symbol(*comparison),
+                    target_value
+                ));
+                if *name_match_mode == OcrNameMatchMode::Exact {
+                    script.push_str(" exact");
+                }
+                if *scale_factor != DEFAULT_OCR_SCALE {
+                    script.push_str(&format!(" scale {}", scale_factor));
+                }
+                if *invert_colors {
+                    script.push_str(" invert");
+                }
+                if !*grayscale {
+                    script.push_str(" color");
+                }
+                if *decode_mode == OcrDecodeMode::BeamSearch {
+                    script.push_str(&format!(" beam {}", beam_width));
+                }
+                for alt in alt_targets {
+                    script.push_str(&format!(
+                        "\nor {} {} {}",
+                        quote(&alt.target_stat),
+                        comparison_symbol(alt.comparison),
+                        alt.target_value
+                    ));
+                    if alt.name_match_mode == OcrNameMatchMode::Exact {
+                        script.push_str(" exact");
+                    }
+                    script.push_str(&format!(" after {}", alt.delay_ms));
+                }
+            }
+            MacroAction::WaitForImageGone {
+                image_path,
+                region,
+                min_confidence,
+                timeout_ms,
+            } => {
+                script.push_str(&format!("wait_gone {}", quote(image_path)));
+                if let Some(rect) = region {
+                    script.push_str(&region_text(*rect, client_size));
+                }
+                script.push_str(&format!(
+                    " confidence {} timeout {}",
+                    min_confidence, timeout_ms
+                ));
+            }
+        }
+        script.push('\n');
+    }
+    script
+}
+
+fn parse_line(
+    mut tokens: Tokens,
+    client_size: (i32, i32),
+    actions: &mut Vec<MacroAction>,
+) -> Result<(), String> {
+    let command = tokens.word("a command")?.to_lowercase();
+    match command.as_str() {
+        "click" => {
+            let mut coordinate = None;
+            if tokens.peek_is_number() {
+                let x: i32 = tokens.number("x")?;
+                let y: i32 = tokens.number("y")?;
+                coordinate = Some(point_from_pixels(x, y, client_size)?);
+            }
+            let mut button = MouseButton::Left;
+            let mut click_method = ClickMethod::SendMessage;
+            while let Some(option) = tokens.next_option()? {
+                match option.as_str() {
+                    "left" => button = MouseButton::Left,
+                    "right" => button = MouseButton::Right,
+                    "middle" => button = MouseButton::Middle,
+                    "mouse" => click_method = ClickMethod::MouseMovement,
+                    other => return Err(unknown_option("click", other)),
+                }
+            }
+            actions.push(MacroAction::Click {
+                coordinate,
+                button,
+                click_method,
+                use_mouse_movement: false,
+            });
+        }
+        "key" => {
+            let name = tokens.word("a key name")?;
+            let key = key_from_name(&name)
+                .ok_or_else(|| format!("unknown key \"{}\" (e.g. F1, A, Digit1, Space)", name))?;
+            tokens.finish()?;
+            actions.push(MacroAction::PressKey { key });
+        }
+        "type" => {
+            let text = tokens.quoted("the text to type")?;
+            tokens.finish()?;
+            actions.push(MacroAction::TypeText { text });
+        }
+        "delay" => {
+            let milliseconds = tokens.number("milliseconds")?;
+            tokens.finish()?;
+            actions.push(MacroAction::Delay { milliseconds });
+        }
+        "ocr" => {
+            let mut ocr_region = None;
+            let mut target = None;
+            let mut name_match_mode = OcrNameMatchMode::Contains;
+            let mut scale_factor = DEFAULT_OCR_SCALE;
+            let mut invert_colors = false;
+            let mut grayscale = true;
+            let mut decode_mode = OcrDecodeMode::Greedy;
+            let mut beam_width = DEFAULT_BEAM_WIDTH;
+            while let Some(option) = tokens.next_option()? {
+                match option.as_str() {
+                    "region" => ocr_region = Some(tokens.region(client_size)?),
+                    "target" => target = Some(tokens.target()?),
+                    "exact" => name_match_mode = OcrNameMatchMode::Exact,
+                    "scale" => scale_factor = tokens.number("a scale factor")?,
+                    "invert" => invert_colors = true,
+                    "color" => grayscale = false,
+                    "beam" => {
+                        decode_mode = OcrDecodeMode::BeamSearch;
+                        beam_width = tokens.number("a beam width")?;
+                    }
+                    other => return Err(unknown_option("ocr", other)),
+                }
+            }
+            let (target_stat, comparison, target_value) =
+                target.ok_or("ocr needs target \"<stat>\" <op> <value>")?;
+            actions.push(MacroAction::OcrSearch {
+                ocr_region,
+                scale_factor,
+                invert_colors,
+                grayscale,
+                decode_mode,
+                beam_width,
+                target_stat,
+                target_value,
+                comparison,
+                name_match_mode,
+                alt_targets: Vec::new(),
+            });
+        }
+        "or" => {
+            let Some(MacroAction::OcrSearch { alt_targets, .. }) = actions.last_mut() else {
+                return Err("\"or\" must follow an ocr line".to_string());
+            };
+            let (target_stat, comparison, target_value) = tokens.target()?;
+            let mut name_match_mode = OcrNameMatchMode::Contains;
+            let mut delay_ms = DEFAULT_ALT_DELAY_MS;
+            while let Some(option) = tokens.next_option()? {
+                match option.as_str() {
+                    "exact" => name_match_mode = OcrNameMatchMode::Exact,
+                    "after" => delay_ms = tokens.number("milliseconds")?,
+                    other => return Err(unknown_option("or", other)),
+                }
+            }
+            alt_targets.push(OcrAltTarget {
+                target_stat,
+                target_value,
+                comparison,
+                name_match_mode,
+                delay_ms,
+            });
+        }
+        "wait_gone" => {
+            let image_path = tokens.quoted("an image path")?;
+            let mut region = None;
+            let mut min_confidence = DEFAULT_WAIT_CONFIDENCE;
+            let mut timeout_ms = DEFAULT_WAIT_TIMEOUT_MS;
+            while let Some(option) = tokens.next_option()? {
+                match option.as_str() {
+                    "region" => region = Some(tokens.region(client_size)?),
+                    "confidence" => {
+                        min_confidence = tokens.number("a confidence")?;
+                        if !(0.0..=1.0).contains(&min_confidence) {
+                            return Err("confidence must be between 0 and 1".to_string());
+                        }
+                    }
+                    "timeout" => timeout_ms = tokens.number("milliseconds")?,
+                    other => return Err(unknown_option("wait_gone", other)),
+                }
+            }
+            actions.push(MacroAction::WaitForImageGone {
+                image_path,
+                region,
+                min_confidence,
+                timeout_ms,
+            });
+        }
+        // Macros run top to bottom; there is no action to jump with
+        "label" | "goto" => {
+            return Err(format!(
+                "\"{}\" is not supported (use the loop settings to repeat a macro)",
+                command
+            ))
+        }
+        other => return Err(format!("unknown command \"{}\"", other)),
+    }
+    Ok(())
+}
+
+fn unknown_option(command: &str, option: &str) -> String {
+    format!("unknown {} option \"{}\"", command, option)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    text: String,
+    quoted: bool,
+}
+
+/// Split a line on whitespace; "double quotes" keep spaces (with \" and \\ escapes)
+/// and a # outside quotes starts a comment
+fn tokenize(line: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '#' {
+            break;
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('n') => text.push('\n'),
+                        Some(escaped @ ('"' | '\\')) => text.push(escaped),
+                        Some(other) => return Err(format!("unknown escape \"\\{}\"", other)),
+                        None => return Err("unterminated string".to_string()),
+                    },
+                    Some(other) => text.push(other),
+                    None => return Err("unterminated string".to_string()),
+                }
+            }
+            tokens.push(Token { text, quoted: true });
+        } else {
+            let mut text = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' || c == '#' {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+            tokens.push(Token {
+                text,
+                quoted: false,
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+struct Tokens {
+    tokens: std::vec::IntoIter<Token>,
+    peeked: Option<Token>,
+}
+
+impl Tokens {
+    fn new(tokens: Vec<Token>) -> Self {
+        let mut tokens = tokens.into_iter();
+        let peeked = tokens.next();
+        Self { tokens, peeked }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        std::mem::replace(&mut self.peeked, self.tokens.next())
+    }
+
+    fn peek_is_number(&self) -> bool {
+        self.peeked
+            .as_ref()
+            .is_some_and(|token| !token.quoted && token.text.parse::<i64>().is_ok())
+    }
+
+    fn word(&mut self, what: &str) -> Result<String, String> {
+        match self.next() {
+            Some(token) if !token.quoted => Ok(token.text),
+            Some(token) => Err(format!("expected {}, found \"{}\"", what, token.text)),
+            None => Err(format!("missing {}", what)),
+        }
+    }
+
+    fn quoted(&mut self, what: &str) -> Result<String, String> {
+        match self.next() {
+            Some(token) if token.quoted => Ok(token.text),
+            Some(token) => Err(format!("{} must be in quotes, found {}", what, token.text)),
+            None => Err(format!("missing {}", what)),
+        }
+    }
+
+    fn number<T: FromStr>(&mut self, what: &str) -> Result<T, String> {
+        let text = self.word(what)?;
+        text.parse()
+            .map_err(|_| format!("expected {}, found \"{}\"", what, text))
+    }
+
+    /// Next keyword option (lowercased), or None at the end of the line
+    fn next_option(&mut self) -> Result<Option<String>, String> {
+        if self.peeked.is_none() {
+            return Ok(None);
+        }
+        self.word("an option").map(|word| Some(word.to_lowercase()))
+    }
+
+    fn finish(&mut self) -> Result<(), String> {
+        match self.next() {
+            None => Ok(()),
+            Some(token) => Err(format!("unexpected \"{}\"", token.text)),
+        }
+    }
+
+    fn region(&mut self, client_size: (i32, i32)) -> Result<NormRect, String> {
+        let x: i32 = self.number("region x")?;
+        let y: i32 = self.number("region y")?;
+        let w: i32 = self.number("region width")?;
+        let h: i32 = self.number("region height")?;
+        rect_from_pixels(x, y, w, h, client_size)
+    }
+
+    /// `"<stat>" <op> <value>`
+    fn target(&mut self) -> Result<(String, ComparisonMode, i32), String> {
+        let stat = self.quoted("the stat name")?;
+        let symbol = self.word("a comparison (=, >= or <=)")?;
+        let comparison = match symbol.as_str() {
+            "=" | "==" => ComparisonMode::Equals,
+            ">=" => ComparisonMode::GreaterThanOrEqual,
+            "<=" => ComparisonMode::LessThanOrEqual,
+            other => {
+                return Err(format!(
+                    "expected a comparison (=, >= or <=), found \"{}\"",
+                    other
+                ))
+            }
+        };
+        let value = self.number("the target value")?;
+        Ok((stat, comparison, value))
+    }
+}
+
+fn comparison_symbol(comparison: ComparisonMode) -> &'static str {
+    match comparison {
+        ComparisonMode::Equals => "=",
+        ComparisonMode::GreaterThanOrEqual => ">=",
+        ComparisonMode::LessThanOrEqual => "<=",
+    }
+}
+
+// Key names are the settings-file names (F1, Digit1, PageUp, ...)
+fn key_name(key: HotkeyKey) -> String {
+    serde_json::to_value(key)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn key_from_name(name: &str) -> Option<HotkeyKey> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+}
+
+// Same scales as the builder's pixel editor (and denormalize_point / denormalize_rect),
+// so pixel values round-trip exactly
+fn point_from_pixels(x: i32, y: i32, (width, height): (i32, i32)) -> Result<NormPoint, String> {
+    if x < 0 || y < 0 || x >= width || y >= height {
+        return Err(format!(
+            "{} {} is outside the {}x{} game window",
+            x, y, width, height
+        ));
+    }
+    let max_x = (width - 1).max(1) as f32;
+    let max_y = (height - 1).max(1) as f32;
+    Ok((x as f32 / max_x, y as f32 / max_y))
+}
+
+fn point_to_pixels(point: NormPoint, (width, height): (i32, i32)) -> (i32, i32) {
+    let max_x = (width - 1).max(1) as f32;
+    let max_y = (height - 1).max(1) as f32;
+    (
+        (point.0.clamp(0.0, 1.0) * max_x).round() as i32,
+        (point.1.clamp(0.0, 1.0) * max_y).round() as i32,
+    )
+}
+
+fn rect_from_pixels(
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+    (width, height): (i32, i32),
+) -> Result<NormRect, String> {
+    if x < 0 || y < 0 || w <= 0 || h <= 0 || x + w > width || y + h > height {
+        return Err(format!(
+            "region {} {} {} {} is outside the {}x{} game window",
+            x, y, w, h, width, height
+        ));
+    }
+    let (client_w, client_h) = (width as f32, height as f32);
+    Ok((
+        x as f32 / client_w,
+        y as f32 / client_h,
+        w as f32 / client_w,
+        h as f32 / client_h,
+    ))
+}
+
+fn region_text(rect: NormRect, (width, height): (i32, i32)) -> String {
+    let (client_w, client_h) = (width as f32, height as f32);
+    format!(
+        " region {} {} {} {}",
+        (rect.0.clamp(0.0, 1.0) * client_w).round() as i32,
+        (rect.1.clamp(0.0, 1.0) * client_h).round() as i32,
+        (rect.2.clamp(0.0, 1.0) * client_w).round() as i32,
+        (rect.3.clamp(0.0, 1.0) * client_h).round() as i32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: (i32, i32) = (1024, 768);
+
+    #[test]
+    fn test_parse_each_command() {
+        let script = r#"
+            # warm up
+            click 512 340 right
+            click mouse
+            key F1
+            type "say \"hi\""
+            delay 200
+            ocr region 10 20 150 40 target "crit dmg" >= 15
+            or "hp" = 100 exact after 50
+            wait_gone "C:\\img\\dialog.png" timeout 3000
+        "#;
+        let actions = parse_script(script, CLIENT).unwrap();
+        assert_eq!(actions.len(), 7);
+        assert_eq!(
+            actions[0],
+            MacroAction::Click {
+                coordinate: Some((512.0 / 1023.0, 340.0 / 767.0)),
+                button: MouseButton::Right,
+                click_method: ClickMethod::SendMessage,
+                use_mouse_movement: false,
+            }
+        );
+        assert!(matches!(
+            actions[1],
+            MacroAction::Click {
+                coordinate: None,
+                button: MouseButton::Left,
+                click_method: ClickMethod::MouseMovement,
+                ..
+            }
+        ));
+        assert_eq!(actions[2], MacroAction::PressKey { key: HotkeyKey::F1 });
+        assert_eq!(
+            actions[3],
+            MacroAction::TypeText {
+                text: "say \"hi\"".to_string()
+            }
+        );
+        assert_eq!(actions[4], MacroAction::Delay { milliseconds: 200 });
+        let MacroAction::OcrSearch {
+            ocr_region,
+            target_stat,
+            target_value,
+            comparison,
+            grayscale,
+            alt_targets,
+            ..
+        } = &actions[5]
+        else {
+            panic!("expected an OCR action");
+        };
+        assert_eq!(
+            *ocr_region,
+            Some((10.0 / 1024.0, 20.0 / 768.0, 150.0 / 1024.0, 40.0 / 768.0))
+        );
+        assert_eq!(target_stat, "crit dmg");
+        assert_eq!(*target_value, 15);
+        assert_eq!(*comparison, ComparisonMode::GreaterThanOrEqual);
+        assert!(*grayscale);
+        assert_eq!(alt_targets.len(), 1);
+        assert_eq!(alt_targets[0].comparison, ComparisonMode::Equals);
+        assert_eq!(alt_targets[0].name_match_mode, OcrNameMatchMode::Exact);
+        assert_eq!(alt_targets[0].delay_ms, 50);
+        assert_eq!(
+            actions[6],
+            MacroAction::WaitForImageGone {
+                image_path: "C:\\img\\dialog.png".to_string(),
+                region: None,
+                min_confidence: DEFAULT_WAIT_CONFIDENCE,
+                timeout_ms: 3000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_script_round_trip() {
+        let script = "\
+click 512 340 right
+click 0 767 mouse
+key Digit1
+type \"line\\none \\\"two\\\"\"
+delay 250
+ocr region 10 20 150 40 target \"crit dmg\" >= 15 exact scale 3 invert color beam 5
+or \"hp\" <= 100 after 0
+ocr target \"\" = 0
+wait_gone \"dialog.png\" region 0 0 1024 768 confidence 0.7 timeout 5000
+";
+        let actions = parse_script(script, CLIENT).unwrap();
+        assert_eq!(to_script(&actions, CLIENT), script);
+        assert_eq!(
+            parse_script(&to_script(&actions, CLIENT), CLIENT).unwrap(),
+            actions
+        );
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        let cases = [
+            ("delay 100\njump 3", "line 2: unknown command \"jump\""),
+            (
+                "click 2000 10",
+                "line 1: 2000 10 is outside the 1024x768 game window",
+            ),
+            (
+                "delay soon",
+                "line 1: expected milliseconds, found \"soon\"",
+            ),
+            (
+                "type hello",
+                "line 1: the text to type must be in quotes, found hello",
+            ),
+            ("\n\ntype \"open", "line 3: unterminated string"),
+            (
+                "key F13",
+                "line 1: unknown key \"F13\" (e.g. F1, A, Digit1, Space)",
+            ),
+            ("or \"hp\" >= 1", "line 1: \"or\" must follow an ocr line"),
+            (
+                "ocr region 0 0 10 10",
+                "line 1: ocr needs target \"<stat>\" <op> <value>",
+            ),
+            (
+                "ocr target \"hp\" > 5",
+                "line 1: expected a comparison (=, >= or <=), found \">\"",
+            ),
+            ("delay 5 extra", "line 1: unexpected \"extra\""),
+            (
+                "label start",
+                "line 1: \"label\" is not supported (use the loop settings to repeat a macro)",
+            ),
+        ];
+        for (script, expected) in cases {
+            assert_eq!(
+                parse_script(script, CLIENT).unwrap_err(),
+                expected,
+                "{}",
+                script
+            );
+        }
+    }
+
+    #[test]
+    fn test_comments_and_blank_lines_are_skipped() {
+        let actions = parse_script("# setup\n\n  delay 10 # short\n", CLIENT).unwrap();
+        assert_eq!(actions, vec![MacroAction::Delay { milliseconds: 10 }]);
+        assert_eq!(parse_script("", CLIENT).unwrap(), Vec::new());
+    }
+}
@@ -1,7 +1,22 @@
+pub mod click_guard;
+pub mod clock;
 pub mod coords;
+pub mod error;
+pub mod file_logger;
+pub mod frame_recorder;
 pub mod hotkey;
 pub mod input;
+pub mod launch_args;
+pub mod macro_dsl;
 pub mod ocr_parser;
+pub mod overlay_window;
+pub mod paths;
+pub mod remote_control;
+pub mod scheduler;
 pub mod screen_capture;
+pub mod session_summary;
+pub mod sounds;
+pub mod stat_history;
+pub mod throttle;
 pub mod window;
 pub mod worker;
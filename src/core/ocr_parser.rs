@@ -29,6 +29,21 @@ pub fn parse_ocr_result(text: &str) -> Option<(String, i32)> {
     }
 }
 
+/// First number of an OCR read of a counter (Alz, honor points), with the digit-group
+/// separators the game draws dropped. `parse_ocr_result` needs a stat name and fits
+/// in an i32, which a bare counter doesn't always do.
+/// Example: "Alz 1,234,567" -> 1234567
+pub fn parse_ocr_number(text: &str) -> Option<i64> {
+    let number_re = Regex::new(r"\d{1,3}(?:[,.' ]\d{3})+\b|\d+").ok()?;
+    let digits: String = number_re
+        .find(text)?
+        .as_str()
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
 fn extract_stat_words(text: &str) -> String {
     let word_re = Regex::new(r"[a-z]+").ok();
     let Some(re) = word_re else { return String::new(); };
@@ -117,6 +132,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_number_drops_group_separators() {
+        assert_eq!(parse_ocr_number("Alz 1,234,567"), Some(1_234_567));
+        assert_eq!(parse_ocr_number("1.234.567.890"), Some(1_234_567_890));
+        assert_eq!(parse_ocr_number("Honor: 98 765"), Some(98_765));
+        assert_eq!(parse_ocr_number("12345"), Some(12_345));
+        // A group of other than three digits is not part of the number
+        assert_eq!(parse_ocr_number("12,34"), Some(12));
+        assert_eq!(parse_ocr_number("no digits"), None);
+    }
+
     #[test]
     fn test_matches_equal() {
         assert!(matches_target("defense", 20, "defense", 20, ComparisonMode::Equals));
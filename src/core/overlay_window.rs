@@ -0,0 +1,210 @@
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    BeginPaint, CreatePen, CreateSolidBrush, DeleteObject, EndPaint, FillRect, GetStockObject,
+    InvalidateRect, Rectangle, SelectObject, SetBkMode, SetTextColor, TextOutW, HGDIOBJ,
+    NULL_BRUSH, PAINTSTRUCT, PS_SOLID, TRANSPARENT,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DestroyWindow, GetWindowLongPtrW, RegisterClassW,
+    SetLayeredWindowAttributes, SetWindowLongPtrW, SetWindowPos, ShowWindow, GWLP_USERDATA,
+    HWND_TOPMOST, LWA_COLORKEY, SWP_NOACTIVATE, SWP_SHOWWINDOW, SW_SHOWNOACTIVATE, WM_PAINT,
+    WNDCLASSW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_EX_TRANSPARENT,
+    WS_POPUP,
+};
+
+const CLASS_NAME: PCWSTR = w!("CabalHelperOverlay");
+
+// Magenta is keyed out, so everything not drawn on top of it is see-through
+const COLOR_KEY: COLORREF = COLORREF(0x00FF00FF);
+
+/// Colors used by the "Show regions" preview
+pub const REGION_AREA_COLOR: (u8, u8, u8) = (0, 230, 90);
+pub const REGION_POINT_COLOR: (u8, u8, u8) = (255, 200, 0);
+
+/// Rectangle (or point marker) drawn on the overlay, in game client pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlayShape {
+    pub rect: (i32, i32, i32, i32), // left, top, width, height
+    pub color: (u8, u8, u8),
+    pub label: Option<String>,
+}
+
+impl OverlayShape {
+    /// Small square marker centered on a point
+    pub fn point(x: i32, y: i32, color: (u8, u8, u8), label: Option<String>) -> Self {
+        Self {
+            rect: (x - 4, y - 4, 9, 9),
+            color,
+            label,
+        }
+    }
+}
+
+struct OverlayState {
+    shapes: Vec<OverlayShape>,
+}
+
+/// Click-through, topmost, layered window used to draw on top of the game
+/// Lives on the UI thread; the egui/winit event loop pumps its messages
+pub struct OverlayWindow {
+    hwnd: HWND,
+    // Boxed so the pointer stored in GWLP_USERDATA stays valid
+    state: Box<OverlayState>,
+    screen_rect: Option<(i32, i32, i32, i32)>,
+}
+
+impl OverlayWindow {
+    /// Create a hidden overlay window (shown by the first `move_to`)
+    pub fn new() -> Option<Self> {
+        unsafe {
+            let instance = GetModuleHandleW(None).ok()?;
+
+            let class = WNDCLASSW {
+                lpfnWndProc: Some(overlay_wndproc),
+                hInstance: instance.into(),
+                lpszClassName: CLASS_NAME,
+                ..Default::default()
+            };
+            // Fails harmlessly if the class is already registered
+            RegisterClassW(&class);
+
+            let hwnd = CreateWindowExW(
+                WS_EX_LAYERED
+                    | WS_EX_TRANSPARENT
+                    | WS_EX_TOPMOST
+                    | WS_EX_TOOLWINDOW
+                    | WS_EX_NOACTIVATE,
+                CLASS_NAME,
+                w!("Cabal Helper Overlay"),
+                WS_POPUP,
+                0,
+                0,
+                0,
+                0,
+                None,
+                None,
+                instance,
+                None,
+            );
+            if hwnd.0 == 0 {
+                return None;
+            }
+
+            if SetLayeredWindowAttributes(hwnd, COLOR_KEY, 0, LWA_COLORKEY).is_err() {
+                let _ = DestroyWindow(hwnd);
+                return None;
+            }
+
+            let mut state = Box::new(OverlayState { shapes: Vec::new() });
+            SetWindowLongPtrW(
+                hwnd,
+                GWLP_USERDATA,
+                state.as_mut() as *mut OverlayState as isize,
+            );
+
+            Some(Self {
+                hwnd,
+                state,
+                screen_rect: None,
+            })
+        }
+    }
+
+    /// Cover the given screen rectangle (x, y, width, height), typically the game client area
+    pub fn move_to(&mut self, rect: (i32, i32, i32, i32)) {
+        if self.screen_rect == Some(rect) {
+            return;
+        }
+        unsafe {
+            let _ = SetWindowPos(
+                self.hwnd,
+                HWND_TOPMOST,
+                rect.0,
+                rect.1,
+                rect.2,
+                rect.3,
+                SWP_NOACTIVATE | SWP_SHOWWINDOW,
+            );
+            if self.screen_rect.is_none() {
+                ShowWindow(self.hwnd, SW_SHOWNOACTIVATE);
+            }
+        }
+        self.screen_rect = Some(rect);
+    }
+
+    /// Replace the drawn shapes (repaints only when something changed)
+    pub fn set_shapes(&mut self, shapes: Vec<OverlayShape>) {
+        if self.state.shapes == shapes {
+            return;
+        }
+        self.state.shapes = shapes;
+        unsafe {
+            InvalidateRect(self.hwnd, None, true);
+        }
+    }
+}
+
+impl Drop for OverlayWindow {
+    fn drop(&mut self) {
+        unsafe {
+            SetWindowLongPtrW(self.hwnd, GWLP_USERDATA, 0);
+            let _ = DestroyWindow(self.hwnd);
+        }
+    }
+}
+
+fn rgb(color: (u8, u8, u8)) -> COLORREF {
+    COLORREF(color.0 as u32 | (color.1 as u32) << 8 | (color.2 as u32) << 16)
+}
+
+unsafe fn paint_overlay(hwnd: HWND, state: &OverlayState) {
+    let mut ps = PAINTSTRUCT::default();
+    let hdc = BeginPaint(hwnd, &mut ps);
+
+    let background = CreateSolidBrush(COLOR_KEY);
+    FillRect(hdc, &ps.rcPaint, background);
+    let _ = DeleteObject(HGDIOBJ(background.0));
+
+    SetBkMode(hdc, TRANSPARENT);
+    let old_brush = SelectObject(hdc, GetStockObject(NULL_BRUSH));
+
+    for shape in &state.shapes {
+        let pen = CreatePen(PS_SOLID, 2, rgb(shape.color));
+        let old_pen = SelectObject(hdc, HGDIOBJ(pen.0));
+
+        let (left, top, width, height) = shape.rect;
+        let _ = Rectangle(hdc, left, top, left + width, top + height);
+
+        if let Some(label) = &shape.label {
+            let text: Vec<u16> = label.encode_utf16().collect();
+            SetTextColor(hdc, rgb(shape.color));
+            // Above the shape, or inside it when it touches the top edge
+            let text_y = if top >= 16 { top - 16 } else { top + 2 };
+            let _ = TextOutW(hdc, left + 2, text_y, &text);
+        }
+
+        SelectObject(hdc, old_pen);
+        let _ = DeleteObject(HGDIOBJ(pen.0));
+    }
+
+    SelectObject(hdc, old_brush);
+    let _ = EndPaint(hwnd, &ps);
+}
+
+unsafe extern "system" fn overlay_wndproc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if msg == WM_PAINT {
+        let state = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *const OverlayState;
+        if let Some(state) = state.as_ref() {
+            paint_overlay(hwnd, state);
+            return LRESULT(0);
+        }
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
@@ -0,0 +1,128 @@
+// Where the app keeps its files: next to the exe in portable mode, else per-user app data
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const APP_DATA_DIR: &str = "CabalHelper";
+const SETTINGS_FILE_NAME: &str = "settings.json";
+const LOGS_DIR_NAME: &str = "logs";
+const TEMPLATES_DIR_NAME: &str = "templates";
+const FRAMES_DIR_NAME: &str = "frames";
+const RUN_JOURNAL_FILE_NAME: &str = "running.json";
+// Marker next to the exe (or the --portable argument) enables portable mode
+const PORTABLE_FLAG_FILE: &str = "portable.flag";
+const PORTABLE_ARG: &str = "--portable";
+
+struct DataLocation {
+    portable: bool,
+    root: PathBuf,
+}
+
+fn exe_dir() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+}
+
+fn is_portable_requested(mut args: impl Iterator<Item = String>, flag_file_exists: bool) -> bool {
+    flag_file_exists || args.any(|arg| arg == PORTABLE_ARG)
+}
+
+/// Root folder for all app files
+/// Falls back to the working directory when neither location is known
+fn resolve_root(portable: bool, exe_dir: Option<&Path>, app_data: Option<&OsStr>) -> PathBuf {
+    let root = if portable {
+        exe_dir.map(Path::to_path_buf)
+    } else {
+        app_data.map(|dir| PathBuf::from(dir).join(APP_DATA_DIR))
+    };
+    root.unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn location() -> &'static DataLocation {
+    static LOCATION: OnceLock<DataLocation> = OnceLock::new();
+    LOCATION.get_or_init(|| {
+        let exe_dir = exe_dir();
+        let flag_file_exists = exe_dir
+            .as_ref()
+            .is_some_and(|dir| dir.join(PORTABLE_FLAG_FILE).exists());
+        let portable = is_portable_requested(std::env::args(), flag_file_exists);
+        let root = resolve_root(
+            portable,
+            exe_dir.as_deref(),
+            std::env::var_os("APPDATA").as_deref(),
+        );
+        DataLocation { portable, root }
+    })
+}
+
+/// True when files are kept next to the exe
+pub fn is_portable() -> bool {
+    location().portable
+}
+
+/// Settings file: <root>\settings.json
+pub fn settings_file() -> PathBuf {
+    location().root.join(SETTINGS_FILE_NAME)
+}
+
+/// Folder for the daily log files: <root>\logs
+pub fn logs_dir() -> PathBuf {
+    location().root.join(LOGS_DIR_NAME)
+}
+
+/// Folder for template images captured from the game: <root>\templates
+pub fn templates_dir() -> PathBuf {
+    location().root.join(TEMPLATES_DIR_NAME)
+}
+
+/// Folder for the frame recorder's session folders: <root>\frames
+pub fn frames_dir() -> PathBuf {
+    location().root.join(FRAMES_DIR_NAME)
+}
+
+/// Journal of the runs in progress, left behind by a crash: <root>\running.json
+pub fn run_journal_file() -> PathBuf {
+    location().root.join(RUN_JOURNAL_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portable_requested_by_flag_or_argument() {
+        let args = |list: &[&str]| {
+            list.iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+        assert!(is_portable_requested(args(&["app.exe"]), true));
+        assert!(is_portable_requested(
+            args(&["app.exe", "--portable"]),
+            false
+        ));
+        assert!(!is_portable_requested(
+            args(&["app.exe", "portable"]),
+            false
+        ));
+    }
+
+    #[test]
+    fn test_resolve_root() {
+        let exe_dir = Path::new("E:\\Games\\Helper");
+        let app_data = OsStr::new("C:\\Users\\me\\AppData\\Roaming");
+
+        assert_eq!(
+            resolve_root(true, Some(exe_dir), Some(app_data)),
+            exe_dir.to_path_buf()
+        );
+        assert_eq!(
+            resolve_root(false, Some(exe_dir), Some(app_data)),
+            Path::new(app_data).join(APP_DATA_DIR)
+        );
+        assert_eq!(resolve_root(false, Some(exe_dir), None), PathBuf::from("."));
+        assert_eq!(resolve_root(true, None, Some(app_data)), PathBuf::from("."));
+    }
+}
@@ -0,0 +1,370 @@
+// Opt-in localhost HTTP endpoint: tool status, and stopping / starting tools remotely
+use crate::settings::RemoteControlSettings;
+use eframe::egui;
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use windows::Win32::Security::Cryptography::{
+    BCryptGenRandom, BCRYPT_ALG_HANDLE, BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+};
+
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+// Most bytes read from a connection (request line plus headers) before it is answered
+const MAX_REQUEST_BYTES: u64 = 8192;
+// How long a request waits for the UI thread to carry out its command
+const REPLY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// What a request asks the app to do; carried out on the UI thread like a button click
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteCommand {
+    StopAll,
+    // Tool or macro name, matched like --start
+    Stop(String),
+    Start(String),
+}
+
+/// A queued command and the connection waiting for its outcome
+pub struct RemoteRequest {
+    pub command: RemoteCommand,
+    reply: Sender<Result<String, String>>,
+}
+
+impl RemoteRequest {
+    /// Answer the request (ignored when it already timed out)
+    pub fn reply(self, result: Result<String, String>) {
+        let _ = self.reply.send(result);
+    }
+}
+
+/// One tool as reported by `GET /status`
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolState {
+    pub name: String,
+    pub running: bool,
+    pub paused: bool,
+    pub status: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route {
+    Status,
+    Command(RemoteCommand),
+}
+
+struct Listener {
+    port: u16,
+    shutdown: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+/// The listener thread and the queue it feeds; owned by the app, which drains the queue
+/// and publishes the tool states every frame
+pub struct RemoteControl {
+    listener: Option<Listener>,
+    // Read per request, so editing the token needs no restart
+    token: Arc<Mutex<String>>,
+    tools: Arc<Mutex<Vec<ToolState>>>,
+    sender: Sender<RemoteRequest>,
+    receiver: Receiver<RemoteRequest>,
+    // Port that could not be bound and why; not retried until the settings change
+    error: Option<(u16, String)>,
+}
+
+impl Default for RemoteControl {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            listener: None,
+            token: Arc::new(Mutex::new(String::new())),
+            tools: Arc::new(Mutex::new(Vec::new())),
+            sender,
+            receiver,
+            error: None,
+        }
+    }
+}
+
+impl RemoteControl {
+    /// Listen when enabled with a token, on the configured port; stop otherwise
+    pub fn sync(&mut self, settings: &RemoteControlSettings, ctx: &egui::Context) {
+        let token = settings.token.trim();
+        *self.token.lock().unwrap() = token.to_string();
+        let wanted = (settings.enabled && !token.is_empty()).then_some(settings.port);
+
+        if wanted != self.error.as_ref().map(|(port, _)| *port) {
+            self.error = None;
+        }
+        if wanted == self.listener.as_ref().map(|listener| listener.port) || self.error.is_some() {
+            return;
+        }
+        self.shutdown();
+        let Some(port) = wanted else {
+            return;
+        };
+        match TcpListener::bind(("127.0.0.1", port)).and_then(|listener| {
+            listener.set_nonblocking(true)?;
+            Ok(listener)
+        }) {
+            Ok(listener) => self.listener = Some(self.spawn(listener, port, ctx.clone())),
+            Err(err) => {
+                self.error = Some((port, format!("Can't listen on 127.0.0.1:{}: {}", port, err)));
+            }
+        }
+    }
+
+    /// Stop accepting and wait for the listener thread to exit
+    pub fn shutdown(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.shutdown.store(true, Ordering::Relaxed);
+            let _ = listener.handle.join();
+        }
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.listener.is_some()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_ref().map(|(_, error)| error.as_str())
+    }
+
+    /// Tool states served by `GET /status`
+    pub fn publish(&self, tools: Vec<ToolState>) {
+        *self.tools.lock().unwrap() = tools;
+    }
+
+    /// Next queued command, if any
+    pub fn try_recv(&self) -> Option<RemoteRequest> {
+        self.receiver.try_recv().ok()
+    }
+
+    fn spawn(&self, listener: TcpListener, port: u16, ctx: egui::Context) -> Listener {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let thread_shutdown = Arc::clone(&shutdown);
+        let token = Arc::clone(&self.token);
+        let tools = Arc::clone(&self.tools);
+        let sender = self.sender.clone();
+
+        let handle = thread::spawn(move || {
+            while !thread_shutdown.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let token = token.lock().unwrap().clone();
+                        handle_connection(stream, &token, &tools, &sender, &ctx);
+                    }
+                    // Nothing waiting (non-blocking), or a failed accept
+                    Err(_) => thread::sleep(ACCEPT_POLL),
+                }
+            }
+        });
+        Listener {
+            port,
+            shutdown,
+            handle,
+        }
+    }
+}
+
+/// 32 random hex digits from the system RNG, for the token field; None if it fails
+pub fn new_token() -> Option<String> {
+    let mut bytes = [0u8; 16];
+    unsafe {
+        BCryptGenRandom(
+            BCRYPT_ALG_HANDLE::default(),
+            &mut bytes,
+            BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+        )
+    }
+    .ok()
+    .ok()?;
+    Some(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Answer one request; the connection is closed afterwards
+fn handle_connection(
+    stream: TcpStream,
+    token: &str,
+    tools: &Mutex<Vec<ToolState>>,
+    sender: &Sender<RemoteRequest>,
+    ctx: &egui::Context,
+) {
+    // Accepted sockets inherit non-blocking mode from the listener
+    if stream.set_nonblocking(false).is_err()
+        || stream.set_read_timeout(Some(READ_TIMEOUT)).is_err()
+    {
+        return;
+    }
+    // Capped so an unauthenticated client can't make the listener buffer without limit
+    let mut reader = BufReader::new((&stream).take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    // Headers are not needed, but are read so the client sees a clean close
+    let mut header = String::new();
+    while reader.read_line(&mut header).is_ok_and(|read| read > 2) {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (code, body) = match route(method, target, token) {
+        Ok(Route::Status) => {
+            let tools = tools.lock().unwrap().clone();
+            (200, serde_json::json!({ "tools": tools }))
+        }
+        Ok(Route::Command(command)) => {
+            let (reply, outcome) = mpsc::channel();
+            if sender.send(RemoteRequest { command, reply }).is_err() {
+                return;
+            }
+            // Wake the UI thread; it may be idle between repaints
+            ctx.request_repaint();
+            match outcome.recv_timeout(REPLY_TIMEOUT) {
+                Ok(Ok(message)) => (200, serde_json::json!({ "ok": true, "message": message })),
+                Ok(Err(message)) => (409, serde_json::json!({ "ok": false, "error": message })),
+                Err(_) => (202, serde_json::json!({ "ok": true, "message": "Queued" })),
+            }
+        }
+        Err((code, message)) => (code, serde_json::json!({ "ok": false, "error": message })),
+    };
+    respond(&stream, code, &body.to_string());
+}
+
+fn respond(mut stream: &TcpStream, code: u16, body: &str) {
+    let reason = match code {
+        200 => "OK",
+        202 => "Accepted",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Conflict",
+    };
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        code,
+        reason,
+        body.len(),
+        body
+    );
+    let _ = stream.flush();
+}
+
+/// Map a request line to what it asks for; wrong tokens are refused before anything else
+fn route(method: &str, target: &str, token: &str) -> Result<Route, (u16, &'static str)> {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let given = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .map(percent_decode);
+    if token.is_empty() || given.as_deref() != Some(token) {
+        return Err((401, "Missing or wrong token"));
+    }
+
+    let wants_post = |route: Route| {
+        if method == "POST" {
+            Ok(route)
+        } else {
+            Err((405, "Use POST"))
+        }
+    };
+    if path == "/status" {
+        return if method == "GET" {
+            Ok(Route::Status)
+        } else {
+            Err((405, "Use GET"))
+        };
+    }
+    if path == "/stop" {
+        return wants_post(Route::Command(RemoteCommand::StopAll));
+    }
+    if let Some(name) = path.strip_prefix("/stop/").filter(|name| !name.is_empty()) {
+        return wants_post(Route::Command(RemoteCommand::Stop(percent_decode(name))));
+    }
+    if let Some(name) = path.strip_prefix("/start/").filter(|name| !name.is_empty()) {
+        return wants_post(Route::Command(RemoteCommand::Start(percent_decode(name))));
+    }
+    Err((404, "Unknown endpoint"))
+}
+
+/// Undo URL encoding ("%20" and "+" for spaces); malformed escapes are kept as typed
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let escaped = if bytes[idx] == b'%' {
+            text.get(idx + 1..idx + 3)
+                .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {
+            None
+        };
+        match (bytes[idx], escaped) {
+            (_, Some(byte)) => {
+                decoded.push(byte);
+                idx += 3;
+            }
+            (b'+', None) => {
+                decoded.push(b' ');
+                idx += 1;
+            }
+            (byte, None) => {
+                decoded.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_checks_the_token_first() {
+        assert_eq!(route("GET", "/status?token=abc", "abc"), Ok(Route::Status));
+        assert_eq!(route("GET", "/status?token=abd", "abc").unwrap_err().0, 401);
+        assert_eq!(route("GET", "/status", "abc").unwrap_err().0, 401);
+        // No token configured: nothing is served
+        assert_eq!(route("GET", "/status?token=", "").unwrap_err().0, 401);
+    }
+
+    #[test]
+    fn test_route_commands() {
+        assert_eq!(
+            route("POST", "/stop?token=abc", "abc"),
+            Ok(Route::Command(RemoteCommand::StopAll))
+        );
+        assert_eq!(
+            route("POST", "/start/Farm%20Loop?x=1&token=abc", "abc"),
+            Ok(Route::Command(RemoteCommand::Start(
+                "Farm Loop".to_string()
+            )))
+        );
+        assert_eq!(
+            route("POST", "/stop/Anti-AFK?token=abc", "abc"),
+            Ok(Route::Command(RemoteCommand::Stop("Anti-AFK".to_string())))
+        );
+        assert_eq!(route("GET", "/stop?token=abc", "abc").unwrap_err().0, 405);
+        assert_eq!(
+            route("POST", "/start/?token=abc", "abc").unwrap_err().0,
+            404
+        );
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("Farm+Loop%20%232"), "Farm Loop #2");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+}
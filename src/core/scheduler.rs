@@ -0,0 +1,214 @@
+// Fires schedule entries when their minute of the week comes round
+use crate::core::clock::LocalTime;
+use crate::settings::ScheduleEntry;
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+const MINUTES_PER_WEEK: u32 = 7 * MINUTES_PER_DAY;
+
+// A longer gap between checks (sleep, a hung frame) skips what fell inside it
+const MAX_CATCH_UP_MINUTES: u32 = 5;
+
+pub const WEEKDAY_KEYS: [&str; 7] = [
+    "schedule.sun",
+    "schedule.mon",
+    "schedule.tue",
+    "schedule.wed",
+    "schedule.thu",
+    "schedule.fri",
+    "schedule.sat",
+];
+
+fn minute_of_week(weekday: u32, hour: u32, minute: u32) -> u32 {
+    (weekday % 7) * MINUTES_PER_DAY + hour * 60 + minute
+}
+
+fn now_minute(now: &LocalTime) -> u32 {
+    minute_of_week(now.weekday.into(), now.hour.into(), now.minute.into())
+}
+
+/// Minutes of the week the entry fires at (none when it is off or has no days)
+fn entry_minutes(entry: &ScheduleEntry) -> impl Iterator<Item = u32> + '_ {
+    (0..7u32)
+        .filter(move |&day| entry.enabled && entry.days[day as usize])
+        .map(move |day| minute_of_week(day, entry.hour.into(), entry.minute.into()))
+}
+
+/// Remembers the last minute checked, so each entry fires once per due minute and
+/// nothing from before the app started is replayed
+#[derive(Default)]
+pub struct Scheduler {
+    last_checked: Option<u32>,
+}
+
+impl Scheduler {
+    /// Indices of the entries that came due since the last call
+    pub fn due(&mut self, entries: &[ScheduleEntry], now: &LocalTime) -> Vec<usize> {
+        let now = now_minute(now);
+        // The first check (and one after a long gap) only covers the current minute
+        let gap = match self.last_checked {
+            Some(last) => (now + MINUTES_PER_WEEK - last) % MINUTES_PER_WEEK,
+            None => 1,
+        };
+        let gap = if gap > MAX_CATCH_UP_MINUTES { 1 } else { gap };
+        self.last_checked = Some(now);
+        if gap == 0 {
+            return Vec::new();
+        }
+
+        entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                entry_minutes(entry).any(|at| {
+                    let behind = (now + MINUTES_PER_WEEK - at) % MINUTES_PER_WEEK;
+                    behind < gap
+                })
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+/// The entry that fires next after `now`, and how many minutes away it is
+pub fn next_due(entries: &[ScheduleEntry], now: &LocalTime) -> Option<(usize, u32)> {
+    let now = now_minute(now);
+    entries
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, entry)| {
+            entry_minutes(entry)
+                .map(
+                    |at| match (at + MINUTES_PER_WEEK - now) % MINUTES_PER_WEEK {
+                        // Firing right now: the next time is a week away
+                        0 => MINUTES_PER_WEEK,
+                        ahead => ahead,
+                    },
+                )
+                .min()
+                .map(|ahead| (idx, ahead))
+        })
+        .min_by_key(|&(_, ahead)| ahead)
+}
+
+/// "Collection Filler at 20:55", with the day when it isn't today
+pub fn describe_next(entry: &ScheduleEntry, now: &LocalTime, minutes_ahead: u32) -> String {
+    let time = format!("{:02}:{:02}", entry.hour, entry.minute);
+    let minute_of_day = u32::from(now.hour) * 60 + u32::from(now.minute);
+    let days_ahead = (minute_of_day + minutes_ahead) / MINUTES_PER_DAY;
+    let action = entry.action.label();
+    if days_ahead == 0 {
+        tr!(
+            "schedule.next",
+            action = action,
+            name = entry.target,
+            time = time
+        )
+    } else {
+        let day = (u32::from(now.weekday) + days_ahead) % 7;
+        tr!(
+            "schedule.next_on",
+            action = action,
+            name = entry.target,
+            day = crate::i18n::text(WEEKDAY_KEYS[day as usize]),
+            time = time
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::ScheduleAction;
+
+    fn at(weekday: u16, hour: u16, minute: u16) -> LocalTime {
+        LocalTime {
+            year: 2024,
+            month: 1,
+            day: 1,
+            hour,
+            minute,
+            second: 30,
+            weekday,
+        }
+    }
+
+    fn entry(hour: u8, minute: u8, days: [bool; 7]) -> ScheduleEntry {
+        ScheduleEntry {
+            enabled: true,
+            target: "Collection Filler".to_string(),
+            hour,
+            minute,
+            days,
+            action: ScheduleAction::Start,
+            macro_id: None,
+        }
+    }
+
+    #[test]
+    fn test_fires_once_in_its_minute() {
+        let entries = vec![entry(20, 55, [true; 7])];
+        let mut scheduler = Scheduler::default();
+        assert!(scheduler.due(&entries, &at(1, 20, 54)).is_empty());
+        assert_eq!(scheduler.due(&entries, &at(1, 20, 55)), vec![0]);
+        // Later frames in the same minute don't fire it again
+        assert!(scheduler.due(&entries, &at(1, 20, 55)).is_empty());
+        assert!(scheduler.due(&entries, &at(1, 20, 56)).is_empty());
+    }
+
+    #[test]
+    fn test_missed_before_start_is_skipped() {
+        let entries = vec![entry(20, 55, [true; 7])];
+        let mut scheduler = Scheduler::default();
+        assert!(scheduler.due(&entries, &at(1, 21, 0)).is_empty());
+        // Started during the minute itself still counts
+        let mut scheduler = Scheduler::default();
+        assert_eq!(scheduler.due(&entries, &at(1, 20, 55)), vec![0]);
+    }
+
+    #[test]
+    fn test_short_gap_catches_up_but_long_gap_does_not() {
+        let entries = vec![entry(20, 55, [true; 7])];
+        let mut scheduler = Scheduler::default();
+        scheduler.due(&entries, &at(1, 20, 53));
+        assert_eq!(scheduler.due(&entries, &at(1, 20, 57)), vec![0]);
+
+        let mut scheduler = Scheduler::default();
+        scheduler.due(&entries, &at(1, 20, 0));
+        assert!(scheduler.due(&entries, &at(1, 21, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_days_and_enabled_are_respected() {
+        let mut days = [false; 7];
+        days[6] = true; // Saturday only
+        let mut entries = vec![entry(0, 0, days)];
+        let mut scheduler = Scheduler::default();
+        assert!(scheduler.due(&entries, &at(5, 0, 0)).is_empty());
+        let mut scheduler = Scheduler::default();
+        assert_eq!(scheduler.due(&entries, &at(6, 0, 0)), vec![0]);
+        // Sunday 00:00 lies in the window wrapping round the end of the week
+        days = [false; 7];
+        days[0] = true;
+        entries[0].days = days;
+        let mut scheduler = Scheduler::default();
+        scheduler.due(&entries, &at(6, 23, 59));
+        assert_eq!(scheduler.due(&entries, &at(0, 0, 1)), vec![0]);
+
+        entries[0].enabled = false;
+        let mut scheduler = Scheduler::default();
+        assert!(scheduler.due(&entries, &at(0, 0, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_next_due_picks_the_soonest() {
+        let mut weekdays = [true; 7];
+        weekdays[0] = false;
+        weekdays[6] = false;
+        let entries = vec![entry(20, 55, [true; 7]), entry(9, 0, weekdays)];
+        assert_eq!(next_due(&entries, &at(1, 8, 0)), Some((1, 60)));
+        assert_eq!(next_due(&entries, &at(1, 12, 0)), Some((0, 8 * 60 + 55)));
+        // Friday night: the weekday entry is Monday morning, the daily one tomorrow
+        assert_eq!(next_due(&entries, &at(5, 21, 0)), Some((0, 23 * 60 + 55)));
+        assert_eq!(next_due(&[], &at(1, 8, 0)), None);
+    }
+}
@@ -1,5 +1,7 @@
+use crate::core::error::HelperError;
 use crate::core::window::{get_client_rect_in_screen_coords, get_window_rect_in_screen_coords};
 use image::{ImageBuffer, Rgba};
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use windows::Win32::Foundation::HWND;
 use windows_capture::capture::{Context, GraphicsCaptureApiHandler};
@@ -102,11 +104,12 @@ impl GraphicsCaptureApiHandler for OneShotCapture {
 pub fn capture_window_region(
     hwnd: HWND,
     region: (i32, i32, i32, i32),
-) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, String> {
-    let client_rect = get_client_rect_in_screen_coords(hwnd)
-        .ok_or_else(|| "Failed to get client rect".to_string())?;
-    let window_rect = get_window_rect_in_screen_coords(hwnd)
-        .ok_or_else(|| "Failed to get window rect".to_string())?;
+) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, HelperError> {
+    if region.2 <= 0 || region.3 <= 0 {
+        return Err(HelperError::InvalidRegion { region });
+    }
+    let client_rect = get_client_rect_in_screen_coords(hwnd).ok_or(HelperError::WindowInvalid)?;
+    let window_rect = get_window_rect_in_screen_coords(hwnd).ok_or(HelperError::WindowInvalid)?;
 
     let client_offset = (client_rect.0 - window_rect.0, client_rect.1 - window_rect.1);
     let window_size = (window_rect.2, window_rect.3);
@@ -131,18 +134,39 @@ pub fn capture_window_region(
         flags,
     );
 
+    let capture_failed = |os_error: String| HelperError::CaptureFailed { os_error };
     let control = OneShotCapture::start_free_threaded(settings)
-        .map_err(|e| format!("Capture start failed: {}", e))?;
+        .map_err(|e| capture_failed(format!("start: {}", e)))?;
     control
         .wait()
-        .map_err(|e| format!("Capture wait failed: {}", e))?;
+        .map_err(|e| capture_failed(format!("wait: {}", e)))?;
 
     let captured = output
         .lock()
         .unwrap()
         .take()
-        .ok_or_else(|| "No capture frame received".to_string())?;
+        .ok_or_else(|| capture_failed("no frame received".to_string()))?;
 
     ImageBuffer::from_raw(captured.width, captured.height, captured.rgba)
-        .ok_or_else(|| "Failed to build capture image".to_string())
+        .ok_or_else(|| capture_failed("frame buffer size mismatch".to_string()))
+}
+
+/// Capture a window region and save it as an image file (e.g. a template to search for
+/// later), creating the folder if needed
+pub fn save_window_region(
+    hwnd: HWND,
+    region: (i32, i32, i32, i32),
+    path: &Path,
+) -> Result<(), HelperError> {
+    let image = capture_window_region(hwnd, region)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| HelperError::FileWrite {
+            path: dir.display().to_string(),
+            source: e.to_string(),
+        })?;
+    }
+    image.save(path).map_err(|e| HelperError::FileWrite {
+        path: path.display().to_string(),
+        source: e.to_string(),
+    })
 }
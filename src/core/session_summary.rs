@@ -0,0 +1,227 @@
+// Recap of what ran during this session, written to the logs folder on exit
+use crate::core::clock::to_local;
+use crate::core::paths;
+use crate::core::worker::WorkerStats;
+use crate::settings::items_label;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+// Distinct error messages kept per tool
+const MAX_ERRORS_PER_TOOL: usize = 5;
+
+/// Totals of one tool over this session's finished runs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionTool {
+    pub name: String,
+    pub runs: u64,
+    pub runtime_secs: u64,
+    pub iterations: u64,
+    pub items: u64,
+    pub matches: u64,
+    pub runs_with_errors: u64,
+    // Last error of each failed run, without repeats
+    pub errors: Vec<String>,
+}
+
+/// Finished runs of this session, per tool in the order they first ran
+pub struct SessionSummary {
+    started_at: SystemTime,
+    tools: Vec<SessionTool>,
+}
+
+impl SessionSummary {
+    pub fn new(started_at: SystemTime) -> Self {
+        Self {
+            started_at,
+            tools: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Add a finished run of `tool`
+    pub fn record_run(&mut self, tool: &str, run: &WorkerStats) {
+        let idx = match self.tools.iter().position(|t| t.name == tool) {
+            Some(idx) => idx,
+            None => {
+                self.tools.push(SessionTool {
+                    name: tool.to_string(),
+                    ..SessionTool::default()
+                });
+                self.tools.len() - 1
+            }
+        };
+        let totals = &mut self.tools[idx];
+        totals.runs += 1;
+        totals.runtime_secs += run.elapsed().map(|d| d.as_secs()).unwrap_or(0);
+        totals.iterations += run.iterations;
+        totals.items += run.items_processed;
+        totals.matches += run.matches;
+        if let Some(error) = &run.last_error {
+            totals.runs_with_errors += 1;
+            if !totals.errors.contains(error) && totals.errors.len() < MAX_ERRORS_PER_TOOL {
+                totals.errors.push(error.clone());
+            }
+        }
+    }
+
+    /// The recap, as written to the file and shown in the closing dialog
+    pub fn text(&self, ended_at: SystemTime) -> String {
+        format_summary(&clock(self.started_at), &clock(ended_at), &self.tools)
+    }
+
+    /// Append `text` to `session_<date>.txt` in the logs folder, returning its path
+    pub fn write(&self, text: &str) -> Result<PathBuf, String> {
+        let date = to_local(self.started_at)
+            .map(|time| time.date())
+            .unwrap_or_else(|| "undated".to_string());
+        let dir = paths::logs_dir();
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let path = dir.join(format!("session_{}.txt", date));
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| e.to_string())?;
+        writeln!(file, "{}", text).map_err(|e| e.to_string())?;
+        Ok(path)
+    }
+}
+
+fn clock(time: SystemTime) -> String {
+    to_local(time)
+        .map(|time| time.clock())
+        .unwrap_or_else(|| "--:--:--".to_string())
+}
+
+fn format_runtime(secs: u64) -> String {
+    format!(
+        "{}h {:02}m {:02}s",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
+
+/// One block per tool; counters that stayed at zero are left out
+fn format_summary(started: &str, ended: &str, tools: &[SessionTool]) -> String {
+    let mut text = format!("Session {} - {}\n", started, ended);
+    for tool in tools {
+        text.push_str(&format!("\n{}\n", tool.name));
+        let runs = if tool.runs_with_errors > 0 {
+            format!("{} ({} with errors)", tool.runs, tool.runs_with_errors)
+        } else {
+            tool.runs.to_string()
+        };
+        text.push_str(&format!("  Runs: {}\n", runs));
+        text.push_str(&format!(
+            "  Runtime: {}\n",
+            format_runtime(tool.runtime_secs)
+        ));
+        if tool.iterations > 0 {
+            text.push_str(&format!("  Loops: {}\n", tool.iterations));
+        }
+        if tool.items > 0 {
+            text.push_str(&format!("  {}: {}\n", items_label(&tool.name), tool.items));
+        }
+        if tool.matches > 0 {
+            text.push_str(&format!("  Matches found: {}\n", tool.matches));
+        }
+        for error in &tool.errors {
+            text.push_str(&format!("  Error: {}\n", error));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn run(secs: u64, iterations: u64, items: u64) -> WorkerStats {
+        let started_at = Instant::now();
+        WorkerStats {
+            started_at: Some(started_at),
+            finished_at: Some(started_at + Duration::from_secs(secs)),
+            iterations,
+            items_processed: items,
+            ..WorkerStats::default()
+        }
+    }
+
+    #[test]
+    fn test_record_run_adds_up_per_tool_in_first_run_order() {
+        let mut summary = SessionSummary::new(SystemTime::now());
+        assert!(summary.is_empty());
+
+        summary.record_run("Reroll", &run(60, 3, 12));
+        summary.record_run("Image Clicker", &run(30, 0, 4));
+        let failed = WorkerStats {
+            matches: 1,
+            last_error: Some("Capture Error: lost window".to_string()),
+            ..run(90, 2, 8)
+        };
+        summary.record_run("Reroll", &failed);
+        summary.record_run("Reroll", &failed);
+
+        let names: Vec<&str> = summary.tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Reroll", "Image Clicker"]);
+        let reroll = &summary.tools[0];
+        assert_eq!(reroll.runs, 3);
+        assert_eq!(reroll.runtime_secs, 240);
+        assert_eq!(reroll.iterations, 7);
+        assert_eq!(reroll.items, 28);
+        assert_eq!(reroll.matches, 2);
+        assert_eq!(reroll.runs_with_errors, 2);
+        assert_eq!(reroll.errors, ["Capture Error: lost window"]);
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let tools = [
+            SessionTool {
+                name: "Image Clicker".to_string(),
+                runs: 2,
+                runtime_secs: 3725,
+                items: 40,
+                ..SessionTool::default()
+            },
+            SessionTool {
+                name: "Reroll".to_string(),
+                runs: 3,
+                runtime_secs: 59,
+                iterations: 7,
+                items: 28,
+                matches: 1,
+                runs_with_errors: 1,
+                errors: vec!["OCR Error: timeout".to_string()],
+            },
+        ];
+
+        assert_eq!(
+            format_summary("19:00:00", "23:30:00", &tools),
+            "Session 19:00:00 - 23:30:00\n\
+             \n\
+             Image Clicker\n  \
+             Runs: 2\n  \
+             Runtime: 1h 02m 05s\n  \
+             Clicks: 40\n\
+             \n\
+             Reroll\n  \
+             Runs: 3 (1 with errors)\n  \
+             Runtime: 0h 00m 59s\n  \
+             Loops: 7\n  \
+             Actions run: 28\n  \
+             Matches found: 1\n  \
+             Error: OCR Error: timeout\n"
+        );
+        assert_eq!(
+            format_summary("19:00:00", "19:05:00", &[]),
+            "Session 19:00:00 - 19:05:00\n"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+// Audio cues for events from any tool, configured per event in the settings
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HMODULE;
+use windows::Win32::Media::Audio::{
+    PlaySoundW, SND_ALIAS, SND_ASYNC, SND_FILENAME, SND_FLAGS, SND_NODEFAULT,
+};
+
+/// Something worth hearing about while the game has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Event {
+    MatchFound,
+    MacroCompleted,
+    WorkerError,
+    WatchdogFired,
+}
+
+impl Event {
+    pub const ALL: [Event; 4] = [
+        Event::MatchFound,
+        Event::MacroCompleted,
+        Event::WorkerError,
+        Event::WatchdogFired,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Event::MatchFound => tr!("sounds.event_match"),
+            Event::MacroCompleted => tr!("sounds.event_completed"),
+            Event::WorkerError => tr!("sounds.event_error"),
+            Event::WatchdogFired => tr!("sounds.event_watchdog"),
+        }
+    }
+}
+
+/// Windows system sounds, played by alias so they follow the user's sound scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuiltInSound {
+    Asterisk,
+    Exclamation,
+    CriticalStop,
+    Beep,
+}
+
+impl BuiltInSound {
+    pub const ALL: [BuiltInSound; 4] = [
+        BuiltInSound::Asterisk,
+        BuiltInSound::Exclamation,
+        BuiltInSound::CriticalStop,
+        BuiltInSound::Beep,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BuiltInSound::Asterisk => tr!("sounds.builtin_asterisk"),
+            BuiltInSound::Exclamation => tr!("sounds.builtin_exclamation"),
+            BuiltInSound::CriticalStop => tr!("sounds.builtin_critical"),
+            BuiltInSound::Beep => tr!("sounds.builtin_beep"),
+        }
+    }
+
+    fn alias(self) -> &'static str {
+        match self {
+            BuiltInSound::Asterisk => "SystemAsterisk",
+            BuiltInSound::Exclamation => "SystemExclamation",
+            BuiltInSound::CriticalStop => "SystemHand",
+            BuiltInSound::Beep => "SystemDefault",
+        }
+    }
+}
+
+/// What an event plays; events missing from the settings map are silent
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventSound {
+    #[default]
+    Off,
+    BuiltIn(BuiltInSound),
+    // Path to a .wav file
+    File(String),
+}
+
+pub struct SoundPlayer {
+    sounds: Mutex<BTreeMap<Event, EventSound>>,
+}
+
+/// Shared player; every event is silent until `configure` maps it to a sound
+pub fn sounds() -> &'static SoundPlayer {
+    static PLAYER: OnceLock<SoundPlayer> = OnceLock::new();
+    PLAYER.get_or_init(SoundPlayer::new)
+}
+
+impl SoundPlayer {
+    fn new() -> Self {
+        Self {
+            sounds: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Called every frame with the settings map, so only a change is copied
+    pub fn configure(&self, sounds: &BTreeMap<Event, EventSound>) {
+        let mut current = self.sounds.lock().unwrap();
+        if *current != *sounds {
+            *current = sounds.clone();
+        }
+    }
+
+    fn sound_for(&self, event: Event) -> EventSound {
+        self.sounds
+            .lock()
+            .unwrap()
+            .get(&event)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Start the event's sound and return at once; safe to call from worker threads.
+    /// A sound that can't be played is skipped (the settings test button reports why).
+    pub fn play(&self, event: Event) {
+        let _ = play_sound(&self.sound_for(event));
+    }
+}
+
+/// Start playing `sound` without waiting for it to finish
+pub fn play_sound(sound: &EventSound) -> Result<(), String> {
+    let (name, flags) = match sound {
+        EventSound::Off => return Ok(()),
+        EventSound::BuiltIn(builtin) => (builtin.alias(), SND_ALIAS),
+        EventSound::File(path) => {
+            if !Path::new(path).is_file() {
+                return Err(format!("Sound file not found: {}", path));
+            }
+            (path.as_str(), SND_FILENAME)
+        }
+    };
+
+    let name_w: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+    // SND_NODEFAULT: a file Windows can't decode stays silent instead of beeping
+    let flags = SND_FLAGS(flags.0 | SND_ASYNC.0 | SND_NODEFAULT.0);
+    let played = unsafe { PlaySoundW(PCWSTR(name_w.as_ptr()), HMODULE::default(), flags) };
+    if played.as_bool() {
+        Ok(())
+    } else {
+        Err(format!("Could not play {}", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unmapped_events_are_silent() {
+        let player = SoundPlayer::new();
+        let mut map = BTreeMap::new();
+        map.insert(
+            Event::MatchFound,
+            EventSound::BuiltIn(BuiltInSound::Asterisk),
+        );
+        player.configure(&map);
+
+        assert_eq!(
+            player.sound_for(Event::MatchFound),
+            EventSound::BuiltIn(BuiltInSound::Asterisk)
+        );
+        assert_eq!(player.sound_for(Event::WatchdogFired), EventSound::Off);
+    }
+
+    #[test]
+    fn test_event_map_round_trips_through_json() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            Event::WorkerError,
+            EventSound::File("alarm.wav".to_string()),
+        );
+        map.insert(Event::MacroCompleted, EventSound::Off);
+
+        let json = serde_json::to_string(&map).unwrap();
+        let parsed: BTreeMap<Event, EventSound> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, map);
+    }
+}
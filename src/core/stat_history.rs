@@ -0,0 +1,61 @@
+// Counter readings of a Stat Logger run, shared between its worker and the UI
+
+/// One reading of the counter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatSample {
+    // Seconds since the run started
+    pub elapsed_secs: f64,
+    pub value: i64,
+}
+
+/// Readings of the current run, oldest first
+#[derive(Debug, Default)]
+pub struct StatHistory {
+    samples: Vec<StatSample>,
+}
+
+impl StatHistory {
+    pub fn push(&mut self, sample: StatSample) {
+        self.samples.push(sample);
+    }
+
+    pub fn samples(&self) -> &[StatSample] {
+        &self.samples
+    }
+
+    pub fn latest(&self) -> Option<StatSample> {
+        self.samples.last().copied()
+    }
+
+    /// Change since the first reading of the run
+    pub fn delta(&self) -> Option<i64> {
+        Some(self.samples.last()?.value - self.samples.first()?.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(elapsed_secs: f64, value: i64) -> StatSample {
+        StatSample {
+            elapsed_secs,
+            value,
+        }
+    }
+
+    #[test]
+    fn test_history_delta_is_measured_from_the_first_reading() {
+        let mut history = StatHistory::default();
+        assert_eq!(history.delta(), None);
+        assert_eq!(history.latest(), None);
+
+        history.push(sample(0.0, 1_000_000));
+        assert_eq!(history.delta(), Some(0));
+        history.push(sample(30.0, 1_250_000));
+        history.push(sample(60.0, 1_100_000));
+        assert_eq!(history.delta(), Some(100_000));
+        assert_eq!(history.latest(), Some(sample(60.0, 1_100_000)));
+        assert_eq!(history.samples().len(), 3);
+    }
+}
@@ -0,0 +1,108 @@
+// Per-run cap on how fast a tool sends clicks and key presses (see ActionRateLimit)
+use crate::core::worker::{push_log_entry, LogLevel, SharedLog};
+use crate::settings::ActionRateLimit;
+use std::time::{Duration, Instant};
+
+/// Spaces the actions of one worker at least `1 / max_per_second` apart.
+/// Each run builds its own, so tools never slow each other down.
+pub struct ActionThrottle {
+    // None when the limit is switched off
+    min_interval: Option<Duration>,
+    max_per_second: u32,
+    // When the previous action went out (after any wait)
+    last_action: Option<Instant>,
+    // The warning is logged once per run, not on every capped action
+    warned: bool,
+    log: SharedLog,
+}
+
+impl ActionThrottle {
+    pub fn new(limit: ActionRateLimit, log: SharedLog) -> Self {
+        let max_per_second = limit.max_per_second.max(1);
+        Self {
+            min_interval: limit
+                .enabled
+                .then_some(Duration::from_secs(1) / max_per_second),
+            max_per_second,
+            last_action: None,
+            warned: false,
+            log,
+        }
+    }
+
+    /// Call right before sending a click or key press; sleeps when the cap would be exceeded
+    pub fn wait(&mut self) {
+        let delay = self.delay_at(Instant::now());
+        if delay.is_zero() {
+            return;
+        }
+        if !self.warned {
+            self.warned = true;
+            push_log_entry(
+                &self.log,
+                LogLevel::Warn,
+                &format!("Rate limited to {} actions/s", self.max_per_second),
+            );
+        }
+        std::thread::sleep(delay);
+    }
+
+    /// How long an action at `now` has to wait, recording it as sent once the wait is over
+    fn delay_at(&mut self, now: Instant) -> Duration {
+        let delay = match (self.min_interval, self.last_action) {
+            (Some(interval), Some(last)) => (last + interval).saturating_duration_since(now),
+            _ => Duration::ZERO,
+        };
+        self.last_action = Some(now + delay);
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::worker::LogBuffer;
+    use std::sync::{Arc, Mutex};
+
+    fn throttle(enabled: bool, max_per_second: u32) -> ActionThrottle {
+        let log: SharedLog = Arc::new(Mutex::new(LogBuffer::new("Test")));
+        ActionThrottle::new(
+            ActionRateLimit {
+                enabled,
+                max_per_second,
+            },
+            log,
+        )
+    }
+
+    #[test]
+    fn test_actions_are_spaced_by_the_limit() {
+        let mut throttle = throttle(true, 10);
+        let start = Instant::now();
+        assert_eq!(throttle.delay_at(start), Duration::ZERO);
+        // Right after the first action: wait out the rest of its 100 ms
+        assert_eq!(
+            throttle.delay_at(start + Duration::from_millis(30)),
+            Duration::from_millis(70)
+        );
+        // The delayed action counts from when it was actually sent
+        assert_eq!(
+            throttle.delay_at(start + Duration::from_millis(100)),
+            Duration::from_millis(100)
+        );
+        // Slower than the limit: no wait at all
+        assert_eq!(
+            throttle.delay_at(start + Duration::from_secs(5)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_disabled_limit_never_waits() {
+        let mut throttle = throttle(false, 10);
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert_eq!(throttle.delay_at(now), Duration::ZERO);
+        }
+    }
+}
@@ -1,9 +1,13 @@
 use windows::{
-    Win32::Foundation::{HWND, POINT},
+    Win32::Foundation::{COLORREF, HWND, POINT},
     Win32::Graphics::Gdi::{ClientToScreen, GetDC, GetPixel, ReleaseDC, ScreenToClient},
+    Win32::System::Threading::GetCurrentProcessId,
     Win32::UI::WindowsAndMessaging::{
-        FindWindowA, GetAncestor, GetClientRect, GetCursorPos, GetWindowRect, GetWindowTextA,
-        IsWindow, WindowFromPoint, GA_PARENT,
+        FindWindowA, FindWindowExA, GetAncestor, GetClientRect, GetCursorPos, GetForegroundWindow,
+        GetSystemMetrics, GetWindowLongPtrW, GetWindowRect, GetWindowTextW,
+        GetWindowThreadProcessId, IsWindow, SetLayeredWindowAttributes, SetWindowLongPtrW,
+        WindowFromPoint, GA_PARENT, GA_ROOT, GWL_EXSTYLE, LWA_ALPHA, SM_CXSCREEN, SM_CYSCREEN,
+        WS_EX_LAYERED, WS_EX_TRANSPARENT,
     },
 };
 
@@ -18,13 +22,7 @@ pub fn find_game_window() -> Option<(HWND, String)> {
 
         if hwnd.0 != 0 && IsWindow(hwnd).as_bool() {
             // Get actual window title
-            let mut buffer = [0u8; 256];
-            let len = GetWindowTextA(hwnd, &mut buffer);
-            let title = if len > 0 {
-                String::from_utf8_lossy(&buffer[..len as usize]).to_string()
-            } else {
-                "D3D Window".to_string()
-            };
+            let title = get_window_title(hwnd).unwrap_or_else(|| "D3D Window".to_string());
             Some((hwnd, title))
         } else {
             None
@@ -32,6 +30,42 @@ pub fn find_game_window() -> Option<(HWND, String)> {
     }
 }
 
+/// Every open game window (one per client), in Z order
+pub fn find_game_windows() -> Vec<(HWND, String)> {
+    let mut found = Vec::new();
+    let mut previous = HWND(0);
+    loop {
+        let hwnd = unsafe {
+            FindWindowExA(
+                HWND(0),
+                previous,
+                windows::core::PCSTR("D3D Window\0".as_ptr()),
+                windows::core::PCSTR::null(),
+            )
+        };
+        if hwnd.0 == 0 {
+            break;
+        }
+        let title = get_window_title(hwnd).unwrap_or_else(|| "D3D Window".to_string());
+        found.push((hwnd, title));
+        previous = hwnd;
+    }
+    found
+}
+
+/// Get a window's title (Unicode, so character names survive)
+pub fn get_window_title(hwnd: HWND) -> Option<String> {
+    unsafe {
+        let mut buffer = [0u16; 256];
+        let len = GetWindowTextW(hwnd, &mut buffer);
+        if len > 0 {
+            Some(String::from_utf16_lossy(&buffer[..len as usize]))
+        } else {
+            None
+        }
+    }
+}
+
 /// Check if window handle is valid
 pub fn is_window_valid(hwnd: HWND) -> bool {
     unsafe { IsWindow(hwnd).as_bool() }
@@ -134,6 +168,11 @@ pub fn get_cursor_pos() -> Option<(i32, i32)> {
     }
 }
 
+/// Size of the primary monitor, whose top-left corner is the screen origin
+pub fn primary_monitor_size() -> (i32, i32) {
+    unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) }
+}
+
 /// Get window under cursor
 pub fn get_window_under_cursor() -> Option<HWND> {
     unsafe {
@@ -151,6 +190,18 @@ pub fn get_window_under_cursor() -> Option<HWND> {
     }
 }
 
+/// Whether the top-level window at a screen point is the game's (not the helper or another
+/// app covering it)
+pub fn is_game_at_screen_point(game_hwnd: HWND, screen_x: i32, screen_y: i32) -> bool {
+    unsafe {
+        let hit = WindowFromPoint(POINT {
+            x: screen_x,
+            y: screen_y,
+        });
+        hit.0 != 0 && GetAncestor(hit, GA_ROOT).0 == GetAncestor(game_hwnd, GA_ROOT).0
+    }
+}
+
 /// Check if a window is the game window or a child of it
 pub fn is_game_window_or_child(check_hwnd: HWND, game_hwnd: HWND) -> bool {
     let mut current_hwnd = check_hwnd;
@@ -168,6 +219,45 @@ pub fn is_game_window_or_child(check_hwnd: HWND, game_hwnd: HWND) -> bool {
     false
 }
 
+/// Check if the foreground window is the game (or a child of it) or one of this app's windows
+pub fn is_game_or_helper_focused(game_hwnd: Option<HWND>) -> bool {
+    let foreground = unsafe { GetForegroundWindow() };
+    if foreground.0 == 0 {
+        return false;
+    }
+    if let Some(game_hwnd) = game_hwnd {
+        if is_game_window_or_child(foreground, game_hwnd) {
+            return true;
+        }
+    }
+    let mut process_id = 0u32;
+    unsafe {
+        GetWindowThreadProcessId(foreground, Some(&mut process_id as *mut u32));
+        process_id == GetCurrentProcessId()
+    }
+}
+
+/// Let mouse input pass through a window to whatever is below it, or take it back
+/// (the window stays layered and fully opaque once click-through was used)
+pub fn set_click_through(hwnd: HWND, enabled: bool) {
+    unsafe {
+        let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        let new_style = if enabled {
+            style | (WS_EX_LAYERED.0 | WS_EX_TRANSPARENT.0) as isize
+        } else {
+            style & !(WS_EX_TRANSPARENT.0 as isize)
+        };
+        if new_style == style {
+            return;
+        }
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, new_style);
+        if enabled {
+            // A layered window stays invisible until its alpha is set
+            let _ = SetLayeredWindowAttributes(hwnd, COLORREF(0), 255, LWA_ALPHA);
+        }
+    }
+}
+
 /// Get the RGB color of a pixel at screen coordinates
 /// Returns (R, G, B) as u8 values
 pub fn get_pixel_color(screen_x: i32, screen_y: i32) -> Option<(u8, u8, u8)> {
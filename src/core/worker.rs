@@ -1,80 +1,953 @@
+use crate::core::click_guard;
+use crate::core::error::HelperError;
+use crate::core::file_logger::file_logger;
+use crate::core::paths;
+use crate::core::sounds::{sounds, Event};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::collections::VecDeque;
-use std::sync::{Arc, Mutex};
-use std::thread;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-pub struct Worker {
-    running: Arc<Mutex<bool>>,
-    status: Arc<Mutex<String>>,
-    log: Arc<Mutex<VecDeque<String>>>,
+const MAX_LOG_LINES: usize = 200;
+
+// Ordered by severity so filters can use a minimum level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 3] = [LogLevel::Info, LogLevel::Warn, LogLevel::Error];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
 }
 
-impl Default for Worker {
-    fn default() -> Self {
-        let mut log = VecDeque::new();
-        log.push_back("Ready".to_string());
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: SystemTime,
+    pub level: LogLevel,
+    // Tool (or "App") the entry came from
+    pub source: String,
+    pub text: String,
+}
+
+impl LogEntry {
+    pub fn new(source: &str, level: LogLevel, text: &str) -> Self {
         Self {
-            running: Arc::new(Mutex::new(false)),
-            status: Arc::new(Mutex::new("Ready".to_string())),
-            log: Arc::new(Mutex::new(log)),
+            time: SystemTime::now(),
+            level,
+            source: source.to_string(),
+            text: text.to_string(),
+        }
+    }
+}
+
+/// Recent entries of one worker; `tag` (the tool name) becomes each entry's source
+pub struct LogBuffer {
+    tag: String,
+    entries: VecDeque<LogEntry>,
+}
+
+impl LogBuffer {
+    pub fn new(tag: &str) -> Self {
+        Self {
+            tag: tag.to_string(),
+            entries: VecDeque::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Log ring buffer shared between a Worker and its thread
+pub type SharedLog = Arc<Mutex<LogBuffer>>;
+
+/// Append to a shared log, dropping the oldest entries past MAX_LOG_LINES,
+/// and copy the entry to the log file when file logging is enabled.
+/// Free function so worker threads (which only hold the Arc) can log too.
+pub fn push_log_entry(log: &SharedLog, level: LogLevel, text: &str) {
+    let mut log = log.lock().unwrap();
+    let entry = LogEntry::new(&log.tag, level, text);
+    file_logger().append(&entry);
+    log.entries.push_back(entry);
+    while log.entries.len() > MAX_LOG_LINES {
+        log.entries.pop_front();
+    }
+}
+
+/// Counters for the current (or last) run, shown under the tool's status
+#[derive(Debug, Clone, Default)]
+pub struct WorkerStats {
+    pub started_at: Option<Instant>,
+    // Set when the task returns, so the elapsed time stops counting
+    pub finished_at: Option<Instant>,
+    pub iterations: u64,
+    pub items_processed: u64,
+    // OCR searches that found their target
+    pub matches: u64,
+    // Clicks the click guard refused (off the game window)
+    pub skipped_clicks: u64,
+    pub last_error: Option<String>,
+    // The typed cause of `last_error`, when it came from a HelperError
+    pub helper_error: Option<HelperError>,
+}
+
+impl WorkerStats {
+    pub fn bump_iteration(&mut self) {
+        self.iterations += 1;
+    }
+
+    pub fn add_items(&mut self, count: u64) {
+        self.items_processed += count;
+    }
+
+    pub fn add_match(&mut self) {
+        self.matches += 1;
+    }
+
+    /// Every tool reports failures here, so the error sound plays from here too
+    /// (once per run, so a tool that keeps failing doesn't sound an alarm each time)
+    pub fn set_error(&mut self, error: &str) {
+        if self.last_error.is_none() {
+            sounds().play(Event::WorkerError);
         }
+        self.last_error = Some(error.to_string());
+        self.helper_error = None;
+    }
+
+    /// `set_error` with the error's status line, keeping the error itself for the UI
+    pub fn record_error(&mut self, error: &HelperError) {
+        self.set_error(&error.status());
+        self.helper_error = Some(error.clone());
+    }
+
+    pub fn elapsed(&self) -> Option<Duration> {
+        let started_at = self.started_at?;
+        Some(
+            self.finished_at
+                .unwrap_or_else(Instant::now)
+                .saturating_duration_since(started_at),
+        )
+    }
+}
+
+/// Wall-clock cap on a run (a tool's max runtime), counted from the worker's start
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunDeadline {
+    at: Instant,
+    minutes: u32,
+}
+
+impl RunDeadline {
+    /// `minutes` after the run in `stats` started (None = no limit)
+    pub fn for_run(stats: &SharedStats, minutes: Option<u32>) -> Option<Self> {
+        let minutes = minutes.filter(|&minutes| minutes > 0)?;
+        let started_at = stats
+            .lock()
+            .unwrap()
+            .started_at
+            .unwrap_or_else(Instant::now);
+        Some(Self {
+            at: started_at + Duration::from_secs(u64::from(minutes) * 60),
+            minutes,
+        })
+    }
+
+    pub fn passed(&self) -> bool {
+        Instant::now() >= self.at
     }
+
+    /// Status (and log line) of a run ended by the limit
+    pub fn reached_message(&self) -> String {
+        tr!("status.time_limit_reached", minutes = self.minutes)
+    }
+}
+
+/// Run statistics shared between a Worker and its thread
+pub type SharedStats = Arc<Mutex<WorkerStats>>;
+
+/// One run in progress as recorded in `running.json`: which tool or macro, since when, and
+/// how far it got at its last checkpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub name: String,
+    pub started_unix_secs: u64,
+    pub iterations: u64,
+    pub items_processed: u64,
+    pub matches: u64,
+}
+
+impl JournalEntry {
+    fn new(name: &str, stats: &WorkerStats) -> Self {
+        Self {
+            name: name.to_string(),
+            started_unix_secs: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            iterations: stats.iterations,
+            items_processed: stats.items_processed,
+            matches: stats.matches,
+        }
+    }
+
+    fn update(&mut self, stats: &WorkerStats) {
+        self.iterations = stats.iterations;
+        self.items_processed = stats.items_processed;
+        self.matches = stats.matches;
+    }
+
+    pub fn started_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.started_unix_secs)
+    }
+}
+
+/// Runs that haven't ended yet, mirrored to disk so a crash or reboot leaves them behind.
+/// Workers add their run on start, refresh the counters every JOURNAL_INTERVAL of checkpoints
+/// and remove it when the task returns; an empty journal deletes the file.
+pub struct RunJournal {
+    path: PathBuf,
+    enabled: AtomicBool,
+    entries: Mutex<Vec<JournalEntry>>,
+}
+
+// How often a running task's counters are written out (from `Worker::checkpoint`)
+const JOURNAL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Shared journal at `paths::run_journal_file()`; writes nothing until `enable` is called
+pub fn run_journal() -> &'static RunJournal {
+    static JOURNAL: OnceLock<RunJournal> = OnceLock::new();
+    JOURNAL.get_or_init(|| RunJournal::new(paths::run_journal_file()))
+}
+
+impl RunJournal {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            enabled: AtomicBool::new(false),
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Runs the last session left unfinished; read before `enable`, which starts over
+    pub fn unfinished(&self) -> Vec<JournalEntry> {
+        read_journal(&self.path)
+    }
+
+    /// Start recording (the app does; headless runs and tests don't)
+    pub fn enable(&self) {
+        if !self.enabled.swap(true, Ordering::Relaxed) {
+            self.write(&self.entries.lock().unwrap());
+        }
+    }
+
+    fn started(&self, entry: JournalEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|existing| existing.name != entry.name);
+        entries.push(entry);
+        self.write(&entries);
+    }
+
+    fn checkpoint(&self, name: &str, stats: &WorkerStats) {
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.name == name) {
+            entry.update(stats);
+            self.write(&entries);
+        }
+    }
+
+    fn finished(&self, name: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|entry| entry.name != name);
+        self.write(&entries);
+    }
+
+    /// Best effort: a journal that can't be written only costs the resume offer
+    fn write(&self, entries: &[JournalEntry]) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        if entries.is_empty() {
+            let _ = std::fs::remove_file(&self.path);
+            return;
+        }
+        if let Ok(json) = serde_json::to_string_pretty(entries) {
+            // Written aside and renamed, so a crash mid-write can't leave half a file
+            let tmp = self.path.with_extension("json.tmp");
+            if std::fs::write(&tmp, json).is_ok() {
+                let _ = std::fs::rename(&tmp, &self.path);
+            }
+        }
+    }
+}
+
+fn read_journal(path: &Path) -> Vec<JournalEntry> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+// The run on this worker thread, for the journal updates in `Worker::checkpoint`
+struct JournalRun {
+    name: String,
+    stats: SharedStats,
+    last_write: Instant,
+}
+
+thread_local! {
+    static JOURNAL_RUN: RefCell<Option<JournalRun>> = const { RefCell::new(None) };
+}
+
+/// Sending half of a worker's status channel; the UI drains it once per frame in `get_status`
+#[derive(Clone)]
+pub struct StatusSender {
+    tx: Sender<String>,
+    // Put in front of every update (e.g. which client a multi-client run is on)
+    prefix: String,
+}
+
+impl StatusSender {
+    pub fn set(&self, text: impl Into<String>) {
+        let text = text.into();
+        let text = if self.prefix.is_empty() {
+            text
+        } else {
+            format!("{}{}", self.prefix, text)
+        };
+        // The receiver lives as long as the Worker, so a failed send only happens during teardown
+        let _ = self.tx.send(text);
+    }
+
+    /// A sender for the same status whose updates all start with `prefix`
+    pub fn prefixed(&self, prefix: &str) -> StatusSender {
+        StatusSender {
+            tx: self.tx.clone(),
+            prefix: format!("{}{}", self.prefix, prefix),
+        }
+    }
+}
+
+pub struct Worker {
+    running: Arc<AtomicBool>,
+    // Tasks that call `checkpoint` wait there while this is set
+    paused: Arc<AtomicBool>,
+    // Latest status seen by the UI; tasks send updates through `status_tx` instead of locking it
+    status: Mutex<String>,
+    status_tx: Sender<String>,
+    status_rx: Mutex<Receiver<String>>,
+    log: SharedLog,
+    stats: SharedStats,
+    // Final stats of the last run, until the app collects them for the lifetime totals
+    finished_run: Arc<Mutex<Option<WorkerStats>>>,
+    // Game client size the current run worked out its pixel positions at
+    client_size: Mutex<Option<(i32, i32)>>,
+    // Counters the next run starts from (resuming an unfinished run)
+    resume_from: Mutex<Option<JournalEntry>>,
+    handle: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl Worker {
-    const MAX_LOG_LINES: usize = 200;
+    // How long stop() waits for the thread before giving up on it
+    const STOP_TIMEOUT: Duration = Duration::from_secs(2);
+    // Granularity of sleep_while_running, i.e. how quickly a sleeping task notices a stop
+    const SLEEP_CHUNK: Duration = Duration::from_millis(50);
 
-    pub fn new() -> Self {
-        Self::default()
+    /// `tag` names the tool in the log file
+    pub fn new(tag: &str) -> Self {
+        let mut log = LogBuffer::new(tag);
+        log.entries
+            .push_back(LogEntry::new(tag, LogLevel::Info, "Ready"));
+        let (status_tx, status_rx) = mpsc::channel();
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+            paused: Arc::new(AtomicBool::new(false)),
+            status: Mutex::new("Ready".to_string()),
+            status_tx,
+            status_rx: Mutex::new(status_rx),
+            log: Arc::new(Mutex::new(log)),
+            stats: Arc::new(Mutex::new(WorkerStats::default())),
+            finished_run: Arc::new(Mutex::new(None)),
+            client_size: Mutex::new(None),
+            resume_from: Mutex::new(None),
+            handle: Mutex::new(None),
+        }
     }
 
-    pub fn start<F>(&self, task: F)
+    /// Spawn `task` on a fresh thread. Returns false without spawning when a run is still active
+    /// or the previous thread has not exited yet, so two runs can never overlap.
+    pub fn start<F>(&self, task: F) -> bool
     where
-        F: FnOnce(Arc<Mutex<bool>>, Arc<Mutex<String>>, Arc<Mutex<VecDeque<String>>>) + Send + 'static,
+        F: FnOnce(Arc<AtomicBool>, Arc<AtomicBool>, StatusSender, SharedLog, SharedStats)
+            + Send
+            + 'static,
     {
-        *self.running.lock().unwrap() = true;
+        // Holding the handle slot for the whole start makes check-and-spawn atomic
+        let mut handle_slot = self.handle.lock().unwrap();
+        if self.is_running() || !Self::wait_for_exit(&mut handle_slot) {
+            return false;
+        }
+
+        self.paused.store(false, Ordering::Relaxed);
+        let resume_from = self.resume_from.lock().unwrap().take();
+        let stats = WorkerStats {
+            started_at: Some(Instant::now()),
+            iterations: resume_from.as_ref().map_or(0, |entry| entry.iterations),
+            items_processed: resume_from
+                .as_ref()
+                .map_or(0, |entry| entry.items_processed),
+            matches: resume_from.as_ref().map_or(0, |entry| entry.matches),
+            ..WorkerStats::default()
+        };
+        let name = self.log.lock().unwrap().tag.clone();
+        run_journal().started(JournalEntry::new(&name, &stats));
+        *self.stats.lock().unwrap() = stats;
+        self.running.store(true, Ordering::Relaxed);
 
         // Clone for the thread
         let running_clone = Arc::clone(&self.running);
-        let status_clone = Arc::clone(&self.status);
+        let paused_clone = Arc::clone(&self.paused);
+        let status_clone = StatusSender {
+            tx: self.status_tx.clone(),
+            prefix: String::new(),
+        };
         let log_clone = Arc::clone(&self.log);
+        let stats_clone = Arc::clone(&self.stats);
+        let finished_run = Arc::clone(&self.finished_run);
 
-        thread::spawn(move || {
-            task(running_clone, status_clone, log_clone);
+        let handle = thread::spawn(move || {
+            click_guard::attach_run(Arc::clone(&log_clone), Arc::clone(&stats_clone));
+            JOURNAL_RUN.with(|run| {
+                *run.borrow_mut() = Some(JournalRun {
+                    name: name.clone(),
+                    stats: Arc::clone(&stats_clone),
+                    last_write: Instant::now(),
+                })
+            });
+            task(
+                running_clone,
+                paused_clone,
+                status_clone,
+                log_clone,
+                Arc::clone(&stats_clone),
+            );
+            let mut stats = stats_clone.lock().unwrap();
+            stats.finished_at = Some(Instant::now());
+            *finished_run.lock().unwrap() = Some(stats.clone());
+            run_journal().finished(&name);
         });
+        *handle_slot = Some(handle);
+        true
     }
 
+    /// Signal the task to stop and wait (bounded) for its thread to exit
     pub fn stop(&self) {
-        *self.running.lock().unwrap() = false;
-        self.set_status("Stopped");
+        self.stop_because("Stopped");
     }
 
+    /// `stop` that shows `reason` as the status. A worker that wasn't running keeps its
+    /// last status, so stopping everything doesn't overwrite "Finished" on idle tools
+    pub fn stop_because(&self, reason: &str) {
+        let was_running = self.is_running() || !self.is_finished();
+        self.running.store(false, Ordering::Relaxed);
+        self.paused.store(false, Ordering::Relaxed);
+        self.join();
+        if was_running {
+            self.set_status(reason);
+        }
+    }
+
+    /// Hold a running task at its next `checkpoint`
+    pub fn pause(&self) {
+        if self.is_running() && !self.paused.swap(true, Ordering::Relaxed) {
+            self.set_status("Paused");
+        }
+    }
+
+    pub fn resume(&self) {
+        if self.paused.swap(false, Ordering::Relaxed) {
+            self.set_status("Resumed");
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Call between task steps: waits while paused, and returns Break once a stop was requested
+    pub fn checkpoint(running: &AtomicBool, paused: &AtomicBool) -> ControlFlow<()> {
+        while paused.load(Ordering::Relaxed) && running.load(Ordering::Relaxed) {
+            thread::sleep(Self::SLEEP_CHUNK);
+        }
+        JOURNAL_RUN.with(|run| {
+            if let Some(run) = run.borrow_mut().as_mut() {
+                if run.last_write.elapsed() >= JOURNAL_INTERVAL {
+                    run.last_write = Instant::now();
+                    run_journal().checkpoint(&run.name, &run.stats.lock().unwrap());
+                }
+            }
+        });
+        if running.load(Ordering::Relaxed) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    }
+
+    /// Wait up to STOP_TIMEOUT for the thread; if it is stuck, log it and detach
+    fn join(&self) {
+        if !Self::wait_for_exit(&mut self.handle.lock().unwrap()) {
+            self.log_warn(&format!(
+                "Worker thread did not exit within {}s; it will stop after its current step",
+                Self::STOP_TIMEOUT.as_secs()
+            ));
+        }
+    }
+
+    /// Wait (bounded) for the thread in `slot` to exit. The handle is kept while the thread is
+    /// still alive so `is_finished` and `start` keep seeing it.
+    fn wait_for_exit(slot: &mut Option<JoinHandle<()>>) -> bool {
+        let Some(handle) = slot.as_ref() else {
+            return true;
+        };
+        let deadline = Instant::now() + Self::STOP_TIMEOUT;
+        while !handle.is_finished() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        if !handle.is_finished() {
+            return false;
+        }
+        if let Some(handle) = slot.take() {
+            let _ = handle.join();
+        }
+        true
+    }
+
+    /// True while the task is asked to run (false as soon as a stop is requested)
     pub fn is_running(&self) -> bool {
-        *self.running.lock().unwrap()
+        self.running.load(Ordering::Relaxed)
+    }
+
+    /// True once the worker thread has actually exited (or was never started)
+    pub fn is_finished(&self) -> bool {
+        match self.handle.lock().unwrap().as_ref() {
+            Some(handle) => handle.is_finished(),
+            None => true,
+        }
+    }
+
+    /// Sleep in short chunks so a stop request is noticed quickly.
+    /// Returns whether the task should keep running.
+    pub fn sleep_while_running(running: &AtomicBool, ms: u64) -> bool {
+        let deadline = Instant::now() + Duration::from_millis(ms);
+        while running.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            thread::sleep((deadline - now).min(Self::SLEEP_CHUNK));
+        }
+        false
+    }
+
+    /// `sleep_while_running` that also wakes at `deadline`, so a long interval can't carry a
+    /// run past its time limit. Returns whether the task should keep running.
+    pub fn sleep_within_deadline(
+        running: &AtomicBool,
+        ms: u64,
+        deadline: Option<RunDeadline>,
+    ) -> bool {
+        let Some(deadline) = deadline else {
+            return Self::sleep_while_running(running, ms);
+        };
+        let left = deadline.at.saturating_duration_since(Instant::now());
+        let ms_to_deadline = u64::try_from(left.as_millis()).unwrap_or(u64::MAX);
+        Self::sleep_while_running(running, ms.min(ms_to_deadline)) && !deadline.passed()
     }
 
     pub fn get_status(&self) -> String {
-        self.status.lock().unwrap().clone()
+        self.latest_status().clone()
+    }
+
+    // Drain pending status updates from the task, keeping only the newest one
+    fn latest_status(&self) -> MutexGuard<'_, String> {
+        let mut status = self.status.lock().unwrap();
+        if let Some(latest) = self.status_rx.lock().unwrap().try_iter().last() {
+            *status = latest;
+        }
+        status
     }
 
-    pub fn get_log(&self) -> Vec<String> {
-        self.log.lock().unwrap().iter().cloned().collect()
+    /// Record the game client size the run just started at
+    pub fn set_client_size(&self, size: Option<(i32, i32)>) {
+        *self.client_size.lock().unwrap() = size;
     }
 
-    pub fn push_log(log: &Arc<Mutex<VecDeque<String>>>, text: &str) {
-        let mut log = log.lock().unwrap();
-        log.push_back(text.to_string());
-        while log.len() > Self::MAX_LOG_LINES {
-            log.pop_front();
+    /// Client size recorded for the current run (None once it stopped)
+    pub fn client_size(&self) -> Option<(i32, i32)> {
+        if !self.is_running() {
+            return None;
         }
+        *self.client_size.lock().unwrap()
+    }
+
+    pub fn get_stats(&self) -> WorkerStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// Let the next run carry on counting from an unfinished run's journal entry
+    pub fn seed_next_run(&self, entry: JournalEntry) {
+        *self.resume_from.lock().unwrap() = Some(entry);
+    }
+
+    /// Stats of a run that ended since the last call; each run is handed out once
+    pub fn take_finished_run(&self) -> Option<WorkerStats> {
+        self.finished_run.lock().unwrap().take()
+    }
+
+    pub fn get_log(&self) -> Vec<LogEntry> {
+        self.log.lock().unwrap().entries.iter().cloned().collect()
+    }
+
+    /// Info-level shorthand for `push_log_entry`
+    pub fn push_log(log: &SharedLog, text: &str) {
+        push_log_entry(log, LogLevel::Info, text);
+    }
+
+    pub fn log_info(&self, text: &str) {
+        push_log_entry(&self.log, LogLevel::Info, text);
+    }
+
+    pub fn log_warn(&self, text: &str) {
+        push_log_entry(&self.log, LogLevel::Warn, text);
+    }
+
+    pub fn log_error(&self, text: &str) {
+        push_log_entry(&self.log, LogLevel::Error, text);
     }
 
     pub fn set_status(&self, text: &str) {
-        let mut status = self.status.lock().unwrap();
+        let mut status = self.latest_status();
         if status.as_str() == text {
             return;
         }
         *status = text.to_string();
 
-        Self::push_log(&self.log, text);
+        self.log_info(text);
+    }
+
+    /// Update the status without logging it (for values that change every frame)
+    pub fn set_status_quiet(&self, text: &str) {
+        *self.latest_status() = text.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_log_keeps_newest_entries() {
+        let log: SharedLog = Arc::new(Mutex::new(LogBuffer::new("Test")));
+        for i in 0..MAX_LOG_LINES + 50 {
+            push_log_entry(&log, LogLevel::Info, &i.to_string());
+        }
+        push_log_entry(&log, LogLevel::Error, "failed");
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.entries.len(), MAX_LOG_LINES);
+        assert_eq!(log.entries.front().unwrap().text, "51");
+        let last = log.entries.back().unwrap();
+        assert_eq!(last.level, LogLevel::Error);
+        assert_eq!(last.text, "failed");
+    }
+
+    #[test]
+    fn test_sleep_within_deadline_wakes_at_the_deadline() {
+        let running = AtomicBool::new(true);
+        let stats: SharedStats = Arc::new(Mutex::new(WorkerStats {
+            // A 1-minute limit on a run that started 59.9s ago
+            started_at: Instant::now().checked_sub(Duration::from_millis(59_900)),
+            ..WorkerStats::default()
+        }));
+        let deadline = RunDeadline::for_run(&stats, Some(1));
+        assert!(deadline.is_some_and(|deadline| !deadline.passed()));
+
+        let started = Instant::now();
+        assert!(!Worker::sleep_within_deadline(&running, 60_000, deadline));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(deadline.is_some_and(|deadline| deadline.passed()));
+
+        assert_eq!(RunDeadline::for_run(&stats, None), None);
+        assert_eq!(RunDeadline::for_run(&stats, Some(0)), None);
+    }
+
+    #[test]
+    fn test_client_size_is_only_reported_while_running() {
+        let worker = Worker::new("Test");
+        worker.start(
+            |running, _paused, _status, _log, _stats| {
+                while Worker::sleep_while_running(&running, 60_000) {}
+            },
+        );
+        worker.set_client_size(Some((1280, 720)));
+        assert_eq!(worker.client_size(), Some((1280, 720)));
+
+        worker.stop();
+        assert_eq!(worker.client_size(), None);
+    }
+
+    #[test]
+    fn test_stop_joins_worker_thread() {
+        let worker = Worker::new("Test");
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let task_ticks = Arc::clone(&ticks);
+        worker.start(move |running, _paused, _status, _log, _stats| {
+            // A long sleep per iteration, like a large macro delay
+            while Worker::sleep_while_running(&running, 60_000) {
+                task_ticks.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        assert!(worker.is_running());
+        assert!(!worker.is_finished());
+
+        worker.stop();
+        assert!(!worker.is_running());
+        assert!(worker.is_finished());
+        let after_stop = ticks.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(ticks.load(Ordering::SeqCst), after_stop);
+    }
+
+    #[test]
+    fn test_stop_reason_only_replaces_the_status_of_a_run() {
+        let worker = Worker::new("Test");
+        worker.start(|running, _paused, status, _log, _stats| {
+            while Worker::sleep_while_running(&running, 60_000) {}
+            status.set("Stopped");
+        });
+        worker.stop_because("Stopped (emergency hotkey)");
+        assert_eq!(worker.get_status(), "Stopped (emergency hotkey)");
+
+        worker.set_status("Finished");
+        worker.stop_because("Stopped (schedule)");
+        assert_eq!(worker.get_status(), "Finished");
+    }
+
+    #[test]
+    fn test_checkpoint_waits_while_paused() {
+        let worker = Worker::new("Test");
+        let steps = Arc::new(AtomicUsize::new(0));
+        let task_steps = Arc::clone(&steps);
+        worker.start(move |running, paused, _status, _log, _stats| {
+            while Worker::checkpoint(&running, &paused).is_continue() {
+                task_steps.fetch_add(1, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(5));
+            }
+        });
+
+        worker.pause();
+        assert!(worker.is_paused());
+        // Let the task reach its checkpoint, then make sure it stays there
+        thread::sleep(Duration::from_millis(100));
+        let while_paused = steps.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(steps.load(Ordering::SeqCst), while_paused);
+
+        worker.resume();
+        thread::sleep(Duration::from_millis(100));
+        assert!(steps.load(Ordering::SeqCst) > while_paused);
+
+        // Stopping while paused must not leave the task stuck in the checkpoint
+        worker.pause();
+        worker.stop();
+        assert!(worker.is_finished());
+        assert!(!worker.is_paused());
+    }
+
+    #[test]
+    fn test_stats_reset_on_start() {
+        let worker = Worker::new("Test");
+        for _ in 0..2 {
+            worker.start(move |_running, _paused, _status, _log, stats| {
+                let mut stats = stats.lock().unwrap();
+                stats.bump_iteration();
+                stats.add_items(3);
+                stats.set_error("failed");
+            });
+            worker.stop();
+            let stats = worker.get_stats();
+            assert_eq!(stats.iterations, 1);
+            assert_eq!(stats.items_processed, 3);
+            assert_eq!(stats.last_error.as_deref(), Some("failed"));
+            assert!(stats.finished_at.is_some());
+        }
+    }
+
+    #[test]
+    fn test_recorded_error_keeps_its_cause() {
+        let mut stats = WorkerStats::default();
+        stats.record_error(&HelperError::WindowNotFound);
+        assert_eq!(
+            stats.last_error.as_deref(),
+            Some("Error: Game window not found")
+        );
+        assert_eq!(stats.helper_error, Some(HelperError::WindowNotFound));
+
+        // A plain error afterwards has no typed cause
+        stats.set_error("failed");
+        assert_eq!(stats.helper_error, None);
+    }
+
+    #[test]
+    fn test_finished_run_is_handed_out_once() {
+        let worker = Worker::new("Test");
+        assert!(worker.take_finished_run().is_none());
+
+        worker.start(|_running, _paused, _status, _log, stats| {
+            stats.lock().unwrap().add_items(2);
+        });
+        worker.join();
+        let run = worker.take_finished_run().unwrap();
+        assert_eq!(run.items_processed, 2);
+        assert!(run.elapsed().is_some());
+        assert!(worker.take_finished_run().is_none());
+    }
+
+    #[test]
+    fn test_start_refuses_while_running() {
+        let worker = Worker::new("Test");
+        let active = Arc::new(AtomicUsize::new(0));
+        let max_active = Arc::new(AtomicUsize::new(0));
+        let spawn_slow = |worker: &Worker| {
+            let active = Arc::clone(&active);
+            let max_active = Arc::clone(&max_active);
+            worker.start(move |running, _paused, _status, _log, _stats| {
+                let now_active = active.fetch_add(1, Ordering::SeqCst) + 1;
+                max_active.fetch_max(now_active, Ordering::SeqCst);
+                while Worker::sleep_while_running(&running, 1_000) {}
+                // Wind down slowly after the stop signal, like a task finishing its step
+                thread::sleep(Duration::from_millis(100));
+                active.fetch_sub(1, Ordering::SeqCst);
+            })
+        };
+
+        assert!(spawn_slow(&worker));
+        assert!(!spawn_slow(&worker));
+        assert!(!spawn_slow(&worker));
+
+        // Stop request only: the old thread is still winding down when the next start arrives
+        worker.running.store(false, Ordering::Relaxed);
+        assert!(spawn_slow(&worker));
+
+        worker.stop();
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+        assert_eq!(max_active.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_run_journal_keeps_unfinished_runs_only() {
+        let path =
+            std::env::temp_dir().join(format!("cabalhelper_journal_{}.json", std::process::id()));
+        let journal = RunJournal::new(path.clone());
+        let stats = WorkerStats {
+            iterations: 12,
+            ..WorkerStats::default()
+        };
+
+        // Nothing is written before enable
+        journal.started(JournalEntry::new("Reroll Epaulet", &stats));
+        assert!(!path.exists());
+        journal.enable();
+        journal.started(JournalEntry::new("Image Clicker", &stats));
+        journal.checkpoint(
+            "Reroll Epaulet",
+            &WorkerStats {
+                iterations: 327,
+                ..WorkerStats::default()
+            },
+        );
+        journal.finished("Image Clicker");
+
+        let unfinished = journal.unfinished();
+        assert_eq!(unfinished.len(), 1);
+        assert_eq!(unfinished[0].name, "Reroll Epaulet");
+        assert_eq!(unfinished[0].iterations, 327);
+
+        journal.finished("Reroll Epaulet");
+        assert!(!path.exists());
+        assert!(journal.unfinished().is_empty());
+    }
+
+    #[test]
+    fn test_seeded_run_starts_from_the_journal_counters() {
+        let worker = Worker::new("Test");
+        worker.seed_next_run(JournalEntry {
+            name: "Test".to_string(),
+            started_unix_secs: 0,
+            iterations: 327,
+            items_processed: 4,
+            matches: 1,
+        });
+        assert!(worker.start(|running, _paused, _status, _log, stats| {
+            stats.lock().unwrap().bump_iteration();
+            running.store(false, Ordering::Relaxed);
+        }));
+        worker.join();
+        let run = worker.take_finished_run().unwrap();
+        assert_eq!(run.iterations, 328);
+        assert_eq!(run.items_processed, 4);
+
+        // Only the next run is seeded
+        assert!(worker.start(|running, _paused, _status, _log, _stats| {
+            running.store(false, Ordering::Relaxed);
+        }));
+        worker.join();
+        assert_eq!(worker.take_finished_run().unwrap().iterations, 0);
+    }
+
+    #[test]
+    fn test_status_channel_keeps_latest_update() {
+        let worker = Worker::new("Test");
+        assert!(worker.start(|running, _paused, status, _log, _stats| {
+            status.set("Loop 1/3");
+            status.set("Loop 2/3");
+            status.set("Finished");
+            running.store(false, Ordering::Relaxed);
+        }));
+        worker.join();
+        assert_eq!(worker.get_status(), "Finished");
+
+        // Drained updates are gone; a UI-side status replaces the task's last report
+        worker.set_status("Stopped");
+        assert_eq!(worker.get_status(), "Stopped");
+    }
+
+    #[test]
+    fn test_prefixed_status_keeps_the_prefix() {
+        let worker = Worker::new("Test");
+        assert!(worker.start(|running, _paused, status, _log, _stats| {
+            let client = status.prefixed("Client 2/2 - ");
+            client.set("Loop 1/3");
+            running.store(false, Ordering::Relaxed);
+        }));
+        worker.join();
+        assert_eq!(worker.get_status(), "Client 2/2 - Loop 1/3");
     }
 }
@@ -0,0 +1,207 @@
+// --headless: run one custom macro without the window, reporting to the console
+use crate::automation::macro_runner::{MacroOutcome, MacroRun};
+use crate::core::click_guard;
+use crate::core::clock::to_local;
+use crate::core::file_logger::file_logger;
+use crate::core::launch_args::{LaunchArgs, AUTO_START_FAILED_EXIT_CODE};
+use crate::core::window::find_game_window;
+use crate::core::worker::{LogEntry, Worker};
+use crate::settings::AppSettings;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use windows::Win32::Foundation::{BOOL, TRUE};
+use windows::Win32::System::Console::{
+    AttachConsole, SetConsoleCtrlHandler, ATTACH_PARENT_PROCESS,
+};
+
+// Exit codes, for scheduled tasks and wrapper scripts
+// (AUTO_START_FAILED_EXIT_CODE: the macro could not be started at all)
+/// Every iteration ran
+const EXIT_COMPLETED: i32 = 0;
+/// The run ended on an error (OCR engine, capture region, ...)
+const EXIT_FAILED: i32 = 1;
+/// An OCR search matched its target
+const EXIT_MATCH_FOUND: i32 = 3;
+/// `--max-minutes` ran out first
+const EXIT_TIME_LIMIT: i32 = 4;
+/// Stopped with Ctrl+C (or the console closing)
+const EXIT_INTERRUPTED: i32 = 130;
+
+// How often the console output catches up with the worker
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// Set by the console control handler; the main loop turns it into a worker stop
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+unsafe extern "system" fn on_console_ctrl(_ctrl_type: u32) -> BOOL {
+    INTERRUPTED.store(true, Ordering::Relaxed);
+    TRUE
+}
+
+/// Run the macro named by `--run` and return the process exit code
+pub fn run(launch: &LaunchArgs) -> i32 {
+    // The exe is a GUI program: borrow the console it was started from, if any
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+
+    match prepare(launch) {
+        Ok((name, run)) => execute(&name, run, launch.max_minutes),
+        Err(error) => {
+            eprintln!("{}", error);
+            AUTO_START_FAILED_EXIT_CODE
+        }
+    }
+}
+
+/// Settings, profile, game window and macro: everything needed before the run starts
+fn prepare(launch: &LaunchArgs) -> Result<(String, MacroRun), String> {
+    if let Some(error) = &launch.error {
+        return Err(error.clone());
+    }
+    let Some(requested) = launch.run.as_deref() else {
+        return Err("--headless needs --run <macro name>".to_string());
+    };
+
+    let mut settings = AppSettings::load().settings;
+    crate::i18n::set_language(settings.global.language);
+    file_logger().configure(
+        settings.global.log_to_file,
+        settings.global.log_retention_days,
+    );
+    click_guard::configure(settings.global.click_guard_warn_only);
+
+    if let Some(profile) = &launch.profile {
+        let name = settings
+            .profile_names()
+            .into_iter()
+            .find(|name| name.eq_ignore_ascii_case(profile))
+            .ok_or_else(|| format!("--profile: there is no profile named \"{}\"", profile))?;
+        if name != settings.active_profile {
+            settings.switch_profile(&name)?;
+        }
+    }
+
+    let (game_hwnd, title) =
+        find_game_window().ok_or_else(|| "The game window was not found".to_string())?;
+    // Same as connecting in the window: a single matching title pattern picks the profile
+    if launch.profile.is_none() {
+        if let [name] = settings.profiles_matching_title(&title).as_slice() {
+            if *name != settings.active_profile {
+                settings.switch_profile(name)?;
+            }
+        }
+    }
+
+    let named_macro = settings
+        .custom_macros
+        .iter()
+        .find(|named_macro| named_macro.name.eq_ignore_ascii_case(requested))
+        .ok_or_else(|| {
+            format!(
+                "--run: profile \"{}\" has no macro named \"{}\"",
+                settings.active_profile, requested
+            )
+        })?;
+    if named_macro.settings.actions.is_empty() {
+        return Err(format!("--run: \"{}\" has no actions", named_macro.name));
+    }
+
+    println!(
+        "Running \"{}\" (profile \"{}\") on \"{}\"",
+        named_macro.name, settings.active_profile, title
+    );
+    let run = MacroRun {
+        settings: named_macro.settings.clone(),
+        game_hwnd,
+        clients: Vec::new(),
+        rate_limit: settings.global.action_rate_limit,
+        ocr_models: settings.global.ocr_models.clone(),
+        one_shot: None,
+    };
+    Ok((named_macro.name.clone(), run))
+}
+
+fn execute(name: &str, run: MacroRun, max_minutes: Option<u64>) -> i32 {
+    unsafe {
+        let _ = SetConsoleCtrlHandler(Some(on_console_ctrl), TRUE);
+    }
+
+    let worker = Worker::new(name);
+    let outcome: Arc<Mutex<Option<MacroOutcome>>> = Arc::new(Mutex::new(None));
+    let outcome_slot = Arc::clone(&outcome);
+    worker.start(move |running, paused, status, log, stats| {
+        let result = run.run(running, paused, status, log, stats);
+        *outcome_slot.lock().unwrap() = Some(result);
+    });
+
+    let deadline = max_minutes.map(|minutes| Instant::now() + Duration::from_secs(minutes * 60));
+    let mut limit_reached = false;
+    let mut output = ConsoleOutput::default();
+    while !worker.is_finished() {
+        if worker.is_running() && INTERRUPTED.load(Ordering::Relaxed) {
+            println!("Interrupted, stopping...");
+            worker.stop();
+        } else if worker.is_running() && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            println!("Time limit reached, stopping...");
+            limit_reached = true;
+            worker.stop();
+        }
+        output.catch_up(&worker);
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    output.catch_up(&worker);
+    file_logger().flush();
+
+    let outcome = outcome.lock().unwrap().take();
+    match outcome {
+        Some(MacroOutcome::Completed) => EXIT_COMPLETED,
+        Some(MacroOutcome::MatchFound { .. }) => EXIT_MATCH_FOUND,
+        Some(MacroOutcome::Stopped) if limit_reached => EXIT_TIME_LIMIT,
+        Some(MacroOutcome::Stopped) => EXIT_INTERRUPTED,
+        Some(MacroOutcome::Failed(_)) | None => EXIT_FAILED,
+    }
+}
+
+/// Prints status changes and new log entries, in the log file's line format
+#[derive(Default)]
+struct ConsoleOutput {
+    last_status: String,
+    last_entry: Option<SystemTime>,
+}
+
+impl ConsoleOutput {
+    fn catch_up(&mut self, worker: &Worker) {
+        for entry in worker.get_log() {
+            if self.last_entry.is_some_and(|last| entry.time <= last) {
+                continue;
+            }
+            self.last_entry = Some(entry.time);
+            println!("{}", format_line(&entry));
+        }
+
+        let status = worker.get_status();
+        if status != self.last_status {
+            println!("{} STATUS {}", clock(SystemTime::now()), status);
+            self.last_status = status;
+        }
+    }
+}
+
+fn clock(time: SystemTime) -> String {
+    to_local(time)
+        .map(|time| time.clock())
+        .unwrap_or_else(|| "--:--:--".to_string())
+}
+
+fn format_line(entry: &LogEntry) -> String {
+    format!(
+        "{} {:<5} [{}] {}",
+        clock(entry.time),
+        entry.level.label(),
+        entry.source,
+        entry.text
+    )
+}
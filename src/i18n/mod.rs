@@ -0,0 +1,147 @@
+// UI string tables: one embedded JSON file per language, English as the fallback
+use crate::settings::Language;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+/// Look up a UI string in the active language: `tr!("header.connect")` gives a `&'static str`,
+/// `tr!("hotkey.one_shot", name = "Buff")` fills `{name}` and gives a `String`
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::text($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format_text($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+type Table = HashMap<String, String>;
+
+// Index into `Language::ALL`
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+fn source(language: Language) -> &'static str {
+    match language {
+        Language::English => include_str!("en.json"),
+        Language::PortugueseBrazil => include_str!("pt-BR.json"),
+    }
+}
+
+fn table(language: Language) -> &'static Table {
+    static TABLES: OnceLock<Vec<Table>> = OnceLock::new();
+    let tables = TABLES.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|&language| parse(source(language)))
+            .collect()
+    });
+    let idx = Language::ALL
+        .iter()
+        .position(|&l| l == language)
+        .unwrap_or(0);
+    &tables[idx]
+}
+
+/// A broken table only costs its translations: everything falls back to English
+fn parse(json: &str) -> Table {
+    serde_json::from_str(json).unwrap_or_default()
+}
+
+pub fn set_language(language: Language) {
+    let idx = Language::ALL
+        .iter()
+        .position(|&l| l == language)
+        .unwrap_or(0);
+    ACTIVE.store(idx, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL[ACTIVE.load(Ordering::Relaxed)]
+}
+
+/// `key` in the active language, else English, else the key itself (never blank)
+pub fn text(key: &'static str) -> &'static str {
+    lookup(language(), key)
+}
+
+fn lookup(language: Language, key: &'static str) -> &'static str {
+    table(language)
+        .get(key)
+        .or_else(|| table(Language::English).get(key))
+        .map(String::as_str)
+        .filter(|text| !text.is_empty())
+        .unwrap_or(key)
+}
+
+/// `text(key)` with each `{name}` replaced by its value
+pub fn format_text(key: &'static str, args: &[(&str, String)]) -> String {
+    fill(text(key), args)
+}
+
+fn fill(template: &str, args: &[(&str, String)]) -> String {
+    let mut result = template.to_string();
+    for (name, value) in args {
+        result = result.replace(&format!("{{{}}}", name), value);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_parse_and_translations_use_known_keys() {
+        let english = parse(source(Language::English));
+        assert!(!english.is_empty());
+        for language in Language::ALL {
+            let json = source(language);
+            let table: Table = serde_json::from_str(json).expect("valid JSON");
+            for key in table.keys() {
+                assert!(
+                    english.contains_key(key),
+                    "{:?} has unknown key {}",
+                    language,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_translation_keeps_placeholders() {
+        let english = table(Language::English);
+        for language in Language::ALL {
+            for (key, text) in table(language) {
+                for part in english[key].split('{').skip(1) {
+                    let name = part.split('}').next().unwrap_or_default();
+                    assert!(
+                        text.contains(&format!("{{{}}}", name)),
+                        "{:?} {} lacks {{{}}}",
+                        language,
+                        key,
+                        name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_key_falls_back_to_english_then_key() {
+        let key = "header.connect";
+        assert_eq!(lookup(Language::English, key), "Connect");
+        assert!(!lookup(Language::PortugueseBrazil, key).is_empty());
+        assert_eq!(
+            lookup(Language::PortugueseBrazil, "no.such.key"),
+            "no.such.key"
+        );
+    }
+
+    #[test]
+    fn test_fill_replaces_named_placeholders() {
+        let args = [("page", 3.to_string()), ("total", "4".to_string())];
+        assert_eq!(fill("Page {page} of {total}", &args), "Page 3 of 4");
+        assert_eq!(fill("No placeholders", &args), "No placeholders");
+    }
+}
@@ -3,23 +3,37 @@ use windows::Win32::UI::HiDpi::{
     SetProcessDpiAwarenessContext, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2,
 };
 
+#[macro_use]
+mod i18n;
+
 mod app;
 mod automation;
 mod calibration;
 mod core;
+mod headless;
 mod settings;
 mod tools;
 mod ui;
 
+use crate::core::launch_args::{auto_start_failed, LaunchArgs, AUTO_START_FAILED_EXIT_CODE};
 use app::CabalHelperApp;
 use eframe::egui;
 
 fn main() -> Result<(), eframe::Error> {
-    // Enable High DPI Awareness
+    // --overlay / --minimized override the saved start mode for this launch;
+    // --profile / --connect / --start set up an unattended run
+    let launch = LaunchArgs::parse(std::env::args().skip(1));
+
+    // Enable High DPI Awareness (headless runs too, so coordinates match the GUI's)
     unsafe {
         let _ = SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2);
     }
 
+    // --headless --run <macro> runs without any window and exits with the outcome
+    if launch.headless {
+        std::process::exit(headless::run(&launch));
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([760.0, 620.0]) // Increased base height for normal view
@@ -28,9 +42,15 @@ fn main() -> Result<(), eframe::Error> {
         ..Default::default()
     };
 
-    eframe::run_native(
+    let result = eframe::run_native(
         "Cabal Helper",
         options,
-        Box::new(|_cc| Box::new(CabalHelperApp::default())),
-    )
+        Box::new(move |_cc| Box::new(CabalHelperApp::with_launch_args(launch))),
+    );
+
+    // Lets wrapper scripts tell a failed auto-start from a normal exit
+    if auto_start_failed() {
+        std::process::exit(AUTO_START_FAILED_EXIT_CODE);
+    }
+    result
 }
@@ -1,5 +1,10 @@
+use crate::core::paths;
+use crate::core::sounds::{Event, EventSound};
+use crate::core::worker::WorkerStats;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::path::{Path, PathBuf};
 
 pub type NormPoint = (f32, f32);
 pub type NormRect = (f32, f32, f32, f32);
@@ -10,12 +15,136 @@ pub struct AppSettings {
 
     pub accept_item: AcceptItemSettings,
 
+    #[serde(default)]
+    pub anti_afk: AntiAfkSettings,
+
+    #[serde(default)]
+    pub pixel_trigger: PixelTriggerSettings,
+
+    #[serde(default)]
+    pub rebuffer: RebufferSettings,
+
+    #[serde(default)]
+    pub rotation: RotationSettings,
+
+    #[serde(default)]
+    pub auto_relog: AutoRelogSettings,
+
+    #[serde(default)]
+    pub loot_clicker: LootClickerSettings,
+
+    #[serde(default)]
+    pub stat_logger: StatLoggerSettings,
+
     pub custom_macros: Vec<NamedMacro>,
 
-    #[serde(default = "default_emergency_stop_hotkey")]
-    pub emergency_stop_hotkey: HotkeyConfig,
+    // Tools and macros started/stopped at set times (per profile, like the macros it names)
+    #[serde(default)]
+    pub schedule: Vec<ScheduleEntry>,
+
+    // App-wide settings: they stay with the app when the profile changes
+    #[serde(flatten)]
+    pub global: GlobalSettings,
+
+    // Pre-`hotkeys` files stored the emergency stop here; moved into the map on load
+    #[serde(default, rename = "emergency_stop_hotkey", skip_serializing)]
+    legacy_emergency_stop_hotkey: Option<HotkeyConfig>,
+
+    // Profiles: these settings belong to `active_profile`, the rest are stored whole
+    #[serde(default = "default_profile_name")]
+    pub active_profile: String,
+    #[serde(default)]
+    pub other_profiles: Vec<NamedProfile>,
+
+    // Auto-select this profile when the game window title contains it (empty = never)
+    #[serde(default)]
+    pub title_pattern: String,
+}
+
+/// Settings shared by every profile. `switch_profile` moves them across as a whole, and
+/// inactive profiles are saved without them. Flattened, so the file layout is unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlobalSettings {
+    // Global hotkey bindings; actions missing from the map use their default binding
+    pub hotkeys: HashMap<HotkeyActionId, HotkeyConfig>,
+
+    // Ignore the emergency stop while neither the game nor the helper has focus
+    pub emergency_stop_focus_only: bool,
 
     pub always_on_top: bool,
+
+    // Where the overlay toolbar sits relative to the game window
+    pub overlay_position: OverlayPlacement,
+
+    // Overlay ignores the mouse; only the overlay/click-through hotkeys get out of it
+    pub overlay_click_through: bool,
+
+    // Window state at launch (the --overlay / --minimized flags override it once)
+    pub start_mode: StartMode,
+
+    // UI language (strings missing from a translation show in English)
+    pub language: Language,
+
+    // Grid used when Ctrl is held while selecting a calibration area
+    pub calibration_grid_px: i32,
+
+    // Append every worker log line to logs\cabalhelper-<date>.log
+    pub log_to_file: bool,
+    // Daily log files older than this are deleted
+    pub log_retention_days: u32,
+
+    // Safety cap on the clicks and key presses each tool sends
+    pub action_rate_limit: ActionRateLimit,
+
+    // Clicks off the game window are only logged, not skipped
+    pub click_guard_warn_only: bool,
+
+    // OCR model files used instead of the built-in ones
+    pub ocr_models: OcrModelSettings,
+
+    // Stop every tool when the cursor is pushed into a screen corner
+    pub corner_failsafe: CornerFailsafe,
+
+    // Screenshots of the game saved while tools run, to see afterwards what they did
+    pub frame_recorder: FrameRecorderSettings,
+
+    // Localhost HTTP endpoint for checking on and stopping tools from elsewhere
+    pub remote_control: RemoteControlSettings,
+
+    // What each event plays (events not listed are silent)
+    pub event_sounds: BTreeMap<Event, EventSound>,
+
+    // Most recently used template image paths, newest first
+    pub recent_image_paths: Vec<String>,
+
+    // Lifetime totals per tool
+    pub usage_stats: UsageStats,
+}
+
+/// Inactive settings profile (its own `other_profiles` is always empty)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedProfile {
+    pub name: String,
+    #[serde(serialize_with = "serialize_without_global")]
+    pub settings: AppSettings,
+}
+
+/// An inactive profile's settings minus the app-wide ones, which only the active
+/// profile's copy carries (they load back as defaults and are replaced on a switch)
+fn serialize_without_global<S: serde::Serializer>(
+    settings: &AppSettings,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::Error;
+    let mut value = serde_json::to_value(settings).map_err(S::Error::custom)?;
+    let global = serde_json::to_value(&settings.global).map_err(S::Error::custom)?;
+    if let (Some(fields), Some(global)) = (value.as_object_mut(), global.as_object()) {
+        for key in global.keys() {
+            fields.remove(key);
+        }
+    }
+    value.serialize(serializer)
 }
 
 impl Default for AppSettings {
@@ -23,17 +152,603 @@ impl Default for AppSettings {
         Self {
             collection_filler: CollectionFillerSettings::default(),
             accept_item: AcceptItemSettings::default(),
+            anti_afk: AntiAfkSettings::default(),
+            pixel_trigger: PixelTriggerSettings::default(),
+            rebuffer: RebufferSettings::default(),
+            rotation: RotationSettings::default(),
+            auto_relog: AutoRelogSettings::default(),
+            loot_clicker: LootClickerSettings::default(),
+            stat_logger: StatLoggerSettings::default(),
             custom_macros: vec![NamedMacro::default()],
-            emergency_stop_hotkey: default_emergency_stop_hotkey(),
+            schedule: Vec::new(),
+            global: GlobalSettings::default(),
+            legacy_emergency_stop_hotkey: None,
+            active_profile: default_profile_name(),
+            other_profiles: Vec::new(),
+            title_pattern: String::new(),
+        }
+    }
+}
+
+impl Default for GlobalSettings {
+    fn default() -> Self {
+        Self {
+            hotkeys: HashMap::new(),
+            emergency_stop_focus_only: false,
             always_on_top: false,
+            overlay_position: OverlayPlacement::default(),
+            overlay_click_through: false,
+            start_mode: StartMode::default(),
+            language: Language::default(),
+            calibration_grid_px: default_calibration_grid_px(),
+            log_to_file: false,
+            log_retention_days: default_log_retention_days(),
+            action_rate_limit: ActionRateLimit::default(),
+            click_guard_warn_only: false,
+            ocr_models: OcrModelSettings::default(),
+            corner_failsafe: CornerFailsafe::default(),
+            frame_recorder: FrameRecorderSettings::default(),
+            remote_control: RemoteControlSettings::default(),
+            event_sounds: BTreeMap::new(),
+            recent_image_paths: Vec::new(),
+            usage_stats: UsageStats::default(),
+        }
+    }
+}
+
+/// Lifetime totals of one tool, summed over its finished runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ToolUsage {
+    pub runs: u64,
+    pub runtime_secs: u64,
+    pub iterations: u64,
+    // Clicks for the Image Clicker, registered items for the Collection Filler,
+    // keep-alives for the Anti-AFK tool, responses for the Pixel Trigger, rebuffs for the
+    // Rebuffer, key presses for the Skill Rotation, actions for macros
+    pub items: u64,
+    pub runs_with_errors: u64,
+}
+
+/// What `ToolUsage::items` counts for this tool
+pub fn items_label(tool_name: &str) -> &'static str {
+    match tool_name {
+        "Image Clicker" => "Clicks",
+        "Collection Filler" => "Items registered",
+        "Anti-AFK" => "Keep-alives sent",
+        "Pixel Trigger" => "Responses sent",
+        "Rebuffer" => "Rebuffs",
+        "Skill Rotation" => "Keys pressed",
+        "Auto-Relog" => "Reconnects",
+        "Loot Clicker" => "Labels clicked",
+        "Stat Logger" => "Readings",
+        _ => "Actions run",
+    }
+}
+
+/// Lifetime statistics keyed by tool (tab) name
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UsageStats {
+    pub tools: BTreeMap<String, ToolUsage>,
+}
+
+impl UsageStats {
+    /// Add a finished run of `tool`
+    pub fn record_run(&mut self, tool: &str, run: &WorkerStats) {
+        let usage = self.tools.entry(tool.to_string()).or_default();
+        usage.runs += 1;
+        usage.runtime_secs += run.elapsed().map(|d| d.as_secs()).unwrap_or(0);
+        usage.iterations += run.iterations;
+        usage.items += run.items_processed;
+        if run.last_error.is_some() {
+            usage.runs_with_errors += 1;
+        }
+    }
+}
+
+pub const MAX_RECENT_PATHS: usize = 10;
+
+/// Move `path` to the front of the MRU list, keeping at most `MAX_RECENT_PATHS`
+pub fn remember_recent_path(recent_paths: &mut Vec<String>, path: &str) {
+    if path.is_empty() {
+        return;
+    }
+    recent_paths.retain(|p| p != path);
+    recent_paths.insert(0, path.to_string());
+    recent_paths.truncate(MAX_RECENT_PATHS);
+}
+
+fn default_profile_name() -> String {
+    "Default".to_string()
+}
+
+fn default_calibration_grid_px() -> i32 {
+    5
+}
+
+fn default_log_retention_days() -> u32 {
+    14
+}
+
+/// Most clicks / key presses a single tool may send per second
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActionRateLimit {
+    // Off: a 0 ms delay sends as fast as the game accepts
+    pub enabled: bool,
+    pub max_per_second: u32,
+}
+
+impl Default for ActionRateLimit {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_per_second: 30,
+        }
+    }
+}
+
+/// `.rten` model files for OCR; an empty path uses the model built into the exe
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OcrModelSettings {
+    pub detection_model_path: String,
+    pub recognition_model_path: String,
+}
+
+/// Corner of the primary monitor watched by the corner failsafe
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ScreenCorner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl ScreenCorner {
+    pub const ALL: [ScreenCorner; 4] = [
+        ScreenCorner::TopLeft,
+        ScreenCorner::TopRight,
+        ScreenCorner::BottomLeft,
+        ScreenCorner::BottomRight,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ScreenCorner::TopLeft => tr!("corner.top_left"),
+            ScreenCorner::TopRight => tr!("corner.top_right"),
+            ScreenCorner::BottomLeft => tr!("corner.bottom_left"),
+            ScreenCorner::BottomRight => tr!("corner.bottom_right"),
+        }
+    }
+}
+
+/// Downscaled screenshots of the game client, taken every `interval_secs` while any tool
+/// runs, into a folder per session. The oldest are deleted past either cap.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FrameRecorderSettings {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    // Size of the saved frames relative to the client area
+    pub scale_percent: u32,
+    pub max_files: u32,
+    pub max_total_mb: u32,
+}
+
+impl Default for FrameRecorderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 30,
+            scale_percent: 50,
+            max_files: 500,
+            max_total_mb: 200,
+        }
+    }
+}
+
+/// HTTP listener on 127.0.0.1:`port`; every request must carry `?token=<token>`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoteControlSettings {
+    pub enabled: bool,
+    pub port: u16,
+    // The listener stays off while this is empty
+    pub token: String,
+}
+
+impl Default for RemoteControlSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8765,
+            token: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ScheduleAction {
+    Start,
+    Stop,
+}
+
+impl ScheduleAction {
+    pub const ALL: [ScheduleAction; 2] = [ScheduleAction::Start, ScheduleAction::Stop];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScheduleAction::Start => tr!("schedule.start"),
+            ScheduleAction::Stop => tr!("schedule.stop"),
+        }
+    }
+}
+
+/// Start or stop the tool or macro whose tab is called `target` at `hour:minute`
+/// local time on the ticked days
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScheduleEntry {
+    pub enabled: bool,
+    pub target: String,
+    pub hour: u8,
+    pub minute: u8,
+    // Sunday first, like the Windows clock
+    pub days: [bool; 7],
+    pub action: ScheduleAction,
+    // Set when `target` is a macro, so the entry follows it through renames and
+    // doesn't jump to another macro that later takes its name
+    #[serde(default)]
+    pub macro_id: Option<MacroId>,
+}
+
+impl Default for ScheduleEntry {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            target: String::new(),
+            hour: 20,
+            minute: 55,
+            days: [true; 7],
+            action: ScheduleAction::Start,
+            macro_id: None,
+        }
+    }
+}
+
+/// Moving the physical cursor into `corner` stops every tool, like the emergency stop
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CornerFailsafe {
+    pub enabled: bool,
+    pub corner: ScreenCorner,
+    // How close (in pixels) to the corner counts as in it
+    pub threshold_px: i32,
+}
+
+impl Default for CornerFailsafe {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            corner: ScreenCorner::TopLeft,
+            threshold_px: 5,
         }
     }
 }
 
-fn default_emergency_stop_hotkey() -> HotkeyConfig {
-    HotkeyConfig {
-        key: None,
-        modifiers: HotkeyModifiers::default(),
+impl CornerFailsafe {
+    /// Whether the cursor at `pos` is in the corner of a `screen`-sized primary monitor
+    pub fn triggered(&self, pos: (i32, i32), screen: (i32, i32)) -> bool {
+        let (x, y) = pos;
+        let (width, height) = screen;
+        // Ranges rather than comparisons: a monitor left of or above the primary one
+        // has negative coordinates, and its edges must not count
+        let near_left = (0..self.threshold_px).contains(&x);
+        let near_top = (0..self.threshold_px).contains(&y);
+        let near_right = (width - self.threshold_px..width).contains(&x);
+        let near_bottom = (height - self.threshold_px..height).contains(&y);
+        match self.corner {
+            ScreenCorner::TopLeft => near_left && near_top,
+            ScreenCorner::TopRight => near_right && near_top,
+            ScreenCorner::BottomLeft => near_left && near_bottom,
+            ScreenCorner::BottomRight => near_right && near_bottom,
+        }
+    }
+}
+
+/// How the window comes up when the app starts
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum StartMode {
+    #[default]
+    Normal,
+    Overlay,
+    Minimized,
+}
+
+impl StartMode {
+    pub const ALL: [StartMode; 3] = [StartMode::Normal, StartMode::Overlay, StartMode::Minimized];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StartMode::Normal => tr!("start_mode.normal"),
+            StartMode::Overlay => tr!("start_mode.overlay"),
+            StartMode::Minimized => tr!("start_mode.minimized"),
+        }
+    }
+
+    /// Start mode requested on the command line (`--overlay` / `--minimized`), if any
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Option<Self> {
+        args.into_iter().find_map(|arg| match arg.as_str() {
+            "--overlay" => Some(StartMode::Overlay),
+            "--minimized" => Some(StartMode::Minimized),
+            _ => None,
+        })
+    }
+}
+
+/// UI language; serialized as its language tag
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "pt-BR")]
+    PortugueseBrazil,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::PortugueseBrazil];
+
+    /// Name in the language itself, so it can be found whatever language is active
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::PortugueseBrazil => "Português (Brasil)",
+        }
+    }
+}
+
+/// Edge of the game's client area the overlay snaps to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OverlayAnchor {
+    TopCenter,
+    TopLeft,
+    TopRight,
+    BottomCenter,
+}
+
+impl OverlayAnchor {
+    pub const ALL: [OverlayAnchor; 4] = [
+        OverlayAnchor::TopCenter,
+        OverlayAnchor::TopLeft,
+        OverlayAnchor::TopRight,
+        OverlayAnchor::BottomCenter,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OverlayAnchor::TopCenter => tr!("anchor.top_center"),
+            OverlayAnchor::TopLeft => tr!("anchor.top_left"),
+            OverlayAnchor::TopRight => tr!("anchor.top_right"),
+            OverlayAnchor::BottomCenter => tr!("anchor.bottom_center"),
+        }
+    }
+
+    /// The anchor after this one, wrapping around
+    pub fn next(&self) -> Self {
+        let idx = Self::ALL.iter().position(|a| a == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Overlay position: snapped to an anchor, or dragged to an offset from the client area's top-left
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OverlayPlacement {
+    AutoSnap(OverlayAnchor),
+    Manual(i32, i32),
+}
+
+impl Default for OverlayPlacement {
+    fn default() -> Self {
+        OverlayPlacement::AutoSnap(OverlayAnchor::TopCenter)
+    }
+}
+
+impl OverlayPlacement {
+    /// Screen position of the overlay's top-left corner (pixels)
+    /// for a client area (x, y, w, h) and an overlay of (w, h)
+    pub fn screen_position(
+        &self,
+        client_rect: (i32, i32, i32, i32),
+        overlay_size: (i32, i32),
+    ) -> (i32, i32) {
+        let (x, y, w, h) = client_rect;
+        let (ow, oh) = overlay_size;
+        match self {
+            OverlayPlacement::AutoSnap(OverlayAnchor::TopCenter) => (x + (w - ow) / 2, y),
+            OverlayPlacement::AutoSnap(OverlayAnchor::TopLeft) => (x, y),
+            OverlayPlacement::AutoSnap(OverlayAnchor::TopRight) => (x + w - ow, y),
+            OverlayPlacement::AutoSnap(OverlayAnchor::BottomCenter) => {
+                (x + (w - ow) / 2, y + h - oh)
+            }
+            OverlayPlacement::Manual(dx, dy) => (x + dx, y + dy),
+        }
+    }
+}
+
+/// Every action a global hotkey can be bound to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum HotkeyActionId {
+    EmergencyStop,
+    PauseResume,
+    ToggleImageClicker,
+    ToggleCollectionFiller,
+    ToggleOverlay,
+    ToggleClickThrough,
+    // Run one iteration of this macro; bound in its `CustomMacroSettings`
+    OneShotMacro(MacroId),
+}
+
+impl HotkeyActionId {
+    pub const ALL: [HotkeyActionId; 6] = [
+        HotkeyActionId::EmergencyStop,
+        HotkeyActionId::PauseResume,
+        HotkeyActionId::ToggleImageClicker,
+        HotkeyActionId::ToggleCollectionFiller,
+        HotkeyActionId::ToggleOverlay,
+        HotkeyActionId::ToggleClickThrough,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            HotkeyActionId::EmergencyStop => tr!("hotkey.emergency_stop").to_string(),
+            HotkeyActionId::PauseResume => tr!("hotkey.pause_resume").to_string(),
+            HotkeyActionId::ToggleImageClicker => tr!("hotkey.toggle_image_clicker").to_string(),
+            HotkeyActionId::ToggleCollectionFiller => {
+                tr!("hotkey.toggle_collection_filler").to_string()
+            }
+            HotkeyActionId::ToggleOverlay => tr!("hotkey.toggle_overlay").to_string(),
+            HotkeyActionId::ToggleClickThrough => tr!("hotkey.toggle_click_through").to_string(),
+            HotkeyActionId::OneShotMacro(_) => tr!("hotkey.one_shot_missing").to_string(),
+        }
+    }
+
+    /// Binding used until the user sets one
+    pub fn default_binding(&self) -> HotkeyConfig {
+        match self {
+            HotkeyActionId::EmergencyStop
+            | HotkeyActionId::PauseResume
+            | HotkeyActionId::ToggleImageClicker
+            | HotkeyActionId::ToggleCollectionFiller
+            | HotkeyActionId::ToggleClickThrough
+            | HotkeyActionId::OneShotMacro(_) => HotkeyConfig::disabled(),
+            HotkeyActionId::ToggleOverlay => HotkeyConfig {
+                key: Some(HotkeyKey::F12),
+                modifiers: HotkeyModifiers {
+                    ctrl: true,
+                    ..HotkeyModifiers::default()
+                },
+                mouse_button: None,
+            },
+        }
+    }
+}
+
+impl AppSettings {
+    /// Current binding for `action` (its default when never set)
+    pub fn hotkey(&self, action: HotkeyActionId) -> HotkeyConfig {
+        let binding = match action {
+            HotkeyActionId::OneShotMacro(id) => self
+                .macro_by_id(id)
+                .and_then(|named_macro| named_macro.settings.one_shot_hotkey.clone()),
+            _ => self.global.hotkeys.get(&action).cloned(),
+        };
+        binding.unwrap_or_else(|| action.default_binding())
+    }
+
+    /// Name shown for `action` in the Hotkeys section (macros by their name)
+    pub fn hotkey_action_label(&self, action: HotkeyActionId) -> String {
+        match action {
+            HotkeyActionId::OneShotMacro(id) => match self.macro_by_id(id) {
+                Some(named_macro) => tr!("hotkey.one_shot", name = named_macro.name),
+                None => action.label(),
+            },
+            _ => action.label(),
+        }
+    }
+
+    /// Every bindable action with its current binding: the fixed actions, then one per macro
+    pub fn hotkey_bindings(&self) -> Vec<(HotkeyActionId, HotkeyConfig)> {
+        HotkeyActionId::ALL
+            .into_iter()
+            .chain(
+                self.custom_macros
+                    .iter()
+                    .map(|named_macro| HotkeyActionId::OneShotMacro(named_macro.id)),
+            )
+            .map(|action| (action, self.hotkey(action)))
+            .collect()
+    }
+
+    /// Store a new binding for `action`
+    pub fn set_hotkey(&mut self, action: HotkeyActionId, config: HotkeyConfig) {
+        match action {
+            HotkeyActionId::OneShotMacro(id) => {
+                if let Some(named_macro) = self.macro_by_id_mut(id) {
+                    named_macro.settings.one_shot_hotkey = Some(config);
+                }
+            }
+            _ => {
+                self.global.hotkeys.insert(action, config);
+            }
+        }
+    }
+
+    /// Position of the macro with `id` in `custom_macros` (None once it was deleted)
+    pub fn macro_index(&self, id: MacroId) -> Option<usize> {
+        self.custom_macros
+            .iter()
+            .position(|named_macro| named_macro.id == id)
+    }
+
+    pub fn macro_by_id(&self, id: MacroId) -> Option<&NamedMacro> {
+        self.custom_macros
+            .iter()
+            .find(|named_macro| named_macro.id == id)
+    }
+
+    pub fn macro_by_id_mut(&mut self, id: MacroId) -> Option<&mut NamedMacro> {
+        self.custom_macros
+            .iter_mut()
+            .find(|named_macro| named_macro.id == id)
+    }
+
+    /// Point schedule entries at their macro by id: entries naming a macro get its id,
+    /// and entries with an id take the macro's current name
+    pub fn sync_schedule_targets(&mut self) {
+        for entry in &mut self.schedule {
+            match entry.macro_id {
+                Some(id) => {
+                    if let Some(named_macro) = self
+                        .custom_macros
+                        .iter()
+                        .find(|named_macro| named_macro.id == id)
+                    {
+                        if entry.target != named_macro.name {
+                            entry.target = named_macro.name.clone();
+                        }
+                    }
+                }
+                None => {
+                    entry.macro_id = self
+                        .custom_macros
+                        .iter()
+                        .find(|named_macro| named_macro.name.eq_ignore_ascii_case(&entry.target))
+                        .map(|named_macro| named_macro.id);
+                }
+            }
+        }
+    }
+
+    /// Give every macro a unique id (files from before ids load them all unset)
+    fn assign_macro_ids(&mut self) {
+        let mut seen = HashSet::new();
+        for named_macro in &mut self.custom_macros {
+            if named_macro.id.is_unset() || !seen.insert(named_macro.id) {
+                named_macro.id = MacroId::generate();
+                seen.insert(named_macro.id);
+            }
+        }
+    }
+
+    /// Move values from older file layouts into their current place
+    fn migrate_legacy_fields(&mut self) {
+        if let Some(legacy) = self.legacy_emergency_stop_hotkey.take() {
+            self.global
+                .hotkeys
+                .entry(HotkeyActionId::EmergencyStop)
+                .or_insert(legacy);
+        }
+        self.assign_macro_ids();
+        self.sync_schedule_targets();
+        for profile in &mut self.other_profiles {
+            profile.settings.migrate_legacy_fields();
+        }
     }
 }
 
@@ -112,10 +827,35 @@ pub struct HotkeyModifiers {
     pub meta: bool,
 }
 
+/// Mouse side buttons that can trigger a hotkey action instead of a key
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HotkeyMouseButton {
+    XButton1,
+    XButton2,
+}
+
+impl HotkeyMouseButton {
+    pub const ALL: [HotkeyMouseButton; 2] =
+        [HotkeyMouseButton::XButton1, HotkeyMouseButton::XButton2];
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct HotkeyConfig {
     pub key: Option<HotkeyKey>,
     pub modifiers: HotkeyModifiers,
+    // Alternative to `key`: fires on a mouse side button (modifiers are ignored)
+    #[serde(default)]
+    pub mouse_button: Option<HotkeyMouseButton>,
+}
+
+impl HotkeyConfig {
+    pub fn disabled() -> Self {
+        Self {
+            key: None,
+            modifiers: HotkeyModifiers::default(),
+            mouse_button: None,
+        }
+    }
 }
 
 impl Default for HotkeyConfig {
@@ -128,6 +868,7 @@ impl Default for HotkeyConfig {
                 alt: false,
                 meta: false,
             },
+            mouse_button: None,
         }
     }
 }
@@ -159,6 +900,26 @@ pub struct CollectionFillerSettings {
     // Red dot image path
     pub red_dot_path: String,
 
+    // Click/scroll via window messages instead of moving the physical mouse
+    #[serde(default)]
+    pub use_background_clicks: bool,
+
+    // Stop the run if nothing changes for this many seconds (0 = disabled)
+    #[serde(default = "default_watchdog_secs")]
+    pub watchdog_secs: u64,
+
+    // Stop the run after this many minutes (None = no limit)
+    #[serde(default)]
+    pub max_runtime_minutes: Option<u32>,
+
+    // How the item list is scrolled between passes
+    #[serde(default)]
+    pub scroll: ScrollSettings,
+
+    // Find dots by color instead of the red dot template
+    #[serde(default)]
+    pub color_detection: ColorDetectionSettings,
+
     pub show_in_overlay: bool,
 }
 
@@ -180,52 +941,439 @@ impl Default for CollectionFillerSettings {
             min_red: 150,
             red_dominance: 30,
             red_dot_path: "red-dot.png".to_string(),
+            use_background_clicks: false,
+            watchdog_secs: default_watchdog_secs(),
+            max_runtime_minutes: None,
+            scroll: ScrollSettings::default(),
+            color_detection: ColorDetectionSettings::default(),
             show_in_overlay: true,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AcceptItemSettings {
-    pub image_path: String,
-    pub interval_ms: u64,
-    pub tolerance: f32, // Treated as Minimum Confidence (0.0-1.0), default 0.85
-    pub search_region: Option<NormRect>,
-    pub show_in_overlay: bool,
+fn default_watchdog_secs() -> u64 {
+    120
 }
 
-impl Default for AcceptItemSettings {
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ColorDetectionSettings {
+    pub enabled: bool,
+    pub color: [u8; 3],
+    // Allowed difference per RGB channel
+    pub tolerance: u8,
+    // Smaller blobs of the color are ignored (anti-aliasing, particles)
+    pub min_blob_size: u32,
+}
+
+impl Default for ColorDetectionSettings {
     fn default() -> Self {
         Self {
-            image_path: "image.png".to_string(),
-            interval_ms: 100, // Reduced from 1000ms for faster detection
-            tolerance: 0.85,
-            search_region: None,
-            show_in_overlay: true,
+            enabled: false,
+            color: [220, 40, 40],
+            tolerance: 40,
+            min_blob_size: 4,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Copy)]
-pub enum ComparisonMode {
-    Equals,
-    GreaterThanOrEqual,
-    LessThanOrEqual,
+/// Which way the mouse wheel turns. Up rolls the wheel away from the user (a positive
+/// WM_MOUSEWHEEL delta), which moves a list towards its first rows; Down is the opposite.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ScrollDirection {
+    Up,
+    #[default]
+    Down,
 }
 
-impl Default for ComparisonMode {
-    fn default() -> Self {
-        ComparisonMode::GreaterThanOrEqual
+impl ScrollDirection {
+    pub const ALL: [ScrollDirection; 2] = [ScrollDirection::Up, ScrollDirection::Down];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScrollDirection::Up => tr!("scroll.up"),
+            ScrollDirection::Down => tr!("scroll.down"),
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Copy)]
-pub enum OcrDecodeMode {
-    Greedy,
-    BeamSearch,
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScrollSettings {
+    pub direction: ScrollDirection,
+    // Wheel notches per scroll
+    pub ticks: u32,
+    // Wheel delta per notch (Windows uses 120 for one notch)
+    pub delta_per_tick: u32,
 }
 
-impl Default for OcrDecodeMode {
+impl Default for ScrollSettings {
+    fn default() -> Self {
+        Self {
+            direction: ScrollDirection::Down,
+            ticks: 1,
+            delta_per_tick: 120,
+        }
+    }
+}
+
+/// How rustautogui searches for a stored template (it matches in grayscale either way)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TemplateMatchMode {
+    // Splits the template into segments; fast for small templates like the red dot
+    #[default]
+    Segmented,
+    // FFT correlation; better for large templates
+    Fft,
+}
+
+impl TemplateMatchMode {
+    pub const ALL: [TemplateMatchMode; 2] = [TemplateMatchMode::Segmented, TemplateMatchMode::Fft];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TemplateMatchMode::Segmented => tr!("match_mode.segmented"),
+            TemplateMatchMode::Fft => tr!("match_mode.fft"),
+        }
+    }
+
+    /// Name for the log
+    pub fn name(&self) -> &'static str {
+        match self {
+            TemplateMatchMode::Segmented => "Segmented",
+            TemplateMatchMode::Fft => "FFT",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceptItemSettings {
+    pub image_path: String,
+    pub interval_ms: u64,
+    pub tolerance: f32, // Treated as Minimum Confidence (0.0-1.0), default 0.85
+    pub search_region: Option<NormRect>,
+    #[serde(default)]
+    pub match_mode: TemplateMatchMode,
+    // Stop the run after this many minutes (None = no limit)
+    #[serde(default)]
+    pub max_runtime_minutes: Option<u32>,
+    pub show_in_overlay: bool,
+}
+
+impl Default for AcceptItemSettings {
+    fn default() -> Self {
+        Self {
+            image_path: "image.png".to_string(),
+            interval_ms: 100, // Reduced from 1000ms for faster detection
+            tolerance: 0.85,
+            search_region: None,
+            match_mode: TemplateMatchMode::default(),
+            max_runtime_minutes: None,
+            show_in_overlay: true,
+        }
+    }
+}
+
+/// What the Anti-AFK tool sends to keep the character from idling out
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum AntiAfkInput {
+    // WM_KEYDOWN / WM_KEYUP of `AntiAfkSettings::key`
+    #[default]
+    Key,
+    // 1-pixel WM_MOUSEMOVE at `AntiAfkSettings::position`
+    MouseWiggle,
+}
+
+impl AntiAfkInput {
+    pub const ALL: [AntiAfkInput; 2] = [AntiAfkInput::Key, AntiAfkInput::MouseWiggle];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AntiAfkInput::Key => tr!("anti_afk.input_key"),
+            AntiAfkInput::MouseWiggle => tr!("anti_afk.input_mouse"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AntiAfkSettings {
+    // Minutes between keep-alives (the server kicks idle characters after 10)
+    pub interval_minutes: u64,
+    pub input: AntiAfkInput,
+    pub key: HotkeyKey,
+    // Safe spot for the mouse wiggle (nothing clickable underneath)
+    pub position: Option<NormPoint>,
+    pub show_in_overlay: bool,
+}
+
+impl Default for AntiAfkSettings {
+    fn default() -> Self {
+        Self {
+            interval_minutes: 5,
+            input: AntiAfkInput::Key,
+            // Unbound in the game by default, so pressing it does nothing
+            key: HotkeyKey::Insert,
+            position: None,
+            show_in_overlay: true,
+        }
+    }
+}
+
+/// What the Pixel Trigger does when the watched pixel changes color
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum PixelTriggerResponse {
+    // WM_KEYDOWN / WM_KEYUP of `PixelTriggerSettings::key`
+    #[default]
+    Key,
+    // Background click at `PixelTriggerSettings::click_position`
+    Click,
+}
+
+impl PixelTriggerResponse {
+    pub const ALL: [PixelTriggerResponse; 2] =
+        [PixelTriggerResponse::Key, PixelTriggerResponse::Click];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PixelTriggerResponse::Key => tr!("pixel_trigger.response_key"),
+            PixelTriggerResponse::Click => tr!("pixel_trigger.response_click"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PixelTriggerSettings {
+    // Watched pixel (e.g. the HP bar at 40% width) and its color while all is well
+    pub pixel: Option<NormPoint>,
+    pub expected_color: [u8; 3],
+    // Largest per-channel difference still counted as the expected color
+    pub tolerance: u8,
+    pub response: PixelTriggerResponse,
+    pub key: HotkeyKey,
+    pub click_position: Option<NormPoint>,
+    // Pause after a response before watching again (e.g. the potion cooldown)
+    pub cooldown_ms: u64,
+    pub poll_interval_ms: u64,
+    pub show_in_overlay: bool,
+}
+
+impl Default for PixelTriggerSettings {
+    fn default() -> Self {
+        Self {
+            pixel: None,
+            expected_color: [200, 30, 30],
+            tolerance: 40,
+            response: PixelTriggerResponse::Key,
+            key: HotkeyKey::Digit1,
+            click_position: None,
+            cooldown_ms: 2_000,
+            poll_interval_ms: 200,
+            show_in_overlay: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebufferSettings {
+    // Click, PressKey and Delay actions only; run through the custom macro executor
+    pub actions: Vec<MacroAction>,
+    // Minutes between runs (buffs last 30)
+    pub interval_minutes: u64,
+    // Run once right away instead of waiting a full interval first
+    pub run_immediately_on_start: bool,
+    pub show_in_overlay: bool,
+}
+
+impl Default for RebufferSettings {
+    fn default() -> Self {
+        Self {
+            actions: Vec::new(),
+            interval_minutes: 29,
+            run_immediately_on_start: true,
+            show_in_overlay: true,
+        }
+    }
+}
+
+impl MacroAction {
+    /// Whether this action can go in the rebuffer's or the auto-relog's list
+    pub fn is_key_click_or_delay(&self) -> bool {
+        matches!(
+            self,
+            MacroAction::Click { .. } | MacroAction::PressKey { .. } | MacroAction::Delay { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoRelogSettings {
+    // Seconds between searches for the disconnect dialog
+    pub check_interval_secs: u64,
+    // Captured image of the disconnect dialog, searched for in the whole window
+    pub dialog_image_path: String,
+    pub confidence: f32,
+    // Click, PressKey and Delay actions that get back in game (OK, server, channel, Enter)
+    pub actions: Vec<MacroAction>,
+    // Start the tools the disconnect stopped again once the actions are done
+    pub resume_previous_tool: bool,
+    pub show_in_overlay: bool,
+}
+
+impl Default for AutoRelogSettings {
+    fn default() -> Self {
+        Self {
+            check_interval_secs: 10,
+            dialog_image_path: String::new(),
+            confidence: 0.85,
+            actions: Vec::new(),
+            resume_previous_tool: true,
+            show_in_overlay: true,
+        }
+    }
+}
+
+/// One key of the skill rotation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RotationEntry {
+    pub key: HotkeyKey,
+    // The skill's own cooldown, counted from the press
+    pub cooldown_ms: u64,
+    // 1 is pressed first when several keys are ready (ties go by list order)
+    pub priority: u32,
+}
+
+impl Default for RotationEntry {
+    fn default() -> Self {
+        Self {
+            key: HotkeyKey::Digit1,
+            cooldown_ms: 5_000,
+            priority: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationSettings {
+    pub entries: Vec<RotationEntry>,
+    // Shared wait after every press, whichever key it was
+    pub global_gcd_ms: u64,
+    pub show_in_overlay: bool,
+}
+
+impl Default for RotationSettings {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            global_gcd_ms: 500,
+            show_in_overlay: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatLoggerSettings {
+    // Part of the game window holding the counter (Alz, honor points)
+    pub region: Option<NormRect>,
+    pub interval_secs: u64,
+    // Preprocessing of the capture, as for a macro's OCR search
+    pub scale_factor: u32,
+    pub invert_colors: bool,
+    pub grayscale: bool,
+    // Also append each reading to a CSV file in the logs folder
+    pub write_csv: bool,
+    pub show_in_overlay: bool,
+}
+
+impl Default for StatLoggerSettings {
+    fn default() -> Self {
+        Self {
+            region: None,
+            interval_secs: 30,
+            scale_factor: 2,
+            invert_colors: false,
+            grayscale: true,
+            write_csv: false,
+            show_in_overlay: true,
+        }
+    }
+}
+
+/// Text color of one item-name grade on the ground
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LootColor {
+    pub color: [u8; 3],
+    // Allowed difference per RGB channel
+    pub tolerance: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LootClickerSettings {
+    // Part of the game window searched for item names
+    pub region: Option<NormRect>,
+    pub colors: Vec<LootColor>,
+    // Smaller blobs of the colors are ignored (anti-aliasing, particles)
+    pub min_blob_size: u32,
+    // Letters closer than this (client pixels) on one line form one label
+    pub merge_distance_px: u32,
+    // Labels closer than this to one already clicked in the sweep are skipped
+    pub dedupe_radius_px: u32,
+    pub max_clicks_per_sweep: u32,
+    pub click_delay_ms: u64,
+    // Sweep again after `sweep_interval_ms` instead of stopping after one
+    pub repeat: bool,
+    pub sweep_interval_ms: u64,
+    pub show_in_overlay: bool,
+}
+
+impl Default for LootClickerSettings {
+    fn default() -> Self {
+        Self {
+            region: None,
+            colors: vec![
+                // Common (white), rare (blue), epic (purple) item names
+                LootColor {
+                    color: [255, 255, 255],
+                    tolerance: 20,
+                },
+                LootColor {
+                    color: [90, 150, 255],
+                    tolerance: 40,
+                },
+                LootColor {
+                    color: [190, 90, 255],
+                    tolerance: 40,
+                },
+            ],
+            min_blob_size: 2,
+            merge_distance_px: 10,
+            dedupe_radius_px: 24,
+            max_clicks_per_sweep: 10,
+            click_delay_ms: 400,
+            repeat: false,
+            sweep_interval_ms: 5_000,
+            show_in_overlay: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Copy)]
+pub enum ComparisonMode {
+    Equals,
+    GreaterThanOrEqual,
+    LessThanOrEqual,
+}
+
+impl Default for ComparisonMode {
+    fn default() -> Self {
+        ComparisonMode::GreaterThanOrEqual
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Copy)]
+pub enum OcrDecodeMode {
+    Greedy,
+    BeamSearch,
+}
+
+impl Default for OcrDecodeMode {
     fn default() -> Self {
         OcrDecodeMode::Greedy
     }
@@ -252,8 +1400,33 @@ pub struct OcrAltTarget {
     pub delay_ms: u64,
 }
 
+/// Stable identity of a macro: hotkeys, tabs, undo entries and schedule entries refer to
+/// macros by id, so renaming, reordering or deleting one doesn't retarget them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MacroId(u64);
+
+impl MacroId {
+    /// A fresh random id (never 0, which is what macros saved before ids load with)
+    pub fn generate() -> Self {
+        use std::collections::hash_map::RandomState;
+        use std::hash::BuildHasher;
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static GENERATED: AtomicU64 = AtomicU64::new(0);
+        let seed = GENERATED.fetch_add(1, Ordering::Relaxed);
+        let hash = RandomState::new().hash_one(seed);
+        Self(hash.max(1))
+    }
+
+    fn is_unset(self) -> bool {
+        self.0 == 0
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NamedMacro {
+    #[serde(default)]
+    pub id: MacroId,
     pub name: String,
     pub show_in_overlay: bool,
     pub settings: CustomMacroSettings,
@@ -262,6 +1435,7 @@ pub struct NamedMacro {
 impl NamedMacro {
     pub fn new(name: String) -> Self {
         Self {
+            id: MacroId::generate(),
             name,
             show_in_overlay: true,
             settings: CustomMacroSettings::default(),
@@ -284,6 +1458,10 @@ pub enum MacroAction {
         click_method: ClickMethod,
         use_mouse_movement: bool,
     },
+    // WM_KEYDOWN / WM_KEYUP sent to the game window (it does not need focus)
+    PressKey {
+        key: HotkeyKey,
+    },
     TypeText {
         text: String,
     },
@@ -303,6 +1481,13 @@ pub enum MacroAction {
         name_match_mode: OcrNameMatchMode,
         alt_targets: Vec<OcrAltTarget>,
     },
+    // Wait until the image no longer shows (e.g. a dialog closed); fails after the timeout
+    WaitForImageGone {
+        image_path: String,
+        region: Option<NormRect>,
+        min_confidence: f32,
+        timeout_ms: u64,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Copy)]
@@ -336,6 +1521,19 @@ pub struct CustomMacroSettings {
     pub loop_enabled: bool,
     pub infinite_loop: bool,
     pub loop_count: u32,
+    // Hotkey that runs the actions once, ignoring the loop settings
+    #[serde(default)]
+    pub one_shot_hotkey: Option<HotkeyConfig>,
+    // Presses during a one-shot run queue another iteration instead of being ignored
+    #[serde(default)]
+    pub queue_one_shots: bool,
+    // Delays run this many times faster (2.0) or slower (0.5), for trying a macro out
+    #[serde(default = "default_speed_multiplier")]
+    pub speed_multiplier: f32,
+}
+
+fn default_speed_multiplier() -> f32 {
+    1.0
 }
 
 impl Default for CustomMacroSettings {
@@ -345,38 +1543,1672 @@ impl Default for CustomMacroSettings {
             loop_enabled: false,
             infinite_loop: false,
             loop_count: 1,
+            one_shot_hotkey: None,
+            queue_one_shots: false,
+            speed_multiplier: default_speed_multiplier(),
         }
     }
 }
 
 pub const MAX_CUSTOM_MACROS: usize = 10;
 
-impl AppSettings {
-    const SETTINGS_FILE: &'static str = "cabalhelper_settings.json";
+// Where the settings file lived before it moved to %APPDATA% (relative to the cwd)
+const LEGACY_SETTINGS_FILE: &str = "cabalhelper_settings.json";
+
+/// Result of loading the settings file at startup
+pub struct LoadedSettings {
+    pub settings: AppSettings,
+    /// Informational messages for the log (e.g. a migrated file)
+    pub notices: Vec<String>,
+    /// Problem the user must see (e.g. a corrupt file was set aside)
+    pub warning: Option<String>,
+}
+
+/// `settings.json` -> `settings.json.<suffix>`
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn parse_settings_file(path: &Path) -> Option<Result<AppSettings, String>> {
+    let contents = fs::read_to_string(path).ok()?;
+    let parsed = serde_json::from_str::<AppSettings>(&contents).map(|mut settings| {
+        settings.migrate_legacy_fields();
+        settings
+    });
+    Some(parsed.map_err(|e| e.to_string()))
+}
 
+impl AppSettings {
     /// Load settings from file, or create default if doesn't exist
-    pub fn load() -> Self {
-        match fs::read_to_string(Self::SETTINGS_FILE) {
-            Ok(contents) => match serde_json::from_str::<AppSettings>(&contents) {
-                Ok(settings) => settings,
-                Err(_) => Self::default(),
-            },
-            Err(_) => Self::default(),
+    pub fn load() -> LoadedSettings {
+        let path = paths::settings_file();
+        let mut notices = vec![format!(
+            "Settings file: {}{}",
+            path.display(),
+            if paths::is_portable() {
+                " (portable mode)"
+            } else {
+                ""
+            }
+        )];
+
+        // First run after the move: copy the old cwd file over (the original stays put)
+        let legacy = Path::new(LEGACY_SETTINGS_FILE);
+        if path != legacy && !path.exists() && legacy.exists() {
+            let copied = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::copy(legacy, &path));
+            match copied {
+                Ok(_) => notices.push(format!(
+                    "Settings migrated from {} to {}",
+                    legacy.display(),
+                    path.display()
+                )),
+                Err(e) => notices.push(format!("Failed to migrate old settings file: {}", e)),
+            }
+        }
+
+        let mut loaded = Self::load_from(&path);
+        notices.append(&mut loaded.notices);
+        let corrections = loaded.settings.validate_and_clamp();
+        if !corrections.is_empty() {
+            notices.push(format!(
+                "Settings file adjusted ({} correction(s)):",
+                corrections.len()
+            ));
+            notices.extend(corrections.into_iter().map(|c| format!("  {}", c)));
+        }
+        loaded.notices = notices;
+        loaded
+    }
+
+    /// Load from a specific file. A file that fails to parse is renamed to
+    /// `<file>.corrupt-<timestamp>` and the `.bak` copy is used if it is readable
+    fn load_from(path: &Path) -> LoadedSettings {
+        let error = match parse_settings_file(path) {
+            None => {
+                return LoadedSettings {
+                    settings: Self::default(),
+                    notices: Vec::new(),
+                    warning: None,
+                }
+            }
+            Some(Ok(settings)) => {
+                return LoadedSettings {
+                    settings,
+                    notices: Vec::new(),
+                    warning: None,
+                }
+            }
+            Some(Err(e)) => e,
+        };
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let corrupt_path = sibling_path(path, &format!("corrupt-{}", timestamp));
+        let kept_as = match fs::rename(path, &corrupt_path) {
+            Ok(()) => format!("it was kept as {}", corrupt_path.display()),
+            Err(e) => format!("it could not be moved aside ({})", e),
+        };
+
+        let backup_path = sibling_path(path, "bak");
+        let (settings, fallback) = match parse_settings_file(&backup_path) {
+            Some(Ok(settings)) => (settings, "restored the last backup"),
+            _ => (Self::default(), "started with default settings"),
+        };
+
+        LoadedSettings {
+            settings,
+            notices: Vec::new(),
+            warning: Some(format!(
+                "Settings file was unreadable ({}); {}, {}.",
+                error, kept_as, fallback
+            )),
         }
     }
 
     /// Save settings to file (auto-save)
     pub fn save(&self) -> Result<(), String> {
+        self.save_to(&paths::settings_file())
+    }
+
+    /// Write to a temp file and rename it over the target, so a crash mid-write
+    /// never leaves a truncated file. The previous file is kept as `.bak`
+    fn save_to(&self, path: &Path) -> Result<(), String> {
         let json = serde_json::to_string_pretty(self)
             .map_err(|e| format!("Failed to serialize: {}", e))?;
 
-        fs::write(Self::SETTINGS_FILE, json).map_err(|e| format!("Failed to write file: {}", e))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create folder: {}", e))?;
+        }
+
+        let temp_path = sibling_path(path, "tmp");
+        fs::write(&temp_path, json).map_err(|e| format!("Failed to write file: {}", e))?;
+
+        if path.exists() {
+            // Only files written by a successful save (or loaded cleanly) sit at `path`
+            let _ = fs::copy(path, sibling_path(path, "bak"));
+        }
+        fs::rename(&temp_path, path).map_err(|e| format!("Failed to replace file: {}", e))?;
 
         Ok(())
     }
+}
+
+// Minimum time between two debounced writes
+const SAVE_DEBOUNCE: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Debounced saving: changes mark the settings dirty and they are written at
+/// most once per `SAVE_DEBOUNCE`, or immediately through `flush`/`save_now`
+pub struct SettingsSaver {
+    dirty: bool,
+    // JSON of the last written state, to notice edits made through `&mut AppSettings`
+    last_saved: String,
+    last_write: std::time::Instant,
+}
+
+impl SettingsSaver {
+    pub fn new(settings: &AppSettings) -> Self {
+        Self {
+            dirty: false,
+            last_saved: serde_json::to_string(settings).unwrap_or_default(),
+            last_write: std::time::Instant::now(),
+        }
+    }
+
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// True while there are changes that haven't been written yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Mark dirty if the settings differ from what was last written
+    pub fn detect_changes(&mut self, settings: &AppSettings) {
+        if !self.dirty && serde_json::to_string(settings).unwrap_or_default() != self.last_saved {
+            self.dirty = true;
+        }
+    }
+
+    /// Write pending changes once the debounce interval has passed
+    pub fn tick(&mut self, settings: &AppSettings) -> Result<(), String> {
+        if self.dirty && self.last_write.elapsed() >= SAVE_DEBOUNCE {
+            self.save_now(settings)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write pending changes right away (exit, disconnect, profile switch)
+    pub fn flush(&mut self, settings: &AppSettings) -> Result<(), String> {
+        self.detect_changes(settings);
+        if self.dirty {
+            self.save_now(settings)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write unconditionally, bypassing the debounce
+    pub fn save_now(&mut self, settings: &AppSettings) -> Result<(), String> {
+        self.last_write = std::time::Instant::now();
+        settings.save()?;
+        self.last_saved = serde_json::to_string(settings).unwrap_or_default();
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+// Bump when the export file layout changes incompatibly
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Header written at the top of an exported settings file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportMetadata {
+    pub format_version: u32,
+    pub app_version: String,
+    // Seconds since the Unix epoch
+    pub exported_at: u64,
+    // Game client size when exported (calibrations were made at this size)
+    pub client_size: Option<(i32, i32)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SettingsExport {
+    metadata: ExportMetadata,
+    settings: AppSettings,
+}
+
+// Export / import
+impl AppSettings {
+    /// Write all settings (every profile) to `path` with a metadata header
+    pub fn export_to(&self, path: &Path, client_size: Option<(i32, i32)>) -> Result<(), String> {
+        // Exports get shared; the remote control token stays on this PC
+        let mut settings = self.clone();
+        settings.global.remote_control.token.clear();
+        let export = SettingsExport {
+            metadata: ExportMetadata {
+                format_version: EXPORT_FORMAT_VERSION,
+                app_version: env!("CARGO_PKG_VERSION").to_string(),
+                exported_at: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default(),
+                client_size,
+            },
+            settings,
+        };
+        let json = serde_json::to_string_pretty(&export)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write file: {}", e))
+    }
+
+    /// Read an exported file. A plain settings file (no header) is accepted too
+    pub fn read_export(path: &Path) -> Result<(Option<ExportMetadata>, AppSettings), String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let value: serde_json::Value =
+            serde_json::from_str(&contents).map_err(|e| format!("Not a JSON file: {}", e))?;
+
+        if value.get("metadata").is_none() {
+            let mut settings = serde_json::from_value::<AppSettings>(value)
+                .map_err(|e| format!("Not a settings file: {}", e))?;
+            settings.migrate_legacy_fields();
+            return Ok((None, settings));
+        }
+
+        let mut export = serde_json::from_value::<SettingsExport>(value)
+            .map_err(|e| format!("Invalid settings export: {}", e))?;
+        export.settings.migrate_legacy_fields();
+        if export.metadata.format_version > EXPORT_FORMAT_VERSION {
+            return Err(format!(
+                "Export was made by a newer version ({}), please update first",
+                export.metadata.app_version
+            ));
+        }
+        Ok((Some(export.metadata), export.settings))
+    }
+}
+
+// Sane ranges for values that come from the (possibly hand-edited) settings file
+const DELAY_RANGE_MS: (u64, u64) = (1, 60_000);
+const MACRO_DELAY_MAX_MS: u64 = 3_600_000;
+const WATCHDOG_MAX_SECS: u64 = 3_600;
+const TOLERANCE_RANGE: (f32, f32) = (0.01, 0.99);
+const GRID_RANGE_PX: (i32, i32) = (1, 100);
+pub const LOG_RETENTION_RANGE_DAYS: (u32, u32) = (1, 365);
+pub const ACTION_RATE_RANGE: (u32, u32) = (1, 1000);
+pub const FAILSAFE_THRESHOLD_RANGE_PX: (i32, i32) = (1, 100);
+pub const MAX_RUNTIME_RANGE_MIN: (u32, u32) = (1, 10_080);
+pub const SPEED_MULTIPLIER_RANGE: (f32, f32) = (0.25, 4.0);
+pub const ANTI_AFK_INTERVAL_RANGE_MIN: (u64, u64) = (1, 60);
+pub const OCR_SCALE_RANGE: (u32, u32) = (1, 8);
+const BEAM_WIDTH_RANGE: (u32, u32) = (2, 20);
+pub const SCROLL_TICKS_RANGE: (u32, u32) = (1, 20);
+pub const SCROLL_DELTA_RANGE: (u32, u32) = (1, 1200);
+pub const MIN_BLOB_SIZE_RANGE: (u32, u32) = (1, 10_000);
+pub const PIXEL_COOLDOWN_RANGE_MS: (u64, u64) = (0, 600_000);
+pub const PIXEL_POLL_RANGE_MS: (u64, u64) = (20, 10_000);
+pub const REBUFFER_INTERVAL_RANGE_MIN: (u64, u64) = (1, 240);
+pub const ROTATION_COOLDOWN_RANGE_MS: (u64, u64) = (0, 3_600_000);
+pub const ROTATION_GCD_RANGE_MS: (u64, u64) = (50, 60_000);
+pub const ROTATION_PRIORITY_RANGE: (u32, u32) = (1, 99);
+pub const RELOG_INTERVAL_RANGE_SECS: (u64, u64) = (1, 3_600);
+pub const LOOT_MERGE_RANGE_PX: (u32, u32) = (1, 100);
+pub const LOOT_DEDUPE_RANGE_PX: (u32, u32) = (0, 500);
+pub const LOOT_MAX_CLICKS_RANGE: (u32, u32) = (1, 100);
+pub const LOOT_CLICK_DELAY_RANGE_MS: (u64, u64) = (0, 10_000);
+pub const LOOT_SWEEP_INTERVAL_RANGE_MS: (u64, u64) = (100, 600_000);
+pub const STAT_LOG_INTERVAL_RANGE_SECS: (u64, u64) = (1, 3_600);
+pub const FRAME_INTERVAL_RANGE_SECS: (u64, u64) = (1, 3_600);
+pub const FRAME_SCALE_RANGE_PERCENT: (u32, u32) = (10, 100);
+pub const FRAME_MAX_FILES_RANGE: (u32, u32) = (1, 100_000);
+pub const FRAME_MAX_TOTAL_RANGE_MB: (u32, u32) = (1, 100_000);
+// Below 1024 needs admin rights
+pub const REMOTE_PORT_RANGE: (u16, u16) = (1024, 65_535);
+
+/// Clamp `value` into `min..=max` (NaN becomes `min`), noting the change
+fn clamp_value<T: PartialOrd + Copy + std::fmt::Display>(
+    value: &mut T,
+    (min, max): (T, T),
+    name: &str,
+    corrections: &mut Vec<String>,
+) {
+    use std::cmp::Ordering;
+    let clamped = match (value.partial_cmp(&min), value.partial_cmp(&max)) {
+        (Some(Ordering::Less) | None, _) => min,
+        (_, Some(Ordering::Greater)) => max,
+        _ => return,
+    };
+    corrections.push(format!("{}: {} -> {}", name, value, clamped));
+    *value = clamped;
+}
+
+/// Drop a normalized point outside the client area
+fn check_point(point: &mut Option<NormPoint>, name: &str, corrections: &mut Vec<String>) {
+    if let Some((x, y)) = *point {
+        if !((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y)) {
+            corrections.push(format!("{}: invalid point ({}, {}) removed", name, x, y));
+            *point = None;
+        }
+    }
+}
+
+/// Keep only the click, key press and delay actions of a tool's list and check those
+fn check_input_actions(actions: &mut Vec<MacroAction>, name: &str, corrections: &mut Vec<String>) {
+    let before = actions.len();
+    actions.retain(MacroAction::is_key_click_or_delay);
+    if actions.len() < before {
+        corrections.push(format!(
+            "{}: {} action(s) other than click, key press or delay removed",
+            name,
+            before - actions.len()
+        ));
+    }
+    for (idx, action) in actions.iter_mut().enumerate() {
+        action.validate(&format!("{} action #{}", name, idx + 1), corrections);
+    }
+}
+
+/// Drop a normalized rectangle with no size or that leaves the client area
+fn check_rect(rect: &mut Option<NormRect>, name: &str, corrections: &mut Vec<String>) {
+    const EPSILON: f32 = 0.001;
+    if let Some((x, y, w, h)) = *rect {
+        let valid = (0.0..=1.0).contains(&x)
+            && (0.0..=1.0).contains(&y)
+            && w > 0.0
+            && h > 0.0
+            && x + w <= 1.0 + EPSILON
+            && y + h <= 1.0 + EPSILON;
+        if !valid {
+            corrections.push(format!(
+                "{}: invalid region ({}, {}, {}x{}) removed",
+                name, x, y, w, h
+            ));
+            *rect = None;
+        }
+    }
+}
+
+fn check_path(path: &mut String, default: String, name: &str, corrections: &mut Vec<String>) {
+    if path.trim().is_empty() {
+        corrections.push(format!("{}: empty path -> {}", name, default));
+        *path = default;
+    }
+}
+
+// Validation
+impl AppSettings {
+    /// Clamp numbers to sane ranges and drop malformed regions/points, in every profile
+    /// Returns one line per correction (empty when the settings were already fine)
+    pub fn validate_and_clamp(&mut self) -> Vec<String> {
+        let mut corrections = Vec::new();
+        self.validate_profile("", &mut corrections);
+
+        clamp_value(
+            &mut self.global.calibration_grid_px,
+            GRID_RANGE_PX,
+            "Calibration grid (px)",
+            &mut corrections,
+        );
+        clamp_value(
+            &mut self.global.log_retention_days,
+            LOG_RETENTION_RANGE_DAYS,
+            "Log retention (days)",
+            &mut corrections,
+        );
+        clamp_value(
+            &mut self.global.action_rate_limit.max_per_second,
+            ACTION_RATE_RANGE,
+            "Max actions per second",
+            &mut corrections,
+        );
+        clamp_value(
+            &mut self.global.corner_failsafe.threshold_px,
+            FAILSAFE_THRESHOLD_RANGE_PX,
+            "Corner failsafe distance (px)",
+            &mut corrections,
+        );
+        let recorder = &mut self.global.frame_recorder;
+        clamp_value(
+            &mut recorder.interval_secs,
+            FRAME_INTERVAL_RANGE_SECS,
+            "Frame recorder interval (s)",
+            &mut corrections,
+        );
+        clamp_value(
+            &mut recorder.scale_percent,
+            FRAME_SCALE_RANGE_PERCENT,
+            "Frame recorder scale (%)",
+            &mut corrections,
+        );
+        clamp_value(
+            &mut recorder.max_files,
+            FRAME_MAX_FILES_RANGE,
+            "Frame recorder file cap",
+            &mut corrections,
+        );
+        clamp_value(
+            &mut recorder.max_total_mb,
+            FRAME_MAX_TOTAL_RANGE_MB,
+            "Frame recorder size cap (MB)",
+            &mut corrections,
+        );
+        clamp_value(
+            &mut self.global.remote_control.port,
+            REMOTE_PORT_RANGE,
+            "Remote control port",
+            &mut corrections,
+        );
+
+        for profile in &mut self.other_profiles {
+            let prefix = format!("[{}] ", profile.name);
+            profile.settings.validate_profile(&prefix, &mut corrections);
+            if !profile.settings.other_profiles.is_empty() {
+                corrections.push(format!("{}nested profiles removed", prefix));
+                profile.settings.other_profiles.clear();
+            }
+        }
+        corrections
+    }
+
+    /// Checks for the per-profile (tool) settings
+    fn validate_profile(&mut self, prefix: &str, corrections: &mut Vec<String>) {
+        let defaults = AppSettings::default();
+
+        let filler = &mut self.collection_filler;
+        let name = |field: &str| format!("{}Collection Filler {}", prefix, field);
+        clamp_value(
+            &mut filler.delay_ms,
+            DELAY_RANGE_MS,
+            &name("delay (ms)"),
+            corrections,
+        );
+        clamp_value(
+            &mut filler.red_dot_tolerance,
+            TOLERANCE_RANGE,
+            &name("red dot tolerance"),
+            corrections,
+        );
+        clamp_value(
+            &mut filler.watchdog_secs,
+            (0, WATCHDOG_MAX_SECS),
+            &name("watchdog (s)"),
+            corrections,
+        );
+        if let Some(minutes) = &mut filler.max_runtime_minutes {
+            clamp_value(
+                minutes,
+                MAX_RUNTIME_RANGE_MIN,
+                &name("max runtime (min)"),
+                corrections,
+            );
+        }
+        clamp_value(
+            &mut filler.scroll.ticks,
+            SCROLL_TICKS_RANGE,
+            &name("scroll ticks"),
+            corrections,
+        );
+        clamp_value(
+            &mut filler.scroll.delta_per_tick,
+            SCROLL_DELTA_RANGE,
+            &name("scroll delta"),
+            corrections,
+        );
+        clamp_value(
+            &mut filler.color_detection.min_blob_size,
+            MIN_BLOB_SIZE_RANGE,
+            &name("minimum blob size"),
+            corrections,
+        );
+        check_path(
+            &mut filler.red_dot_path,
+            defaults.collection_filler.red_dot_path.clone(),
+            &name("red dot image"),
+            corrections,
+        );
+        check_rect(
+            &mut filler.collection_tabs_area,
+            &name("tabs area"),
+            corrections,
+        );
+        check_rect(
+            &mut filler.dungeon_list_area,
+            &name("dungeon list area"),
+            corrections,
+        );
+        check_rect(
+            &mut filler.collection_items_area,
+            &name("items area"),
+            corrections,
+        );
+        check_point(
+            &mut filler.auto_refill_pos,
+            &name("auto refill"),
+            corrections,
+        );
+        check_point(&mut filler.register_pos, &name("register"), corrections);
+        check_point(&mut filler.yes_pos, &name("yes"), corrections);
+        check_point(&mut filler.page_2_pos, &name("page 2"), corrections);
+        check_point(&mut filler.page_3_pos, &name("page 3"), corrections);
+        check_point(&mut filler.page_4_pos, &name("page 4"), corrections);
+        check_point(
+            &mut filler.arrow_right_pos,
+            &name("arrow right"),
+            corrections,
+        );
+
+        let accept = &mut self.accept_item;
+        let name = |field: &str| format!("{}Image Clicker {}", prefix, field);
+        clamp_value(
+            &mut accept.interval_ms,
+            DELAY_RANGE_MS,
+            &name("interval (ms)"),
+            corrections,
+        );
+        clamp_value(
+            &mut accept.tolerance,
+            TOLERANCE_RANGE,
+            &name("confidence"),
+            corrections,
+        );
+        if let Some(minutes) = &mut accept.max_runtime_minutes {
+            clamp_value(
+                minutes,
+                MAX_RUNTIME_RANGE_MIN,
+                &name("max runtime (min)"),
+                corrections,
+            );
+        }
+        check_path(
+            &mut accept.image_path,
+            defaults.accept_item.image_path.clone(),
+            &name("image"),
+            corrections,
+        );
+        check_rect(
+            &mut accept.search_region,
+            &name("search region"),
+            corrections,
+        );
+
+        let anti_afk = &mut self.anti_afk;
+        let name = |field: &str| format!("{}Anti-AFK {}", prefix, field);
+        clamp_value(
+            &mut anti_afk.interval_minutes,
+            ANTI_AFK_INTERVAL_RANGE_MIN,
+            &name("interval (min)"),
+            corrections,
+        );
+        check_point(&mut anti_afk.position, &name("position"), corrections);
+
+        let pixel_trigger = &mut self.pixel_trigger;
+        let name = |field: &str| format!("{}Pixel Trigger {}", prefix, field);
+        clamp_value(
+            &mut pixel_trigger.cooldown_ms,
+            PIXEL_COOLDOWN_RANGE_MS,
+            &name("cooldown (ms)"),
+            corrections,
+        );
+        clamp_value(
+            &mut pixel_trigger.poll_interval_ms,
+            PIXEL_POLL_RANGE_MS,
+            &name("poll interval (ms)"),
+            corrections,
+        );
+        check_point(&mut pixel_trigger.pixel, &name("pixel"), corrections);
+        check_point(
+            &mut pixel_trigger.click_position,
+            &name("click position"),
+            corrections,
+        );
+
+        let rebuffer = &mut self.rebuffer;
+        clamp_value(
+            &mut rebuffer.interval_minutes,
+            REBUFFER_INTERVAL_RANGE_MIN,
+            &format!("{}Rebuffer interval (min)", prefix),
+            corrections,
+        );
+        check_input_actions(
+            &mut rebuffer.actions,
+            &format!("{}Rebuffer", prefix),
+            corrections,
+        );
+
+        let auto_relog = &mut self.auto_relog;
+        clamp_value(
+            &mut auto_relog.check_interval_secs,
+            RELOG_INTERVAL_RANGE_SECS,
+            &format!("{}Auto-Relog check interval (s)", prefix),
+            corrections,
+        );
+        clamp_value(
+            &mut auto_relog.confidence,
+            TOLERANCE_RANGE,
+            &format!("{}Auto-Relog confidence", prefix),
+            corrections,
+        );
+        check_input_actions(
+            &mut auto_relog.actions,
+            &format!("{}Auto-Relog", prefix),
+            corrections,
+        );
+
+        let loot = &mut self.loot_clicker;
+        let name = |field: &str| format!("{}Loot Clicker {}", prefix, field);
+        check_rect(&mut loot.region, &name("region"), corrections);
+        clamp_value(
+            &mut loot.min_blob_size,
+            MIN_BLOB_SIZE_RANGE,
+            &name("minimum blob size"),
+            corrections,
+        );
+        clamp_value(
+            &mut loot.merge_distance_px,
+            LOOT_MERGE_RANGE_PX,
+            &name("letter distance (px)"),
+            corrections,
+        );
+        clamp_value(
+            &mut loot.dedupe_radius_px,
+            LOOT_DEDUPE_RANGE_PX,
+            &name("dedupe radius (px)"),
+            corrections,
+        );
+        clamp_value(
+            &mut loot.max_clicks_per_sweep,
+            LOOT_MAX_CLICKS_RANGE,
+            &name("clicks per sweep"),
+            corrections,
+        );
+        clamp_value(
+            &mut loot.click_delay_ms,
+            LOOT_CLICK_DELAY_RANGE_MS,
+            &name("click delay (ms)"),
+            corrections,
+        );
+        clamp_value(
+            &mut loot.sweep_interval_ms,
+            LOOT_SWEEP_INTERVAL_RANGE_MS,
+            &name("sweep interval (ms)"),
+            corrections,
+        );
+
+        let stat_logger = &mut self.stat_logger;
+        let name = |field: &str| format!("{}Stat Logger {}", prefix, field);
+        check_rect(&mut stat_logger.region, &name("region"), corrections);
+        clamp_value(
+            &mut stat_logger.interval_secs,
+            STAT_LOG_INTERVAL_RANGE_SECS,
+            &name("interval (s)"),
+            corrections,
+        );
+        clamp_value(
+            &mut stat_logger.scale_factor,
+            OCR_SCALE_RANGE,
+            &name("scale"),
+            corrections,
+        );
+
+        let rotation = &mut self.rotation;
+        clamp_value(
+            &mut rotation.global_gcd_ms,
+            ROTATION_GCD_RANGE_MS,
+            &format!("{}Skill Rotation global cooldown (ms)", prefix),
+            corrections,
+        );
+        for (idx, entry) in rotation.entries.iter_mut().enumerate() {
+            let name = |field: &str| format!("{}Skill Rotation key #{} {}", prefix, idx + 1, field);
+            clamp_value(
+                &mut entry.cooldown_ms,
+                ROTATION_COOLDOWN_RANGE_MS,
+                &name("cooldown (ms)"),
+                corrections,
+            );
+            clamp_value(
+                &mut entry.priority,
+                ROTATION_PRIORITY_RANGE,
+                &name("priority"),
+                corrections,
+            );
+        }
+
+        for (idx, entry) in self.schedule.iter_mut().enumerate() {
+            let name = |field: &str| format!("{}Schedule entry #{} {}", prefix, idx + 1, field);
+            clamp_value(&mut entry.hour, (0, 23), &name("hour"), corrections);
+            clamp_value(&mut entry.minute, (0, 59), &name("minute"), corrections);
+        }
+
+        if self.custom_macros.is_empty() {
+            corrections.push(format!("{}no macros, added a default one", prefix));
+            self.custom_macros.push(NamedMacro::default());
+        }
+        for (idx, named_macro) in self.custom_macros.iter_mut().enumerate() {
+            if named_macro.name.trim().is_empty() {
+                named_macro.name = format!("Macro {}", idx + 1);
+                corrections.push(format!(
+                    "{}macro #{} had no name, renamed to \"{}\"",
+                    prefix,
+                    idx + 1,
+                    named_macro.name
+                ));
+            }
+            let macro_name = format!("{}Macro \"{}\"", prefix, named_macro.name);
+            named_macro.settings.validate(&macro_name, corrections);
+        }
+    }
+}
+
+impl CustomMacroSettings {
+    fn validate(&mut self, macro_name: &str, corrections: &mut Vec<String>) {
+        clamp_value(
+            &mut self.loop_count,
+            (1, u32::MAX),
+            &format!("{} loop count", macro_name),
+            corrections,
+        );
+        clamp_value(
+            &mut self.speed_multiplier,
+            SPEED_MULTIPLIER_RANGE,
+            &format!("{} speed", macro_name),
+            corrections,
+        );
+
+        for (idx, action) in self.actions.iter_mut().enumerate() {
+            action.validate(&format!("{} action #{}", macro_name, idx + 1), corrections);
+        }
+    }
+}
+
+impl MacroAction {
+    fn validate(&mut self, action_name: &str, corrections: &mut Vec<String>) {
+        let name = |field: &str| format!("{} {}", action_name, field);
+        match self {
+            MacroAction::Click { coordinate, .. } => {
+                check_point(coordinate, &name("click position"), corrections);
+            }
+            MacroAction::Delay { milliseconds } => {
+                clamp_value(
+                    milliseconds,
+                    (0, MACRO_DELAY_MAX_MS),
+                    &name("delay (ms)"),
+                    corrections,
+                );
+            }
+            MacroAction::OcrSearch {
+                ocr_region,
+                scale_factor,
+                beam_width,
+                alt_targets,
+                ..
+            } => {
+                check_rect(ocr_region, &name("OCR region"), corrections);
+                clamp_value(scale_factor, OCR_SCALE_RANGE, &name("scale"), corrections);
+                clamp_value(
+                    beam_width,
+                    BEAM_WIDTH_RANGE,
+                    &name("beam width"),
+                    corrections,
+                );
+                for alt in alt_targets.iter_mut() {
+                    clamp_value(
+                        &mut alt.delay_ms,
+                        (0, MACRO_DELAY_MAX_MS),
+                        &name("alt target delay (ms)"),
+                        corrections,
+                    );
+                }
+            }
+            MacroAction::WaitForImageGone {
+                region,
+                min_confidence,
+                timeout_ms,
+                ..
+            } => {
+                check_rect(region, &name("image region"), corrections);
+                clamp_value(
+                    min_confidence,
+                    TOLERANCE_RANGE,
+                    &name("confidence"),
+                    corrections,
+                );
+                clamp_value(
+                    timeout_ms,
+                    (0, MACRO_DELAY_MAX_MS),
+                    &name("timeout (ms)"),
+                    corrections,
+                );
+            }
+            MacroAction::PressKey { .. } | MacroAction::TypeText { .. } => {}
+        }
+    }
+}
+
+// Profile management
+impl AppSettings {
+    /// All profile names, sorted
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = std::iter::once(self.active_profile.clone())
+            .chain(self.other_profiles.iter().map(|p| p.name.clone()))
+            .collect();
+        names.sort_by_key(|name| name.to_lowercase());
+        names
+    }
+
+    fn validate_new_profile_name(&self, name: &str) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Profile name cannot be empty".to_string());
+        }
+        if self
+            .profile_names()
+            .iter()
+            .any(|n| n.eq_ignore_ascii_case(name))
+        {
+            return Err(format!("Profile \"{}\" already exists", name));
+        }
+        Ok(name.to_string())
+    }
+
+    /// Make `name` the active profile, storing the current one
+    /// The app-wide `global` settings carry over
+    pub fn switch_profile(&mut self, name: &str) -> Result<(), String> {
+        let idx = self
+            .other_profiles
+            .iter()
+            .position(|p| p.name == name)
+            .ok_or_else(|| format!("Profile \"{}\" not found", name))?;
+
+        let target = self.other_profiles.remove(idx);
+        let others = std::mem::take(&mut self.other_profiles);
+        let mut previous = std::mem::replace(self, target.settings);
+        let previous_name = std::mem::replace(&mut self.active_profile, target.name);
+
+        self.global = std::mem::take(&mut previous.global);
+
+        previous.active_profile = previous_name.clone();
+        self.other_profiles = others;
+        self.other_profiles.push(NamedProfile {
+            name: previous_name,
+            settings: previous,
+        });
+        Ok(())
+    }
+
+    /// Create a profile with default tool settings and switch to it
+    pub fn new_profile(&mut self, name: &str) -> Result<(), String> {
+        let name = self.validate_new_profile_name(name)?;
+        let mut settings = AppSettings::default();
+        settings.active_profile = name.clone();
+        self.other_profiles.push(NamedProfile {
+            name: name.clone(),
+            settings,
+        });
+        self.switch_profile(&name)
+    }
+
+    /// Copy the active profile under a new name and switch to the copy
+    pub fn clone_profile(&mut self, name: &str) -> Result<(), String> {
+        let name = self.validate_new_profile_name(name)?;
+        let mut settings = self.clone();
+        settings.other_profiles.clear();
+        // Two profiles with one pattern would always be ambiguous
+        settings.title_pattern.clear();
+        settings.active_profile = name.clone();
+        self.other_profiles.push(NamedProfile {
+            name: name.clone(),
+            settings,
+        });
+        self.switch_profile(&name)
+    }
+
+    /// Profiles whose title pattern appears in `title` (case-insensitive)
+    pub fn profiles_matching_title(&self, title: &str) -> Vec<String> {
+        let title = title.to_lowercase();
+        std::iter::once((&self.active_profile, &self.title_pattern))
+            .chain(
+                self.other_profiles
+                    .iter()
+                    .map(|p| (&p.name, &p.settings.title_pattern)),
+            )
+            .filter(|(_, pattern)| {
+                let pattern = pattern.trim();
+                !pattern.is_empty() && title.contains(&pattern.to_lowercase())
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    pub fn rename_active_profile(&mut self, name: &str) -> Result<(), String> {
+        let name = self.validate_new_profile_name(name)?;
+        self.active_profile = name;
+        Ok(())
+    }
+
+    /// Delete the active profile and switch to the first remaining one
+    pub fn delete_active_profile(&mut self) -> Result<(), String> {
+        let next = self
+            .profile_names()
+            .into_iter()
+            .find(|name| name != &self.active_profile)
+            .ok_or_else(|| "Cannot delete the only profile".to_string())?;
+        let deleted = self.active_profile.clone();
+        self.switch_profile(&next)?;
+        self.other_profiles.retain(|p| p.name != deleted);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_settings_load_as_default_profile() {
+        let json = serde_json::to_string(&AppSettings::default()).unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let map = value.as_object_mut().unwrap();
+        map.remove("active_profile");
+        map.remove("other_profiles");
+
+        let settings: AppSettings = serde_json::from_value(value).unwrap();
+        assert_eq!(settings.active_profile, "Default");
+        assert!(settings.other_profiles.is_empty());
+    }
+
+    #[test]
+    fn test_switch_profile_keeps_each_profiles_settings() {
+        let mut settings = AppSettings::default();
+        settings.collection_filler.delay_ms = 111;
+        settings.new_profile("Alt").unwrap();
+        assert_eq!(settings.active_profile, "Alt");
+        assert_eq!(
+            settings.collection_filler.delay_ms,
+            CollectionFillerSettings::default().delay_ms
+        );
+
+        settings.collection_filler.delay_ms = 222;
+        settings.switch_profile("Default").unwrap();
+        assert_eq!(settings.collection_filler.delay_ms, 111);
+        settings.switch_profile("Alt").unwrap();
+        assert_eq!(settings.collection_filler.delay_ms, 222);
+        assert_eq!(settings.profile_names(), vec!["Alt", "Default"]);
+    }
+
+    #[test]
+    fn test_global_settings_follow_the_active_profile() {
+        let mut settings = AppSettings::default();
+        settings.global.always_on_top = true;
+        settings.global.calibration_grid_px = 9;
+        settings.new_profile("Alt").unwrap();
+        assert!(settings.global.always_on_top);
+        assert_eq!(settings.global.calibration_grid_px, 9);
+
+        // Inactive profiles are saved without an app-wide copy of their own
+        let json = serde_json::to_value(&settings).unwrap();
+        let stored = json["other_profiles"][0]["settings"].as_object().unwrap();
+        assert!(!stored.contains_key("always_on_top"));
+        assert!(!stored.contains_key("hotkeys"));
+        assert!(stored.contains_key("collection_filler"));
+        assert_eq!(json["calibration_grid_px"], 9);
+
+        let mut loaded: AppSettings = serde_json::from_value(json).unwrap();
+        loaded.switch_profile("Default").unwrap();
+        assert!(loaded.global.always_on_top);
+        assert_eq!(loaded.global.calibration_grid_px, 9);
+    }
+
+    fn temp_settings_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("cabalhelper_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn dir_entries(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_save_keeps_backup_and_no_temp_file() {
+        let dir = temp_settings_dir("save");
+        let path = dir.join("settings.json");
+
+        let mut settings = AppSettings::default();
+        settings.collection_filler.delay_ms = 111;
+        settings.save_to(&path).unwrap();
+        settings.collection_filler.delay_ms = 222;
+        settings.save_to(&path).unwrap();
+
+        assert_eq!(
+            dir_entries(&dir),
+            vec!["settings.json", "settings.json.bak"]
+        );
+        let backup = parse_settings_file(&sibling_path(&path, "bak"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(backup.collection_filler.delay_ms, 111);
+        let loaded = AppSettings::load_from(&path);
+        assert_eq!(loaded.settings.collection_filler.delay_ms, 222);
+        assert!(loaded.warning.is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_corrupt_file_is_moved_aside_and_backup_restored() {
+        let dir = temp_settings_dir("corrupt");
+        let path = dir.join("settings.json");
+
+        let mut settings = AppSettings::default();
+        settings.collection_filler.delay_ms = 333;
+        settings.save_to(&path).unwrap();
+        settings.save_to(&path).unwrap();
+        fs::write(&path, "{ \"collection_filler\": ").unwrap();
+
+        let loaded = AppSettings::load_from(&path);
+        assert!(loaded.warning.is_some());
+        assert_eq!(loaded.settings.collection_filler.delay_ms, 333);
+        assert!(!path.exists());
+
+        let entries = dir_entries(&dir);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], "settings.json.bak");
+        assert!(entries[1].starts_with("settings.json.corrupt-"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_corrupt_file_without_backup_uses_defaults() {
+        let dir = temp_settings_dir("corrupt_no_backup");
+        let path = dir.join("settings.json");
+        fs::write(&path, "not json").unwrap();
+
+        let loaded = AppSettings::load_from(&path);
+        assert!(loaded.warning.is_some());
+        assert_eq!(
+            loaded.settings.collection_filler.delay_ms,
+            CollectionFillerSettings::default().delay_ms
+        );
+        assert!(!path.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_export_round_trip_and_plain_file_import() {
+        let dir = temp_settings_dir("export");
+        let export_path = dir.join("export.json");
+
+        let mut settings = AppSettings::default();
+        settings.collection_filler.delay_ms = 444;
+        settings.export_to(&export_path, Some((1024, 768))).unwrap();
+        let (metadata, imported) = AppSettings::read_export(&export_path).unwrap();
+        let metadata = metadata.unwrap();
+        assert_eq!(metadata.format_version, EXPORT_FORMAT_VERSION);
+        assert_eq!(metadata.client_size, Some((1024, 768)));
+        assert_eq!(imported.collection_filler.delay_ms, 444);
+
+        let plain_path = dir.join("settings.json");
+        settings.save_to(&plain_path).unwrap();
+        let (metadata, imported) = AppSettings::read_export(&plain_path).unwrap();
+        assert!(metadata.is_none());
+        assert_eq!(imported.collection_filler.delay_ms, 444);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_import_rejects_newer_format_and_garbage() {
+        let dir = temp_settings_dir("import_reject");
+        let path = dir.join("export.json");
+
+        AppSettings::default().export_to(&path, None).unwrap();
+        let mut value: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        value["metadata"]["format_version"] = (EXPORT_FORMAT_VERSION + 1).into();
+        fs::write(&path, value.to_string()).unwrap();
+        assert!(AppSettings::read_export(&path).is_err());
+
+        fs::write(&path, "{\"hello\": 1}").unwrap();
+        assert!(AppSettings::read_export(&path).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_profiles_matching_title() {
+        let mut settings = AppSettings::default();
+        settings.title_pattern = "Warrior".to_string();
+        settings.new_profile("Alt").unwrap();
+        settings.title_pattern = "  archer ".to_string();
+        settings.new_profile("Unset").unwrap();
+
+        assert_eq!(
+            settings.profiles_matching_title("CABAL - ArcherBob"),
+            vec!["Alt"]
+        );
+        assert_eq!(
+            settings.profiles_matching_title("Cabal warrior archer"),
+            vec!["Alt", "Default"]
+        );
+        assert!(settings.profiles_matching_title("D3D Window").is_empty());
+    }
+
+    #[test]
+    fn test_default_settings_need_no_corrections() {
+        let mut settings = AppSettings::default();
+        settings.new_profile("Alt").unwrap();
+        assert!(settings.validate_and_clamp().is_empty());
+    }
+
+    #[test]
+    fn test_validate_and_clamp_bad_values() {
+        type Case = (&'static str, fn(&mut AppSettings), fn(&AppSettings) -> bool);
+        let cases: Vec<Case> = vec![
+            (
+                "tolerance far above 1",
+                |s| s.accept_item.tolerance = 37.0,
+                |s| s.accept_item.tolerance == TOLERANCE_RANGE.1,
+            ),
+            (
+                "NaN red dot tolerance",
+                |s| s.collection_filler.red_dot_tolerance = f32::NAN,
+                |s| s.collection_filler.red_dot_tolerance == TOLERANCE_RANGE.0,
+            ),
+            (
+                "zero filler delay",
+                |s| s.collection_filler.delay_ms = 0,
+                |s| s.collection_filler.delay_ms == DELAY_RANGE_MS.0,
+            ),
+            (
+                "zero scroll ticks",
+                |s| s.collection_filler.scroll.ticks = 0,
+                |s| s.collection_filler.scroll.ticks == SCROLL_TICKS_RANGE.0,
+            ),
+            (
+                "scroll delta past the wheel range",
+                |s| s.collection_filler.scroll.delta_per_tick = 50_000,
+                |s| s.collection_filler.scroll.delta_per_tick == SCROLL_DELTA_RANGE.1,
+            ),
+            (
+                "huge image clicker interval",
+                |s| s.accept_item.interval_ms = u64::MAX,
+                |s| s.accept_item.interval_ms == DELAY_RANGE_MS.1,
+            ),
+            (
+                "zero-minute image clicker time limit",
+                |s| s.accept_item.max_runtime_minutes = Some(0),
+                |s| s.accept_item.max_runtime_minutes == Some(MAX_RUNTIME_RANGE_MIN.0),
+            ),
+            (
+                "zero-size search region",
+                |s| s.accept_item.search_region = Some((0.1, 0.1, 0.0, 0.2)),
+                |s| s.accept_item.search_region.is_none(),
+            ),
+            (
+                "region past the client edge",
+                |s| s.collection_filler.dungeon_list_area = Some((0.8, 0.1, 0.5, 0.2)),
+                |s| s.collection_filler.dungeon_list_area.is_none(),
+            ),
+            (
+                "point outside the client",
+                |s| s.collection_filler.yes_pos = Some((1.5, -0.2)),
+                |s| s.collection_filler.yes_pos.is_none(),
+            ),
+            (
+                "empty image path",
+                |s| s.accept_item.image_path = " ".to_string(),
+                |s| s.accept_item.image_path == AcceptItemSettings::default().image_path,
+            ),
+            (
+                "unnamed macro",
+                |s| s.custom_macros[0].name = String::new(),
+                |s| s.custom_macros[0].name == "Macro 1",
+            ),
+            (
+                "no macros",
+                |s| s.custom_macros.clear(),
+                |s| s.custom_macros.len() == 1,
+            ),
+            (
+                "zero loop count",
+                |s| s.custom_macros[0].settings.loop_count = 0,
+                |s| s.custom_macros[0].settings.loop_count == 1,
+            ),
+            (
+                "zero macro speed",
+                |s| s.custom_macros[0].settings.speed_multiplier = 0.0,
+                |s| s.custom_macros[0].settings.speed_multiplier == SPEED_MULTIPLIER_RANGE.0,
+            ),
+            (
+                "beam width out of range",
+                |s| {
+                    s.custom_macros[0]
+                        .settings
+                        .actions
+                        .push(MacroAction::OcrSearch {
+                            ocr_region: Some((0.0, 0.0, 0.0, 0.0)),
+                            scale_factor: 0,
+                            invert_colors: false,
+                            grayscale: false,
+                            decode_mode: OcrDecodeMode::Greedy,
+                            beam_width: 500,
+                            target_stat: String::new(),
+                            target_value: 0,
+                            comparison: ComparisonMode::Equals,
+                            name_match_mode: OcrNameMatchMode::Contains,
+                            alt_targets: Vec::new(),
+                        })
+                },
+                |s| {
+                    s.custom_macros[0].settings.actions[0]
+                        == MacroAction::OcrSearch {
+                            ocr_region: None,
+                            scale_factor: OCR_SCALE_RANGE.0,
+                            invert_colors: false,
+                            grayscale: false,
+                            decode_mode: OcrDecodeMode::Greedy,
+                            beam_width: BEAM_WIDTH_RANGE.1,
+                            target_stat: String::new(),
+                            target_value: 0,
+                            comparison: ComparisonMode::Equals,
+                            name_match_mode: OcrNameMatchMode::Contains,
+                            alt_targets: Vec::new(),
+                        }
+                },
+            ),
+            (
+                "wait-for-image confidence above 1",
+                |s| {
+                    s.custom_macros[0]
+                        .settings
+                        .actions
+                        .push(MacroAction::WaitForImageGone {
+                            image_path: "dialog.png".to_string(),
+                            region: None,
+                            min_confidence: 3.0,
+                            timeout_ms: 5000,
+                        })
+                },
+                |s| {
+                    s.custom_macros[0].settings.actions[0]
+                        == MacroAction::WaitForImageGone {
+                            image_path: "dialog.png".to_string(),
+                            region: None,
+                            min_confidence: TOLERANCE_RANGE.1,
+                            timeout_ms: 5000,
+                        }
+                },
+            ),
+            (
+                "grid size of zero",
+                |s| s.global.calibration_grid_px = 0,
+                |s| s.global.calibration_grid_px == GRID_RANGE_PX.0,
+            ),
+            (
+                "log retention of zero days",
+                |s| s.global.log_retention_days = 0,
+                |s| s.global.log_retention_days == LOG_RETENTION_RANGE_DAYS.0,
+            ),
+            (
+                "anti-afk interval of zero minutes",
+                |s| s.anti_afk.interval_minutes = 0,
+                |s| s.anti_afk.interval_minutes == ANTI_AFK_INTERVAL_RANGE_MIN.0,
+            ),
+            (
+                "rebuffer interval of zero minutes",
+                |s| s.rebuffer.interval_minutes = 0,
+                |s| s.rebuffer.interval_minutes == REBUFFER_INTERVAL_RANGE_MIN.0,
+            ),
+            (
+                "rebuffer action it can't run",
+                |s| {
+                    s.rebuffer.actions = vec![
+                        MacroAction::Delay { milliseconds: 500 },
+                        MacroAction::TypeText {
+                            text: "/buff".to_string(),
+                        },
+                    ]
+                },
+                |s| s.rebuffer.actions == [MacroAction::Delay { milliseconds: 500 }],
+            ),
+            (
+                "auto-relog checking every zero seconds",
+                |s| s.auto_relog.check_interval_secs = 0,
+                |s| s.auto_relog.check_interval_secs == RELOG_INTERVAL_RANGE_SECS.0,
+            ),
+            (
+                "loot clicker without a click cap",
+                |s| s.loot_clicker.max_clicks_per_sweep = 0,
+                |s| s.loot_clicker.max_clicks_per_sweep == LOOT_MAX_CLICKS_RANGE.0,
+            ),
+            (
+                "stat logger reading every zero seconds",
+                |s| s.stat_logger.interval_secs = 0,
+                |s| s.stat_logger.interval_secs == STAT_LOG_INTERVAL_RANGE_SECS.0,
+            ),
+            (
+                "rotation key with priority zero",
+                |s| {
+                    s.rotation.entries = vec![RotationEntry {
+                        priority: 0,
+                        ..RotationEntry::default()
+                    }]
+                },
+                |s| s.rotation.entries[0].priority == ROTATION_PRIORITY_RANGE.0,
+            ),
+            (
+                "pixel trigger polling with no interval",
+                |s| s.pixel_trigger.poll_interval_ms = 0,
+                |s| s.pixel_trigger.poll_interval_ms == PIXEL_POLL_RANGE_MS.0,
+            ),
+            (
+                "rate limit of zero actions per second",
+                |s| s.global.action_rate_limit.max_per_second = 0,
+                |s| s.global.action_rate_limit.max_per_second == ACTION_RATE_RANGE.0,
+            ),
+            (
+                "negative corner failsafe distance",
+                |s| s.global.corner_failsafe.threshold_px = -3,
+                |s| s.global.corner_failsafe.threshold_px == FAILSAFE_THRESHOLD_RANGE_PX.0,
+            ),
+            (
+                "frame recorder keeping no frames",
+                |s| s.global.frame_recorder.max_files = 0,
+                |s| s.global.frame_recorder.max_files == FRAME_MAX_FILES_RANGE.0,
+            ),
+            (
+                "remote control on a privileged port",
+                |s| s.global.remote_control.port = 80,
+                |s| s.global.remote_control.port == REMOTE_PORT_RANGE.0,
+            ),
+            (
+                "bad value in an inactive profile",
+                |s| {
+                    s.new_profile("Alt").unwrap();
+                    s.collection_filler.delay_ms = 0;
+                    s.switch_profile("Default").unwrap();
+                },
+                |s| s.other_profiles[0].settings.collection_filler.delay_ms == DELAY_RANGE_MS.0,
+            ),
+        ];
+
+        for (description, corrupt, is_fixed) in cases {
+            let mut settings = AppSettings::default();
+            corrupt(&mut settings);
+            let corrections = settings.validate_and_clamp();
+            assert!(
+                !corrections.is_empty(),
+                "no correction for: {}",
+                description
+            );
+            assert!(is_fixed(&settings), "not fixed: {}", description);
+            assert!(
+                settings.validate_and_clamp().is_empty(),
+                "not stable: {}",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn test_remember_recent_path() {
+        let mut recent = Vec::new();
+        for i in 0..MAX_RECENT_PATHS + 2 {
+            remember_recent_path(&mut recent, &format!("{}.png", i));
+        }
+        remember_recent_path(&mut recent, "5.png");
+        remember_recent_path(&mut recent, "");
+
+        assert_eq!(recent.len(), MAX_RECENT_PATHS);
+        assert_eq!(recent[0], "5.png");
+        assert_eq!(recent[1], "11.png");
+        assert_eq!(recent.iter().filter(|p| *p == "5.png").count(), 1);
+    }
+
+    #[test]
+    fn test_legacy_emergency_stop_hotkey_moves_into_map() {
+        let dir = temp_settings_dir("legacy_hotkey");
+        let path = dir.join("settings.json");
+
+        let mut value = serde_json::to_value(AppSettings::default()).unwrap();
+        let map = value.as_object_mut().unwrap();
+        map.remove("hotkeys");
+        map.insert(
+            "emergency_stop_hotkey".to_string(),
+            serde_json::to_value(HotkeyConfig::default()).unwrap(),
+        );
+        fs::write(&path, value.to_string()).unwrap();
+
+        let settings = parse_settings_file(&path).unwrap().unwrap();
+        assert_eq!(
+            settings.hotkey(HotkeyActionId::EmergencyStop),
+            HotkeyConfig::default()
+        );
+        let saved = serde_json::to_string(&settings).unwrap();
+        assert!(!saved.contains("emergency_stop_hotkey"));
+        assert!(saved.contains("EmergencyStop"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn macros(names: &[&str]) -> AppSettings {
+        AppSettings {
+            custom_macros: names
+                .iter()
+                .map(|name| NamedMacro::new(name.to_string()))
+                .collect(),
+            ..AppSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_macros_without_ids_get_unique_ones() {
+        let mut settings = macros(&["A", "B", "C"]);
+        let mut value = serde_json::to_value(&settings).unwrap();
+        for named_macro in value["custom_macros"].as_array_mut().unwrap() {
+            named_macro.as_object_mut().unwrap().remove("id");
+        }
+        let mut loaded: AppSettings = serde_json::from_value(value).unwrap();
+        assert!(loaded.custom_macros.iter().all(|m| m.id.is_unset()));
+        loaded.migrate_legacy_fields();
+        let ids: HashSet<MacroId> = loaded.custom_macros.iter().map(|m| m.id).collect();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.iter().all(|id| !id.is_unset()));
+
+        // A hand-copied macro keeps its id; the copy gets a new one
+        let first = settings.custom_macros[0].clone();
+        settings.custom_macros.push(first);
+        settings.assign_macro_ids();
+        assert_eq!(settings.custom_macros[3].name, "A");
+        assert_ne!(settings.custom_macros[3].id, settings.custom_macros[0].id);
+    }
+
+    #[test]
+    fn test_one_shot_hotkey_follows_its_macro() {
+        let mut settings = macros(&["A", "B", "C"]);
+        let b = settings.custom_macros[1].id;
+        let binding = HotkeyConfig {
+            key: Some(HotkeyKey::F2),
+            modifiers: HotkeyModifiers::default(),
+            mouse_button: None,
+        };
+        settings.set_hotkey(HotkeyActionId::OneShotMacro(b), binding.clone());
+
+        settings.custom_macros.swap(0, 1);
+        settings.custom_macros.remove(2);
+        assert_eq!(settings.macro_index(b), Some(0));
+        assert_eq!(settings.hotkey(HotkeyActionId::OneShotMacro(b)), binding);
+
+        settings.custom_macros.remove(0);
+        assert_eq!(settings.macro_index(b), None);
+        assert_eq!(
+            settings.hotkey(HotkeyActionId::OneShotMacro(b)),
+            HotkeyConfig::disabled()
+        );
+        assert!(!settings
+            .hotkey_bindings()
+            .iter()
+            .any(|(action, _)| *action == HotkeyActionId::OneShotMacro(b)));
+    }
+
+    #[test]
+    fn test_schedule_entry_follows_its_macro() {
+        let mut settings = macros(&["Reroll", "Mail"]);
+        let reroll = settings.custom_macros[0].id;
+        settings.schedule.push(ScheduleEntry {
+            target: "reroll".to_string(),
+            ..ScheduleEntry::default()
+        });
+        settings.sync_schedule_targets();
+        assert_eq!(settings.schedule[0].macro_id, Some(reroll));
+
+        // Renamed and moved: the entry keeps pointing at it, under its new name
+        settings.custom_macros[0].name = "Reroll Epaulet".to_string();
+        settings.custom_macros.swap(0, 1);
+        settings.sync_schedule_targets();
+        assert_eq!(settings.schedule[0].target, "Reroll Epaulet");
+        assert_eq!(settings.macro_index(reroll), Some(1));
+
+        // Deleted: a new macro with the old name doesn't take the entry over
+        settings.custom_macros.remove(1);
+        settings
+            .custom_macros
+            .push(NamedMacro::new("Reroll Epaulet".to_string()));
+        settings.sync_schedule_targets();
+        assert_eq!(settings.schedule[0].macro_id, Some(reroll));
+        assert_eq!(settings.macro_index(reroll), None);
+    }
+
+    #[test]
+    fn test_overlay_placement_positions() {
+        let client = (100, 50, 800, 600);
+        let size = (120, 54);
+        let at = |placement: OverlayPlacement| placement.screen_position(client, size);
+
+        assert_eq!(at(OverlayPlacement::default()), (440, 50));
+        assert_eq!(
+            at(OverlayPlacement::AutoSnap(OverlayAnchor::TopLeft)),
+            (100, 50)
+        );
+        assert_eq!(
+            at(OverlayPlacement::AutoSnap(OverlayAnchor::TopRight)),
+            (780, 50)
+        );
+        assert_eq!(
+            at(OverlayPlacement::AutoSnap(OverlayAnchor::BottomCenter)),
+            (440, 596)
+        );
+        assert_eq!(at(OverlayPlacement::Manual(10, -20)), (110, 30));
+        assert_eq!(OverlayAnchor::BottomCenter.next(), OverlayAnchor::TopCenter);
+    }
+
+    #[test]
+    fn test_start_mode_from_args() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            StartMode::from_args(args(&["--minimized"])),
+            Some(StartMode::Minimized)
+        );
+        assert_eq!(
+            StartMode::from_args(args(&["--verbose", "--overlay"])),
+            Some(StartMode::Overlay)
+        );
+        assert_eq!(StartMode::from_args(args(&[])), None);
+    }
+
+    #[test]
+    fn test_language_saved_as_tag_and_defaults_to_english() {
+        let mut settings = AppSettings::default();
+        settings.global.language = Language::PortugueseBrazil;
+        let json = serde_json::to_string(&settings).unwrap();
+        assert!(json.contains("\"language\":\"pt-BR\""));
+        let loaded: AppSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.global.language, Language::PortugueseBrazil);
+
+        // Settings saved before the option existed
+        let old = json.replace("\"language\":\"pt-BR\",", "");
+        let loaded: AppSettings = serde_json::from_str(&old).unwrap();
+        assert_eq!(loaded.global.language, Language::English);
+    }
+
+    #[test]
+    fn test_corner_failsafe_only_fires_in_its_corner() {
+        let screen = (1920, 1080);
+        let mut failsafe = CornerFailsafe::default();
+        assert!(failsafe.triggered((0, 0), screen));
+        assert!(failsafe.triggered((4, 4), screen));
+        assert!(!failsafe.triggered((5, 0), screen));
+        assert!(!failsafe.triggered((0, 500), screen));
+        // The top edge of a second monitor left of the primary one
+        assert!(!failsafe.triggered((-800, 2), screen));
+
+        failsafe.corner = ScreenCorner::BottomRight;
+        assert!(failsafe.triggered((1919, 1079), screen));
+        assert!(!failsafe.triggered((0, 0), screen));
+    }
+
+    #[test]
+    fn test_usage_stats_add_up_runs() {
+        let mut usage = UsageStats::default();
+        let run = WorkerStats {
+            iterations: 2,
+            items_processed: 5,
+            ..WorkerStats::default()
+        };
+        usage.record_run("Image Clicker", &run);
+        usage.record_run(
+            "Image Clicker",
+            &WorkerStats {
+                last_error: Some("failed".to_string()),
+                ..run
+            },
+        );
+
+        let totals = &usage.tools["Image Clicker"];
+        assert_eq!(totals.runs, 2);
+        assert_eq!(totals.iterations, 4);
+        assert_eq!(totals.items, 10);
+        assert_eq!(totals.runs_with_errors, 1);
+        assert!(!usage.tools.contains_key("Collection Filler"));
+    }
+
+    #[test]
+    fn test_profile_names_must_be_unique() {
+        let mut settings = AppSettings::default();
+        assert!(settings.new_profile("default").is_err());
+        assert!(settings.clone_profile("  ").is_err());
+        assert!(settings.delete_active_profile().is_err());
+    }
+
+    #[test]
+    fn test_saver_notices_unsaved_edits() {
+        let mut settings = AppSettings::default();
+        let mut saver = SettingsSaver::new(&settings);
+        saver.detect_changes(&settings);
+        assert!(!saver.is_dirty());
 
-    /// Auto-save (ignores errors)
-    pub fn auto_save(&self) {
-        let _ = self.save();
+        settings.global.always_on_top = !settings.global.always_on_top;
+        saver.detect_changes(&settings);
+        assert!(saver.is_dirty());
     }
 }
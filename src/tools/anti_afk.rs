@@ -0,0 +1,293 @@
+use crate::calibration::{CalibrationManager, CalibrationResult};
+use crate::core::clock::format_countdown;
+use crate::core::coords::denormalize_point;
+use crate::core::hotkey::{hotkey_key_label, hotkey_key_to_vk};
+use crate::core::input::{key_press_post, mouse_wiggle_post};
+use crate::core::overlay_window::{OverlayShape, REGION_POINT_COLOR};
+use crate::core::throttle::ActionThrottle;
+use crate::core::window::get_client_size;
+use crate::core::worker::{
+    push_log_entry, LogEntry, LogLevel, SharedLog, SharedStats, StatusSender, Worker, WorkerStats,
+};
+use crate::settings::{ActionRateLimit, AntiAfkInput, AntiAfkSettings};
+use crate::tools::r#trait::{StopReason, Tool};
+use crate::ui::anti_afk::{render_ui, AntiAfkUiAction};
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use windows::Win32::Foundation::HWND;
+
+// How often the countdown in the status line is refreshed
+const COUNTDOWN_STEP: Duration = Duration::from_secs(1);
+
+/// Sends a harmless background input every few minutes so the server doesn't
+/// disconnect an idle character
+pub struct AntiAfkTool {
+    // Runtime state (Worker)
+    worker: Worker,
+
+    // Calibration of the mouse wiggle spot
+    calibration: CalibrationManager,
+    show_regions: bool,
+}
+
+impl Default for AntiAfkTool {
+    fn default() -> Self {
+        Self {
+            worker: Worker::new("Anti-AFK"),
+            calibration: CalibrationManager::new(),
+            show_regions: false,
+        }
+    }
+}
+
+impl Tool for AntiAfkTool {
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
+    }
+
+    fn is_running(&self) -> bool {
+        self.worker.is_running()
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
+        if let Some(hwnd) = game_hwnd {
+            self.start_keep_alive(
+                app_settings.anti_afk.clone(),
+                hwnd,
+                app_settings.global.action_rate_limit,
+            );
+        } else {
+            self.worker.set_status(tr!("status.connect_first"));
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        settings: &mut crate::settings::AppSettings,
+        game_hwnd: Option<HWND>,
+        hotkey_error: Option<&str>,
+    ) {
+        // Handle calibration interaction
+        if let Some(hwnd) = game_hwnd {
+            self.update_calibration(settings, hwnd);
+        } else {
+            // Disconnected logic
+            if self.worker.is_running() {
+                self.worker.stop();
+                self.worker.set_status(tr!("header.disconnected"));
+            }
+            self.calibration.cancel();
+        }
+
+        let rate_limit = settings.global.action_rate_limit;
+        let settings = &mut settings.anti_afk;
+
+        // Repaint if calibrating to capture clicks immediately
+        if self.calibration.is_active() {
+            ctx.request_repaint();
+        }
+
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
+        let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
+
+        let action = render_ui(
+            ui,
+            settings,
+            &mut self.show_regions,
+            self.calibration.is_active(),
+            self.calibration.magnifier(),
+            is_running,
+            &status,
+            &stats,
+            game_hwnd.is_some(),
+            game_hwnd.and_then(get_client_size),
+            hotkey_error,
+        );
+
+        match action {
+            AntiAfkUiAction::StartPositionCalibration => {
+                self.calibration.start_point();
+                self.worker.set_status(tr!("status.click_button"));
+            }
+            AntiAfkUiAction::CancelCalibration => {
+                self.calibration.cancel();
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            AntiAfkUiAction::ResetToDefaults => {
+                self.calibration.cancel();
+                *settings = AntiAfkSettings::default();
+                self.worker.set_status(tr!("status.settings_reset"));
+            }
+            AntiAfkUiAction::Start => {
+                if let Some(hwnd) = game_hwnd {
+                    self.start_keep_alive(settings.clone(), hwnd, rate_limit);
+                } else {
+                    self.worker.set_status(tr!("status.connect_first"));
+                }
+            }
+            AntiAfkUiAction::Stop => {
+                self.stop(StopReason::User);
+            }
+            AntiAfkUiAction::None => {}
+        }
+    }
+
+    fn update_calibration(&mut self, settings: &mut crate::settings::AppSettings, hwnd: HWND) {
+        self.calibration
+            .set_grid_size(settings.global.calibration_grid_px);
+        match self.calibration.update(hwnd) {
+            Some(CalibrationResult::Point(x, y)) => {
+                settings.anti_afk.position = Some((x, y));
+                self.worker.set_status(tr!("status.calibration_recorded"));
+            }
+            Some(CalibrationResult::Cancelled) => {
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            _ => {}
+        }
+        if self.calibration.is_adjusting() {
+            self.worker.set_status(tr!("status.nudge_hint"));
+        }
+    }
+
+    fn is_calibrating(&self) -> bool {
+        self.calibration.is_active()
+    }
+
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
+
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        self.worker.client_size()
+    }
+
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
+
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
+
+    fn get_log(&self) -> Vec<LogEntry> {
+        self.worker.get_log()
+    }
+
+    fn region_preview(
+        &self,
+        app_settings: &crate::settings::AppSettings,
+        game_hwnd: HWND,
+    ) -> Option<Vec<OverlayShape>> {
+        if !self.show_regions {
+            return None;
+        }
+        let shapes = app_settings
+            .anti_afk
+            .position
+            .and_then(|(x, y)| denormalize_point(game_hwnd, x, y))
+            .map(|(x, y)| {
+                OverlayShape::point(x, y, REGION_POINT_COLOR, Some("Anti-AFK".to_string()))
+            })
+            .into_iter()
+            .collect();
+        Some(shapes)
+    }
+}
+
+impl AntiAfkTool {
+    fn start_keep_alive(
+        &mut self,
+        settings: AntiAfkSettings,
+        game_hwnd: HWND,
+        rate_limit: ActionRateLimit,
+    ) {
+        if settings.input == AntiAfkInput::MouseWiggle && settings.position.is_none() {
+            self.worker.set_status(tr!("status.calibrate_first"));
+            return;
+        }
+        self.worker.set_status(tr!("status.starting"));
+
+        let started = self.worker.start(
+            move |running: Arc<AtomicBool>,
+                  paused: Arc<AtomicBool>,
+                  status: StatusSender,
+                  log: SharedLog,
+                  stats: SharedStats| {
+                let mut throttle = ActionThrottle::new(rate_limit, Arc::clone(&log));
+                let interval = Duration::from_secs(settings.interval_minutes * 60);
+                let input = match settings.input {
+                    AntiAfkInput::Key => format!("key {}", hotkey_key_label(settings.key)),
+                    AntiAfkInput::MouseWiggle => "mouse wiggle".to_string(),
+                };
+                Worker::push_log(
+                    &log,
+                    &format!(
+                        "Keep-alive ({}) every {} min",
+                        input, settings.interval_minutes
+                    ),
+                );
+
+                let mut next_keep_alive = Instant::now() + interval;
+                while Worker::checkpoint(&running, &paused).is_continue() {
+                    let remaining = next_keep_alive.saturating_duration_since(Instant::now());
+                    if !remaining.is_zero() {
+                        status.set(tr!(
+                            "status.keep_alive_countdown",
+                            time = format_countdown(remaining)
+                        ));
+                        let step = remaining.min(COUNTDOWN_STEP);
+                        Worker::sleep_while_running(&running, step.as_millis() as u64);
+                        continue;
+                    }
+
+                    throttle.wait();
+                    let sent = match settings.input {
+                        AntiAfkInput::Key => {
+                            key_press_post(game_hwnd, hotkey_key_to_vk(settings.key))
+                        }
+                        AntiAfkInput::MouseWiggle => settings
+                            .position
+                            .and_then(|(x, y)| denormalize_point(game_hwnd, x, y))
+                            .is_some_and(|(x, y)| mouse_wiggle_post(game_hwnd, x, y)),
+                    };
+                    if sent {
+                        stats.lock().unwrap().add_items(1);
+                        Worker::push_log(&log, &format!("Keep-alive sent ({})", input));
+                    } else {
+                        let error = "Keep-alive could not be sent to the game window";
+                        stats.lock().unwrap().set_error(error);
+                        push_log_entry(&log, LogLevel::Warn, error);
+                    }
+                    next_keep_alive = Instant::now() + interval;
+                }
+
+                running.store(false, Ordering::Relaxed);
+                status.set(tr!("status.stopped"));
+            },
+        );
+        if started {
+            self.worker.set_client_size(get_client_size(game_hwnd));
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
+    }
+}
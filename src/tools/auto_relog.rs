@@ -0,0 +1,439 @@
+use crate::automation::context::AutomationContext;
+use crate::automation::detection::find_stored_template;
+use crate::automation::macro_runner::run_input_action;
+use crate::calibration::{CalibrationManager, CalibrationResult};
+use crate::core::coords::{denormalize_point, denormalize_rect};
+use crate::core::error::HelperError;
+use crate::core::hotkey::hotkey_key_label;
+use crate::core::overlay_window::{OverlayShape, REGION_POINT_COLOR};
+use crate::core::paths;
+use crate::core::screen_capture::save_window_region;
+use crate::core::throttle::ActionThrottle;
+use crate::core::window::get_client_size;
+use crate::core::worker::{
+    push_log_entry, LogEntry, LogLevel, SharedLog, SharedStats, StatusSender, Worker, WorkerStats,
+};
+use crate::settings::{
+    remember_recent_path, ActionRateLimit, AutoRelogSettings, MacroAction, TemplateMatchMode,
+};
+use crate::tools::r#trait::{RelogEvent, StopReason, Tool};
+use crate::ui::auto_relog::{render_ui, AutoRelogUiAction};
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+use windows::Win32::Foundation::HWND;
+
+// Template alias of the disconnect dialog
+const DIALOG_ALIAS: &str = "disconnect_dialog";
+// File the captured dialog is saved to, in the templates folder
+const DIALOG_FILE_NAME: &str = "disconnect_dialog.png";
+// Time the app gets to stop the other tools before the reconnect clicks start
+// (it handles the event on its next frame, at most 500 ms away)
+const HANDOFF_MS: u64 = 1_000;
+
+/// What a calibration click is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CalibrationTarget {
+    // Area of the game window saved as the dialog template
+    DialogCapture,
+    // Position of the click action at this index
+    Action(usize),
+}
+
+/// Watches for the disconnect dialog and clicks through it, so long runs survive a
+/// dropped connection
+pub struct AutoRelogTool {
+    // Runtime state (Worker)
+    worker: Worker,
+    // Reconnect steps of the current run, for the app
+    events: Option<Receiver<RelogEvent>>,
+
+    calibration: CalibrationManager,
+    calibrating: Option<CalibrationTarget>,
+    show_regions: bool,
+}
+
+impl Default for AutoRelogTool {
+    fn default() -> Self {
+        Self {
+            worker: Worker::new("Auto-Relog"),
+            events: None,
+            calibration: CalibrationManager::new(),
+            calibrating: None,
+            show_regions: false,
+        }
+    }
+}
+
+impl Tool for AutoRelogTool {
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
+    }
+
+    fn is_running(&self) -> bool {
+        self.worker.is_running()
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
+        if let Some(hwnd) = game_hwnd {
+            self.start_watching(
+                app_settings.auto_relog.clone(),
+                hwnd,
+                app_settings.global.action_rate_limit,
+            );
+        } else {
+            self.worker.set_status(tr!("status.connect_first"));
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        settings: &mut crate::settings::AppSettings,
+        game_hwnd: Option<HWND>,
+        hotkey_error: Option<&str>,
+    ) {
+        // Handle calibration interaction
+        if let Some(hwnd) = game_hwnd {
+            self.update_calibration(settings, hwnd);
+        } else {
+            // Disconnected logic
+            if self.worker.is_running() {
+                self.worker.stop();
+                self.worker.set_status(tr!("header.disconnected"));
+            }
+            self.calibration.cancel();
+            self.calibrating = None;
+        }
+
+        let rate_limit = settings.global.action_rate_limit;
+        let recent_image_paths = &mut settings.global.recent_image_paths;
+        let settings = &mut settings.auto_relog;
+
+        // Repaint if calibrating to capture clicks immediately
+        if self.calibration.is_active() {
+            ctx.request_repaint();
+        }
+
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
+        let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
+
+        let action = render_ui(
+            ui,
+            settings,
+            recent_image_paths,
+            &mut self.show_regions,
+            self.calibrating == Some(CalibrationTarget::DialogCapture),
+            match self.calibrating {
+                Some(CalibrationTarget::Action(idx)) => Some(idx),
+                _ => None,
+            },
+            self.calibration.magnifier(),
+            is_running,
+            &status,
+            &stats,
+            game_hwnd.is_some(),
+            game_hwnd.and_then(get_client_size),
+            hotkey_error,
+        );
+
+        match action {
+            AutoRelogUiAction::StartDialogCapture => {
+                self.calibration.start_area();
+                self.calibrating = Some(CalibrationTarget::DialogCapture);
+                self.worker.set_status(tr!("status.click_area_corners"));
+            }
+            AutoRelogUiAction::StartCalibration(idx) => {
+                self.calibration.start_point();
+                self.calibrating = Some(CalibrationTarget::Action(idx));
+                self.worker.set_status(tr!("status.click_to_set_position"));
+            }
+            AutoRelogUiAction::CancelCalibration => {
+                self.calibration.cancel();
+                self.calibrating = None;
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            AutoRelogUiAction::ResetToDefaults => {
+                self.calibration.cancel();
+                self.calibrating = None;
+                *settings = AutoRelogSettings::default();
+                self.worker.set_status(tr!("status.settings_reset"));
+            }
+            AutoRelogUiAction::Start => {
+                if let Some(hwnd) = game_hwnd {
+                    self.start_watching(settings.clone(), hwnd, rate_limit);
+                } else {
+                    self.worker.set_status(tr!("status.connect_first"));
+                }
+            }
+            AutoRelogUiAction::Stop => {
+                self.stop(StopReason::User);
+            }
+            AutoRelogUiAction::None => {}
+        }
+    }
+
+    fn update_calibration(&mut self, settings: &mut crate::settings::AppSettings, hwnd: HWND) {
+        self.calibration
+            .set_grid_size(settings.global.calibration_grid_px);
+        match self.calibration.update(hwnd) {
+            Some(CalibrationResult::Area(x, y, w, h)) => {
+                self.calibrating = None;
+                let path = paths::templates_dir().join(DIALOG_FILE_NAME);
+                let saved = denormalize_rect(hwnd, x, y, w, h)
+                    .ok_or(HelperError::WindowInvalid)
+                    .and_then(|region| save_window_region(hwnd, region, &path));
+                match saved {
+                    Ok(()) => {
+                        let path = path.display().to_string();
+                        remember_recent_path(&mut settings.global.recent_image_paths, &path);
+                        settings.auto_relog.dialog_image_path = path;
+                        self.worker.set_status(tr!("status.dialog_captured"));
+                    }
+                    Err(e) => self.worker.set_status(e.status()),
+                }
+            }
+            Some(CalibrationResult::Point(x, y)) => {
+                if let Some(CalibrationTarget::Action(idx)) = self.calibrating.take() {
+                    if let Some(MacroAction::Click { coordinate, .. }) =
+                        settings.auto_relog.actions.get_mut(idx)
+                    {
+                        *coordinate = Some((x, y));
+                        self.worker.set_status(tr!("status.calibration_recorded"));
+                    }
+                }
+            }
+            Some(CalibrationResult::Cancelled) => {
+                self.calibrating = None;
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            _ => {}
+        }
+        if self.calibration.is_adjusting() {
+            self.worker.set_status(tr!("status.nudge_hint"));
+        }
+    }
+
+    fn is_calibrating(&self) -> bool {
+        self.calibration.is_active()
+    }
+
+    fn take_relog_event(&mut self) -> Option<RelogEvent> {
+        self.events.as_ref()?.try_recv().ok()
+    }
+
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
+
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        self.worker.client_size()
+    }
+
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
+
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
+
+    fn get_log(&self) -> Vec<LogEntry> {
+        self.worker.get_log()
+    }
+
+    fn region_preview(
+        &self,
+        app_settings: &crate::settings::AppSettings,
+        game_hwnd: HWND,
+    ) -> Option<Vec<OverlayShape>> {
+        if !self.show_regions {
+            return None;
+        }
+        let shapes = app_settings
+            .auto_relog
+            .actions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, action)| match action {
+                MacroAction::Click {
+                    coordinate: Some((x, y)),
+                    ..
+                } => {
+                    let (x, y) = denormalize_point(game_hwnd, *x, *y)?;
+                    Some(OverlayShape::point(
+                        x,
+                        y,
+                        REGION_POINT_COLOR,
+                        Some(format!("Click #{}", idx + 1)),
+                    ))
+                }
+                _ => None,
+            })
+            .collect();
+        Some(shapes)
+    }
+}
+
+impl AutoRelogTool {
+    fn start_watching(
+        &mut self,
+        settings: AutoRelogSettings,
+        game_hwnd: HWND,
+        rate_limit: ActionRateLimit,
+    ) {
+        let click_unset = settings.actions.iter().any(|action| {
+            matches!(
+                action,
+                MacroAction::Click {
+                    coordinate: None,
+                    ..
+                }
+            )
+        });
+        if settings.dialog_image_path.trim().is_empty() || click_unset {
+            self.worker.set_status(tr!("status.calibrate_first"));
+            return;
+        }
+        if settings.actions.is_empty() {
+            self.worker.set_status(tr!("status.no_actions"));
+            return;
+        }
+        self.worker.set_status(tr!("status.starting"));
+
+        let (events, receiver) = channel();
+        let started = self.worker.start(
+            move |running: Arc<AtomicBool>,
+                  paused: Arc<AtomicBool>,
+                  status: StatusSender,
+                  log: SharedLog,
+                  stats: SharedStats| {
+                let fail = |error: HelperError| {
+                    push_log_entry(&log, LogLevel::Error, &error.to_string());
+                    stats.lock().unwrap().record_error(&error);
+                    running.store(false, Ordering::Relaxed);
+                    status.set(error.status());
+                };
+                let throttle = ActionThrottle::new(rate_limit, Arc::clone(&log));
+                let mut ctx = match AutomationContext::new(game_hwnd, throttle) {
+                    Ok(ctx) => ctx,
+                    Err(e) => return fail(e),
+                };
+                if let Err(e) = ctx.store_template(
+                    &settings.dialog_image_path,
+                    None,
+                    DIALOG_ALIAS,
+                    TemplateMatchMode::default(),
+                ) {
+                    return fail(e);
+                }
+                Worker::push_log(
+                    &log,
+                    &format!(
+                        "Watching for the disconnect dialog every {} s ({} reconnect actions)",
+                        settings.check_interval_secs,
+                        settings.actions.len()
+                    ),
+                );
+
+                let mut reconnects = 0;
+                while Worker::checkpoint(&running, &paused).is_continue() {
+                    status.set("Watching for the disconnect dialog");
+                    ctx.refresh(&log);
+                    let found =
+                        find_stored_template(&mut ctx.gui, DIALOG_ALIAS, settings.confidence, None);
+                    if let Some(dialog) = found {
+                        reconnects += 1;
+                        push_log_entry(
+                            &log,
+                            LogLevel::Warn,
+                            &format!(
+                                "Reconnect #{}: disconnect dialog at ({}, {}), score {:.2}",
+                                reconnects, dialog[0].x, dialog[0].y, dialog[0].score
+                            ),
+                        );
+                        let _ = events.send(RelogEvent::Disconnected);
+                        Worker::sleep_while_running(&running, HANDOFF_MS);
+
+                        let total = settings.actions.len();
+                        for (idx, action) in settings.actions.iter().enumerate() {
+                            if Worker::checkpoint(&running, &paused).is_break() {
+                                break;
+                            }
+                            Worker::push_log(
+                                &log,
+                                &format!(
+                                    "Reconnect #{}: action {}/{} ({})",
+                                    reconnects,
+                                    idx + 1,
+                                    total,
+                                    describe_action(action)
+                                ),
+                            );
+                            run_input_action(
+                                &mut ctx, idx, action, &running, &status, &log, &stats,
+                            );
+                        }
+                        if !running.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        stats.lock().unwrap().add_items(1);
+                        Worker::push_log(&log, &format!("Reconnect #{}: done", reconnects));
+                        let _ = events.send(RelogEvent::Reconnected);
+                    }
+
+                    status.set(if reconnects > 0 {
+                        format!(
+                            "Watching for the disconnect dialog ({} reconnects)",
+                            reconnects
+                        )
+                    } else {
+                        "Watching for the disconnect dialog".to_string()
+                    });
+                    Worker::sleep_while_running(&running, settings.check_interval_secs * 1000);
+                }
+
+                running.store(false, Ordering::Relaxed);
+                status.set("Stopped");
+            },
+        );
+        if started {
+            self.worker.set_client_size(get_client_size(game_hwnd));
+            self.events = Some(receiver);
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
+    }
+}
+
+/// Short description of a reconnect action for the log
+fn describe_action(action: &MacroAction) -> String {
+    match action {
+        MacroAction::Click {
+            coordinate: Some((x, y)),
+            ..
+        } => format!("click at ({:.3}, {:.3})", x, y),
+        MacroAction::Click { .. } => "click".to_string(),
+        MacroAction::PressKey { key } => format!("key {}", hotkey_key_label(*key)),
+        MacroAction::Delay { milliseconds } => format!("wait {} ms", milliseconds),
+        MacroAction::TypeText { .. }
+        | MacroAction::OcrSearch { .. }
+        | MacroAction::WaitForImageGone { .. } => "skipped".to_string(),
+    }
+}
@@ -1,17 +1,36 @@
 use crate::automation::context::AutomationContext;
-use crate::automation::detection::{find_stored_template, is_position_near};
+use crate::automation::detection::{
+    filter_red_dots, find_color_regions, is_position_near, scan_stored_templates, TemplateMatch,
+    TemplateScan,
+};
 use crate::automation::interaction::{
-    click_at_screen, click_at_window_pos, delay_ms, scroll_in_area,
+    background_click_at_screen, background_click_at_window_pos, background_scroll_in_area,
+    click_at_screen, click_at_window_pos, scroll_in_area,
+};
+use crate::calibration::{CalibrationManager, CalibrationResult};
+use crate::core::coords::{denormalize_point, denormalize_rect};
+use crate::core::error::HelperError;
+use crate::core::overlay_window::{OverlayShape, REGION_AREA_COLOR, REGION_POINT_COLOR};
+use crate::core::sounds::{sounds, Event};
+use crate::core::throttle::ActionThrottle;
+use crate::core::window::{client_to_screen_coords, get_client_size};
+use crate::core::worker::{
+    push_log_entry, LogEntry, LogLevel, RunDeadline, SharedLog, SharedStats, StatusSender, Worker,
+    WorkerStats,
+};
+use crate::settings::{
+    ActionRateLimit, CollectionFillerSettings, NormPoint, NormRect, TemplateMatchMode,
 };
-use crate::calibration::CalibrationManager;
-use crate::core::worker::Worker;
-use crate::settings::CollectionFillerSettings;
-use crate::tools::r#trait::Tool;
+use crate::tools::r#trait::{StopReason, Tool};
+use crate::tools::undo::UndoEntry;
 use crate::ui::collection_filler::{
     apply_calibration_result, clear_calibration, CalibrationItem, UiAction,
 };
 use eframe::egui;
-use std::sync::{Arc, Mutex};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use windows::Win32::Foundation::HWND;
 
 pub struct CollectionFillerTool {
@@ -21,44 +40,67 @@ pub struct CollectionFillerTool {
     // Calibration
     calibration: CalibrationManager,
     calibrating_item: Option<CalibrationItem>,
+    // "Calibrate all": items still to click, and how many were queued
+    calibration_queue: VecDeque<CalibrationItem>,
+    calibration_queue_total: usize,
+    show_regions: bool,
+    sampled_dot_color: Option<(u8, u8, u8)>,
+
+    // Removed data waiting to be collected by the app's undo stack
+    undo_entries: Vec<UndoEntry>,
 }
 
 impl Default for CollectionFillerTool {
     fn default() -> Self {
         Self {
-            worker: Worker::new(),
+            worker: Worker::new("Collection Filler"),
             calibration: CalibrationManager::new(),
             calibrating_item: None,
+            calibration_queue: VecDeque::new(),
+            calibration_queue_total: 0,
+            show_regions: false,
+            sampled_dot_color: None,
+            undo_entries: Vec::new(),
         }
     }
 }
 
 impl Tool for CollectionFillerTool {
-    fn stop(&mut self) {
-        self.worker.stop();
-        if self.worker.get_status().contains("Stopped") {
-            // Already stopped
-        } else {
-            self.worker.set_status("Stopped (emergency hotkey)");
-        }
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
     }
 
     fn is_running(&self) -> bool {
         self.worker.is_running()
     }
 
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
     fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
         let settings = &app_settings.collection_filler;
 
         if self.is_fully_calibrated(settings) {
             if let Some(hwnd) = game_hwnd {
-                self.start_automation(settings.clone(), hwnd);
+                self.start_automation(
+                    settings.clone(),
+                    hwnd,
+                    app_settings.global.action_rate_limit,
+                );
             } else {
-                self.worker.set_status("Connect to game first");
+                self.worker.set_status(tr!("status.connect_first"));
             }
         } else {
-            self.worker
-                .set_status("Please calibrate all required items first");
+            self.worker.set_status(tr!("status.calibrate_first"));
         }
     }
 
@@ -70,85 +112,227 @@ impl Tool for CollectionFillerTool {
         game_hwnd: Option<HWND>,
         hotkey_error: Option<&str>,
     ) {
-        let settings = &mut settings.collection_filler;
-
         // Handle calibration interaction
         if let Some(hwnd) = game_hwnd {
-            if let Some(result) = self.calibration.update(hwnd) {
-                if let Some(item) = self.calibrating_item.take() {
-                    apply_calibration_result(result, item, settings);
-                    self.worker.set_status("Calibration recorded");
-                }
-            }
+            self.update_calibration(settings, hwnd);
         } else {
             // Disconnected logic
             if self.worker.is_running() {
                 self.worker.stop();
-                self.worker.set_status("Disconnected");
+                self.worker.set_status(tr!("header.disconnected"));
             }
             self.calibration.cancel();
             self.calibrating_item = None;
+            self.clear_calibration_queue();
         }
 
-        let is_running = self.worker.is_running();
+        let rate_limit = settings.global.action_rate_limit;
+        let recent_image_paths = &mut settings.global.recent_image_paths;
+        let settings = &mut settings.collection_filler;
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
         let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
 
         // Render UI and get action
         let action = crate::ui::collection_filler::render_ui(
             ui,
             ctx,
             settings,
+            recent_image_paths,
             &self.calibration,
             &self.calibrating_item,
+            &mut self.show_regions,
+            self.sampled_dot_color,
             is_running,
             &status,
+            &stats,
             game_hwnd.is_some(),
+            game_hwnd.and_then(get_client_size),
             hotkey_error,
         );
 
         // Handle action
         match action {
             UiAction::StartCalibration(item, is_area) => {
+                self.clear_calibration_queue();
                 self.calibrating_item = Some(item.clone());
                 if is_area {
                     self.calibration.start_area();
-                    self.worker.set_status("Click top-left, then bottom-right");
+                    self.worker.set_status(tr!("status.click_area_corners"));
+                } else if item == CalibrationItem::RedDotColor {
+                    self.calibration.start_pixel();
+                    self.worker.set_status(tr!("status.click_red_dot"));
                 } else {
                     self.calibration.start_point();
-                    self.worker.set_status("Click the button");
+                    self.worker.set_status(tr!("status.click_button"));
                 }
             }
+            UiAction::CalibrateAll => {
+                self.calibration_queue = CalibrationItem::ALL
+                    .into_iter()
+                    .filter(|item| !item.is_set(settings))
+                    .collect();
+                self.calibration_queue_total = self.calibration_queue.len();
+                // One click per item; no arrow-key confirmation step
+                self.calibration.set_nudge_points(false);
+                self.start_next_queued();
+            }
             UiAction::CancelCalibration => {
                 self.calibration.cancel();
                 self.calibrating_item = None;
-                self.worker.set_status("Calibration cancelled");
+                self.clear_calibration_queue();
+                self.worker.set_status(tr!("status.calibration_cancelled"));
             }
             UiAction::ClearCalibration(item) => {
+                if item == CalibrationItem::RedDotColor {
+                    self.sampled_dot_color = None;
+                }
+                self.undo_entries.push(UndoEntry::ClearedCalibration {
+                    item: item.clone(),
+                    previous: settings.clone(),
+                });
                 clear_calibration(item, settings);
             }
+            UiAction::ResetToDefaults => {
+                self.calibration.cancel();
+                self.calibrating_item = None;
+                self.clear_calibration_queue();
+                self.sampled_dot_color = None;
+                *settings = CollectionFillerSettings::default();
+                self.worker.set_status(tr!("status.settings_reset"));
+            }
             UiAction::StartAutomation => {
                 if self.is_fully_calibrated(settings) {
                     // Need game_hwnd here
                     if let Some(hwnd) = game_hwnd {
-                        self.start_automation(settings.clone(), hwnd);
+                        self.start_automation(settings.clone(), hwnd, rate_limit);
                     } else {
-                        self.worker.set_status("Connect to game first");
+                        self.worker.set_status(tr!("status.connect_first"));
                     }
                 } else {
-                    self.worker
-                        .set_status("Please calibrate all required items first");
+                    self.worker.set_status(tr!("status.calibrate_first"));
                 }
             }
             UiAction::StopAutomation => {
-                self.stop();
+                self.stop(StopReason::User);
             }
             UiAction::None => {}
         }
     }
 
-    fn get_log(&self) -> Vec<String> {
+    fn update_calibration(&mut self, app_settings: &mut crate::settings::AppSettings, hwnd: HWND) {
+        self.calibration
+            .set_grid_size(app_settings.global.calibration_grid_px);
+        let settings = &mut app_settings.collection_filler;
+
+        match self.calibration.update(hwnd) {
+            Some(CalibrationResult::Cancelled) => {
+                self.calibrating_item = None;
+                self.clear_calibration_queue();
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            Some(result) => {
+                if let CalibrationResult::Pixel(_, _, r, g, b) = result {
+                    self.sampled_dot_color = Some((r, g, b));
+                }
+                if let Some(item) = self.calibrating_item.take() {
+                    apply_calibration_result(result, item, settings);
+                    if self.calibration_queue_total > 0 {
+                        self.start_next_queued();
+                    } else {
+                        self.worker.set_status(tr!("status.calibration_recorded"));
+                    }
+                }
+            }
+            None => {}
+        }
+        if let Some(text) = self.calibration.selection_status() {
+            self.worker.set_status_quiet(&text);
+        }
+        if self.calibration.is_adjusting() {
+            self.worker.set_status(tr!("status.nudge_hint"));
+        }
+    }
+
+    fn is_calibrating(&self) -> bool {
+        self.calibration.is_active()
+    }
+
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
+
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        self.worker.client_size()
+    }
+
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
+
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
+
+    fn get_log(&self) -> Vec<LogEntry> {
         self.worker.get_log()
     }
+
+    fn take_undo_entries(&mut self) -> Vec<UndoEntry> {
+        std::mem::take(&mut self.undo_entries)
+    }
+
+    fn region_preview(
+        &self,
+        app_settings: &crate::settings::AppSettings,
+        game_hwnd: HWND,
+    ) -> Option<Vec<OverlayShape>> {
+        if !self.show_regions {
+            return None;
+        }
+        let settings = &app_settings.collection_filler;
+        let mut shapes = Vec::new();
+
+        let areas = [
+            ("Tabs", settings.collection_tabs_area),
+            ("Dungeon List", settings.dungeon_list_area),
+            ("Items", settings.collection_items_area),
+        ];
+        for (label, area) in areas {
+            if let Some(rect) =
+                area.and_then(|(x, y, w, h)| denormalize_rect(game_hwnd, x, y, w, h))
+            {
+                shapes.push(OverlayShape {
+                    rect,
+                    color: REGION_AREA_COLOR,
+                    label: Some(label.to_string()),
+                });
+            }
+        }
+
+        let points = [
+            ("Auto Refill", settings.auto_refill_pos),
+            ("Register", settings.register_pos),
+            ("Yes", settings.yes_pos),
+            ("Page 2", settings.page_2_pos),
+            ("Page 3", settings.page_3_pos),
+            ("Page 4", settings.page_4_pos),
+            ("Arrow Right", settings.arrow_right_pos),
+        ];
+        for (label, pos) in points {
+            if let Some((x, y)) = pos.and_then(|(x, y)| denormalize_point(game_hwnd, x, y)) {
+                shapes.push(OverlayShape::point(
+                    x,
+                    y,
+                    REGION_POINT_COLOR,
+                    Some(label.to_string()),
+                ));
+            }
+        }
+
+        Some(shapes)
+    }
 }
 
 impl CollectionFillerTool {
@@ -163,118 +347,468 @@ impl CollectionFillerTool {
 
     // start method removed as it's now internal to UiAction handling
 
-    fn start_automation(&mut self, settings: CollectionFillerSettings, game_hwnd: HWND) {
-        self.worker.set_status("Starting automation...");
+    fn clear_calibration_queue(&mut self) {
+        self.calibration_queue.clear();
+        self.calibration_queue_total = 0;
+        self.calibration.set_nudge_points(true);
+    }
+
+    /// Start calibrating the next queued item, or finish the "Calibrate all" session
+    fn start_next_queued(&mut self) {
+        let Some(item) = self.calibration_queue.pop_front() else {
+            if self.calibration_queue_total > 0 {
+                self.worker.set_status(tr!("status.all_calibrated"));
+            } else {
+                self.worker.set_status(tr!("status.already_calibrated"));
+            }
+            self.clear_calibration_queue();
+            return;
+        };
+
+        let step = self.calibration_queue_total - self.calibration_queue.len();
+        let status = if item.is_area() {
+            self.calibration.start_area();
+            format!(
+                "Area: {} - click top-left, then bottom-right ({}/{})",
+                item.label(),
+                step,
+                self.calibration_queue_total
+            )
+        } else {
+            self.calibration.start_point();
+            format!(
+                "Click: {} ({}/{})",
+                item.label(),
+                step,
+                self.calibration_queue_total
+            )
+        };
+        self.calibrating_item = Some(item);
+        self.worker.set_status(&status);
+    }
+
+    fn start_automation(
+        &mut self,
+        settings: CollectionFillerSettings,
+        game_hwnd: HWND,
+        rate_limit: ActionRateLimit,
+    ) {
+        self.worker.set_status(tr!("status.starting_automation"));
         let red_dot_path = settings.red_dot_path.clone();
 
-        self.worker.start(
-            move |running: Arc<Mutex<bool>>,
-                  status: Arc<Mutex<String>>,
-                  _log: Arc<Mutex<std::collections::VecDeque<String>>>| {
-                let mut ctx = match AutomationContext::new(game_hwnd) {
+        let started = self.worker.start(
+            move |running: Arc<AtomicBool>,
+                  paused: Arc<AtomicBool>,
+                  status: StatusSender,
+                  log: SharedLog,
+                  stats: SharedStats| {
+                let throttle = ActionThrottle::new(rate_limit, Arc::clone(&log));
+                let mut ctx = match AutomationContext::new(game_hwnd, throttle) {
                     Ok(c) => c,
                     Err(e) => {
-                        *status.lock().unwrap() = format!("Error: {}", e);
-                        *running.lock().unwrap() = false;
+                        status.set(e.status());
+                        push_log_entry(&log, LogLevel::Error, &e.status());
+                        stats.lock().unwrap().record_error(&e);
+                        running.store(false, Ordering::Relaxed);
                         return;
                     }
                 };
 
-                // Load templates
-                let res = (|| -> Result<(), String> {
-                    ctx.store_template(&red_dot_path, settings.collection_tabs_area, "tabs_dots")?;
-                    ctx.store_template(&red_dot_path, settings.dungeon_list_area, "dungeon_dots")?;
+                // Load templates (not needed when detecting by color)
+                // The filler searches through scan_stored_templates, so the rustautogui
+                // match mode doesn't apply here
+                let res = (|| -> Result<(), HelperError> {
+                    if settings.color_detection.enabled {
+                        return Ok(());
+                    }
+                    let mode = TemplateMatchMode::default();
+                    ctx.store_template(
+                        &red_dot_path,
+                        settings.collection_tabs_area,
+                        "tabs_dots",
+                        mode,
+                    )?;
+                    ctx.store_template(
+                        &red_dot_path,
+                        settings.dungeon_list_area,
+                        "dungeon_dots",
+                        mode,
+                    )?;
                     ctx.store_template(
                         &red_dot_path,
                         settings.collection_items_area,
                         "items_dots",
+                        mode,
                     )?;
                     Ok(())
                 })();
 
                 if let Err(e) = res {
-                    *status.lock().unwrap() = format!("Template Error: {}", e);
-                    *running.lock().unwrap() = false;
+                    status.set(e.status());
+                    push_log_entry(&log, LogLevel::Error, &e.status());
+                    stats.lock().unwrap().record_error(&e);
+                    running.store(false, Ordering::Relaxed);
                     return;
                 }
 
-                *status.lock().unwrap() = "Scanning tabs...".to_string();
+                status.set(tr!("status.scanning_tabs"));
+                if settings.use_background_clicks {
+                    Worker::push_log(&log, "Background clicks enabled");
+                }
 
-                run_automation_loop(&mut ctx, settings, &running, &status);
+                let completed =
+                    run_automation_loop(&mut ctx, settings, &running, paused, &status, &log, stats);
 
-                *running.lock().unwrap() = false;
-                *status.lock().unwrap() = "Finished".to_string();
+                running.store(false, Ordering::Relaxed);
+                if completed {
+                    status.set(tr!("status.finished"));
+                }
             },
         );
+        if started {
+            self.worker.set_client_size(get_client_size(game_hwnd));
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
+    }
+}
+
+/// Tracks the last successful state change (new dot clicked, item registered, dot cleared)
+struct Watchdog {
+    last_progress: Instant,
+    // Re-clicking the same tab or dungeon is not progress
+    last_tab: Option<(u32, u32)>,
+    last_dungeon: Option<(u32, u32)>,
+    timeout: Option<Duration>,
+    recent_actions: VecDeque<String>,
+    // Time spent paused is not counted as lost progress
+    paused: Arc<AtomicBool>,
+    // Run statistics: each registered item counts as processed
+    stats: SharedStats,
+    // The run's time limit, if any
+    deadline: Option<RunDeadline>,
+}
+
+impl Watchdog {
+    const RECENT_ACTIONS: usize = 5;
+
+    fn new(
+        settings: &CollectionFillerSettings,
+        paused: Arc<AtomicBool>,
+        stats: SharedStats,
+    ) -> Self {
+        let timeout_secs = settings.watchdog_secs;
+        Self {
+            deadline: RunDeadline::for_run(&stats, settings.max_runtime_minutes),
+            last_progress: Instant::now(),
+            last_tab: None,
+            last_dungeon: None,
+            // 0 disables the watchdog
+            timeout: (timeout_secs > 0).then_some(Duration::from_secs(timeout_secs)),
+            recent_actions: VecDeque::new(),
+            paused,
+            stats,
+        }
+    }
+
+    /// Wait out a pause; false once a stop was requested
+    fn checkpoint(&mut self, running: &AtomicBool) -> bool {
+        let pause_started = Instant::now();
+        let was_paused = self.paused.load(Ordering::Relaxed);
+        let keep_going = Worker::checkpoint(running, &self.paused).is_continue();
+        if was_paused {
+            self.last_progress += pause_started.elapsed();
+        }
+        keep_going
+    }
+
+    /// Remember an action for the watchdog report without resetting the timer
+    fn note(&mut self, action: &str) {
+        self.recent_actions.push_back(action.to_string());
+        while self.recent_actions.len() > Self::RECENT_ACTIONS {
+            self.recent_actions.pop_front();
+        }
+    }
+
+    fn progress(&mut self, action: &str) {
+        self.last_progress = Instant::now();
+        self.note(action);
+    }
+
+    fn tab_clicked(&mut self, pos: (u32, u32)) {
+        let action = format!("Clicked tab at ({}, {})", pos.0, pos.1);
+        if Self::is_new_position(&mut self.last_tab, pos) {
+            self.progress(&action);
+        } else {
+            self.note(&action);
+        }
+    }
+
+    fn dungeon_clicked(&mut self, pos: (u32, u32)) {
+        let action = format!("Clicked dungeon at ({}, {})", pos.0, pos.1);
+        if Self::is_new_position(&mut self.last_dungeon, pos) {
+            self.progress(&action);
+        } else {
+            self.note(&action);
+        }
+    }
+
+    fn is_new_position(last: &mut Option<(u32, u32)>, pos: (u32, u32)) -> bool {
+        let is_new = !last.is_some_and(|last| is_position_near(pos, last, 20.0));
+        *last = Some(pos);
+        is_new
+    }
+
+    fn item_registered(&mut self, pos: (u32, u32)) {
+        self.progress(&format!("Registered item at ({}, {})", pos.0, pos.1));
+        self.stats.lock().unwrap().add_items(1);
+    }
+
+    /// Sleep between actions, cut short by a stop or the time limit
+    fn sleep(&self, running: &AtomicBool, ms: u64) -> bool {
+        Worker::sleep_within_deadline(running, ms, self.deadline)
+    }
+
+    fn time_limit_reached(&self) -> Option<RunDeadline> {
+        self.deadline.filter(RunDeadline::passed)
+    }
+
+    /// No progress for too long, or out of time: either way the nested loops unwind
+    fn expired(&self) -> bool {
+        self.time_limit_reached().is_some()
+            || self
+                .timeout
+                .is_some_and(|timeout| self.last_progress.elapsed() >= timeout)
+    }
+}
+
+// Input helpers honoring the background-click setting and the action rate limit
+fn click_screen_pos(
+    ctx: &mut AutomationContext,
+    settings: &CollectionFillerSettings,
+    pos: (u32, u32),
+) {
+    ctx.throttle.wait();
+    if settings.use_background_clicks {
+        background_click_at_screen(ctx.game_hwnd, pos.0, pos.1);
+    } else {
+        click_at_screen(&mut ctx.gui, ctx.game_hwnd, pos.0, pos.1);
+    }
+}
+
+fn click_button_pos(
+    ctx: &mut AutomationContext,
+    settings: &CollectionFillerSettings,
+    pos: NormPoint,
+) {
+    ctx.throttle.wait();
+    if settings.use_background_clicks {
+        background_click_at_window_pos(ctx.game_hwnd, pos);
+    } else {
+        click_at_window_pos(&mut ctx.gui, ctx.game_hwnd, pos);
+    }
+}
+
+fn scroll_area(
+    ctx: &mut AutomationContext,
+    settings: &CollectionFillerSettings,
+    log: &SharedLog,
+    area: NormRect,
+) {
+    let scroll = &settings.scroll;
+    ctx.throttle.wait();
+    if settings.use_background_clicks {
+        if background_scroll_in_area(
+            ctx.game_hwnd,
+            area,
+            scroll.direction,
+            scroll.ticks,
+            scroll.delta_per_tick,
+        ) {
+            return;
+        }
+        // Some clients ignore posted wheel messages; physical scroll still works
+        push_log_entry(
+            log,
+            LogLevel::Warn,
+            "Background scroll not possible, using physical scroll",
+        );
+    }
+    scroll_in_area(
+        &mut ctx.gui,
+        ctx.game_hwnd,
+        area,
+        scroll.direction,
+        scroll.ticks,
+        scroll.delta_per_tick,
+    );
+}
+
+/// Search area of each dot alias
+fn dot_area(settings: &CollectionFillerSettings, alias: &str) -> Option<NormRect> {
+    match alias {
+        "tabs_dots" => settings.collection_tabs_area,
+        "dungeon_dots" => settings.dungeon_list_area,
+        _ => settings.collection_items_area,
+    }
+}
+
+/// Dots in the areas of `aliases`: template matches from one capture, or blobs of the
+/// dot color when detecting by color
+fn scan_dots(
+    ctx: &AutomationContext,
+    settings: &CollectionFillerSettings,
+    aliases: &[&str],
+) -> TemplateScan {
+    let color = &settings.color_detection;
+    if !color.enabled {
+        return scan_stored_templates(ctx, aliases, settings.red_dot_tolerance, None);
+    }
+
+    let mut scan = TemplateScan::default();
+    for &alias in aliases {
+        let region = match dot_area(settings, alias) {
+            Some(area) => denormalize_rect(ctx.game_hwnd, area.0, area.1, area.2, area.3),
+            None => get_client_size(ctx.game_hwnd).map(|(w, h)| (0, 0, w, h)),
+        };
+        let Some(region) = region else {
+            continue;
+        };
+        let blobs = find_color_regions(
+            ctx.game_hwnd,
+            region,
+            color.color,
+            color.tolerance,
+            color.min_blob_size,
+        )
+        .unwrap_or_default();
+        let dots = blobs
+            .into_iter()
+            .filter_map(|(x, y)| client_to_screen_coords(ctx.game_hwnd, x, y))
+            .map(|(x, y)| TemplateMatch {
+                x: x as u32,
+                y: y as u32,
+                score: 1.0,
+            })
+            .collect();
+        scan.insert(alias, dots);
+    }
+    scan
+}
+
+/// Keep only RED dots (not grey dots); blobs found by color already have the dot color
+fn only_red(settings: &CollectionFillerSettings, dots: Vec<TemplateMatch>) -> Vec<TemplateMatch> {
+    if settings.color_detection.enabled {
+        dots
+    } else {
+        filter_red_dots(dots, settings.min_red, settings.red_dominance)
     }
 }
 
 // Automation logic (non-UI)
+// Returns false if the run was aborted by the watchdog or the time limit
 fn run_automation_loop(
     ctx: &mut AutomationContext,
     settings: CollectionFillerSettings,
-    running: &Arc<Mutex<bool>>,
-    status: &Arc<Mutex<String>>,
-) {
-    while *running.lock().unwrap() {
+    running: &AtomicBool,
+    paused: Arc<AtomicBool>,
+    status: &StatusSender,
+    log: &SharedLog,
+    stats: SharedStats,
+) -> bool {
+    let mut watchdog = Watchdog::new(&settings, paused, stats);
+
+    while watchdog.checkpoint(running) {
+        if let Some(deadline) = watchdog.time_limit_reached() {
+            let message = deadline.reached_message();
+            status.set(message.clone());
+            Worker::push_log(log, &message);
+            return false;
+        }
+        if watchdog.expired() {
+            let message = format!("Watchdog: no progress for {}s", settings.watchdog_secs);
+            status.set(message.clone());
+            push_log_entry(log, LogLevel::Warn, &message);
+            sounds().play(Event::WatchdogFired);
+            Worker::push_log(log, "Last actions:");
+            for action in &watchdog.recent_actions {
+                Worker::push_log(log, &format!("  {}", action));
+            }
+            return false;
+        }
+
+        // Clicks and searches follow the game window if it was dragged
+        ctx.refresh(log);
+
         // Find potential tab dots (using lower tolerance to catch all candidates)
-        let potential_dots =
-            match find_stored_template(&mut ctx.gui, "tabs_dots", settings.red_dot_tolerance) {
-                Some(dots) if !dots.is_empty() => dots,
-                _ => {
-                    *status.lock().unwrap() = "All collections complete!".to_string();
-                    break;
-                }
-            };
+        let potential_dots = scan_dots(ctx, &settings, &["tabs_dots"]).take("tabs_dots");
+        if potential_dots.is_empty() {
+            status.set(tr!("status.collections_complete"));
+            break;
+        }
 
         // Filter by color to keep only RED dots (not grey dots)
-        let red_dots = crate::automation::detection::filter_red_dots(
-            potential_dots,
-            settings.min_red,
-            settings.red_dominance,
-        );
+        let red_dots = only_red(&settings, potential_dots);
 
         if red_dots.is_empty() {
-            *status.lock().unwrap() = "All collections complete!".to_string();
+            status.set(tr!("status.collections_complete"));
             break;
         }
 
-        let tab_pos = red_dots[0];
-        *status.lock().unwrap() = "Found tab, clicking...".to_string();
-        click_at_screen(&mut ctx.gui, tab_pos.0, tab_pos.1);
-        delay_ms(settings.delay_ms);
+        let tab_pos = red_dots[0].pos();
+        status.set(tr!("status.found_tab"));
+        click_screen_pos(ctx, &settings, tab_pos);
+        watchdog.tab_clicked(tab_pos);
+        watchdog.sleep(running, settings.delay_ms);
 
-        process_dungeon_list(ctx, &settings, running, status, tab_pos);
+        process_dungeon_list(ctx, &settings, running, status, log, &mut watchdog, tab_pos);
     }
+
+    true
 }
 
 fn process_dungeon_list(
     ctx: &mut AutomationContext,
     settings: &CollectionFillerSettings,
-    running: &Arc<Mutex<bool>>,
-    status: &Arc<Mutex<String>>,
+    running: &AtomicBool,
+    status: &StatusSender,
+    log: &SharedLog,
+    watchdog: &mut Watchdog,
     original_tab_pos: (u32, u32),
 ) {
     let mut current_page = 1;
     let mut pages_checked_this_cycle = 0;
 
-    let tab_check = |gui: &mut rustautogui::RustAutoGui| -> bool {
-        find_stored_template(gui, "tabs_dots", settings.red_dot_tolerance)
-            .map(|dots| {
-                dots.iter().any(|d| {
-                    ((d.0 as f32 - original_tab_pos.0 as f32).powi(2)
-                        + (d.1 as f32 - original_tab_pos.1 as f32).powi(2))
-                    .sqrt()
-                        < 20.0
-                })
-            })
-            .unwrap_or(false)
-    };
+    while watchdog.checkpoint(running) && !watchdog.expired() {
+        ctx.refresh(log);
 
-    while *running.lock().unwrap() && tab_check(&mut ctx.gui) {
-        *status.lock().unwrap() = format!("Processing page {}", current_page);
+        // The tab check and the page's first dungeon probe share one capture
+        let mut scan = scan_dots(ctx, settings, &["tabs_dots", "dungeon_dots"]);
+        let tab_still_red = scan.take("tabs_dots").iter().any(|d| {
+            ((d.x as f32 - original_tab_pos.0 as f32).powi(2)
+                + (d.y as f32 - original_tab_pos.1 as f32).powi(2))
+            .sqrt()
+                < 20.0
+        });
+        if !tab_still_red {
+            watchdog.progress(&format!(
+                "Tab at ({}, {}) cleared",
+                original_tab_pos.0, original_tab_pos.1
+            ));
+            break;
+        }
+
+        status.set(tr!("status.processing_page", page = current_page));
 
-        let found_work = process_page_dungeons(ctx, settings, running, status);
+        let found_work = process_page_dungeons(
+            ctx,
+            settings,
+            running,
+            status,
+            log,
+            watchdog,
+            scan.take("dungeon_dots"),
+        );
 
         if found_work {
             current_page = 1;
@@ -290,15 +824,17 @@ fn process_dungeon_list(
                     4 => settings.page_4_pos,
                     _ => None,
                 };
-                if let Some((x, y)) = btn {
-                    click_at_window_pos(&mut ctx.gui, ctx.game_hwnd, (x, y));
-                    delay_ms(settings.delay_ms);
+                if let Some(pos) = btn {
+                    click_button_pos(ctx, settings, pos);
+                    watchdog.note(&format!("Advanced to page {}", current_page));
+                    watchdog.sleep(running, settings.delay_ms);
                 }
             } else {
                 if pages_checked_this_cycle >= 4 {
-                    if let Some((x, y)) = settings.arrow_right_pos {
-                        click_at_window_pos(&mut ctx.gui, ctx.game_hwnd, (x, y));
-                        delay_ms(settings.delay_ms);
+                    if let Some(pos) = settings.arrow_right_pos {
+                        click_button_pos(ctx, settings, pos);
+                        watchdog.note("Clicked arrow right");
+                        watchdog.sleep(running, settings.delay_ms);
                         current_page = 1;
                     } else {
                         break;
@@ -313,121 +849,129 @@ fn process_dungeon_list(
     }
 }
 
+// `dungeon_dots` are the dungeon list matches from a capture taken since the last click
 fn process_page_dungeons(
     ctx: &mut AutomationContext,
     settings: &CollectionFillerSettings,
-    running: &Arc<Mutex<bool>>,
-    status: &Arc<Mutex<String>>,
+    running: &AtomicBool,
+    status: &StatusSender,
+    log: &SharedLog,
+    watchdog: &mut Watchdog,
+    dungeon_dots: Vec<TemplateMatch>,
 ) -> bool {
     let mut any_work_done = false;
+    // Matches still valid for the list as it is now, so the next probe can skip a capture
+    let mut fresh_dots = Some(dungeon_dots);
 
     // Loop until no more red dots found in dungeon list on this page
-    while *running.lock().unwrap() {
+    while watchdog.checkpoint(running) && !watchdog.expired() {
         // Find potential dungeon dots and filter by color
-        let potential_dots =
-            match find_stored_template(&mut ctx.gui, "dungeon_dots", settings.red_dot_tolerance) {
-                Some(dots) if !dots.is_empty() => dots,
-                _ => break, // No more dungeons on this page
-            };
-
-        let red_dots = crate::automation::detection::filter_red_dots(
-            potential_dots,
-            settings.min_red,
-            settings.red_dominance,
-        );
+        let potential_dots = fresh_dots
+            .take()
+            .unwrap_or_else(|| scan_dots(ctx, settings, &["dungeon_dots"]).take("dungeon_dots"));
+        if potential_dots.is_empty() {
+            break; // No more dungeons on this page
+        }
+
+        let red_dots = only_red(settings, potential_dots);
 
         if red_dots.is_empty() {
             break; // No red dungeons on this page
         }
 
-        let dungeon_dot = red_dots[0];
+        let dungeon_dot = red_dots[0].pos();
 
         // Found a dungeon with a red dot
-        *status.lock().unwrap() = "Processing dungeon...".to_string();
-        click_at_screen(&mut ctx.gui, dungeon_dot.0, dungeon_dot.1);
-        delay_ms(settings.delay_ms);
+        status.set(tr!("status.processing_dungeon"));
+        click_screen_pos(ctx, settings, dungeon_dot);
+        watchdog.dungeon_clicked(dungeon_dot);
+        watchdog.sleep(running, settings.delay_ms);
         // Note: No scroll-up needed - game UI always starts at top when entering dungeon
 
         let max_scroll_passes = 50;
         let mut dungeon_finished = false;
 
         for _ in 0..max_scroll_passes {
-            if !*running.lock().unwrap() {
+            if !watchdog.checkpoint(running) || watchdog.expired() {
                 break;
             }
 
             // 1. Process all visible items at current scroll
-            let _ = process_visible_items(ctx, settings, running, status);
+            let _ = process_visible_items(ctx, settings, running, status, watchdog);
             any_work_done = true;
 
             // 2. Double check item area for stragglers (Python logic compliance)
-            let _ = process_visible_items(ctx, settings, running, status);
+            let last_scan = process_visible_items(ctx, settings, running, status, watchdog);
 
             // 3. Check if THIS dungeon is complete
-            // We scan the dungeon list again to see if our dungeon_dot is still red
-            let still_active = match find_stored_template(
-                &mut ctx.gui,
-                "dungeon_dots",
-                settings.red_dot_tolerance,
-            ) {
-                Some(dots) => dots.iter().any(|d| is_position_near(*d, dungeon_dot, 20.0)),
-                None => false,
-            };
+            // We scan the dungeon list again to see if our dungeon_dot is still red,
+            // reusing the capture that found no items when nothing was clicked since
+            let dots = last_scan.unwrap_or_else(|| {
+                scan_dots(ctx, settings, &["dungeon_dots"]).take("dungeon_dots")
+            });
+            let still_active = dots
+                .iter()
+                .any(|d| is_position_near(d.pos(), dungeon_dot, 20.0));
 
             if !still_active {
                 dungeon_finished = true;
+                watchdog.progress(&format!(
+                    "Dungeon at ({}, {}) cleared",
+                    dungeon_dot.0, dungeon_dot.1
+                ));
+                // Nothing was clicked since, so pick the next dungeon from these matches
+                fresh_dots = Some(dots);
                 break; // Dungeon done!
             }
 
-            // 4. Scroll down to find more items (1 tick = 1 row in game)
+            // 4. Scroll to find more items (1 tick of 120 = 1 row in game)
             if let Some(items_area) = settings.collection_items_area {
-                scroll_in_area(&mut ctx.gui, ctx.game_hwnd, items_area, 1);
+                scroll_area(ctx, settings, log, items_area);
             }
-            delay_ms(settings.delay_ms);
+            watchdog.sleep(running, settings.delay_ms);
         }
 
         if !dungeon_finished {
             // Safe guard: if we scrolled 50 times and it's still red, maybe we're stuck.
             // But we break the inner loop to move to next dungeon check (or see it again)
-            *status.lock().unwrap() = "Dungeon timeout/stuck, scanning list again...".to_string();
+            status.set(tr!("status.dungeon_stuck"));
         }
     }
 
     any_work_done
 }
 
+// Clicks every red item dot in view. Each probe also matches the dungeon list from the
+// same capture; when the pass ends because no items are left, those dungeon list
+// matches are returned (nothing was clicked after that capture).
 fn process_visible_items(
     ctx: &mut AutomationContext,
     settings: &CollectionFillerSettings,
-    running: &Arc<Mutex<bool>>,
-    status: &Arc<Mutex<String>>,
-) -> bool {
-    let mut processed = false;
+    running: &AtomicBool,
+    status: &StatusSender,
+    watchdog: &mut Watchdog,
+) -> Option<Vec<TemplateMatch>> {
     let mut last_pos: Option<(u32, u32)> = None;
     let mut stuck_hits = 0;
 
-    while *running.lock().unwrap() {
+    while watchdog.checkpoint(running) && !watchdog.expired() {
         // Find potential item dots and filter by color
-        let potential_dots =
-            match find_stored_template(&mut ctx.gui, "items_dots", settings.red_dot_tolerance) {
-                Some(dots) if !dots.is_empty() => dots,
-                _ => break,
-            };
-
-        let red_dots = crate::automation::detection::filter_red_dots(
-            potential_dots,
-            settings.min_red,
-            settings.red_dominance,
-        );
+        let mut scan = scan_dots(ctx, settings, &["items_dots", "dungeon_dots"]);
+        let potential_dots = scan.take("items_dots");
+        if potential_dots.is_empty() {
+            return Some(scan.take("dungeon_dots"));
+        }
+
+        let red_dots = only_red(settings, potential_dots);
 
-        match red_dots.first() {
-            Some(&pos) => {
+        match red_dots.first().map(TemplateMatch::pos) {
+            Some(pos) => {
                 // Stuck check
                 if let Some(last) = last_pos {
                     if is_position_near(pos, last, 5.0) {
                         stuck_hits += 1;
                         if stuck_hits >= 3 {
-                            *status.lock().unwrap() = "Stuck on item, skipping".to_string();
+                            status.set(tr!("status.item_stuck"));
                             break;
                         }
                     } else {
@@ -436,8 +980,8 @@ fn process_visible_items(
                 }
                 last_pos = Some(pos);
 
-                click_at_screen(&mut ctx.gui, pos.0, pos.1);
-                delay_ms(settings.delay_ms);
+                click_screen_pos(ctx, settings, pos);
+                watchdog.sleep(running, settings.delay_ms);
 
                 let btns = [
                     settings.auto_refill_pos,
@@ -445,17 +989,54 @@ fn process_visible_items(
                     settings.yes_pos,
                 ];
                 for btn in btns {
-                    if let Some((x, y)) = btn {
-                        click_at_window_pos(&mut ctx.gui, ctx.game_hwnd, (x, y));
-                        delay_ms(settings.delay_ms);
+                    if let Some(btn_pos) = btn {
+                        click_button_pos(ctx, settings, btn_pos);
+                        if !watchdog.sleep(running, settings.delay_ms) {
+                            break;
+                        }
                     }
                 }
 
-                processed = true;
-                delay_ms(settings.delay_ms);
+                // Repeated hits on the same dot don't count as progress
+                if stuck_hits == 0 {
+                    watchdog.item_registered(pos);
+                }
+                watchdog.sleep(running, settings.delay_ms);
             }
-            None => break,
+            None => return Some(scan.take("dungeon_dots")),
         }
     }
-    processed
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_watchdog_ignores_reclicks_of_the_same_dot() {
+        let stats: SharedStats = Arc::new(Mutex::new(WorkerStats::default()));
+        let mut watchdog = Watchdog::new(
+            &CollectionFillerSettings::default(),
+            Arc::new(AtomicBool::new(false)),
+            stats,
+        );
+
+        watchdog.tab_clicked((100, 50));
+        let first = watchdog.last_progress;
+        watchdog.tab_clicked((104, 52));
+        watchdog.dungeon_clicked((300, 200));
+        let after_dungeon = watchdog.last_progress;
+        watchdog.dungeon_clicked((300, 200));
+        watchdog.note("Advanced to page 2");
+
+        assert!(after_dungeon >= first);
+        assert_eq!(watchdog.last_progress, after_dungeon);
+        assert_eq!(watchdog.recent_actions.len(), 5);
+
+        watchdog.tab_clicked((100, 90));
+        assert!(watchdog.last_progress >= after_dungeon);
+        assert_eq!(watchdog.last_tab, Some((100, 90)));
+    }
 }
@@ -1,28 +1,24 @@
-use crate::automation::interaction::delay_ms;
+use crate::automation::macro_runner::{MacroOutcome, MacroRun};
 use crate::calibration::{CalibrationManager, CalibrationResult};
 use crate::core::coords::{denormalize_point, denormalize_rect};
-use crate::core::worker::Worker;
+use crate::core::overlay_window::{OverlayShape, REGION_AREA_COLOR, REGION_POINT_COLOR};
+use crate::core::window::{find_game_windows, get_client_size};
+use crate::core::worker::{JournalEntry, LogEntry, Worker, WorkerStats};
 use crate::settings::{
-    ComparisonMode, CustomMacroSettings, MacroAction, OcrDecodeMode, OcrNameMatchMode,
+    ActionRateLimit, CustomMacroSettings, MacroAction, MacroId, OcrModelSettings,
 };
-use crate::tools::r#trait::Tool;
-use crate::ui::custom_macro::{render_ui, CustomMacroUiAction};
+use crate::tools::r#trait::{StopReason, Tool, ToolsChange};
+use crate::tools::undo::UndoEntry;
+use crate::ui::custom_macro::{render_ui, ClientTarget, CustomMacroUiAction};
 use eframe::egui;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::HWND;
 use windows::Win32::UI::WindowsAndMessaging::{
     MessageBoxW, MB_ICONINFORMATION, MB_OK, MB_SETFOREGROUND, MB_TOPMOST,
 };
 
-fn format_ocr_display(text: &str) -> String {
-    let mut display = text.replace("\r\n", "\\n");
-    display = display.replace('\r', "\\n");
-    display = display.replace('\n', "\\n");
-    display
-}
-
 fn show_success_message(stat: &str, value: i32) {
     let title = "OCR Match Found";
     let body = format!("Match found: {} {}", stat, value);
@@ -41,7 +37,7 @@ fn show_success_message(stat: &str, value: i32) {
 
 pub struct CustomMacroTool {
     // Which macro profile this tool is managing
-    macro_index: usize,
+    macro_id: MacroId,
 
     // Runtime state (Generic Worker)
     worker: Worker,
@@ -51,54 +47,127 @@ pub struct CustomMacroTool {
     calibrating_action_index: Option<usize>,
     ocr_region_calibration: CalibrationManager,
     ocr_calibrating_action_index: Option<usize>,
+    show_regions: bool,
+
+    // Removed data waiting to be collected by the app's undo stack
+    undo_entries: Vec<UndoEntry>,
+
+    // Macro list change waiting to be collected by the app
+    tools_change: Option<ToolsChange>,
+
+    // One-shot runs: iterations still to run, and whether the current run is one
+    one_shot_pending: Arc<AtomicU32>,
+    one_shot_run: bool,
+
+    // Game clients from the last "Find clients" (window handles don't outlive the game,
+    // so they aren't saved)
+    clients: Vec<ClientTarget>,
 }
 
 impl CustomMacroTool {
-    pub fn new(macro_index: usize) -> Self {
+    pub fn new(macro_id: MacroId, name: &str) -> Self {
         Self {
-            macro_index,
-            worker: Worker::new(),
+            macro_id,
+            worker: Worker::new(name),
             calibration: CalibrationManager::new(),
             calibrating_action_index: None,
             ocr_region_calibration: CalibrationManager::new(),
             ocr_calibrating_action_index: None,
+            show_regions: false,
+            undo_entries: Vec::new(),
+            tools_change: None,
+            one_shot_pending: Arc::new(AtomicU32::new(0)),
+            one_shot_run: false,
+            clients: Vec::new(),
         }
     }
 }
 
 impl Tool for CustomMacroTool {
-    fn stop(&mut self) {
-        self.worker.stop();
-        if self.worker.get_status().contains("Stopped") {
-            // Already stopped
-        } else {
-            self.worker.set_status("Stopped (emergency hotkey)");
-        }
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
     }
 
     fn is_running(&self) -> bool {
         self.worker.is_running()
     }
 
-    fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
-        if self.macro_index >= app_settings.custom_macros.len() {
-            self.worker.set_status("Macro profile not found");
-            return;
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
         }
+    }
 
-        let settings = &app_settings.custom_macros[self.macro_index].settings;
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
+        let Some(named_macro) = app_settings.macro_by_id(self.macro_id) else {
+            self.worker.set_status_quiet("Macro profile not found");
+            self.worker.log_error("Macro profile not found");
+            return;
+        };
+        let settings = &named_macro.settings;
 
         if let Some(hwnd) = game_hwnd {
             if !settings.actions.is_empty() {
-                self.start_macro(settings.clone(), hwnd);
+                self.start_macro(
+                    settings.clone(),
+                    hwnd,
+                    app_settings.global.action_rate_limit,
+                    app_settings.global.ocr_models.clone(),
+                    None,
+                );
             } else {
-                self.worker.set_status("No actions configured");
+                self.worker.set_status(tr!("status.no_actions"));
             }
         } else {
-            self.worker.set_status("Connect to game first");
+            self.worker.set_status(tr!("status.connect_first"));
         }
     }
 
+    fn start_one_shot(
+        &mut self,
+        app_settings: &crate::settings::AppSettings,
+        game_hwnd: Option<HWND>,
+    ) {
+        let Some(named_macro) = app_settings.macro_by_id(self.macro_id) else {
+            return;
+        };
+        let settings = &named_macro.settings;
+
+        if self.worker.is_running() {
+            // A normal run is never interrupted; one-shot presses queue only when asked to
+            if self.one_shot_run && settings.queue_one_shots {
+                let queued = self.one_shot_pending.fetch_add(1, Ordering::Relaxed) + 1;
+                self.worker
+                    .set_status(&tr!("status.one_shot_queued", pending = queued));
+            }
+            return;
+        }
+
+        let Some(hwnd) = game_hwnd else {
+            self.worker.set_status(tr!("status.connect_first"));
+            return;
+        };
+        if settings.actions.is_empty() {
+            self.worker.set_status(tr!("status.no_actions"));
+            return;
+        }
+        self.one_shot_pending.store(1, Ordering::Relaxed);
+        let pending = Arc::clone(&self.one_shot_pending);
+        self.start_macro(
+            settings.clone(),
+            hwnd,
+            app_settings.global.action_rate_limit,
+            app_settings.global.ocr_models.clone(),
+            Some(pending),
+        );
+    }
+
     fn update(
         &mut self,
         ctx: &egui::Context,
@@ -107,52 +176,23 @@ impl Tool for CustomMacroTool {
         game_hwnd: Option<HWND>,
         hotkey_error: Option<&str>,
     ) {
-        if self.macro_index >= settings.custom_macros.len() {
+        let Some(macro_index) = settings.macro_index(self.macro_id) else {
             ui.colored_label(egui::Color32::RED, "Error: Macro profile not found");
             return;
-        }
+        };
 
         // Can delete this macro if there's more than 1 total
         // Calculate this BEFORE taking mutable borrow
         let can_delete = settings.custom_macros.len() > 1;
 
-        let macro_settings = &mut settings.custom_macros[self.macro_index];
-
         // Handle calibration interaction
         if let Some(hwnd) = game_hwnd {
-            if let Some(result) = self.calibration.update(hwnd) {
-                if let CalibrationResult::Point(x, y) = result {
-                    if let Some(idx) = self.calibrating_action_index.take() {
-                        if let Some(action) = macro_settings.settings.actions.get_mut(idx) {
-                            if let MacroAction::Click { coordinate, .. } = action {
-                                *coordinate = Some((x, y));
-                                self.worker.set_status(&format!(
-                                    "Click position set: ({:.3}, {:.3})",
-                                    x, y
-                                ));
-                            }
-                        }
-                    }
-                }
-            }
-
-            if let Some(result) = self.ocr_region_calibration.update(hwnd) {
-                if let CalibrationResult::Area(l, t, w, h) = result {
-                    if let Some(idx) = self.ocr_calibrating_action_index.take() {
-                        if let Some(action) = macro_settings.settings.actions.get_mut(idx) {
-                            if let MacroAction::OcrSearch { ocr_region, .. } = action {
-                                *ocr_region = Some((l, t, w, h));
-                                self.worker.set_status("OCR region calibrated");
-                            }
-                        }
-                    }
-                }
-            }
+            self.update_calibration(settings, hwnd);
         } else {
             // If disconnected, ensure we aren't running
             if self.worker.is_running() {
                 self.worker.stop();
-                self.worker.set_status("Disconnected");
+                self.worker.set_status(tr!("header.disconnected"));
             }
         }
 
@@ -160,20 +200,36 @@ impl Tool for CustomMacroTool {
             ctx.request_repaint();
         }
 
-        let is_running = self.worker.is_running();
+        let rate_limit = settings.global.action_rate_limit;
+        let ocr_models = settings.global.ocr_models.clone();
+        let macro_settings = &mut settings.custom_macros[macro_index];
+        let recent_image_paths = &mut settings.global.recent_image_paths;
+
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
         let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
         let click_calibrating_index = self.calibrating_action_index;
         let ocr_calibrating_index = self.ocr_calibrating_action_index;
 
         let action = render_ui(
             ui,
             macro_settings,
+            recent_image_paths,
             click_calibrating_index,
             ocr_calibrating_index,
+            self.calibration
+                .magnifier()
+                .or(self.ocr_region_calibration.magnifier()),
+            &mut self.show_regions,
             is_running,
+            self.worker.is_paused(),
             &status,
+            &stats,
             game_hwnd.is_some(),
+            game_hwnd.and_then(get_client_size),
             can_delete,
+            &mut self.clients,
             hotkey_error,
         );
 
@@ -181,446 +237,299 @@ impl Tool for CustomMacroTool {
             CustomMacroUiAction::StartCalibration(action_index) => {
                 self.calibrating_action_index = Some(action_index);
                 self.calibration.start_point();
-                self.worker
-                    .set_status("Click on the game window to set coordinates");
+                self.worker.set_status(tr!("status.click_to_set_position"));
             }
             CustomMacroUiAction::CancelCalibration => {
                 self.calibration.cancel();
                 self.calibrating_action_index = None;
-                self.worker.set_status("Cancelled");
+                self.worker.set_status(tr!("status.cancelled"));
             }
             CustomMacroUiAction::StartOcrRegionCalibration(action_index) => {
                 self.ocr_calibrating_action_index = Some(action_index);
                 self.ocr_region_calibration.start_area();
-                self.worker.set_status("Click top-left, then bottom-right");
+                self.worker.set_status(tr!("status.click_area_corners"));
             }
             CustomMacroUiAction::CancelOcrRegionCalibration => {
                 self.ocr_region_calibration.cancel();
                 self.ocr_calibrating_action_index = None;
-                self.worker.set_status("OCR region calibration cancelled");
+                self.worker
+                    .set_status(tr!("status.ocr_calibration_cancelled"));
             }
             CustomMacroUiAction::StartMacro => {
                 if game_hwnd.is_none() {
-                    self.worker.set_status("Connect to game first");
+                    self.worker.set_status(tr!("status.connect_first"));
                 } else if macro_settings.settings.actions.is_empty() {
-                    self.worker.set_status("No actions configured");
+                    self.worker.set_status(tr!("status.no_actions"));
                 } else {
-                    self.start_macro(macro_settings.settings.clone(), game_hwnd.unwrap());
+                    self.start_macro(
+                        macro_settings.settings.clone(),
+                        game_hwnd.unwrap(),
+                        rate_limit,
+                        ocr_models,
+                        None,
+                    );
                 }
             }
             CustomMacroUiAction::StopMacro => {
-                self.stop();
+                self.stop(StopReason::User);
+            }
+            CustomMacroUiAction::TogglePause => {
+                self.set_paused(!self.worker.is_paused());
             }
             CustomMacroUiAction::DeleteMacro => {
                 // Delete this macro from settings
-                if settings.custom_macros.len() > 1
-                    && self.macro_index < settings.custom_macros.len()
-                {
-                    settings.custom_macros.remove(self.macro_index);
-                    settings.auto_save();
-                    // Note: app.rs needs to rebuild tools after this frame
+                if settings.custom_macros.len() > 1 {
+                    let named_macro = settings.custom_macros.remove(macro_index);
+                    self.undo_entries.push(UndoEntry::DeletedMacro {
+                        index: macro_index,
+                        named_macro,
+                    });
+                    self.tools_change = Some(ToolsChange::MacroRemoved(macro_index));
                 }
             }
+            CustomMacroUiAction::DeleteAction(action_index) => {
+                if let Some(named_macro) = settings.macro_by_id_mut(self.macro_id) {
+                    if action_index < named_macro.settings.actions.len() {
+                        let action = named_macro.settings.actions.remove(action_index);
+                        self.undo_entries.push(UndoEntry::DeletedAction {
+                            macro_id: self.macro_id,
+                            action_index,
+                            action,
+                        });
+                    }
+                }
+            }
+            CustomMacroUiAction::ImportScript(actions) => {
+                if let Some(named_macro) = settings.macro_by_id_mut(self.macro_id) {
+                    let count = actions.len();
+                    let previous = std::mem::replace(&mut named_macro.settings.actions, actions);
+                    self.undo_entries.push(UndoEntry::ReplacedActions {
+                        macro_id: self.macro_id,
+                        actions: previous,
+                    });
+                    self.worker
+                        .set_status(&tr!("status.script_imported", count = count));
+                }
+            }
+            CustomMacroUiAction::ResetToDefaults => {
+                // Keeps the macro's name and overlay flag
+                self.calibration.cancel();
+                self.calibrating_action_index = None;
+                self.ocr_region_calibration.cancel();
+                self.ocr_calibrating_action_index = None;
+                if let Some(named_macro) = settings.macro_by_id_mut(self.macro_id) {
+                    named_macro.settings = CustomMacroSettings::default();
+                }
+                self.worker.set_status(tr!("status.settings_reset"));
+            }
+            CustomMacroUiAction::FindClients => {
+                let selected = self.selected_clients();
+                self.clients = find_game_windows()
+                    .into_iter()
+                    .map(|(hwnd, title)| ClientTarget {
+                        selected: selected.contains(&hwnd),
+                        hwnd,
+                        title,
+                    })
+                    .collect();
+                self.worker
+                    .set_status(&tr!("status.clients_found", count = self.clients.len()));
+            }
             CustomMacroUiAction::None => {}
         }
     }
 
-    fn get_log(&self) -> Vec<String> {
-        self.worker.get_log()
-    }
-}
-
-impl CustomMacroTool {
-    fn start_macro(&mut self, settings: CustomMacroSettings, game_hwnd: HWND) {
-        self.worker.set_status("Running macro...");
+    fn update_calibration(&mut self, settings: &mut crate::settings::AppSettings, hwnd: HWND) {
+        self.ocr_region_calibration
+            .set_grid_size(settings.global.calibration_grid_px);
+        let Some(macro_settings) = settings.macro_by_id_mut(self.macro_id) else {
+            return;
+        };
 
-        // Use generic worker
-        self.worker.start(move |running: Arc<Mutex<bool>>, status: Arc<Mutex<String>>, log: Arc<Mutex<std::collections::VecDeque<String>>>| {
-            use crate::core::input::click_at_position;
-            use crate::automation::context::AutomationContext;
-            use crate::core::screen_capture::capture_window_region;
-            use crate::core::ocr_parser::{parse_ocr_result, matches_target};
-            use crate::core::window::client_to_screen_coords;
-            use ocrs::{OcrEngine, OcrEngineParams, ImageSource, DecodeMethod};
-
-            let mut ctx = match AutomationContext::new(game_hwnd) {
-                Ok(c) => c,
-                Err(e) => {
-                    *status.lock().unwrap() = format!("Error: {}", e);
-                    *running.lock().unwrap() = false;
-                    return;
-                }
-            };
-
-            // Initialize OCR engine only if needed
-            let has_ocr_actions = settings.actions.iter().any(|a| matches!(a, MacroAction::OcrSearch { .. }));
-            let mut ocr_engine: Option<OcrEngine> = None;
-
-            if has_ocr_actions {
-                *status.lock().unwrap() = "Loading OCR models...".to_string();
-
-                // Determine decode configuration from first OCR action
-                let mut decode_mode_cfg = OcrDecodeMode::Greedy;
-                let mut beam_width_cfg: u32 = 10;
-                for a in &settings.actions {
-                    if let MacroAction::OcrSearch { decode_mode, beam_width, .. } = a {
-                        decode_mode_cfg = *decode_mode;
-                        beam_width_cfg = *beam_width;
-                        break;
+        if let Some(result) = self.calibration.update(hwnd) {
+            if let CalibrationResult::Cancelled = result {
+                self.calibrating_action_index = None;
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            } else if let CalibrationResult::Point(x, y) = result {
+                if let Some(idx) = self.calibrating_action_index.take() {
+                    if let Some(action) = macro_settings.settings.actions.get_mut(idx) {
+                        if let MacroAction::Click { coordinate, .. } = action {
+                            *coordinate = Some((x, y));
+                            self.worker.set_status(&tr!(
+                                "status.click_position_set",
+                                position = format!("({:.3}, {:.3})", x, y)
+                            ));
+                        }
                     }
                 }
+            }
+        }
+        if self.calibration.is_adjusting() {
+            self.worker.set_status(tr!("status.nudge_hint"));
+        }
 
-                // Embed the OCR models directly into the binary (same as OCR macro)
-                const DETECTION_MODEL_BYTES: &[u8] = include_bytes!("../models/text-detection.rten");
-                const RECOGNITION_MODEL_BYTES: &[u8] = include_bytes!("../models/text-recognition.rten");
-
-                let detection_model = match rten::Model::load(DETECTION_MODEL_BYTES.to_vec()) {
-                    Ok(m) => m,
-                    Err(e) => {
-                        *status.lock().unwrap() = format!("Detection model error: {:?}", e);
-                        *running.lock().unwrap() = false;
-                        return;
-                    }
-                };
-
-                let recognition_model = match rten::Model::load(RECOGNITION_MODEL_BYTES.to_vec()) {
-                    Ok(m) => m,
-                    Err(e) => {
-                        *status.lock().unwrap() = format!("Recognition model error: {:?}", e);
-                        *running.lock().unwrap() = false;
-                        return;
+        if let Some(result) = self.ocr_region_calibration.update(hwnd) {
+            if let CalibrationResult::Cancelled = result {
+                self.ocr_calibrating_action_index = None;
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            } else if let CalibrationResult::Area(l, t, w, h) = result {
+                if let Some(idx) = self.ocr_calibrating_action_index.take() {
+                    match macro_settings.settings.actions.get_mut(idx) {
+                        Some(MacroAction::OcrSearch { ocr_region, .. }) => {
+                            *ocr_region = Some((l, t, w, h));
+                            self.worker.set_status(tr!("status.ocr_region_calibrated"));
+                        }
+                        Some(MacroAction::WaitForImageGone { region, .. }) => {
+                            *region = Some((l, t, w, h));
+                            self.worker
+                                .set_status(tr!("status.image_region_calibrated"));
+                        }
+                        _ => {}
                     }
-                };
+                }
+            }
+        }
+        if let Some(text) = self.ocr_region_calibration.selection_status() {
+            self.worker.set_status_quiet(&text);
+        }
+    }
 
-                let dm = match decode_mode_cfg {
-                    OcrDecodeMode::Greedy => DecodeMethod::Greedy,
-                    OcrDecodeMode::BeamSearch => {
-                        let width = beam_width_cfg.max(2);
-                        DecodeMethod::BeamSearch { width }
-                    }
-                };
-
-                let engine = match OcrEngine::new(OcrEngineParams {
-                    detection_model: Some(detection_model),
-                    recognition_model: Some(recognition_model),
-                    decode_method: dm,
-                    ..Default::default()
-                }) {
-                    Ok(engine) => engine,
-                    Err(e) => {
-                        *status.lock().unwrap() = format!("OCR Engine error: {:?}", e);
-                        *running.lock().unwrap() = false;
-                        return;
-                    }
-                };
+    fn is_calibrating(&self) -> bool {
+        self.calibration.is_active() || self.ocr_region_calibration.is_active()
+    }
 
-                ocr_engine = Some(engine);
-            }
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
 
-            let mut iteration: u32 = 0;
-            let mut ocr_counts: HashMap<String, u32> = HashMap::new();
-            let mut end_status = "Macro completed!";
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        self.worker.client_size()
+    }
 
-            loop {
-                if !*running.lock().unwrap() {
-                    break;
-                }
+    fn seed_next_run(&mut self, entry: JournalEntry) {
+        self.worker.seed_next_run(entry);
+    }
 
-                // Determine if we should exit based on loop settings
-                if settings.loop_enabled {
-                    if !settings.infinite_loop && iteration >= settings.loop_count {
-                        break;
-                    }
-                    if settings.infinite_loop {
-                         *status.lock().unwrap() = format!("Loop {} (Infinite)", iteration + 1);
-                    } else {
-                         *status.lock().unwrap() = format!("Loop {}/{}", iteration + 1, settings.loop_count);
-                    }
-                } else {
-                    if iteration >= 1 {
-                        break;
-                    }
-                }
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
 
-                for (idx, action) in settings.actions.iter().enumerate() {
-                    if !*running.lock().unwrap() {
-                        break;
-                    }
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
 
-                    match action {
-                        MacroAction::Click { coordinate, button, click_method, use_mouse_movement: _ } => {
-                            if let Some((x, y)) = coordinate {
-                                let (client_x, client_y) = match denormalize_point(game_hwnd, *x, *y) {
-                                    Some(pos) => pos,
-                                    None => {
-                                        *status.lock().unwrap() = "Invalid click position".to_string();
-                                        continue;
-                                    }
-                                };
-                                let btn_text = match button {
-                                    crate::settings::MouseButton::Left => "Left",
-                                    crate::settings::MouseButton::Right => "Right",
-                                    crate::settings::MouseButton::Middle => "Middle",
-                                };
-                                *status.lock().unwrap() = format!("{} Clicking at ({}, {})", btn_text, client_x, client_y);
-
-                                match click_method {
-                                    crate::settings::ClickMethod::SendMessage => {
-                                        // Direct click without mouse movement (default)
-                                        match button {
-                                            crate::settings::MouseButton::Left => {
-                                                click_at_position(game_hwnd, client_x, client_y);
-                                            }
-                                            crate::settings::MouseButton::Right => {
-                                                use crate::core::input::right_click_at_position;
-                                                right_click_at_position(game_hwnd, client_x, client_y);
-                                            }
-                                            crate::settings::MouseButton::Middle => {
-                                                use crate::core::input::middle_click_at_position;
-                                                middle_click_at_position(game_hwnd, client_x, client_y);
-                                            }
-                                        }
-                                    },
-                                    crate::settings::ClickMethod::MouseMovement => {
-                                        // Use screen coordinates with mouse movement
-                                        let (screen_x, screen_y) = match client_to_screen_coords(game_hwnd, client_x, client_y) {
-                                            Some(pos) => pos,
-                                            None => {
-                                                *status.lock().unwrap() = "Failed to convert to screen coords".to_string();
-                                                continue;
-                                            }
-                                        };
-                                        match button {
-                                            crate::settings::MouseButton::Left => {
-                                                use crate::automation::interaction::click_at_screen;
-                                                click_at_screen(&mut ctx.gui, screen_x as u32, screen_y as u32);
-                                            }
-                                            crate::settings::MouseButton::Right => {
-                                                use crate::automation::interaction::right_click_at_screen;
-                                                right_click_at_screen(&mut ctx.gui, screen_x as u32, screen_y as u32);
-                                            }
-                                            crate::settings::MouseButton::Middle => {
-                                                use crate::automation::interaction::middle_click_at_screen;
-                                                middle_click_at_screen(&mut ctx.gui, screen_x as u32, screen_y as u32);
-                                            }
-                                        }
-                                    },
-                                }
-                            } else {
-                                *status.lock().unwrap() = format!("Action {}: Click position not set", idx + 1);
-                            }
-                        },
-                        MacroAction::TypeText { text } => {
-                            *status.lock().unwrap() = format!("Typing: {}", text);
-                            if let Err(e) = ctx.gui.keyboard_input(text) {
-                                *status.lock().unwrap() = format!("Keyboard error: {:?}", e);
-                            }
-                        },
-                        MacroAction::Delay { milliseconds } => {
-                            *status.lock().unwrap() = format!("Waiting {}ms", milliseconds);
-                            delay_ms(*milliseconds);
-                        },
-                        MacroAction::OcrSearch {
-                            ocr_region,
-                            scale_factor,
-                            invert_colors,
-                            grayscale,
-                            target_stat,
-                            target_value,
-                            comparison,
-                            name_match_mode,
-                            alt_targets,
-                            ..
-                        } => {
-                            if ocr_engine.is_none() {
-                                *status.lock().unwrap() = "OCR engine not initialized".to_string();
-                                *running.lock().unwrap() = false;
-                                break;
-                            }
-
-                            let region = if let Some(region) = ocr_region {
-                                match denormalize_rect(game_hwnd, region.0, region.1, region.2, region.3) {
-                                    Some(rect) => rect,
-                                    None => {
-                                        *status.lock().unwrap() = format!("Action {}: Invalid OCR region", idx + 1);
-                                        *running.lock().unwrap() = false;
-                                        break;
-                                    }
-                                }
-                            } else {
-                                *status.lock().unwrap() = format!("Action {}: OCR region not set", idx + 1);
-                                *running.lock().unwrap() = false;
-                                break;
-                            };
-
-                            let engine = ocr_engine.as_ref().unwrap();
-
-                            match capture_window_region(game_hwnd, region) {
-                                Ok(img) => {
-                                    let mut processed_img = image::DynamicImage::ImageRgba8(img);
-
-                                    if *invert_colors {
-                                        processed_img.invert();
-                                    }
-
-                                    if *grayscale {
-                                        processed_img = image::DynamicImage::ImageLuma8(processed_img.to_luma8());
-                                    }
-
-                                    if *scale_factor > 1 {
-                                        let (w, h) = (processed_img.width(), processed_img.height());
-                                        processed_img = processed_img.resize(
-                                            w * *scale_factor,
-                                            h * *scale_factor,
-                                            image::imageops::FilterType::Lanczos3,
-                                        );
-                                    }
-
-                                    let rgb_img = processed_img.into_rgb8();
-                                    let (width, height) = rgb_img.dimensions();
-
-                                    let img_source = match ImageSource::from_bytes(rgb_img.as_raw(), (width, height)) {
-                                        Ok(src) => src,
-                                        Err(e) => {
-                                            *status.lock().unwrap() = format!("Image Error: {:?}", e);
-                                            continue;
-                                        }
-                                    };
-
-                                    let ocr_input = match engine.prepare_input(img_source) {
-                                        Ok(input) => input,
-                                        Err(e) => {
-                                            *status.lock().unwrap() = format!("Prep Error: {:?}", e);
-                                            continue;
-                                        }
-                                    };
-
-                                    match engine.get_text(&ocr_input) {
-                                        Ok(text) => {
-                                            {
-                                                let counter =
-                                                    ocr_counts.entry(text.clone()).or_insert(0);
-                                                *counter += 1;
-                                            }
-
-                                            Worker::push_log(&log, &format_ocr_display(&text));
-
-                                            if let Some((detected_stat, detected_value)) = parse_ocr_result(&text) {
-                                                let normalize_contains = |s: &str| -> String {
-                                                    s.chars()
-                                                        .filter(|c| c.is_ascii_alphanumeric())
-                                                        .flat_map(|c| c.to_lowercase())
-                                                        .collect()
-                                                };
-
-                                                let matches_config = |stat: &str,
-                                                                      value: i32,
-                                                                      comparison: ComparisonMode,
-                                                                      name_match_mode: OcrNameMatchMode|
-                                                 -> bool {
-                                                    if stat.trim().is_empty() {
-                                                        return false;
-                                                    }
-                                                    match name_match_mode {
-                                                        OcrNameMatchMode::Exact => matches_target(
-                                                            &detected_stat,
-                                                            detected_value,
-                                                            stat,
-                                                            value,
-                                                            comparison,
-                                                        ),
-                                                        OcrNameMatchMode::Contains => {
-                                                            let detected = normalize_contains(&detected_stat);
-                                                            let target = normalize_contains(stat);
-                                                            if target.is_empty() {
-                                                                false
-                                                            } else if !detected.contains(&target) {
-                                                                false
-                                                            } else {
-                                                                match comparison {
-                                                                    ComparisonMode::Equals => detected_value == value,
-                                                                    ComparisonMode::GreaterThanOrEqual => detected_value >= value,
-                                                                    ComparisonMode::LessThanOrEqual => detected_value <= value,
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                };
-
-                                                let mut matched = matches_config(
-                                                    target_stat,
-                                                    *target_value,
-                                                    *comparison,
-                                                    *name_match_mode,
-                                                );
-                                                if !matched {
-                                                    for alt in alt_targets.iter() {
-                                                        if alt.delay_ms > 0 {
-                                                            delay_ms(alt.delay_ms);
-                                                        }
-                                                        if matches_config(
-                                                            &alt.target_stat,
-                                                            alt.target_value,
-                                                            alt.comparison,
-                                                            alt.name_match_mode,
-                                                        ) {
-                                                            matched = true;
-                                                            break;
-                                                        }
-                                                    }
-                                                }
-
-                                                if matched {
-                                                    *status.lock().unwrap() =
-                                                        format!("MATCH FOUND! {} {}", detected_stat, detected_value);
-                                                    show_success_message(&detected_stat, detected_value);
-                                                    end_status = "Stopped (match found)";
-                                                    *running.lock().unwrap() = false;
-                                                    break;
-                                                }
-                                            }
-                                        }
-                                        Err(e) => {
-                                            *status.lock().unwrap() = format!("OCR Error: {:?}", e);
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    *status.lock().unwrap() = format!("Capture Error: {}", e);
-                                }
-                            }
-                        },
-                    }
-                }
+    fn get_log(&self) -> Vec<LogEntry> {
+        self.worker.get_log()
+    }
 
-                iteration += 1;
-            }
+    fn take_undo_entries(&mut self) -> Vec<UndoEntry> {
+        std::mem::take(&mut self.undo_entries)
+    }
 
-            if *running.lock().unwrap() {
-                *status.lock().unwrap() = end_status.to_string();
-            } else {
-                if end_status == "Macro completed!" {
-                    *status.lock().unwrap() = "Stopped by user".to_string();
-                } else {
-                    *status.lock().unwrap() = end_status.to_string();
+    fn take_tools_change(&mut self) -> Option<ToolsChange> {
+        self.tools_change.take()
+    }
+
+    fn region_preview(
+        &self,
+        settings: &crate::settings::AppSettings,
+        game_hwnd: HWND,
+    ) -> Option<Vec<OverlayShape>> {
+        if !self.show_regions {
+            return None;
+        }
+        let macro_settings = settings.macro_by_id(self.macro_id)?;
+
+        let shapes = macro_settings
+            .settings
+            .actions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, action)| match action {
+                MacroAction::Click {
+                    coordinate: Some((x, y)),
+                    ..
+                } => {
+                    let (x, y) = denormalize_point(game_hwnd, *x, *y)?;
+                    Some(OverlayShape::point(
+                        x,
+                        y,
+                        REGION_POINT_COLOR,
+                        Some(format!("Click #{}", idx + 1)),
+                    ))
                 }
-            }
+                MacroAction::OcrSearch {
+                    ocr_region: Some((x, y, w, h)),
+                    ..
+                } => Some(OverlayShape {
+                    rect: denormalize_rect(game_hwnd, *x, *y, *w, *h)?,
+                    color: REGION_AREA_COLOR,
+                    label: Some(format!("OCR #{}", idx + 1)),
+                }),
+                MacroAction::WaitForImageGone {
+                    region: Some((x, y, w, h)),
+                    ..
+                } => Some(OverlayShape {
+                    rect: denormalize_rect(game_hwnd, *x, *y, *w, *h)?,
+                    color: REGION_AREA_COLOR,
+                    label: Some(format!("Wait #{}", idx + 1)),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        Some(shapes)
+    }
+}
 
-            if !ocr_counts.is_empty() {
-                let mut ranking: Vec<(String, u32)> = ocr_counts.into_iter().collect();
-                ranking.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+impl CustomMacroTool {
+    fn selected_clients(&self) -> Vec<HWND> {
+        self.clients
+            .iter()
+            .filter(|client| client.selected)
+            .map(|client| client.hwnd)
+            .collect()
+    }
 
-                if let Ok(mut log) = log.lock() {
-                    log.clear();
-                }
+    /// `one_shot` overrides the loop settings: one iteration per pending press, then stop
+    fn start_macro(
+        &mut self,
+        settings: CustomMacroSettings,
+        game_hwnd: HWND,
+        rate_limit: ActionRateLimit,
+        ocr_models: OcrModelSettings,
+        one_shot: Option<Arc<AtomicU32>>,
+    ) {
+        self.worker.set_status("Running macro...");
+        self.one_shot_run = one_shot.is_some();
 
-                Worker::push_log(&log, "OCR SUMMARY (most frequent to least):");
-                for (key, value) in ranking {
-                    Worker::push_log(&log, &format!("{} x{}", format_ocr_display(&key), value));
+        // One-shot presses stay on the connected client
+        let clients = if one_shot.is_none() {
+            self.selected_clients()
+        } else {
+            Vec::new()
+        };
+        let run = MacroRun {
+            settings,
+            game_hwnd,
+            clients,
+            rate_limit,
+            ocr_models,
+            one_shot,
+        };
+        let started = self
+            .worker
+            .start(move |running, paused, status, log, stats| {
+                if let MacroOutcome::MatchFound { stat, value } =
+                    run.run(running, paused, status, log, stats)
+                {
+                    show_success_message(&stat, value);
                 }
-            }
-
-            *running.lock().unwrap() = false;
-        });
+            });
+        if started {
+            self.worker.set_client_size(get_client_size(game_hwnd));
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
     }
 }
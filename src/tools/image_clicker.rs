@@ -2,12 +2,19 @@ use crate::automation::context::AutomationContext;
 use crate::automation::detection::find_stored_template;
 use crate::automation::interaction::delay_ms;
 use crate::calibration::{CalibrationManager, CalibrationResult};
-use crate::core::worker::Worker;
-use crate::settings::AcceptItemSettings;
-use crate::tools::r#trait::Tool;
+use crate::core::throttle::ActionThrottle;
+use crate::core::window::get_client_size;
+use crate::core::worker::{
+    push_log_entry, LogEntry, LogLevel, RunDeadline, SharedLog, SharedStats, StatusSender, Worker,
+    WorkerStats,
+};
+use crate::settings::{AcceptItemSettings, ActionRateLimit};
+use crate::tools::r#trait::{StopReason, Tool};
+use crate::tools::undo::UndoEntry;
 use crate::ui::image_clicker::{render_ui, ImageUiAction};
 use eframe::egui;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use windows::Win32::Foundation::HWND;
 
 pub struct ImageClickerTool {
@@ -20,6 +27,9 @@ pub struct ImageClickerTool {
 
     // Calibration
     calibration: CalibrationManager,
+
+    // Removed data waiting to be collected by the app's undo stack
+    undo_entries: Vec<UndoEntry>,
 }
 
 impl Default for ImageClickerTool {
@@ -27,33 +37,45 @@ impl Default for ImageClickerTool {
         Self {
             interval_ms_str: "1000".to_string(),
             settings_synced: false,
-            worker: Worker::new(),
+            worker: Worker::new("Image Clicker"),
             calibration: CalibrationManager::new(),
+            undo_entries: Vec::new(),
         }
     }
 }
 
 impl Tool for ImageClickerTool {
-    fn stop(&mut self) {
-        self.worker.stop();
-        if self.worker.get_status().contains("Stopped") {
-            // Already stopped
-        } else {
-            self.worker.set_status("Stopped (emergency hotkey)");
-        }
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
     }
 
     fn is_running(&self) -> bool {
         self.worker.is_running()
     }
 
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
     fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
         let settings = &app_settings.accept_item;
 
         if let Some(hwnd) = game_hwnd {
-            self.start_automation(settings.clone(), hwnd);
+            self.start_automation(
+                settings.clone(),
+                hwnd,
+                app_settings.global.action_rate_limit,
+            );
         } else {
-            self.worker.set_status("Connect to game first");
+            self.worker.set_status(tr!("status.connect_first"));
         }
     }
 
@@ -65,52 +87,57 @@ impl Tool for ImageClickerTool {
         game_hwnd: Option<HWND>,
         hotkey_error: Option<&str>,
     ) {
-        let settings = &mut settings.accept_item;
-
-        // Sync UI with Settings on first load
-        if !self.settings_synced {
-            self.interval_ms_str = settings.interval_ms.to_string();
-            self.settings_synced = true;
-        }
-
         // Handle calibration interaction
         if let Some(hwnd) = game_hwnd {
-            if let Some(result) = self.calibration.update(hwnd) {
-                if let CalibrationResult::Area(l, t, w, h) = result {
-                    settings.search_region = Some((l, t, w, h));
-                    self.worker.set_status("Region calibrated");
-                }
-            }
+            self.update_calibration(settings, hwnd);
         } else {
             // Disconnected logic
             if self.worker.is_running() {
                 self.worker.stop();
-                self.worker.set_status("Disconnected");
+                self.worker.set_status(tr!("header.disconnected"));
             }
         }
 
+        let rate_limit = settings.global.action_rate_limit;
+        let recent_image_paths = &mut settings.global.recent_image_paths;
+        let settings = &mut settings.accept_item;
+
+        // Sync UI with Settings on first load
+        if !self.settings_synced {
+            self.interval_ms_str = settings.interval_ms.to_string();
+            self.settings_synced = true;
+        }
+
         // Repaint if calibrating to capture clicks immediately
         if self.calibration.is_active() {
             ctx.request_repaint();
         }
 
-        let is_running = self.worker.is_running();
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
         let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
         let is_calibrating = self.calibration.is_active();
         let is_waiting_for_second_click = self.calibration.is_waiting_for_second_click();
 
         let action = render_ui(
             ui,
             &mut settings.image_path, // Bind directly to settings string
+            recent_image_paths,
             &mut self.interval_ms_str,
             &mut settings.tolerance,
+            &mut settings.match_mode,
+            &mut settings.max_runtime_minutes,
             &mut settings.show_in_overlay,
-            settings.search_region,
+            &mut settings.search_region,
             is_calibrating,
             is_waiting_for_second_click,
+            self.calibration.magnifier(),
             is_running,
             &status,
+            &stats,
             game_hwnd.is_some(),
+            game_hwnd.and_then(get_client_size),
             hotkey_error,
         );
 
@@ -122,72 +149,161 @@ impl Tool for ImageClickerTool {
         match action {
             ImageUiAction::StartRegionCalibration => {
                 self.calibration.start_area();
-                self.worker.set_status("Click top-left, then bottom-right");
+                self.worker.set_status(tr!("status.click_area_corners"));
             }
             ImageUiAction::CancelCalibration => {
                 self.calibration.cancel();
-                self.worker.set_status("Calibration cancelled");
+                self.worker.set_status(tr!("status.calibration_cancelled"));
             }
             ImageUiAction::ClearRegion => {
-                settings.search_region = None;
+                if let Some(previous) = settings.search_region.take() {
+                    self.undo_entries
+                        .push(UndoEntry::ClearedSearchRegion { previous });
+                }
+            }
+            ImageUiAction::ResetToDefaults => {
+                self.calibration.cancel();
+                *settings = AcceptItemSettings::default();
+                self.interval_ms_str = settings.interval_ms.to_string();
+                self.worker.set_status(tr!("status.settings_reset"));
             }
             ImageUiAction::Start => {
                 if game_hwnd.is_none() {
-                    self.worker.set_status("Connect to game first");
+                    self.worker.set_status(tr!("status.connect_first"));
                 } else {
-                    self.start_automation(settings.clone(), game_hwnd.unwrap());
+                    self.start_automation(settings.clone(), game_hwnd.unwrap(), rate_limit);
                 }
             }
             ImageUiAction::Stop => {
-                self.stop();
+                self.stop(StopReason::User);
             }
             ImageUiAction::None => {}
         }
     }
 
-    fn get_log(&self) -> Vec<String> {
+    fn update_calibration(&mut self, settings: &mut crate::settings::AppSettings, hwnd: HWND) {
+        self.calibration
+            .set_grid_size(settings.global.calibration_grid_px);
+        if let Some(result) = self.calibration.update(hwnd) {
+            if let CalibrationResult::Cancelled = result {
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            } else if let CalibrationResult::Area(l, t, w, h) = result {
+                settings.accept_item.search_region = Some((l, t, w, h));
+                self.worker.set_status(tr!("status.region_calibrated"));
+            }
+        }
+        if let Some(text) = self.calibration.selection_status() {
+            self.worker.set_status_quiet(&text);
+        }
+    }
+
+    fn is_calibrating(&self) -> bool {
+        self.calibration.is_active()
+    }
+
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
+
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        self.worker.client_size()
+    }
+
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
+
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
+
+    fn get_log(&self) -> Vec<LogEntry> {
         self.worker.get_log()
     }
+
+    fn take_undo_entries(&mut self) -> Vec<UndoEntry> {
+        std::mem::take(&mut self.undo_entries)
+    }
 }
 
 impl ImageClickerTool {
     // start_automation kept as private helper
-    fn start_automation(&mut self, settings: AcceptItemSettings, game_hwnd: HWND) {
-        self.worker.set_status("Starting...");
+    fn start_automation(
+        &mut self,
+        settings: AcceptItemSettings,
+        game_hwnd: HWND,
+        rate_limit: ActionRateLimit,
+    ) {
+        self.worker.set_status(tr!("status.starting"));
 
         let image_path = settings.image_path.clone(); // Clone for thread
 
-        self.worker.start(
-            move |running: Arc<Mutex<bool>>,
-                  status: Arc<Mutex<String>>,
-                  _log: Arc<Mutex<std::collections::VecDeque<String>>>| {
-                let mut ctx = match AutomationContext::new(game_hwnd) {
+        let started = self.worker.start(
+            move |running: Arc<AtomicBool>,
+                  paused: Arc<AtomicBool>,
+                  status: StatusSender,
+                  log: SharedLog,
+                  stats: SharedStats| {
+                let throttle = ActionThrottle::new(rate_limit, Arc::clone(&log));
+                let mut ctx = match AutomationContext::new(game_hwnd, throttle) {
                     Ok(c) => c,
                     Err(e) => {
-                        *status.lock().unwrap() = format!("Error: {}", e);
-                        *running.lock().unwrap() = false;
+                        status.set(e.status());
+                        push_log_entry(&log, LogLevel::Error, &e.status());
+                        stats.lock().unwrap().record_error(&e);
+                        running.store(false, Ordering::Relaxed);
                         return;
                     }
                 };
 
-                if let Err(e) =
-                    ctx.store_template(&image_path, settings.search_region, "target_image")
-                {
-                    *status.lock().unwrap() = format!("Image Error: {}", e);
-                    *running.lock().unwrap() = false;
+                if let Err(e) = ctx.store_template(
+                    &image_path,
+                    settings.search_region,
+                    "target_image",
+                    settings.match_mode,
+                ) {
+                    status.set(e.status());
+                    push_log_entry(&log, LogLevel::Error, &e.status());
+                    stats.lock().unwrap().record_error(&e);
+                    running.store(false, Ordering::Relaxed);
                     return;
                 }
+                Worker::push_log(
+                    &log,
+                    &format!(
+                        "Template {} stored ({} matching)",
+                        image_path,
+                        settings.match_mode.name()
+                    ),
+                );
 
-                *status.lock().unwrap() = "Searching...".to_string();
+                status.set(tr!("status.searching"));
+
+                let deadline = RunDeadline::for_run(&stats, settings.max_runtime_minutes);
+                let mut timed_out = None;
+                while Worker::checkpoint(&running, &paused).is_continue() {
+                    if let Some(deadline) = deadline.filter(RunDeadline::passed) {
+                        timed_out = Some(deadline);
+                        break;
+                    }
+
+                    // The search region moves with the game window
+                    ctx.refresh(&log);
 
-                while *running.lock().unwrap() {
                     // Using settings.tolerance which is now treated as Minimum Confidence
-                    match find_stored_template(&mut ctx.gui, "target_image", settings.tolerance) {
+                    match find_stored_template(
+                        &mut ctx.gui,
+                        "target_image",
+                        settings.tolerance,
+                        None,
+                    ) {
                         Some(matches) if !matches.is_empty() => {
-                            let (screen_x, screen_y) = matches[0];
+                            let (screen_x, screen_y) = matches[0].pos();
 
-                            *status.lock().unwrap() =
-                                format!("Found at ({}, {}), clicking...", screen_x, screen_y);
+                            status.set(format!(
+                                "Found at ({}, {}), clicking...",
+                                screen_x, screen_y
+                            ));
 
                             // Convert screen coords to window coords for Direct Click
                             use crate::core::input::click_at_position;
@@ -196,26 +312,40 @@ impl ImageClickerTool {
                             if let Some((client_x, client_y)) =
                                 screen_to_window_coords(game_hwnd, screen_x as i32, screen_y as i32)
                             {
-                                click_at_position(game_hwnd, client_x, client_y);
+                                ctx.throttle.wait();
+                                if click_at_position(game_hwnd, client_x, client_y) {
+                                    stats.lock().unwrap().add_items(1);
+                                }
                             } else {
-                                *status.lock().unwrap() =
-                                    "Error converting coordinates".to_string();
+                                status.set("Error converting coordinates");
                             }
 
                             // Hardcoded safety delay after click to prevent double-clicking
                             delay_ms(500);
                         }
                         _ => {
-                            *status.lock().unwrap() = "Searching...".to_string();
+                            status.set(tr!("status.searching"));
                         }
                     }
 
                     // User-configured polling interval (how often to check screen)
-                    delay_ms(settings.interval_ms);
+                    Worker::sleep_within_deadline(&running, settings.interval_ms, deadline);
                 }
 
-                *status.lock().unwrap() = "Stopped".to_string();
+                if let Some(deadline) = timed_out {
+                    let message = deadline.reached_message();
+                    Worker::push_log(&log, &message);
+                    status.set(message);
+                    running.store(false, Ordering::Relaxed);
+                } else {
+                    status.set("Stopped");
+                }
             },
         );
+        if started {
+            self.worker.set_client_size(get_client_size(game_hwnd));
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
     }
 }
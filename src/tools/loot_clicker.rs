@@ -0,0 +1,370 @@
+use crate::automation::detection::find_color_labels;
+use crate::calibration::{CalibrationManager, CalibrationResult};
+use crate::core::coords::denormalize_rect;
+use crate::core::input::click_at_position;
+use crate::core::overlay_window::{OverlayShape, REGION_AREA_COLOR};
+use crate::core::throttle::ActionThrottle;
+use crate::core::window::get_client_size;
+use crate::core::worker::{
+    push_log_entry, LogEntry, LogLevel, SharedLog, SharedStats, StatusSender, Worker, WorkerStats,
+};
+use crate::settings::{ActionRateLimit, LootClickerSettings};
+use crate::tools::r#trait::{StopReason, Tool};
+use crate::ui::loot_clicker::{render_ui, LootClickerUiAction};
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use windows::Win32::Foundation::HWND;
+
+/// Clicks the item names lying on the ground in a calibrated area, picking up the drops
+pub struct LootClickerTool {
+    // Runtime state (Worker)
+    worker: Worker,
+
+    // Calibration of the search region
+    calibration: CalibrationManager,
+    show_regions: bool,
+}
+
+impl Default for LootClickerTool {
+    fn default() -> Self {
+        Self {
+            worker: Worker::new("Loot Clicker"),
+            calibration: CalibrationManager::new(),
+            show_regions: false,
+        }
+    }
+}
+
+impl Tool for LootClickerTool {
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
+    }
+
+    fn is_running(&self) -> bool {
+        self.worker.is_running()
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
+        if let Some(hwnd) = game_hwnd {
+            self.start_looting(
+                app_settings.loot_clicker.clone(),
+                hwnd,
+                app_settings.global.action_rate_limit,
+            );
+        } else {
+            self.worker.set_status(tr!("status.connect_first"));
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        settings: &mut crate::settings::AppSettings,
+        game_hwnd: Option<HWND>,
+        hotkey_error: Option<&str>,
+    ) {
+        // Handle calibration interaction
+        if let Some(hwnd) = game_hwnd {
+            self.update_calibration(settings, hwnd);
+        } else {
+            // Disconnected logic
+            if self.worker.is_running() {
+                self.worker.stop();
+                self.worker.set_status(tr!("header.disconnected"));
+            }
+            self.calibration.cancel();
+        }
+
+        let rate_limit = settings.global.action_rate_limit;
+        let settings = &mut settings.loot_clicker;
+
+        // Repaint if calibrating to capture clicks immediately
+        if self.calibration.is_active() {
+            ctx.request_repaint();
+        }
+
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
+        let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
+
+        let action = render_ui(
+            ui,
+            settings,
+            &mut self.show_regions,
+            self.calibration.is_active(),
+            self.calibration.is_waiting_for_second_click(),
+            self.calibration.magnifier(),
+            is_running,
+            &status,
+            &stats,
+            game_hwnd.is_some(),
+            game_hwnd.and_then(get_client_size),
+            hotkey_error,
+        );
+
+        match action {
+            LootClickerUiAction::StartRegionCalibration => {
+                self.calibration.start_area();
+                self.worker.set_status(tr!("status.click_area_corners"));
+            }
+            LootClickerUiAction::CancelCalibration => {
+                self.calibration.cancel();
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            LootClickerUiAction::ResetToDefaults => {
+                self.calibration.cancel();
+                *settings = LootClickerSettings::default();
+                self.worker.set_status(tr!("status.settings_reset"));
+            }
+            LootClickerUiAction::Start => {
+                if let Some(hwnd) = game_hwnd {
+                    self.start_looting(settings.clone(), hwnd, rate_limit);
+                } else {
+                    self.worker.set_status(tr!("status.connect_first"));
+                }
+            }
+            LootClickerUiAction::Stop => {
+                self.stop(StopReason::User);
+            }
+            LootClickerUiAction::None => {}
+        }
+    }
+
+    fn update_calibration(&mut self, settings: &mut crate::settings::AppSettings, hwnd: HWND) {
+        self.calibration
+            .set_grid_size(settings.global.calibration_grid_px);
+        match self.calibration.update(hwnd) {
+            Some(CalibrationResult::Area(l, t, w, h)) => {
+                settings.loot_clicker.region = Some((l, t, w, h));
+                self.worker.set_status(tr!("status.region_calibrated"));
+            }
+            Some(CalibrationResult::Cancelled) => {
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            _ => {}
+        }
+        if let Some(text) = self.calibration.selection_status() {
+            self.worker.set_status_quiet(&text);
+        }
+    }
+
+    fn is_calibrating(&self) -> bool {
+        self.calibration.is_active()
+    }
+
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
+
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        self.worker.client_size()
+    }
+
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
+
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
+
+    fn get_log(&self) -> Vec<LogEntry> {
+        self.worker.get_log()
+    }
+
+    fn region_preview(
+        &self,
+        app_settings: &crate::settings::AppSettings,
+        game_hwnd: HWND,
+    ) -> Option<Vec<OverlayShape>> {
+        if !self.show_regions {
+            return None;
+        }
+        let shapes = app_settings
+            .loot_clicker
+            .region
+            .and_then(|(x, y, w, h)| denormalize_rect(game_hwnd, x, y, w, h))
+            .map(|rect| OverlayShape {
+                rect,
+                color: REGION_AREA_COLOR,
+                label: Some("Loot".to_string()),
+            })
+            .into_iter()
+            .collect();
+        Some(shapes)
+    }
+}
+
+impl LootClickerTool {
+    fn start_looting(
+        &mut self,
+        settings: LootClickerSettings,
+        game_hwnd: HWND,
+        rate_limit: ActionRateLimit,
+    ) {
+        let Some(area) = settings.region else {
+            self.worker.set_status(tr!("status.calibrate_first"));
+            return;
+        };
+        if settings.colors.is_empty() {
+            self.worker.set_status(tr!("status.no_loot_colors"));
+            return;
+        }
+        self.worker.set_status(tr!("status.starting"));
+
+        let started = self.worker.start(
+            move |running: Arc<AtomicBool>,
+                  paused: Arc<AtomicBool>,
+                  status: StatusSender,
+                  log: SharedLog,
+                  stats: SharedStats| {
+                let mut throttle = ActionThrottle::new(rate_limit, Arc::clone(&log));
+                let colors: Vec<([u8; 3], u8)> = settings
+                    .colors
+                    .iter()
+                    .map(|loot| (loot.color, loot.tolerance))
+                    .collect();
+                Worker::push_log(
+                    &log,
+                    &format!(
+                        "Looting {} label colors, up to {} clicks per sweep{}",
+                        colors.len(),
+                        settings.max_clicks_per_sweep,
+                        if settings.repeat {
+                            format!(", every {} ms", settings.sweep_interval_ms)
+                        } else {
+                            String::new()
+                        }
+                    ),
+                );
+
+                let mut sweeps = 0;
+                while Worker::checkpoint(&running, &paused).is_continue() {
+                    // Re-read every sweep, the window may have been resized
+                    let Some(region) = denormalize_rect(game_hwnd, area.0, area.1, area.2, area.3)
+                    else {
+                        let error = "Failed to convert the loot region";
+                        push_log_entry(&log, LogLevel::Error, error);
+                        stats.lock().unwrap().set_error(error);
+                        break;
+                    };
+
+                    sweeps += 1;
+                    status.set("Scanning for loot");
+                    let labels = find_color_labels(
+                        game_hwnd,
+                        region,
+                        &colors,
+                        settings.min_blob_size,
+                        settings.merge_distance_px as f32,
+                    )
+                    .unwrap_or_else(|e| {
+                        push_log_entry(&log, LogLevel::Warn, &e.to_string());
+                        Vec::new()
+                    });
+                    let targets = plan_sweep(
+                        &labels,
+                        settings.dedupe_radius_px as f32,
+                        settings.max_clicks_per_sweep,
+                    );
+                    Worker::push_log(
+                        &log,
+                        &format!(
+                            "Sweep #{}: {} labels found, clicking {}",
+                            sweeps,
+                            labels.len(),
+                            targets.len()
+                        ),
+                    );
+
+                    for (idx, &(x, y)) in targets.iter().enumerate() {
+                        if Worker::checkpoint(&running, &paused).is_break() {
+                            break;
+                        }
+                        status.set(format!(
+                            "Clicking label {}/{} at ({}, {})",
+                            idx + 1,
+                            targets.len(),
+                            x,
+                            y
+                        ));
+                        throttle.wait();
+                        click_at_position(game_hwnd, x, y);
+                        stats.lock().unwrap().add_items(1);
+                        Worker::sleep_while_running(&running, settings.click_delay_ms);
+                    }
+
+                    if !settings.repeat {
+                        break;
+                    }
+                    status.set(format!("Waiting for the next sweep ({} done)", sweeps));
+                    Worker::sleep_while_running(&running, settings.sweep_interval_ms);
+                }
+
+                running.store(false, Ordering::Relaxed);
+                status.set("Stopped");
+            },
+        );
+        if started {
+            self.worker.set_client_size(get_client_size(game_hwnd));
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
+    }
+}
+
+/// Labels to click in one sweep, in the order found: a label within `dedupe_radius`
+/// of one already picked is the same drop seen twice and is skipped, and at most
+/// `max_clicks` are picked
+fn plan_sweep(labels: &[(i32, i32)], dedupe_radius: f32, max_clicks: u32) -> Vec<(i32, i32)> {
+    let mut picked: Vec<(i32, i32)> = Vec::new();
+    for &(x, y) in labels {
+        if picked.len() >= max_clicks as usize {
+            break;
+        }
+        let duplicate = picked
+            .iter()
+            .any(|&(px, py)| ((px - x) as f32).hypot((py - y) as f32) <= dedupe_radius);
+        if !duplicate {
+            picked.push((x, y));
+        }
+    }
+    picked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_sweep_skips_labels_already_picked() {
+        let labels = [(100, 50), (110, 52), (300, 50), (102, 120)];
+        assert_eq!(
+            plan_sweep(&labels, 24.0, 10),
+            vec![(100, 50), (300, 50), (102, 120)]
+        );
+        // No radius: every label is its own drop
+        assert_eq!(plan_sweep(&labels, 0.0, 10).len(), 4);
+    }
+
+    #[test]
+    fn test_plan_sweep_stops_at_the_click_cap() {
+        let labels = [(0, 0), (100, 0), (200, 0), (300, 0)];
+        assert_eq!(plan_sweep(&labels, 24.0, 2), vec![(0, 0), (100, 0)]);
+    }
+}
@@ -1,4 +1,12 @@
 pub mod r#trait;
 pub mod image_clicker;
 pub mod collection_filler;
+pub mod anti_afk;
+pub mod pixel_trigger;
+pub mod rebuffer;
+pub mod rotation;
+pub mod auto_relog;
+pub mod loot_clicker;
+pub mod stat_logger;
 pub mod custom_macro;
+pub mod undo;
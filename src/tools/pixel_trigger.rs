@@ -0,0 +1,432 @@
+use crate::automation::detection::{color_matches, read_pixel};
+use crate::calibration::{CalibrationManager, CalibrationResult};
+use crate::core::coords::denormalize_point;
+use crate::core::error::HelperError;
+use crate::core::hotkey::{hotkey_key_label, hotkey_key_to_vk};
+use crate::core::input::{click_at_position_post, key_press_post};
+use crate::core::overlay_window::{OverlayShape, REGION_POINT_COLOR};
+use crate::core::throttle::ActionThrottle;
+use crate::core::window::get_client_size;
+use crate::core::worker::{
+    push_log_entry, LogEntry, LogLevel, SharedLog, SharedStats, StatusSender, Worker, WorkerStats,
+};
+use crate::settings::{ActionRateLimit, PixelTriggerResponse, PixelTriggerSettings};
+use crate::tools::r#trait::{StopReason, Tool};
+use crate::ui::pixel_trigger::{render_ui, PixelTriggerTarget, PixelTriggerUiAction};
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use windows::Win32::Foundation::HWND;
+
+/// Watches one pixel (e.g. the HP bar) and presses a key or clicks when its color
+/// changes, then waits out a cooldown before watching again
+pub struct PixelTriggerTool {
+    // Runtime state (Worker)
+    worker: Worker,
+
+    // Calibration of the watched pixel and the click spot
+    calibration: CalibrationManager,
+    calibrating: Option<PixelTriggerTarget>,
+    show_regions: bool,
+}
+
+impl Default for PixelTriggerTool {
+    fn default() -> Self {
+        Self {
+            worker: Worker::new("Pixel Trigger"),
+            calibration: CalibrationManager::new(),
+            calibrating: None,
+            show_regions: false,
+        }
+    }
+}
+
+impl Tool for PixelTriggerTool {
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
+    }
+
+    fn is_running(&self) -> bool {
+        self.worker.is_running()
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
+        if let Some(hwnd) = game_hwnd {
+            self.start_watching(
+                app_settings.pixel_trigger.clone(),
+                hwnd,
+                app_settings.global.action_rate_limit,
+            );
+        } else {
+            self.worker.set_status(tr!("status.connect_first"));
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        settings: &mut crate::settings::AppSettings,
+        game_hwnd: Option<HWND>,
+        hotkey_error: Option<&str>,
+    ) {
+        // Handle calibration interaction
+        if let Some(hwnd) = game_hwnd {
+            self.update_calibration(settings, hwnd);
+        } else {
+            // Disconnected logic
+            if self.worker.is_running() {
+                self.worker.stop();
+                self.worker.set_status(tr!("header.disconnected"));
+            }
+            self.calibration.cancel();
+            self.calibrating = None;
+        }
+
+        let rate_limit = settings.global.action_rate_limit;
+        let settings = &mut settings.pixel_trigger;
+
+        // Repaint if calibrating to capture clicks immediately
+        if self.calibration.is_active() {
+            ctx.request_repaint();
+        }
+
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
+        let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
+
+        let action = render_ui(
+            ui,
+            settings,
+            &mut self.show_regions,
+            self.calibrating,
+            self.calibration.magnifier(),
+            is_running,
+            &status,
+            &stats,
+            game_hwnd.is_some(),
+            game_hwnd.and_then(get_client_size),
+            hotkey_error,
+        );
+
+        match action {
+            PixelTriggerUiAction::StartCalibration(target) => {
+                match target {
+                    PixelTriggerTarget::Pixel => self.calibration.start_pixel(),
+                    PixelTriggerTarget::ClickPosition => self.calibration.start_point(),
+                }
+                self.calibrating = Some(target);
+                self.worker.set_status(tr!("status.click_button"));
+            }
+            PixelTriggerUiAction::CancelCalibration => {
+                self.calibration.cancel();
+                self.calibrating = None;
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            PixelTriggerUiAction::ResetToDefaults => {
+                self.calibration.cancel();
+                self.calibrating = None;
+                *settings = PixelTriggerSettings::default();
+                self.worker.set_status(tr!("status.settings_reset"));
+            }
+            PixelTriggerUiAction::Start => {
+                if let Some(hwnd) = game_hwnd {
+                    self.start_watching(settings.clone(), hwnd, rate_limit);
+                } else {
+                    self.worker.set_status(tr!("status.connect_first"));
+                }
+            }
+            PixelTriggerUiAction::Stop => {
+                self.stop(StopReason::User);
+            }
+            PixelTriggerUiAction::None => {}
+        }
+    }
+
+    fn update_calibration(&mut self, settings: &mut crate::settings::AppSettings, hwnd: HWND) {
+        self.calibration
+            .set_grid_size(settings.global.calibration_grid_px);
+        let settings = &mut settings.pixel_trigger;
+        match self.calibration.update(hwnd) {
+            Some(CalibrationResult::Pixel(x, y, r, g, b)) => {
+                settings.pixel = Some((x, y));
+                settings.expected_color = [r, g, b];
+                self.calibrating = None;
+                self.worker.set_status(tr!("status.calibration_recorded"));
+            }
+            Some(CalibrationResult::Point(x, y)) => {
+                settings.click_position = Some((x, y));
+                self.calibrating = None;
+                self.worker.set_status(tr!("status.calibration_recorded"));
+            }
+            Some(CalibrationResult::Cancelled) => {
+                self.calibrating = None;
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            _ => {}
+        }
+        if self.calibration.is_adjusting() {
+            self.worker.set_status(tr!("status.nudge_hint"));
+        }
+    }
+
+    fn is_calibrating(&self) -> bool {
+        self.calibration.is_active()
+    }
+
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
+
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        self.worker.client_size()
+    }
+
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
+
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
+
+    fn get_log(&self) -> Vec<LogEntry> {
+        self.worker.get_log()
+    }
+
+    fn region_preview(
+        &self,
+        app_settings: &crate::settings::AppSettings,
+        game_hwnd: HWND,
+    ) -> Option<Vec<OverlayShape>> {
+        if !self.show_regions {
+            return None;
+        }
+        let settings = &app_settings.pixel_trigger;
+        let mut points = vec![(settings.pixel, "Watched pixel")];
+        if settings.response == PixelTriggerResponse::Click {
+            points.push((settings.click_position, "Click"));
+        }
+        let shapes = points
+            .into_iter()
+            .filter_map(|(point, label)| {
+                let (x, y) = point.and_then(|(x, y)| denormalize_point(game_hwnd, x, y))?;
+                Some(OverlayShape::point(
+                    x,
+                    y,
+                    REGION_POINT_COLOR,
+                    Some(label.to_string()),
+                ))
+            })
+            .collect();
+        Some(shapes)
+    }
+}
+
+impl PixelTriggerTool {
+    fn start_watching(
+        &mut self,
+        settings: PixelTriggerSettings,
+        game_hwnd: HWND,
+        rate_limit: ActionRateLimit,
+    ) {
+        let Some((pixel_x, pixel_y)) = settings.pixel else {
+            self.worker.set_status(tr!("status.calibrate_first"));
+            return;
+        };
+        if settings.response == PixelTriggerResponse::Click && settings.click_position.is_none() {
+            self.worker.set_status(tr!("status.calibrate_first"));
+            return;
+        }
+        self.worker.set_status(tr!("status.starting"));
+
+        let started = self.worker.start(
+            move |running: Arc<AtomicBool>,
+                  paused: Arc<AtomicBool>,
+                  status: StatusSender,
+                  log: SharedLog,
+                  stats: SharedStats| {
+                let mut throttle = ActionThrottle::new(rate_limit, Arc::clone(&log));
+                let mut trigger = PixelTrigger::new(&settings);
+                let response = match settings.response {
+                    PixelTriggerResponse::Key => format!("key {}", hotkey_key_label(settings.key)),
+                    PixelTriggerResponse::Click => "click".to_string(),
+                };
+                let [r, g, b] = settings.expected_color;
+                Worker::push_log(
+                    &log,
+                    &format!(
+                        "Watching for ({}, {}, {}) +/-{}; responding with {}",
+                        r, g, b, settings.tolerance, response
+                    ),
+                );
+
+                // Only the first of a run of failed reads is logged
+                let mut read_failed = false;
+                while Worker::checkpoint(&running, &paused).is_continue() {
+                    // Re-resolved each poll so a resized window keeps the same spot
+                    let color = denormalize_point(game_hwnd, pixel_x, pixel_y)
+                        .ok_or(HelperError::WindowInvalid)
+                        .and_then(|(x, y)| read_pixel(game_hwnd, x, y));
+                    match color {
+                        Ok(color) => {
+                            read_failed = false;
+                            if trigger.sample(color, Instant::now()) {
+                                throttle.wait();
+                                if send_response(&settings, game_hwnd) {
+                                    stats.lock().unwrap().add_items(1);
+                                    let [r, g, b] = color;
+                                    Worker::push_log(
+                                        &log,
+                                        &format!(
+                                            "Pixel changed to ({}, {}, {}), sent {}",
+                                            r, g, b, response
+                                        ),
+                                    );
+                                } else {
+                                    let error = "Response could not be sent to the game window";
+                                    stats.lock().unwrap().set_error(error);
+                                    push_log_entry(&log, LogLevel::Warn, error);
+                                }
+                            }
+                        }
+                        Err(e) if !read_failed => {
+                            read_failed = true;
+                            push_log_entry(
+                                &log,
+                                LogLevel::Warn,
+                                &format!("Could not read the watched pixel: {}", e),
+                            );
+                        }
+                        Err(_) => {}
+                    }
+
+                    match trigger.cooldown_left(Instant::now()) {
+                        Some(left) => status.set(tr!(
+                            "status.cooling_down",
+                            seconds = format!("{:.1}", left.as_secs_f32())
+                        )),
+                        None => status.set(tr!("status.watching_pixel")),
+                    }
+                    Worker::sleep_while_running(&running, settings.poll_interval_ms);
+                }
+
+                running.store(false, Ordering::Relaxed);
+                status.set(tr!("status.stopped"));
+            },
+        );
+        if started {
+            self.worker.set_client_size(get_client_size(game_hwnd));
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
+    }
+}
+
+/// Press the configured key or click the configured spot in the background
+fn send_response(settings: &PixelTriggerSettings, game_hwnd: HWND) -> bool {
+    match settings.response {
+        PixelTriggerResponse::Key => key_press_post(game_hwnd, hotkey_key_to_vk(settings.key)),
+        PixelTriggerResponse::Click => settings
+            .click_position
+            .and_then(|(x, y)| denormalize_point(game_hwnd, x, y))
+            .is_some_and(|(x, y)| click_at_position_post(game_hwnd, x, y)),
+    }
+}
+
+/// Decides from each sampled color whether to respond: fires once the pixel leaves
+/// the expected color, then ignores samples until the cooldown is over
+struct PixelTrigger {
+    expected: [u8; 3],
+    tolerance: u8,
+    cooldown: Duration,
+    cooldown_until: Option<Instant>,
+}
+
+impl PixelTrigger {
+    fn new(settings: &PixelTriggerSettings) -> Self {
+        Self {
+            expected: settings.expected_color,
+            tolerance: settings.tolerance,
+            cooldown: Duration::from_millis(settings.cooldown_ms),
+            cooldown_until: None,
+        }
+    }
+
+    /// Whether `color`, sampled at `now`, calls for a response (which starts the cooldown)
+    fn sample(&mut self, color: [u8; 3], now: Instant) -> bool {
+        if self.cooldown_left(now).is_some() || color_matches(color, self.expected, self.tolerance)
+        {
+            return false;
+        }
+        self.cooldown_until = Some(now + self.cooldown);
+        true
+    }
+
+    /// Time until samples count again, if a cooldown is running
+    fn cooldown_left(&self, now: Instant) -> Option<Duration> {
+        self.cooldown_until
+            .map(|until| until.saturating_duration_since(now))
+            .filter(|left| !left.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(cooldown_ms: u64) -> PixelTrigger {
+        PixelTrigger::new(&PixelTriggerSettings {
+            expected_color: [200, 30, 30],
+            tolerance: 20,
+            cooldown_ms,
+            ..PixelTriggerSettings::default()
+        })
+    }
+
+    #[test]
+    fn test_pixel_trigger_ignores_colors_within_tolerance() {
+        let mut trigger = trigger(1_000);
+        let now = Instant::now();
+        assert!(!trigger.sample([200, 30, 30], now));
+        assert!(!trigger.sample([180, 50, 10], now));
+        assert!(trigger.sample([179, 30, 30], now));
+    }
+
+    #[test]
+    fn test_pixel_trigger_waits_out_the_cooldown() {
+        let mut trigger = trigger(1_000);
+        let start = Instant::now();
+        let empty_bar = [40, 40, 40];
+        assert!(trigger.sample(empty_bar, start));
+        assert!(!trigger.sample(empty_bar, start + Duration::from_millis(999)));
+        assert_eq!(
+            trigger.cooldown_left(start + Duration::from_millis(400)),
+            Some(Duration::from_millis(600))
+        );
+        assert_eq!(trigger.cooldown_left(start + Duration::from_secs(1)), None);
+        assert!(trigger.sample(empty_bar, start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_pixel_trigger_without_cooldown_fires_every_sample() {
+        let mut trigger = trigger(0);
+        let now = Instant::now();
+        assert!(trigger.sample([0, 0, 0], now));
+        assert!(trigger.sample([0, 0, 0], now));
+    }
+}
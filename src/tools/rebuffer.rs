@@ -0,0 +1,332 @@
+use crate::automation::context::AutomationContext;
+use crate::automation::macro_runner::run_input_action;
+use crate::calibration::{CalibrationManager, CalibrationResult};
+use crate::core::clock::format_countdown;
+use crate::core::coords::denormalize_point;
+use crate::core::overlay_window::{OverlayShape, REGION_POINT_COLOR};
+use crate::core::throttle::ActionThrottle;
+use crate::core::window::get_client_size;
+use crate::core::worker::{
+    push_log_entry, LogEntry, LogLevel, SharedLog, SharedStats, StatusSender, Worker, WorkerStats,
+};
+use crate::settings::{ActionRateLimit, MacroAction, RebufferSettings};
+use crate::tools::r#trait::{StopReason, Tool};
+use crate::ui::rebuffer::{render_ui, RebufferUiAction};
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use windows::Win32::Foundation::HWND;
+
+// How often the countdown in the status line is refreshed
+const COUNTDOWN_STEP: Duration = Duration::from_secs(1);
+
+/// Runs a short click / key press / delay list every few minutes to renew buffs
+pub struct RebufferTool {
+    // Runtime state (Worker)
+    worker: Worker,
+
+    // Calibration of a click action's position
+    calibration: CalibrationManager,
+    calibrating_action: Option<usize>,
+    show_regions: bool,
+}
+
+impl Default for RebufferTool {
+    fn default() -> Self {
+        Self {
+            worker: Worker::new("Rebuffer"),
+            calibration: CalibrationManager::new(),
+            calibrating_action: None,
+            show_regions: false,
+        }
+    }
+}
+
+impl Tool for RebufferTool {
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
+    }
+
+    fn is_running(&self) -> bool {
+        self.worker.is_running()
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
+        if let Some(hwnd) = game_hwnd {
+            self.start_rebuffing(
+                app_settings.rebuffer.clone(),
+                hwnd,
+                app_settings.global.action_rate_limit,
+            );
+        } else {
+            self.worker.set_status(tr!("status.connect_first"));
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        settings: &mut crate::settings::AppSettings,
+        game_hwnd: Option<HWND>,
+        hotkey_error: Option<&str>,
+    ) {
+        // Handle calibration interaction
+        if let Some(hwnd) = game_hwnd {
+            self.update_calibration(settings, hwnd);
+        } else {
+            // Disconnected logic
+            if self.worker.is_running() {
+                self.worker.stop();
+                self.worker.set_status(tr!("header.disconnected"));
+            }
+            self.calibration.cancel();
+            self.calibrating_action = None;
+        }
+
+        let rate_limit = settings.global.action_rate_limit;
+        let settings = &mut settings.rebuffer;
+
+        // Repaint if calibrating to capture clicks immediately
+        if self.calibration.is_active() {
+            ctx.request_repaint();
+        }
+
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
+        let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
+
+        let action = render_ui(
+            ui,
+            settings,
+            &mut self.show_regions,
+            self.calibrating_action,
+            self.calibration.magnifier(),
+            is_running,
+            &status,
+            &stats,
+            game_hwnd.is_some(),
+            game_hwnd.and_then(get_client_size),
+            hotkey_error,
+        );
+
+        match action {
+            RebufferUiAction::StartCalibration(idx) => {
+                self.calibration.start_point();
+                self.calibrating_action = Some(idx);
+                self.worker.set_status(tr!("status.click_to_set_position"));
+            }
+            RebufferUiAction::CancelCalibration => {
+                self.calibration.cancel();
+                self.calibrating_action = None;
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            RebufferUiAction::ResetToDefaults => {
+                self.calibration.cancel();
+                self.calibrating_action = None;
+                *settings = RebufferSettings::default();
+                self.worker.set_status(tr!("status.settings_reset"));
+            }
+            RebufferUiAction::Start => {
+                if let Some(hwnd) = game_hwnd {
+                    self.start_rebuffing(settings.clone(), hwnd, rate_limit);
+                } else {
+                    self.worker.set_status(tr!("status.connect_first"));
+                }
+            }
+            RebufferUiAction::Stop => {
+                self.stop(StopReason::User);
+            }
+            RebufferUiAction::None => {}
+        }
+    }
+
+    fn update_calibration(&mut self, settings: &mut crate::settings::AppSettings, hwnd: HWND) {
+        self.calibration
+            .set_grid_size(settings.global.calibration_grid_px);
+        match self.calibration.update(hwnd) {
+            Some(CalibrationResult::Point(x, y)) => {
+                let action = self
+                    .calibrating_action
+                    .take()
+                    .and_then(|idx| settings.rebuffer.actions.get_mut(idx));
+                if let Some(MacroAction::Click { coordinate, .. }) = action {
+                    *coordinate = Some((x, y));
+                    self.worker.set_status(tr!("status.calibration_recorded"));
+                }
+            }
+            Some(CalibrationResult::Cancelled) => {
+                self.calibrating_action = None;
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            _ => {}
+        }
+        if self.calibration.is_adjusting() {
+            self.worker.set_status(tr!("status.nudge_hint"));
+        }
+    }
+
+    fn is_calibrating(&self) -> bool {
+        self.calibration.is_active()
+    }
+
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
+
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        self.worker.client_size()
+    }
+
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
+
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
+
+    fn get_log(&self) -> Vec<LogEntry> {
+        self.worker.get_log()
+    }
+
+    fn region_preview(
+        &self,
+        app_settings: &crate::settings::AppSettings,
+        game_hwnd: HWND,
+    ) -> Option<Vec<OverlayShape>> {
+        if !self.show_regions {
+            return None;
+        }
+        let shapes = app_settings
+            .rebuffer
+            .actions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, action)| match action {
+                MacroAction::Click {
+                    coordinate: Some((x, y)),
+                    ..
+                } => {
+                    let (x, y) = denormalize_point(game_hwnd, *x, *y)?;
+                    Some(OverlayShape::point(
+                        x,
+                        y,
+                        REGION_POINT_COLOR,
+                        Some(format!("Click #{}", idx + 1)),
+                    ))
+                }
+                _ => None,
+            })
+            .collect();
+        Some(shapes)
+    }
+}
+
+impl RebufferTool {
+    fn start_rebuffing(
+        &mut self,
+        settings: RebufferSettings,
+        game_hwnd: HWND,
+        rate_limit: ActionRateLimit,
+    ) {
+        if settings.actions.is_empty() {
+            self.worker.set_status(tr!("status.no_actions"));
+            return;
+        }
+        let click_unset = settings.actions.iter().any(|action| {
+            matches!(
+                action,
+                MacroAction::Click {
+                    coordinate: None,
+                    ..
+                }
+            )
+        });
+        if click_unset {
+            self.worker.set_status(tr!("status.calibrate_first"));
+            return;
+        }
+        self.worker.set_status(tr!("status.starting"));
+
+        let started = self.worker.start(
+            move |running: Arc<AtomicBool>,
+                  paused: Arc<AtomicBool>,
+                  status: StatusSender,
+                  log: SharedLog,
+                  stats: SharedStats| {
+                let throttle = ActionThrottle::new(rate_limit, Arc::clone(&log));
+                let mut ctx = match AutomationContext::new(game_hwnd, throttle) {
+                    Ok(ctx) => ctx,
+                    Err(e) => {
+                        push_log_entry(&log, LogLevel::Error, &e.status());
+                        stats.lock().unwrap().record_error(&e);
+                        running.store(false, Ordering::Relaxed);
+                        status.set(e.status());
+                        return;
+                    }
+                };
+                let interval = Duration::from_secs(settings.interval_minutes * 60);
+                Worker::push_log(
+                    &log,
+                    &format!(
+                        "Rebuff ({} actions) every {} min",
+                        settings.actions.len(),
+                        settings.interval_minutes
+                    ),
+                );
+
+                let mut next_rebuff = Instant::now();
+                if !settings.run_immediately_on_start {
+                    next_rebuff += interval;
+                }
+                while Worker::checkpoint(&running, &paused).is_continue() {
+                    let remaining = next_rebuff.saturating_duration_since(Instant::now());
+                    if !remaining.is_zero() {
+                        status.set(format!(
+                            "Active - next rebuff in {}",
+                            format_countdown(remaining)
+                        ));
+                        let step = remaining.min(COUNTDOWN_STEP);
+                        Worker::sleep_while_running(&running, step.as_millis() as u64);
+                        continue;
+                    }
+
+                    for (idx, action) in settings.actions.iter().enumerate() {
+                        if Worker::checkpoint(&running, &paused).is_break() {
+                            break;
+                        }
+                        run_input_action(&mut ctx, idx, action, &running, &status, &log, &stats);
+                    }
+                    if running.load(Ordering::Relaxed) {
+                        stats.lock().unwrap().add_items(1);
+                        Worker::push_log(&log, "Rebuff sent");
+                    }
+                    next_rebuff = Instant::now() + interval;
+                }
+
+                running.store(false, Ordering::Relaxed);
+                status.set("Stopped");
+            },
+        );
+        if started {
+            self.worker.set_client_size(get_client_size(game_hwnd));
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use crate::core::hotkey::{hotkey_key_label, hotkey_key_to_vk};
+use crate::core::input::key_press_post;
+use crate::core::throttle::ActionThrottle;
+use crate::core::worker::{
+    push_log_entry, LogEntry, LogLevel, SharedLog, SharedStats, StatusSender, Worker, WorkerStats,
+};
+use crate::settings::{ActionRateLimit, RotationEntry, RotationSettings};
+use crate::tools::r#trait::{StopReason, Tool};
+use crate::ui::rotation::{render_ui, RotationUiAction};
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use windows::Win32::Foundation::HWND;
+
+// Longest the worker sleeps between scheduling decisions
+const ROTATION_TICK: Duration = Duration::from_millis(25);
+
+// How often the cooldown bars are redrawn while running
+const COOLDOWN_BAR_REFRESH: Duration = Duration::from_millis(100);
+
+/// When each entry of the running rotation is off cooldown again (`None` = ready)
+type CooldownTimers = Arc<Mutex<Vec<Option<Instant>>>>;
+
+/// Presses whichever key of a list is off cooldown next, by priority, in the background
+pub struct RotationTool {
+    // Runtime state (Worker)
+    worker: Worker,
+    // Per-entry timers of the current run, shared with the worker for the cooldown bars
+    timers: CooldownTimers,
+    // Entries of the current run (the list can't be edited while it runs)
+    running_entries: Vec<RotationEntry>,
+}
+
+impl Default for RotationTool {
+    fn default() -> Self {
+        Self {
+            worker: Worker::new("Skill Rotation"),
+            timers: Arc::new(Mutex::new(Vec::new())),
+            running_entries: Vec::new(),
+        }
+    }
+}
+
+impl Tool for RotationTool {
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
+    }
+
+    fn is_running(&self) -> bool {
+        self.worker.is_running()
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
+        if let Some(hwnd) = game_hwnd {
+            self.start_rotation(
+                app_settings.rotation.clone(),
+                hwnd,
+                app_settings.global.action_rate_limit,
+            );
+        } else {
+            self.worker.set_status(tr!("status.connect_first"));
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        settings: &mut crate::settings::AppSettings,
+        game_hwnd: Option<HWND>,
+        hotkey_error: Option<&str>,
+    ) {
+        // Disconnected logic
+        if game_hwnd.is_none() && self.worker.is_running() {
+            self.worker.stop();
+            self.worker.set_status(tr!("header.disconnected"));
+        }
+
+        let rate_limit = settings.global.action_rate_limit;
+        let settings = &mut settings.rotation;
+
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
+        let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
+
+        let cooldowns = if is_running {
+            // Keep the bars moving
+            ctx.request_repaint_after(COOLDOWN_BAR_REFRESH);
+            Some(self.cooldown_fractions(Instant::now()))
+        } else {
+            None
+        };
+
+        let action = render_ui(
+            ui,
+            settings,
+            cooldowns.as_deref(),
+            is_running,
+            &status,
+            &stats,
+            game_hwnd.is_some(),
+            hotkey_error,
+        );
+
+        match action {
+            RotationUiAction::ResetToDefaults => {
+                *settings = RotationSettings::default();
+                self.worker.set_status(tr!("status.settings_reset"));
+            }
+            RotationUiAction::Start => {
+                if let Some(hwnd) = game_hwnd {
+                    self.start_rotation(settings.clone(), hwnd, rate_limit);
+                } else {
+                    self.worker.set_status(tr!("status.connect_first"));
+                }
+            }
+            RotationUiAction::Stop => {
+                self.stop(StopReason::User);
+            }
+            RotationUiAction::None => {}
+        }
+    }
+
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
+
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
+
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
+
+    fn get_log(&self) -> Vec<LogEntry> {
+        self.worker.get_log()
+    }
+}
+
+impl RotationTool {
+    /// Share of each entry's cooldown still left (0 = ready), in list order
+    fn cooldown_fractions(&self, now: Instant) -> Vec<f32> {
+        let timers = self.timers.lock().unwrap();
+        self.running_entries
+            .iter()
+            .zip(timers.iter())
+            .map(|(entry, ready_at)| {
+                let left = ready_at.map_or(Duration::ZERO, |at| at.saturating_duration_since(now));
+                if entry.cooldown_ms == 0 {
+                    0.0
+                } else {
+                    (left.as_secs_f32() * 1000.0 / entry.cooldown_ms as f32).min(1.0)
+                }
+            })
+            .collect()
+    }
+
+    fn start_rotation(
+        &mut self,
+        settings: RotationSettings,
+        game_hwnd: HWND,
+        rate_limit: ActionRateLimit,
+    ) {
+        if settings.entries.is_empty() {
+            self.worker.set_status(tr!("status.no_actions"));
+            return;
+        }
+        self.worker.set_status(tr!("status.starting"));
+
+        // A fresh timer list, so a run that is still winding down can't touch it
+        let timers: CooldownTimers = Arc::new(Mutex::new(vec![None; settings.entries.len()]));
+        let worker_timers = Arc::clone(&timers);
+        let entries = settings.entries.clone();
+        let started = self.worker.start(
+            move |running: Arc<AtomicBool>,
+                  paused: Arc<AtomicBool>,
+                  status: StatusSender,
+                  log: SharedLog,
+                  stats: SharedStats| {
+                let mut throttle = ActionThrottle::new(rate_limit, Arc::clone(&log));
+                let gcd = Duration::from_millis(settings.global_gcd_ms);
+                let keys: Vec<&str> = settings
+                    .entries
+                    .iter()
+                    .map(|entry| hotkey_key_label(entry.key))
+                    .collect();
+                Worker::push_log(
+                    &log,
+                    &format!(
+                        "Rotation: {} (global cooldown {} ms)",
+                        keys.join(", "),
+                        settings.global_gcd_ms
+                    ),
+                );
+
+                let mut gcd_until: Option<Instant> = None;
+                while Worker::checkpoint(&running, &paused).is_continue() {
+                    let now = Instant::now();
+                    let next = {
+                        let timers = worker_timers.lock().unwrap();
+                        next_entry(&settings.entries, &timers, gcd_until, now)
+                    };
+                    let Some(idx) = next else {
+                        status.set(tr!("status.waiting_for_cooldowns"));
+                        Worker::sleep_while_running(&running, ROTATION_TICK.as_millis() as u64);
+                        continue;
+                    };
+
+                    let entry = &settings.entries[idx];
+                    throttle.wait();
+                    if key_press_post(game_hwnd, hotkey_key_to_vk(entry.key)) {
+                        stats.lock().unwrap().add_items(1);
+                        status.set(tr!("status.pressed_key", key = hotkey_key_label(entry.key)));
+                    } else {
+                        let error = "Key press could not be sent to the game window";
+                        stats.lock().unwrap().set_error(error);
+                        push_log_entry(&log, LogLevel::Warn, error);
+                    }
+                    // Timed from the press, after any throttle wait
+                    let pressed = Instant::now();
+                    worker_timers.lock().unwrap()[idx] =
+                        Some(pressed + Duration::from_millis(entry.cooldown_ms));
+                    gcd_until = Some(pressed + gcd);
+                }
+
+                running.store(false, Ordering::Relaxed);
+                status.set(tr!("status.stopped"));
+            },
+        );
+        if started {
+            self.timers = timers;
+            self.running_entries = entries;
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
+    }
+}
+
+/// Entry to press at `now`: the ready one with the best (lowest) priority, earlier in the
+/// list on ties. `None` while the global cooldown runs or every entry is cooling down.
+/// `ready_at[i]` is when entry `i` is off cooldown (`None` = never pressed).
+fn next_entry(
+    entries: &[RotationEntry],
+    ready_at: &[Option<Instant>],
+    gcd_until: Option<Instant>,
+    now: Instant,
+) -> Option<usize> {
+    if gcd_until.is_some_and(|until| now < until) {
+        return None;
+    }
+    entries
+        .iter()
+        .zip(ready_at)
+        .enumerate()
+        .filter(|(_, (_, ready_at))| !ready_at.is_some_and(|at| now < at))
+        .min_by_key(|(idx, (entry, _))| (entry.priority, *idx))
+        .map(|(idx, _)| idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::HotkeyKey;
+
+    fn entry(key: HotkeyKey, priority: u32) -> RotationEntry {
+        RotationEntry {
+            key,
+            cooldown_ms: 1_000,
+            priority,
+        }
+    }
+
+    #[test]
+    fn test_next_entry_prefers_priority_then_list_order() {
+        let entries = [
+            entry(HotkeyKey::Digit1, 2),
+            entry(HotkeyKey::Digit2, 1),
+            entry(HotkeyKey::Digit3, 1),
+        ];
+        let now = Instant::now();
+        assert_eq!(next_entry(&entries, &[None; 3], None, now), Some(1));
+    }
+
+    #[test]
+    fn test_next_entry_skips_keys_on_cooldown() {
+        let entries = [entry(HotkeyKey::Digit1, 1), entry(HotkeyKey::Digit2, 2)];
+        let now = Instant::now();
+        let cooling = Some(now + Duration::from_millis(300));
+        assert_eq!(next_entry(&entries, &[cooling, None], None, now), Some(1));
+        assert_eq!(next_entry(&entries, &[cooling, cooling], None, now), None);
+        // Ready again the moment the cooldown is over
+        let later = now + Duration::from_millis(300);
+        assert_eq!(
+            next_entry(&entries, &[cooling, cooling], None, later),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_next_entry_waits_for_the_global_cooldown() {
+        let entries = [entry(HotkeyKey::Digit1, 1)];
+        let now = Instant::now();
+        let gcd_until = Some(now + Duration::from_millis(500));
+        assert_eq!(next_entry(&entries, &[None], gcd_until, now), None);
+        assert_eq!(
+            next_entry(
+                &entries,
+                &[None],
+                gcd_until,
+                now + Duration::from_millis(500)
+            ),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,372 @@
+use crate::automation::ocr_models::{build_engine, read_region_text, OcrPreprocess};
+use crate::calibration::{CalibrationManager, CalibrationResult};
+use crate::core::clock::to_local;
+use crate::core::coords::denormalize_rect;
+use crate::core::error::HelperError;
+use crate::core::ocr_parser::parse_ocr_number;
+use crate::core::overlay_window::{OverlayShape, REGION_AREA_COLOR};
+use crate::core::paths;
+use crate::core::stat_history::{StatHistory, StatSample};
+use crate::core::window::get_client_size;
+use crate::core::worker::{
+    push_log_entry, LogEntry, LogLevel, SharedLog, SharedStats, StatusSender, Worker, WorkerStats,
+};
+use crate::settings::{OcrModelSettings, StatLoggerSettings};
+use crate::tools::r#trait::{StopReason, Tool};
+use crate::ui::stat_logger::{render_ui, StatLoggerUiAction};
+use eframe::egui;
+use ocrs::DecodeMethod;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime};
+use windows::Win32::Foundation::HWND;
+
+/// Reads a counter from the screen every few seconds and keeps its history for the session
+pub struct StatLoggerTool {
+    // Runtime state (Worker)
+    worker: Worker,
+    // Readings of the current (or last) run, shared with the worker
+    history: Arc<Mutex<StatHistory>>,
+
+    // Calibration of the counter region
+    calibration: CalibrationManager,
+    show_regions: bool,
+}
+
+impl Default for StatLoggerTool {
+    fn default() -> Self {
+        Self {
+            worker: Worker::new("Stat Logger"),
+            history: Arc::new(Mutex::new(StatHistory::default())),
+            calibration: CalibrationManager::new(),
+            show_regions: false,
+        }
+    }
+}
+
+impl Tool for StatLoggerTool {
+    fn stop(&mut self, reason: StopReason) {
+        self.worker.stop_because(reason.status());
+    }
+
+    fn is_running(&self) -> bool {
+        self.worker.is_running()
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if paused {
+            self.worker.pause();
+        } else {
+            self.worker.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    fn start(&mut self, app_settings: &crate::settings::AppSettings, game_hwnd: Option<HWND>) {
+        if let Some(hwnd) = game_hwnd {
+            self.start_logging(
+                app_settings.stat_logger.clone(),
+                app_settings.global.ocr_models.clone(),
+                hwnd,
+            );
+        } else {
+            self.worker.set_status(tr!("status.connect_first"));
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        settings: &mut crate::settings::AppSettings,
+        game_hwnd: Option<HWND>,
+        hotkey_error: Option<&str>,
+    ) {
+        // Handle calibration interaction
+        if let Some(hwnd) = game_hwnd {
+            self.update_calibration(settings, hwnd);
+        } else {
+            // Disconnected logic
+            if self.worker.is_running() {
+                self.worker.stop();
+                self.worker.set_status(tr!("header.disconnected"));
+            }
+            self.calibration.cancel();
+        }
+
+        let ocr_models = settings.global.ocr_models.clone();
+        let settings = &mut settings.stat_logger;
+
+        // Repaint if calibrating to capture clicks immediately
+        if self.calibration.is_active() {
+            ctx.request_repaint();
+        }
+
+        // Still "running" until the thread has actually exited
+        let is_running = self.worker.is_running() || !self.worker.is_finished();
+        let status = self.worker.get_status();
+        let stats = self.worker.get_stats();
+
+        let action = {
+            let history = self.history.lock().unwrap();
+            render_ui(
+                ui,
+                settings,
+                &history,
+                &mut self.show_regions,
+                self.calibration.is_active(),
+                self.calibration.is_waiting_for_second_click(),
+                self.calibration.magnifier(),
+                is_running,
+                &status,
+                &stats,
+                game_hwnd.is_some(),
+                game_hwnd.and_then(get_client_size),
+                hotkey_error,
+            )
+        };
+
+        match action {
+            StatLoggerUiAction::StartRegionCalibration => {
+                self.calibration.start_area();
+                self.worker.set_status(tr!("status.click_area_corners"));
+            }
+            StatLoggerUiAction::CancelCalibration => {
+                self.calibration.cancel();
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            StatLoggerUiAction::ResetToDefaults => {
+                self.calibration.cancel();
+                *settings = StatLoggerSettings::default();
+                self.worker.set_status(tr!("status.settings_reset"));
+            }
+            StatLoggerUiAction::Start => {
+                if let Some(hwnd) = game_hwnd {
+                    self.start_logging(settings.clone(), ocr_models, hwnd);
+                } else {
+                    self.worker.set_status(tr!("status.connect_first"));
+                }
+            }
+            StatLoggerUiAction::Stop => {
+                self.stop(StopReason::User);
+            }
+            StatLoggerUiAction::None => {}
+        }
+    }
+
+    fn update_calibration(&mut self, settings: &mut crate::settings::AppSettings, hwnd: HWND) {
+        self.calibration
+            .set_grid_size(settings.global.calibration_grid_px);
+        match self.calibration.update(hwnd) {
+            Some(CalibrationResult::Area(l, t, w, h)) => {
+                settings.stat_logger.region = Some((l, t, w, h));
+                self.worker.set_status(tr!("status.region_calibrated"));
+            }
+            Some(CalibrationResult::Cancelled) => {
+                self.worker.set_status(tr!("status.calibration_cancelled"));
+            }
+            _ => {}
+        }
+        if let Some(text) = self.calibration.selection_status() {
+            self.worker.set_status_quiet(&text);
+        }
+    }
+
+    fn is_calibrating(&self) -> bool {
+        self.calibration.is_active()
+    }
+
+    fn get_status(&self) -> String {
+        self.worker.get_status()
+    }
+
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        self.worker.client_size()
+    }
+
+    fn get_stats(&self) -> WorkerStats {
+        self.worker.get_stats()
+    }
+
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        self.worker.take_finished_run()
+    }
+
+    fn get_log(&self) -> Vec<LogEntry> {
+        self.worker.get_log()
+    }
+
+    fn region_preview(
+        &self,
+        app_settings: &crate::settings::AppSettings,
+        game_hwnd: HWND,
+    ) -> Option<Vec<OverlayShape>> {
+        if !self.show_regions {
+            return None;
+        }
+        let shapes = app_settings
+            .stat_logger
+            .region
+            .and_then(|(x, y, w, h)| denormalize_rect(game_hwnd, x, y, w, h))
+            .map(|rect| OverlayShape {
+                rect,
+                color: REGION_AREA_COLOR,
+                label: Some("Stat".to_string()),
+            })
+            .into_iter()
+            .collect();
+        Some(shapes)
+    }
+}
+
+impl StatLoggerTool {
+    fn start_logging(
+        &mut self,
+        settings: StatLoggerSettings,
+        ocr_models: OcrModelSettings,
+        game_hwnd: HWND,
+    ) {
+        let Some(area) = settings.region else {
+            self.worker.set_status(tr!("status.calibrate_first"));
+            return;
+        };
+        self.worker.set_status(tr!("status.loading_ocr"));
+
+        // A fresh series, so a run that is still winding down can't add to it
+        let history = Arc::new(Mutex::new(StatHistory::default()));
+        let worker_history = Arc::clone(&history);
+        let started = self.worker.start(
+            move |running: Arc<AtomicBool>,
+                  paused: Arc<AtomicBool>,
+                  status: StatusSender,
+                  log: SharedLog,
+                  stats: SharedStats| {
+                let engine = match build_engine(&ocr_models, DecodeMethod::Greedy, &status, &log) {
+                    Ok(engine) => engine,
+                    Err(error) => {
+                        push_log_entry(&log, LogLevel::Error, &error.to_string());
+                        stats.lock().unwrap().record_error(&error);
+                        running.store(false, Ordering::Relaxed);
+                        status.set(error.status());
+                        return;
+                    }
+                };
+                let preprocess = OcrPreprocess {
+                    scale_factor: settings.scale_factor,
+                    invert_colors: settings.invert_colors,
+                    grayscale: settings.grayscale,
+                };
+                let mut csv = if settings.write_csv {
+                    match open_csv() {
+                        Ok((file, path)) => {
+                            Worker::push_log(
+                                &log,
+                                &format!("Writing readings to {}", path.display()),
+                            );
+                            Some(file)
+                        }
+                        Err(e) => {
+                            push_log_entry(
+                                &log,
+                                LogLevel::Warn,
+                                &format!("CSV file not written: {}", e),
+                            );
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                Worker::push_log(&log, &format!("Reading every {} s", settings.interval_secs));
+
+                let started_at = Instant::now();
+                while Worker::checkpoint(&running, &paused).is_continue() {
+                    let read = denormalize_rect(game_hwnd, area.0, area.1, area.2, area.3)
+                        .ok_or(HelperError::WindowInvalid)
+                        .and_then(|region| {
+                            read_region_text(&engine, game_hwnd, region, preprocess)
+                        });
+                    match read.map(|text| (parse_ocr_number(&text), text)) {
+                        Ok((Some(value), _)) => {
+                            let mut history = worker_history.lock().unwrap();
+                            history.push(StatSample {
+                                elapsed_secs: started_at.elapsed().as_secs_f64(),
+                                value,
+                            });
+                            let delta = history.delta().unwrap_or(0);
+                            drop(history);
+
+                            stats.lock().unwrap().add_items(1);
+                            Worker::push_log(&log, &format!("Read {} ({:+})", value, delta));
+                            status.set(format!("Latest: {} ({:+} this session)", value, delta));
+                            if let Some(file) = csv.as_mut() {
+                                if let Err(e) =
+                                    writeln!(file, "{}", csv_row(SystemTime::now(), value))
+                                {
+                                    push_log_entry(
+                                        &log,
+                                        LogLevel::Warn,
+                                        &format!("CSV file not written: {}", e),
+                                    );
+                                    csv = None;
+                                }
+                            }
+                        }
+                        // Skipped; the series goes on with the next reading
+                        Ok((None, text)) => push_log_entry(
+                            &log,
+                            LogLevel::Warn,
+                            &format!("No number in \"{}\"", text.trim()),
+                        ),
+                        Err(e) => push_log_entry(&log, LogLevel::Warn, &e.to_string()),
+                    }
+                    Worker::sleep_while_running(&running, settings.interval_secs * 1000);
+                }
+
+                running.store(false, Ordering::Relaxed);
+                status.set("Stopped");
+            },
+        );
+        if started {
+            self.worker.set_client_size(get_client_size(game_hwnd));
+            self.history = history;
+        } else {
+            self.worker.set_status(tr!("status.already_running"));
+        }
+    }
+}
+
+/// Today's `stats_<date>.csv` in the logs folder, opened for appending; the header is
+/// written when the file is new
+fn open_csv() -> Result<(File, PathBuf), String> {
+    let date = to_local(SystemTime::now())
+        .map(|time| time.date())
+        .unwrap_or_else(|| "undated".to_string());
+    let dir = paths::logs_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("stats_{}.csv", date));
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| e.to_string())?;
+    if is_new {
+        writeln!(file, "time,value").map_err(|e| e.to_string())?;
+    }
+    Ok((file, path))
+}
+
+/// `YYYY-MM-DD HH:MM:SS,value`
+fn csv_row(at: SystemTime, value: i64) -> String {
+    let time = to_local(at)
+        .map(|time| format!("{} {}", time.date(), time.clock()))
+        .unwrap_or_default();
+    format!("{},{}", time, value)
+}
@@ -1,14 +1,77 @@
 // Shared trait for all automation tools
 use windows::Win32::Foundation::HWND;
 use eframe::egui;
+use crate::core::overlay_window::OverlayShape;
+use crate::core::worker::{JournalEntry, LogEntry, WorkerStats};
 use crate::settings::AppSettings;
+use crate::tools::undo::UndoEntry;
+
+/// Change already made to `custom_macros` that the app must mirror in its tool list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolsChange {
+    MacroInserted(usize),
+    MacroRemoved(usize),
+    MacrosSwapped(usize, usize),
+}
+
+/// Step of an automatic reconnect that the app must act on (the other tools are its business)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelogEvent {
+    /// The disconnect dialog showed up: stop the other tools before the reconnect clicks
+    Disconnected,
+    /// The reconnect actions are done: the stopped tools may start again
+    Reconnected,
+}
+
+/// Why the app stops a tool; a tool that was running shows it as its status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// Stop button, overlay or running panel (also app exit and macro removal)
+    User,
+    EmergencyHotkey,
+    CornerFailsafe,
+    RemoteControl,
+    Schedule,
+    /// The auto-relog saw the disconnect dialog
+    Disconnected,
+    /// Every other tool stops before one starts
+    OtherToolStarted,
+    /// Profile switch or settings import
+    SettingsReplaced,
+    /// The game window no longer has the size the calibrations were made at
+    WindowResized,
+}
+
+impl StopReason {
+    pub fn status(self) -> &'static str {
+        match self {
+            StopReason::User => tr!("status.stopped"),
+            StopReason::EmergencyHotkey => tr!("status.stopped_emergency_hotkey"),
+            StopReason::CornerFailsafe => tr!("status.stopped_corner_failsafe"),
+            StopReason::RemoteControl => tr!("status.stopped_remote_control"),
+            StopReason::Schedule => tr!("status.stopped_schedule"),
+            StopReason::Disconnected => tr!("status.stopped_disconnected"),
+            StopReason::OtherToolStarted => tr!("status.stopped_other_tool"),
+            StopReason::SettingsReplaced => tr!("status.stopped_settings_replaced"),
+            StopReason::WindowResized => tr!("status.stopped_window_resized"),
+        }
+    }
+}
 
 /// Common interface that all tools must implement
 pub trait Tool {
 
 
-    /// Stop the tool (emergency stop)
-    fn stop(&mut self);
+    /// Stop the tool; a running one shows `reason` as its status
+    fn stop(&mut self, reason: StopReason);
+
+    /// Hold a running tool at its next checkpoint, or let it continue (no-op without pause support)
+    fn set_paused(&mut self, _paused: bool) {}
+
+    /// Check if the tool is held by a pause
+    fn is_paused(&self) -> bool {
+        false
+    }
 
     /// Check if the tool is currently running
     fn is_running(&self) -> bool;
@@ -16,6 +79,9 @@ pub trait Tool {
     /// Start the tool with the given settings
     fn start(&mut self, settings: &AppSettings, game_hwnd: Option<HWND>);
 
+    /// Run a single iteration regardless of loop settings (no-op for tools without iterations)
+    fn start_one_shot(&mut self, _settings: &AppSettings, _game_hwnd: Option<HWND>) {}
+
     /// Update loop for UI and logic
     fn update(
         &mut self,
@@ -26,6 +92,60 @@ pub trait Tool {
         hotkey_error: Option<&str>,
     );
 
+    /// Advance an in-progress calibration without drawing the UI
+    /// (called for hidden tabs and in overlay mode so calibrations never freeze)
+    fn update_calibration(&mut self, _settings: &mut AppSettings, _game_hwnd: HWND) {}
+
+    /// Check if the tool is waiting for a calibration click in the game
+    fn is_calibrating(&self) -> bool {
+        false
+    }
+
+    /// Hand over data removed by destructive UI actions since the last call (for Undo)
+    fn take_undo_entries(&mut self) -> Vec<UndoEntry> {
+        Vec::new()
+    }
+
+    /// Hand over a change to the macro list made since the last call (the app rebuilds its tabs)
+    fn take_tools_change(&mut self) -> Option<ToolsChange> {
+        None
+    }
+
+    /// Hand over a reconnect step reached since the last call (only the auto-relog has any)
+    fn take_relog_event(&mut self) -> Option<RelogEvent> {
+        None
+    }
+
+    /// Carry an unfinished run's counters into the next start (no-op where they mean nothing,
+    /// i.e. tools whose runs don't count attempts)
+    fn seed_next_run(&mut self, _entry: JournalEntry) {}
+
+    /// Game client size the running tool worked out its calibrated pixel positions at
+    /// (None when stopped, or for tools that only send keys)
+    fn run_client_size(&self) -> Option<(i32, i32)> {
+        None
+    }
+
+    /// Latest status line, the same text the tool's own UI shows
+    fn get_status(&self) -> String;
+
+    /// Counters of the current run (or the last one once it ended)
+    fn get_stats(&self) -> WorkerStats;
+
+    /// Stats of a run that ended since the last call (for the lifetime statistics)
+    fn take_finished_run(&mut self) -> Option<WorkerStats> {
+        None
+    }
+
     /// Read current status log (for UI display)
-    fn get_log(&self) -> Vec<String>;
+    fn get_log(&self) -> Vec<LogEntry>;
+
+    /// Calibrated areas/points to draw over the game (client pixels), or None when "Show regions" is off
+    fn region_preview(
+        &self,
+        _settings: &AppSettings,
+        _game_hwnd: HWND,
+    ) -> Option<Vec<OverlayShape>> {
+        None
+    }
 }
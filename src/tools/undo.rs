@@ -0,0 +1,195 @@
+// Session-only undo for destructive edits (delete macro/action, script import,
+// clear calibration/region)
+use crate::settings::{
+    AppSettings, CollectionFillerSettings, MacroAction, MacroId, NamedMacro, NormRect,
+};
+use crate::tools::r#trait::ToolsChange;
+use crate::ui::collection_filler::{restore_calibration, CalibrationItem};
+use std::collections::VecDeque;
+
+pub const MAX_UNDO_ENTRIES: usize = 20;
+
+/// Data removed by a destructive action, enough to put it back where it was
+#[derive(Debug, Clone)]
+pub enum UndoEntry {
+    DeletedMacro {
+        index: usize,
+        named_macro: NamedMacro,
+    },
+    DeletedAction {
+        macro_id: MacroId,
+        action_index: usize,
+        action: MacroAction,
+    },
+    // Actions overwritten by "Import from script"
+    ReplacedActions {
+        macro_id: MacroId,
+        actions: Vec<MacroAction>,
+    },
+    ClearedCalibration {
+        item: CalibrationItem,
+        previous: CollectionFillerSettings,
+    },
+    ClearedSearchRegion {
+        previous: NormRect,
+    },
+}
+
+impl UndoEntry {
+    pub fn description(&self) -> String {
+        match self {
+            UndoEntry::DeletedMacro { named_macro, .. } => {
+                format!("Deleted macro \"{}\"", named_macro.name)
+            }
+            UndoEntry::DeletedAction { action_index, .. } => {
+                format!("Deleted action #{}", action_index + 1)
+            }
+            UndoEntry::ReplacedActions { .. } => "Imported a macro script".to_string(),
+            UndoEntry::ClearedCalibration { item, .. } => format!("Cleared {}", item.label()),
+            UndoEntry::ClearedSearchRegion { .. } => "Cleared the search region".to_string(),
+        }
+    }
+
+    /// Put the removed data back. Returns the change the tool list must mirror, if any
+    pub fn restore(self, settings: &mut AppSettings) -> Result<Option<ToolsChange>, String> {
+        match self {
+            UndoEntry::DeletedMacro { index, named_macro } => {
+                let index = index.min(settings.custom_macros.len());
+                settings.custom_macros.insert(index, named_macro);
+                Ok(Some(ToolsChange::MacroInserted(index)))
+            }
+            UndoEntry::DeletedAction {
+                macro_id,
+                action_index,
+                action,
+            } => {
+                let actions = &mut settings
+                    .macro_by_id_mut(macro_id)
+                    .ok_or("the macro no longer exists")?
+                    .settings
+                    .actions;
+                actions.insert(action_index.min(actions.len()), action);
+                Ok(None)
+            }
+            UndoEntry::ReplacedActions { macro_id, actions } => {
+                settings
+                    .macro_by_id_mut(macro_id)
+                    .ok_or("the macro no longer exists")?
+                    .settings
+                    .actions = actions;
+                Ok(None)
+            }
+            UndoEntry::ClearedCalibration { item, previous } => {
+                restore_calibration(item, &previous, &mut settings.collection_filler);
+                Ok(None)
+            }
+            UndoEntry::ClearedSearchRegion { previous } => {
+                settings.accept_item.search_region = Some(previous);
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Bounded stack of undo entries (oldest dropped first)
+#[derive(Default)]
+pub struct UndoStack {
+    entries: VecDeque<UndoEntry>,
+}
+
+impl UndoStack {
+    pub fn push(&mut self, entry: UndoEntry) {
+        if self.entries.len() >= MAX_UNDO_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn pop(&mut self) -> Option<UndoEntry> {
+        self.entries.pop_back()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_is_bounded() {
+        let mut stack = UndoStack::default();
+        for i in 0..MAX_UNDO_ENTRIES + 5 {
+            stack.push(UndoEntry::ClearedSearchRegion {
+                previous: (i as f32, 0.0, 1.0, 1.0),
+            });
+        }
+
+        let mut count = 0;
+        let mut last = None;
+        while let Some(UndoEntry::ClearedSearchRegion { previous }) = stack.pop() {
+            count += 1;
+            last = Some(previous.0);
+        }
+        assert_eq!(count, MAX_UNDO_ENTRIES);
+        assert_eq!(last, Some(5.0));
+    }
+
+    #[test]
+    fn test_deleted_macro_returns_to_its_index() {
+        let mut settings = AppSettings::default();
+        settings.custom_macros = vec![
+            NamedMacro::new("A".to_string()),
+            NamedMacro::new("B".to_string()),
+            NamedMacro::new("C".to_string()),
+        ];
+
+        let named_macro = settings.custom_macros.remove(1);
+        let entry = UndoEntry::DeletedMacro {
+            index: 1,
+            named_macro,
+        };
+        assert_eq!(
+            entry.restore(&mut settings),
+            Ok(Some(ToolsChange::MacroInserted(1)))
+        );
+
+        let names: Vec<&str> = settings
+            .custom_macros
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_deleted_action_returns_to_its_macro_after_reordering() {
+        let mut settings = AppSettings::default();
+        settings.custom_macros = vec![
+            NamedMacro::new("A".to_string()),
+            NamedMacro::new("B".to_string()),
+        ];
+        let b = settings.custom_macros[1].id;
+        let entry = UndoEntry::DeletedAction {
+            macro_id: b,
+            action_index: 0,
+            action: MacroAction::Delay { milliseconds: 250 },
+        };
+
+        settings.custom_macros.swap(0, 1);
+        assert_eq!(entry.clone().restore(&mut settings), Ok(None));
+        assert_eq!(settings.custom_macros[0].name, "B");
+        assert_eq!(settings.custom_macros[0].settings.actions.len(), 1);
+        assert!(settings.custom_macros[1].settings.actions.is_empty());
+
+        // Its macro deleted meanwhile: nothing lands in the macro that took its place
+        settings.custom_macros.remove(0);
+        assert_eq!(
+            entry.restore(&mut settings),
+            Err("the macro no longer exists".to_string())
+        );
+        assert!(settings.custom_macros[0].settings.actions.is_empty());
+    }
+}
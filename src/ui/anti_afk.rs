@@ -0,0 +1,209 @@
+use crate::calibration::MagnifierFrame;
+use crate::core::hotkey::{hotkey_key_label, try_capture_hotkey};
+use crate::core::worker::WorkerStats;
+use crate::settings::{AntiAfkInput, AntiAfkSettings, HotkeyKey, ANTI_AFK_INTERVAL_RANGE_MIN};
+use crate::ui::coord_edit::point_value;
+use crate::ui::magnifier::render_magnifier;
+use crate::ui::reset_button::reset_to_defaults_button;
+use eframe::egui;
+
+#[derive(Debug)]
+pub enum AntiAfkUiAction {
+    StartPositionCalibration,
+    CancelCalibration,
+    ResetToDefaults,
+    Start,
+    Stop,
+    None,
+}
+
+/// Render Anti-AFK UI
+pub fn render_ui(
+    ui: &mut egui::Ui,
+    settings: &mut AntiAfkSettings,
+    show_regions: &mut bool,
+    is_calibrating: bool,
+    magnifier: Option<&MagnifierFrame>,
+    is_running: bool,
+    status: &str,
+    stats: &WorkerStats,
+    game_connected: bool,
+    client_size: Option<(i32, i32)>,
+    hotkey_error: Option<&str>,
+) -> AntiAfkUiAction {
+    let mut action = AntiAfkUiAction::None;
+
+    if !game_connected {
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
+        return AntiAfkUiAction::None;
+    }
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.show_in_overlay, tr!("common.show_in_overlay"));
+        ui.checkbox(show_regions, tr!("common.show_regions"))
+            .on_hover_text(tr!("anti_afk.show_regions_hint"));
+        if reset_to_defaults_button(ui, "anti_afk_reset") {
+            action = AntiAfkUiAction::ResetToDefaults;
+        }
+    });
+    ui.add_space(8.0);
+
+    // 1. Settings Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("common.configuration"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        ui.label(
+            egui::RichText::new(tr!("anti_afk.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("anti_afk.interval")).strong());
+            ui.add(
+                egui::DragValue::new(&mut settings.interval_minutes)
+                    .clamp_range(ANTI_AFK_INTERVAL_RANGE_MIN.0..=ANTI_AFK_INTERVAL_RANGE_MIN.1)
+                    .suffix(" min"),
+            );
+        });
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("anti_afk.input")).strong());
+            for input in AntiAfkInput::ALL {
+                ui.radio_value(&mut settings.input, input, input.label());
+            }
+        });
+
+        ui.add_space(4.0);
+
+        match settings.input {
+            AntiAfkInput::Key => render_key_row(
+                ui,
+                "anti_afk_key_capture",
+                &mut settings.key,
+                tr!("anti_afk.key_hint"),
+            ),
+            AntiAfkInput::MouseWiggle => {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(tr!("anti_afk.position")).strong());
+
+                    if let Some(point) = settings.position.as_mut() {
+                        let (x, y) = *point;
+                        point_value(
+                            ui,
+                            "anti_afk_position",
+                            egui::RichText::new(format!("({:.3}, {:.3})", x, y))
+                                .monospace()
+                                .strong(),
+                            point,
+                            client_size,
+                        );
+                    } else {
+                        ui.label(
+                            egui::RichText::new(tr!("common.not_set"))
+                                .color(egui::Color32::YELLOW)
+                                .italics(),
+                        );
+                    }
+
+                    ui.separator();
+
+                    if is_calibrating {
+                        if ui
+                            .button(
+                                egui::RichText::new(tr!("common.stop"))
+                                    .color(egui::Color32::from_rgb(255, 100, 100)),
+                            )
+                            .clicked()
+                        {
+                            action = AntiAfkUiAction::CancelCalibration;
+                        }
+                        ui.label(
+                            egui::RichText::new(tr!("calibration.click_button"))
+                                .color(egui::Color32::YELLOW),
+                        );
+                    } else if ui
+                        .button(tr!("common.set"))
+                        .on_hover_text(tr!("anti_afk.position_hint"))
+                        .clicked()
+                    {
+                        action = AntiAfkUiAction::StartPositionCalibration;
+                    }
+                });
+
+                if let Some(frame) = magnifier {
+                    ui.add_space(4.0);
+                    render_magnifier(ui, frame);
+                }
+            }
+        }
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 2. Controls
+    ui.vertical_centered(|ui| {
+        let (btn_text, btn_color) = if is_running {
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
+        } else {
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
+        };
+
+        let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
+            .min_size(egui::vec2(200.0, 35.0));
+
+        if ui.add(button).clicked() {
+            action = if is_running {
+                AntiAfkUiAction::Stop
+            } else {
+                AntiAfkUiAction::Start
+            };
+        }
+    });
+
+    ui.add_space(12.0);
+    ui.separator();
+    ui.add_space(6.0);
+
+    // 3. Status
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
+
+    action
+}
+
+/// Key binding button: click it, then press the key to send (modifiers are ignored)
+pub fn render_key_row(ui: &mut egui::Ui, id: &str, key: &mut HotkeyKey, hint: &str) {
+    let capturing_id = ui.make_persistent_id(id);
+    let mut capturing = ui.data(|d| d.get_temp::<bool>(capturing_id).unwrap_or(false));
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("anti_afk.key")).strong());
+        let label = if capturing {
+            tr!("anti_afk.key_capturing")
+        } else {
+            hotkey_key_label(*key)
+        };
+        if ui.button(label).on_hover_text(hint).clicked() {
+            capturing = !capturing;
+        }
+    });
+
+    // Side buttons can't be sent as a key, so only a key press ends the capture
+    if capturing {
+        if let Some(captured) = try_capture_hotkey(ui.ctx()).and_then(|config| config.key) {
+            *key = captured;
+            capturing = false;
+        }
+    }
+    ui.data_mut(|d| d.insert_temp(capturing_id, capturing));
+}
@@ -1,7 +1,12 @@
-use crate::core::hotkey::{hotkey_label, try_capture_hotkey};
+use crate::core::hotkey::HotkeyProblem;
+use crate::core::sounds::Event;
 use crate::core::window::find_game_window;
-use crate::settings::{HotkeyConfig, HotkeyModifiers};
+use crate::settings::{
+    AppSettings, GlobalSettings, HotkeyActionId, HotkeyConfig, Language, StartMode,
+    ACTION_RATE_RANGE,
+};
 use eframe::egui;
+use std::collections::HashMap;
 use windows::Win32::Foundation::HWND;
 
 pub enum HeaderAction {
@@ -9,20 +14,89 @@ pub enum HeaderAction {
     Disconnect,
     ToggleLog,
     ToggleOverlay,
+    Statistics,
     Help,
+    Profile(ProfileAction),
+    SaveNow,
+    ExportSettings,
+    ImportSettings,
+    SetHotkey(HotkeyActionId, HotkeyConfig),
+    TestSound(Event),
+    TestOcrModels,
+    OpenFrameFolder,
     None,
 }
 
+pub enum ProfileAction {
+    Switch(String),
+    New(String),
+    Clone(String),
+    Rename(String),
+    Delete,
+}
+
+/// Read-only data the header shows next to the settings it edits
+pub struct HeaderContext<'a> {
+    pub ocr_model_report: &'a [(bool, String)],
+    pub frame_recorder_error: Option<&'a str>,
+    pub remote_listening: bool,
+    pub remote_error: Option<&'a str>,
+    pub tool_names: &'a [String],
+    pub next_scheduled: Option<&'a str>,
+    pub hotkey_rows: &'a [(HotkeyActionId, String, HotkeyConfig)],
+    pub hotkey_problems: &'a HashMap<HotkeyActionId, HotkeyProblem>,
+    pub hotkey_error: Option<&'a str>,
+    pub active_profile: &'a str,
+    pub profile_names: &'a [String],
+    pub unsaved_changes: bool,
+    pub settings_error: Option<&'a str>,
+}
+
 /// Render the unified app header (Connection Status + Utility Buttons)
 pub fn render_header(
     ui: &mut egui::Ui,
+    settings: &mut AppSettings,
     game_hwnd: &mut Option<HWND>,
     game_title: &mut String,
-    always_on_top: &mut bool,
-    emergency_stop_hotkey: &mut HotkeyConfig,
-    capturing_emergency_hotkey: &mut bool,
-    hotkey_error: Option<&str>,
+    capturing_hotkey: &mut Option<HotkeyActionId>,
+    context: HeaderContext,
 ) -> HeaderAction {
+    let HeaderContext {
+        ocr_model_report,
+        frame_recorder_error,
+        remote_listening,
+        remote_error,
+        tool_names,
+        next_scheduled,
+        hotkey_rows,
+        hotkey_problems,
+        hotkey_error,
+        active_profile,
+        profile_names,
+        unsaved_changes,
+        settings_error,
+    } = context;
+    let AppSettings {
+        global,
+        schedule,
+        title_pattern,
+        ..
+    } = settings;
+    let GlobalSettings {
+        always_on_top,
+        overlay_click_through,
+        start_mode,
+        language,
+        action_rate_limit,
+        click_guard_warn_only,
+        event_sounds,
+        ocr_models,
+        frame_recorder,
+        remote_control,
+        emergency_stop_focus_only,
+        corner_failsafe,
+        ..
+    } = global;
     let mut action = HeaderAction::None;
 
     // Use a Frame to give the header a distinct look
@@ -77,7 +151,7 @@ pub fn render_header(
                         if game_hwnd.is_none() {
                             if styled_button(
                                 ui,
-                                "Connect",
+                                tr!("header.connect"),
                                 Some(egui::Color32::from_rgb(50, 100, 200)), // Nice Blue
                             )
                             .clicked()
@@ -87,18 +161,18 @@ pub fn render_header(
                                     *game_title = title;
                                     action = HeaderAction::Connect(hwnd);
                                 } else {
-                                    *game_title = "No D3D Window found".to_string();
+                                    *game_title = tr!("header.no_window").to_string();
                                 }
                             }
                         } else if styled_button(
                             ui,
-                            "Disconnect",
+                            tr!("header.disconnect"),
                             Some(egui::Color32::from_rgb(200, 60, 60)), // Red
                         )
                         .clicked()
                         {
                             *game_hwnd = None;
-                            *game_title = "Disconnected".to_string();
+                            *game_title = tr!("header.disconnected").to_string();
                             action = HeaderAction::Disconnect;
                         }
 
@@ -122,7 +196,7 @@ pub fn render_header(
                             } else {
                                 ui.add(
                                     egui::Label::new(
-                                        egui::RichText::new("Waiting for game window...")
+                                        egui::RichText::new(tr!("header.waiting"))
                                             .color(egui::Color32::from_rgb(100, 100, 100))
                                             .size(11.0),
                                     )
@@ -130,6 +204,16 @@ pub fn render_header(
                                 );
                             }
                         }
+                        if let Some(next) = next_scheduled {
+                            ui.add(
+                                egui::Label::new(
+                                    egui::RichText::new(next)
+                                        .color(egui::Color32::from_rgb(150, 150, 150))
+                                        .size(11.0),
+                                )
+                                .wrap(false),
+                            );
+                        }
                     });
                 });
 
@@ -138,12 +222,15 @@ pub fn render_header(
                 ui.add_space(12.0);
 
                 // --- Middle: Quick actions ---
-                if styled_button(ui, "Overlay", None).clicked() {
+                if styled_button(ui, tr!("header.overlay"), None).clicked() {
                     action = HeaderAction::ToggleOverlay;
                 }
-                if styled_button(ui, "Log", None).clicked() {
+                if styled_button(ui, tr!("header.log"), None).clicked() {
                     action = HeaderAction::ToggleLog;
                 }
+                if styled_button(ui, tr!("header.stats"), None).clicked() {
+                    action = HeaderAction::Statistics;
+                }
                 if ui
                     .add(
                         egui::Button::new("?")
@@ -160,61 +247,200 @@ pub fn render_header(
                 ui.separator();
                 ui.add_space(12.0);
 
-                ui.checkbox(always_on_top, "Always on top");
+                ui.checkbox(always_on_top, tr!("header.always_on_top"));
+                ui.checkbox(overlay_click_through, tr!("header.click_through"))
+                    .on_hover_text(tr!("header.click_through_hint"));
 
-                ui.add_space(12.0);
-                ui.separator();
-                ui.add_space(12.0);
+                ui.checkbox(&mut action_rate_limit.enabled, tr!("header.rate_limit"))
+                    .on_hover_text(tr!("header.rate_limit_hint"));
+                ui.add_enabled(
+                    action_rate_limit.enabled,
+                    egui::DragValue::new(&mut action_rate_limit.max_per_second)
+                        .clamp_range(ACTION_RATE_RANGE.0..=ACTION_RATE_RANGE.1)
+                        .suffix(tr!("header.rate_limit_suffix")),
+                );
+                ui.checkbox(click_guard_warn_only, tr!("header.click_guard_warn_only"))
+                    .on_hover_text(tr!("header.click_guard_warn_only_hint"));
+
+                ui.label(tr!("header.start_mode"));
+                egui::ComboBox::from_id_source("start_mode")
+                    .selected_text(start_mode.label())
+                    .show_ui(ui, |ui| {
+                        for mode in StartMode::ALL {
+                            ui.selectable_value(start_mode, mode, mode.label());
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("header.start_mode_hint"));
+
+                egui::ComboBox::from_id_source("language")
+                    .selected_text(format!("🌐 {}", language.label()))
+                    .show_ui(ui, |ui| {
+                        for option in Language::ALL {
+                            ui.selectable_value(language, option, option.label());
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("header.language_hint"));
+            });
 
-                // --- Emergency hotkey ---
+            ui.add_space(8.0);
+
+            // --- Profiles ---
+            ui.horizontal(|ui| {
                 ui.label(
-                    egui::RichText::new("Emergency stop:")
+                    egui::RichText::new(tr!("header.profile"))
                         .color(egui::Color32::from_rgb(180, 180, 180)),
                 );
 
-                let label = if *capturing_emergency_hotkey {
-                    "Press a key...".to_string()
+                egui::ComboBox::from_id_source("active_profile")
+                    .selected_text(active_profile)
+                    .show_ui(ui, |ui| {
+                        for name in profile_names {
+                            if ui.selectable_label(name == active_profile, name).clicked()
+                                && name != active_profile
+                            {
+                                action = HeaderAction::Profile(ProfileAction::Switch(name.clone()));
+                            }
+                        }
+                    });
+
+                ui.add(
+                    egui::TextEdit::singleline(title_pattern)
+                        .hint_text(tr!("header.title_pattern"))
+                        .desired_width(130.0),
+                )
+                .on_hover_text(tr!("header.title_pattern_hint"));
+
+                // Name used by New / Clone / Rename
+                let name_id = ui.make_persistent_id("profile_name_input");
+                let mut new_name = ui.data(|d| d.get_temp::<String>(name_id).unwrap_or_default());
+                ui.add(
+                    egui::TextEdit::singleline(&mut new_name)
+                        .hint_text(tr!("header.profile_name"))
+                        .desired_width(110.0),
+                );
+
+                let has_name = !new_name.trim().is_empty();
+                let mut profile_action = None;
+                if ui
+                    .add_enabled(has_name, egui::Button::new(tr!("header.profile_new")))
+                    .clicked()
+                {
+                    profile_action = Some(ProfileAction::New(new_name.clone()));
+                }
+                if ui
+                    .add_enabled(has_name, egui::Button::new(tr!("header.profile_clone")))
+                    .on_hover_text(tr!("header.profile_clone_hint"))
+                    .clicked()
+                {
+                    profile_action = Some(ProfileAction::Clone(new_name.clone()));
+                }
+                if ui
+                    .add_enabled(has_name, egui::Button::new(tr!("header.profile_rename")))
+                    .clicked()
+                {
+                    profile_action = Some(ProfileAction::Rename(new_name.clone()));
+                }
+                // Two-step delete so one misclick can't drop a profile
+                let confirm_id = ui.make_persistent_id("profile_delete_confirm");
+                let confirming = ui.data(|d| d.get_temp::<bool>(confirm_id).unwrap_or(false));
+                let delete_text = if confirming {
+                    tr!("header.profile_confirm_delete")
                 } else {
-                    hotkey_label(emergency_stop_hotkey)
+                    tr!("header.profile_delete")
                 };
+                let delete_response = ui
+                    .add_enabled(profile_names.len() > 1, egui::Button::new(delete_text))
+                    .on_hover_text(tr!("header.profile_delete_hint"));
+                if delete_response.clicked() {
+                    if confirming {
+                        profile_action = Some(ProfileAction::Delete);
+                    }
+                    ui.data_mut(|d| d.insert_temp(confirm_id, !confirming));
+                } else if confirming && delete_response.clicked_elsewhere() {
+                    ui.data_mut(|d| d.insert_temp(confirm_id, false));
+                }
 
-                let button =
-                    egui::Button::new(egui::RichText::new(label).color(egui::Color32::WHITE))
-                        .min_size(egui::vec2(0.0, 24.0))
-                        .fill(if *capturing_emergency_hotkey {
-                            egui::Color32::from_rgb(90, 90, 120)
-                        } else {
-                            egui::Color32::from_white_alpha(10)
-                        });
-
-                if ui.add(button).clicked() {
-                    *capturing_emergency_hotkey = true;
+                if let Some(profile_action) = profile_action {
+                    new_name.clear();
+                    action = HeaderAction::Profile(profile_action);
                 }
+                ui.data_mut(|d| d.insert_temp(name_id, new_name));
+
+                ui.add_space(12.0);
+                ui.separator();
+                ui.add_space(12.0);
 
                 if ui
-                    .add(
-                        egui::Button::new(
-                            egui::RichText::new("Clear")
-                                .color(egui::Color32::from_rgb(200, 160, 160)),
-                        )
-                        .fill(egui::Color32::from_white_alpha(10))
-                        .min_size(egui::vec2(0.0, 22.0)),
+                    .button(tr!("header.save_now"))
+                    .on_hover_text(tr!("header.save_now_hint"))
+                    .clicked()
+                {
+                    action = HeaderAction::SaveNow;
+                }
+                if unsaved_changes {
+                    ui.label(
+                        egui::RichText::new(tr!("header.unsaved"))
+                            .color(egui::Color32::from_rgb(230, 180, 80)),
                     )
+                    .on_hover_text(tr!("header.unsaved_hint"));
+                }
+                if ui
+                    .button(tr!("header.export"))
+                    .on_hover_text(tr!("header.export_hint"))
+                    .clicked()
+                {
+                    action = HeaderAction::ExportSettings;
+                }
+                if ui
+                    .button(tr!("header.import"))
+                    .on_hover_text(tr!("header.import_hint"))
                     .clicked()
                 {
-                    emergency_stop_hotkey.key = None;
-                    emergency_stop_hotkey.modifiers = HotkeyModifiers::default();
+                    action = HeaderAction::ImportSettings;
                 }
 
-                let _ = hotkey_error;
+                if let Some(err) = settings_error {
+                    ui.label(
+                        egui::RichText::new(err).color(egui::Color32::from_rgb(200, 120, 120)),
+                    );
+                }
             });
 
-            if *capturing_emergency_hotkey {
-                if let Some(new_hotkey) = try_capture_hotkey(ui.ctx()) {
-                    *emergency_stop_hotkey = new_hotkey;
-                    *capturing_emergency_hotkey = false;
-                }
+            ui.add_space(4.0);
+            if let Some((hotkey_action, config)) = crate::ui::hotkeys::render_hotkeys_section(
+                ui,
+                hotkey_rows,
+                capturing_hotkey,
+                hotkey_problems,
+                emergency_stop_focus_only,
+                corner_failsafe,
+                hotkey_error,
+            ) {
+                action = HeaderAction::SetHotkey(hotkey_action, config);
+            }
+            if let Some(event) = crate::ui::sounds::render_sounds_section(ui, event_sounds) {
+                action = HeaderAction::TestSound(event);
+            }
+            if crate::ui::ocr_models::render_ocr_models_section(ui, ocr_models, ocr_model_report) {
+                action = HeaderAction::TestOcrModels;
+            }
+            if crate::ui::frame_recorder::render_frame_recorder_section(
+                ui,
+                frame_recorder,
+                frame_recorder_error,
+            ) {
+                action = HeaderAction::OpenFrameFolder;
             }
+            crate::ui::remote_control::render_remote_control_section(
+                ui,
+                remote_control,
+                remote_listening,
+                remote_error,
+            );
+            crate::ui::schedule::render_schedule_section(ui, schedule, tool_names);
+            crate::ui::probe::render_probe_section(ui, *game_hwnd);
         });
 
     action
@@ -0,0 +1,200 @@
+use crate::calibration::MagnifierFrame;
+use crate::core::worker::WorkerStats;
+use crate::settings::{AutoRelogSettings, RELOG_INTERVAL_RANGE_SECS};
+use crate::ui::image_path_field::image_path_field;
+use crate::ui::input_actions::{render_input_actions, InputActionsUiAction};
+use crate::ui::magnifier::render_magnifier;
+use crate::ui::reset_button::reset_to_defaults_button;
+use eframe::egui;
+
+#[derive(Debug)]
+pub enum AutoRelogUiAction {
+    StartDialogCapture,
+    StartCalibration(usize), // Click action index
+    CancelCalibration,
+    ResetToDefaults,
+    Start,
+    Stop,
+    None,
+}
+
+/// Render Auto-Relog UI
+pub fn render_ui(
+    ui: &mut egui::Ui,
+    settings: &mut AutoRelogSettings,
+    recent_image_paths: &mut Vec<String>,
+    show_regions: &mut bool,
+    capturing_dialog: bool,
+    calibrating_action: Option<usize>,
+    magnifier: Option<&MagnifierFrame>,
+    is_running: bool,
+    status: &str,
+    stats: &WorkerStats,
+    game_connected: bool,
+    client_size: Option<(i32, i32)>,
+    hotkey_error: Option<&str>,
+) -> AutoRelogUiAction {
+    let mut action = AutoRelogUiAction::None;
+
+    if !game_connected {
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
+        return AutoRelogUiAction::None;
+    }
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.show_in_overlay, tr!("common.show_in_overlay"));
+        ui.checkbox(show_regions, tr!("common.show_regions"))
+            .on_hover_text(tr!("auto_relog.show_regions_hint"));
+        if reset_to_defaults_button(ui, "auto_relog_reset") {
+            action = AutoRelogUiAction::ResetToDefaults;
+        }
+    });
+    ui.add_space(8.0);
+
+    // 1. Detection Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("common.configuration"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        ui.label(
+            egui::RichText::new(tr!("auto_relog.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("auto_relog.dialog_image")).strong());
+            image_path_field(
+                ui,
+                &mut settings.dialog_image_path,
+                recent_image_paths,
+                tr!("auto_relog.select_dialog_image"),
+            );
+            if capturing_dialog {
+                if ui
+                    .button(
+                        egui::RichText::new(tr!("common.stop"))
+                            .color(egui::Color32::from_rgb(255, 100, 100)),
+                    )
+                    .clicked()
+                {
+                    action = AutoRelogUiAction::CancelCalibration;
+                }
+            } else if ui
+                .add_enabled(
+                    calibrating_action.is_none(),
+                    egui::Button::new(tr!("auto_relog.capture")),
+                )
+                .on_hover_text(tr!("auto_relog.capture_hint"))
+                .clicked()
+            {
+                action = AutoRelogUiAction::StartDialogCapture;
+            }
+        });
+        if capturing_dialog {
+            ui.label(
+                egui::RichText::new(tr!("auto_relog.capture_active")).color(egui::Color32::YELLOW),
+            );
+            if let Some(frame) = magnifier {
+                ui.add_space(4.0);
+                render_magnifier(ui, frame);
+            }
+        }
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("auto_relog.confidence")).strong());
+            ui.add(egui::Slider::new(&mut settings.confidence, 0.01..=0.99));
+        });
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("auto_relog.interval")).strong());
+            ui.add(
+                egui::DragValue::new(&mut settings.check_interval_secs)
+                    .clamp_range(RELOG_INTERVAL_RANGE_SECS.0..=RELOG_INTERVAL_RANGE_SECS.1)
+                    .suffix(" s"),
+            );
+            ui.checkbox(
+                &mut settings.resume_previous_tool,
+                tr!("auto_relog.resume_previous"),
+            )
+            .on_hover_text(tr!("auto_relog.resume_previous_hint"));
+        });
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 2. Reconnect Actions Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("auto_relog.reconnect_actions"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        match render_input_actions(
+            ui,
+            "auto_relog",
+            &mut settings.actions,
+            tr!("auto_relog.no_actions"),
+            tr!("auto_relog.key_hint"),
+            calibrating_action,
+            magnifier,
+            client_size,
+        ) {
+            Some(InputActionsUiAction::StartCalibration(idx)) => {
+                if !capturing_dialog {
+                    action = AutoRelogUiAction::StartCalibration(idx);
+                }
+            }
+            Some(InputActionsUiAction::CancelCalibration) => {
+                action = AutoRelogUiAction::CancelCalibration;
+            }
+            None => {}
+        }
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 3. Controls
+    ui.vertical_centered(|ui| {
+        let (btn_text, btn_color) = if is_running {
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
+        } else {
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
+        };
+
+        let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
+            .min_size(egui::vec2(200.0, 35.0));
+
+        if ui.add(button).clicked() {
+            action = if is_running {
+                AutoRelogUiAction::Stop
+            } else {
+                AutoRelogUiAction::Start
+            };
+        }
+    });
+
+    ui.add_space(12.0);
+    ui.separator();
+    ui.add_space(6.0);
+
+    // 4. Status
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
+
+    action
+}
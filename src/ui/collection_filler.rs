@@ -1,8 +1,17 @@
 use crate::calibration::{CalibrationManager, CalibrationResult};
-use crate::settings::CollectionFillerSettings;
+use crate::core::worker::WorkerStats;
+use crate::settings::{
+    CollectionFillerSettings, NormPoint, NormRect, ScrollDirection, MIN_BLOB_SIZE_RANGE,
+    SCROLL_DELTA_RANGE, SCROLL_TICKS_RANGE,
+};
+use crate::ui::color_swatch::color_swatch;
+use crate::ui::coord_edit::{point_value, rect_value};
+use crate::ui::image_path_field::image_path_field;
+use crate::ui::magnifier::render_magnifier;
+use crate::ui::reset_button::reset_to_defaults_button;
 use eframe::egui;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Hash)]
 pub enum CalibrationItem {
     // Areas
     CollectionTabsArea,
@@ -16,13 +25,75 @@ pub enum CalibrationItem {
     Page3Button,
     Page4Button,
     ArrowRightButton,
+    // Color sample for the red dot filter
+    RedDotColor,
+}
+
+impl CalibrationItem {
+    /// Every item, in the order "Calibrate all" walks through them
+    pub const ALL: [CalibrationItem; 10] = [
+        CalibrationItem::CollectionTabsArea,
+        CalibrationItem::DungeonListArea,
+        CalibrationItem::CollectionItemsArea,
+        CalibrationItem::AutoRefillButton,
+        CalibrationItem::RegisterButton,
+        CalibrationItem::YesButton,
+        CalibrationItem::Page2Button,
+        CalibrationItem::Page3Button,
+        CalibrationItem::Page4Button,
+        CalibrationItem::ArrowRightButton,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CalibrationItem::CollectionTabsArea => tr!("collection_filler.item.tabs_area"),
+            CalibrationItem::DungeonListArea => tr!("collection_filler.item.dungeon_list_area"),
+            CalibrationItem::CollectionItemsArea => tr!("collection_filler.item.items_area"),
+            CalibrationItem::AutoRefillButton => tr!("collection_filler.item.auto_refill_button"),
+            CalibrationItem::RegisterButton => tr!("collection_filler.item.register_button"),
+            CalibrationItem::YesButton => tr!("collection_filler.item.yes_button"),
+            CalibrationItem::Page2Button => tr!("collection_filler.item.page2_button"),
+            CalibrationItem::Page3Button => tr!("collection_filler.item.page3_button"),
+            CalibrationItem::Page4Button => tr!("collection_filler.item.page4_button"),
+            CalibrationItem::ArrowRightButton => tr!("collection_filler.item.arrow_right_button"),
+            CalibrationItem::RedDotColor => tr!("collection_filler.item.red_dot_color"),
+        }
+    }
+
+    pub fn is_area(&self) -> bool {
+        matches!(
+            self,
+            CalibrationItem::CollectionTabsArea
+                | CalibrationItem::DungeonListArea
+                | CalibrationItem::CollectionItemsArea
+        )
+    }
+
+    pub fn is_set(&self, settings: &CollectionFillerSettings) -> bool {
+        match self {
+            CalibrationItem::CollectionTabsArea => settings.collection_tabs_area.is_some(),
+            CalibrationItem::DungeonListArea => settings.dungeon_list_area.is_some(),
+            CalibrationItem::CollectionItemsArea => settings.collection_items_area.is_some(),
+            CalibrationItem::AutoRefillButton => settings.auto_refill_pos.is_some(),
+            CalibrationItem::RegisterButton => settings.register_pos.is_some(),
+            CalibrationItem::YesButton => settings.yes_pos.is_some(),
+            CalibrationItem::Page2Button => settings.page_2_pos.is_some(),
+            CalibrationItem::Page3Button => settings.page_3_pos.is_some(),
+            CalibrationItem::Page4Button => settings.page_4_pos.is_some(),
+            CalibrationItem::ArrowRightButton => settings.arrow_right_pos.is_some(),
+            // Thresholds always have a value (defaults until sampled)
+            CalibrationItem::RedDotColor => true,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum UiAction {
     StartCalibration(CalibrationItem, bool), // item, is_area
+    CalibrateAll,
     CancelCalibration,
     ClearCalibration(CalibrationItem),
+    ResetToDefaults,
     StartAutomation,
     StopAutomation,
     None,
@@ -33,20 +104,22 @@ pub fn render_ui(
     ui: &mut egui::Ui,
     ctx: &egui::Context,
     settings: &mut CollectionFillerSettings,
+    recent_image_paths: &mut Vec<String>,
     calibration: &CalibrationManager,
     calibrating_item: &Option<CalibrationItem>,
+    show_regions: &mut bool,
+    sampled_dot_color: Option<(u8, u8, u8)>,
     is_running: bool,
     status: &str,
+    stats: &WorkerStats,
     game_connected: bool,
+    client_size: Option<(i32, i32)>,
     hotkey_error: Option<&str>,
 ) -> UiAction {
     let mut action = UiAction::None;
 
     if !game_connected {
-        ui.colored_label(
-            egui::Color32::RED,
-            "Please connect to game first (top left)",
-        );
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
         return UiAction::None;
     }
 
@@ -55,33 +128,39 @@ pub fn render_ui(
         ctx.request_repaint();
     }
 
-    ui.checkbox(&mut settings.show_in_overlay, "Show in overlay");
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.show_in_overlay, tr!("common.show_in_overlay"));
+        ui.checkbox(show_regions, tr!("common.show_regions"))
+            .on_hover_text(tr!("collection_filler.show_regions_hint"));
+        if reset_to_defaults_button(ui, "collection_filler_reset") {
+            action = UiAction::ResetToDefaults;
+        }
+    });
     ui.add_space(8.0);
 
     // 1. Settings Group
     ui.group(|ui| {
-        ui.heading(egui::RichText::new("Configuration").size(14.0).strong());
+        ui.heading(
+            egui::RichText::new(tr!("common.configuration"))
+                .size(14.0)
+                .strong(),
+        );
         ui.add_space(4.0);
 
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Red Dot Image:").strong());
-            ui.text_edit_singleline(&mut settings.red_dot_path);
-            if ui.button("Browse...").clicked() {
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("Image Files", &["png", "jpg", "jpeg", "bmp"])
-                    .set_title("Select Red Dot Image")
-                    .set_directory(std::env::current_dir().unwrap_or_default())
-                    .pick_file()
-                {
-                    settings.red_dot_path = path.display().to_string();
-                }
-            }
+            ui.label(egui::RichText::new(tr!("collection_filler.red_dot_image")).strong());
+            image_path_field(
+                ui,
+                &mut settings.red_dot_path,
+                recent_image_paths,
+                tr!("collection_filler.select_red_dot_image"),
+            );
         });
 
         ui.add_space(4.0);
 
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Delay (ms):").strong());
+            ui.label(egui::RichText::new(tr!("collection_filler.delay")).strong());
             let mut delay = settings.delay_ms.to_string();
             if ui
                 .add(egui::TextEdit::singleline(&mut delay).desired_width(80.0))
@@ -96,127 +175,291 @@ pub fn render_ui(
         ui.add_space(4.0);
 
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Red Dot Tolerance:").strong());
+            ui.label(egui::RichText::new(tr!("collection_filler.watchdog")).strong());
+            let mut watchdog = settings.watchdog_secs.to_string();
+            if ui
+                .add(egui::TextEdit::singleline(&mut watchdog).desired_width(80.0))
+                .on_hover_text(tr!("collection_filler.watchdog_hint"))
+                .changed()
+            {
+                if let Ok(v) = watchdog.parse() {
+                    settings.watchdog_secs = v;
+                }
+            }
+        });
+
+        ui.add_space(4.0);
+
+        crate::ui::status::max_runtime_field(ui, &mut settings.max_runtime_minutes);
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("collection_filler.scroll")).strong());
+            let scroll = &mut settings.scroll;
+            egui::ComboBox::from_id_source("collection_filler_scroll_direction")
+                .selected_text(scroll.direction.label())
+                .show_ui(ui, |ui| {
+                    for direction in ScrollDirection::ALL {
+                        ui.selectable_value(&mut scroll.direction, direction, direction.label());
+                    }
+                });
+            ui.add(
+                egui::DragValue::new(&mut scroll.ticks)
+                    .clamp_range(SCROLL_TICKS_RANGE.0..=SCROLL_TICKS_RANGE.1)
+                    .suffix(tr!("collection_filler.scroll_ticks")),
+            );
+            ui.add(
+                egui::DragValue::new(&mut scroll.delta_per_tick)
+                    .clamp_range(SCROLL_DELTA_RANGE.0..=SCROLL_DELTA_RANGE.1)
+                    .suffix(tr!("collection_filler.scroll_delta")),
+            )
+            .on_hover_text(tr!("collection_filler.scroll_hint"));
+        });
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("collection_filler.red_dot_tolerance")).strong());
             ui.add(egui::Slider::new(
                 &mut settings.red_dot_tolerance,
                 0.01..=0.99,
             ));
         });
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("collection_filler.red_dot_color")).strong());
+            ui.label(
+                egui::RichText::new(tr!(
+                    "collection_filler.red_dot_filter",
+                    min_red = settings.min_red,
+                    dominance = settings.red_dominance,
+                ))
+                .monospace(),
+            );
+            if let Some(color) = sampled_dot_color {
+                color_swatch(ui, color);
+            }
+
+            if calibrating_item.as_ref() == Some(&CalibrationItem::RedDotColor) {
+                if ui
+                    .button(
+                        egui::RichText::new(tr!("common.stop"))
+                            .color(egui::Color32::from_rgb(255, 100, 100)),
+                    )
+                    .clicked()
+                {
+                    action = UiAction::CancelCalibration;
+                }
+                ui.label(
+                    egui::RichText::new(tr!("collection_filler.click_red_dot"))
+                        .color(egui::Color32::YELLOW),
+                );
+            } else {
+                if ui
+                    .button(tr!("collection_filler.sample"))
+                    .on_hover_text(tr!("collection_filler.sample_hint"))
+                    .clicked()
+                {
+                    action = UiAction::StartCalibration(CalibrationItem::RedDotColor, false);
+                }
+                if ui.button(tr!("common.reset")).clicked() {
+                    action = UiAction::ClearCalibration(CalibrationItem::RedDotColor);
+                }
+            }
+        });
+
+        ui.add_space(4.0);
+
+        let color_detection = &mut settings.color_detection;
+        ui.checkbox(
+            &mut color_detection.enabled,
+            tr!("collection_filler.detect_by_color"),
+        )
+        .on_hover_text(tr!("collection_filler.detect_by_color_hint"));
+        if color_detection.enabled {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(tr!("collection_filler.dot_color")).strong());
+                ui.color_edit_button_srgb(&mut color_detection.color)
+                    .on_hover_text(tr!("collection_filler.dot_color_hint"));
+                ui.label(tr!("collection_filler.color_tolerance"));
+                ui.add(egui::DragValue::new(&mut color_detection.tolerance));
+                ui.label(tr!("collection_filler.min_blob_size"));
+                ui.add(
+                    egui::DragValue::new(&mut color_detection.min_blob_size)
+                        .clamp_range(MIN_BLOB_SIZE_RANGE.0..=MIN_BLOB_SIZE_RANGE.1)
+                        .suffix(" px"),
+                );
+            });
+        }
+
+        ui.add_space(4.0);
+
+        ui.checkbox(
+            &mut settings.use_background_clicks,
+            tr!("collection_filler.background_clicks"),
+        )
+        .on_hover_text(tr!("collection_filler.background_clicks_hint"));
     });
 
     ui.add_space(12.0);
 
     // 2. Calibration Section
     ui.group(|ui| {
-        ui.heading(egui::RichText::new("Calibration").size(14.0).strong());
+        ui.horizontal(|ui| {
+            ui.heading(
+                egui::RichText::new(tr!("collection_filler.calibration"))
+                    .size(14.0)
+                    .strong(),
+            );
+
+            let any_unset = CalibrationItem::ALL
+                .iter()
+                .any(|item| !item.is_set(settings));
+            if ui
+                .add_enabled(
+                    any_unset && calibrating_item.is_none(),
+                    egui::Button::new(tr!("collection_filler.calibrate_all")),
+                )
+                .on_hover_text(tr!("collection_filler.calibrate_all_hint"))
+                .clicked()
+            {
+                action = UiAction::CalibrateAll;
+            }
+        });
         ui.add_space(4.0);
 
-        ui.label(egui::RichText::new("Detection Areas:").strong().underline());
+        if let Some(frame) = calibration.magnifier() {
+            render_magnifier(ui, frame);
+            ui.add_space(4.0);
+        }
+
+        ui.label(
+            egui::RichText::new(tr!("collection_filler.detection_areas"))
+                .strong()
+                .underline(),
+        );
         ui.add_space(4.0);
 
         if let Some(act) = render_area_calibration(
             ui,
-            "Tabs Area",
+            tr!("collection_filler.tabs_area"),
             CalibrationItem::CollectionTabsArea,
-            settings.collection_tabs_area,
+            &mut settings.collection_tabs_area,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
         if let Some(act) = render_area_calibration(
             ui,
-            "Dungeon List",
+            tr!("collection_filler.dungeon_list"),
             CalibrationItem::DungeonListArea,
-            settings.dungeon_list_area,
+            &mut settings.dungeon_list_area,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
         if let Some(act) = render_area_calibration(
             ui,
-            "Items Area",
+            tr!("collection_filler.items_area"),
             CalibrationItem::CollectionItemsArea,
-            settings.collection_items_area,
+            &mut settings.collection_items_area,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
 
         ui.add_space(8.0);
-        ui.label(egui::RichText::new("Action Buttons:").strong().underline());
+        ui.label(
+            egui::RichText::new(tr!("collection_filler.action_buttons"))
+                .strong()
+                .underline(),
+        );
         ui.add_space(4.0);
 
         if let Some(act) = render_button_calibration(
             ui,
-            "Auto Refill",
+            tr!("collection_filler.auto_refill"),
             CalibrationItem::AutoRefillButton,
-            settings.auto_refill_pos,
+            &mut settings.auto_refill_pos,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
         if let Some(act) = render_button_calibration(
             ui,
-            "Register",
+            tr!("collection_filler.register"),
             CalibrationItem::RegisterButton,
-            settings.register_pos,
+            &mut settings.register_pos,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
         if let Some(act) = render_button_calibration(
             ui,
-            "Yes",
+            tr!("collection_filler.yes"),
             CalibrationItem::YesButton,
-            settings.yes_pos,
+            &mut settings.yes_pos,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
         ui.separator();
         if let Some(act) = render_button_calibration(
             ui,
-            "Page 2",
+            tr!("collection_filler.page2"),
             CalibrationItem::Page2Button,
-            settings.page_2_pos,
+            &mut settings.page_2_pos,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
         if let Some(act) = render_button_calibration(
             ui,
-            "Page 3",
+            tr!("collection_filler.page3"),
             CalibrationItem::Page3Button,
-            settings.page_3_pos,
+            &mut settings.page_3_pos,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
         if let Some(act) = render_button_calibration(
             ui,
-            "Page 4",
+            tr!("collection_filler.page4"),
             CalibrationItem::Page4Button,
-            settings.page_4_pos,
+            &mut settings.page_4_pos,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
         if let Some(act) = render_button_calibration(
             ui,
-            "Arrow Right",
+            tr!("collection_filler.arrow_right"),
             CalibrationItem::ArrowRightButton,
-            settings.arrow_right_pos,
+            &mut settings.arrow_right_pos,
             calibrating_item,
             calibration,
+            client_size,
         ) {
             action = act;
         }
@@ -227,9 +470,9 @@ pub fn render_ui(
     // 3. Control
     ui.vertical_centered(|ui| {
         let (btn_text, btn_color) = if is_running {
-            ("Stop", egui::Color32::from_rgb(255, 100, 100))
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
         } else {
-            ("Start", egui::Color32::from_rgb(100, 255, 100))
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
         };
 
         let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
@@ -249,7 +492,8 @@ pub fn render_ui(
     ui.add_space(6.0);
 
     // 4. Status
-    crate::ui::status::render_status(ui, status, hotkey_error);
+    crate::ui::status::render_run_timer(ui, stats, settings.max_runtime_minutes);
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
 
     action
 }
@@ -258,26 +502,32 @@ fn render_area_calibration(
     ui: &mut egui::Ui,
     label: &str,
     item: CalibrationItem,
-    current: Option<(f32, f32, f32, f32)>,
+    current: &mut Option<NormRect>,
     calibrating_item: &Option<CalibrationItem>,
     calibration: &CalibrationManager,
+    client_size: Option<(i32, i32)>,
 ) -> Option<UiAction> {
     let mut action = None;
     ui.horizontal(|ui| {
         ui.label(format!("{}:", label));
 
-        if let Some((left, top, width, height)) = current {
-            ui.label(
+        if let Some(rect) = current.as_mut() {
+            let (left, top, width, height) = *rect;
+            rect_value(
+                ui,
+                &item,
                 egui::RichText::new(format!(
                     "({:.3}, {:.3}, {:.3}x{:.3})",
                     left, top, width, height
                 ))
                 .monospace()
                 .strong(),
+                rect,
+                client_size,
             );
         } else {
             ui.label(
-                egui::RichText::new("Not set")
+                egui::RichText::new(tr!("common.not_set"))
                     .color(egui::Color32::from_rgb(150, 150, 150))
                     .italics(),
             );
@@ -287,22 +537,30 @@ fn render_area_calibration(
 
         if is_this_calibrating {
             if ui
-                .button(egui::RichText::new("Stop").color(egui::Color32::from_rgb(255, 100, 100)))
+                .button(
+                    egui::RichText::new(tr!("common.stop"))
+                        .color(egui::Color32::from_rgb(255, 100, 100)),
+                )
                 .clicked()
             {
                 action = Some(UiAction::CancelCalibration);
             }
             let label = if calibration.is_waiting_for_second_click() {
-                "Click bottom-right"
+                tr!("calibration.click_bottom_right_short")
             } else {
-                "Click top-left"
+                tr!("calibration.click_top_left_short")
             };
             ui.label(egui::RichText::new(label).color(egui::Color32::YELLOW));
         } else {
-            if ui.button("Set").clicked() {
+            if ui.button(tr!("common.set")).clicked() {
                 action = Some(UiAction::StartCalibration(item.clone(), true));
             }
-            if current.is_some() && ui.button("Clear").on_hover_text("Clear").clicked() {
+            if current.is_some()
+                && ui
+                    .button(tr!("common.clear"))
+                    .on_hover_text(tr!("common.clear"))
+                    .clicked()
+            {
                 action = Some(UiAction::ClearCalibration(item));
             }
         }
@@ -314,23 +572,29 @@ fn render_button_calibration(
     ui: &mut egui::Ui,
     label: &str,
     item: CalibrationItem,
-    current: Option<(f32, f32)>,
+    current: &mut Option<NormPoint>,
     calibrating_item: &Option<CalibrationItem>,
-    _calibration: &CalibrationManager,
+    calibration: &CalibrationManager,
+    client_size: Option<(i32, i32)>,
 ) -> Option<UiAction> {
     let mut action = None;
     ui.horizontal(|ui| {
         ui.label(format!("{}:", label));
 
-        if let Some((x, y)) = current {
-            ui.label(
+        if let Some(point) = current.as_mut() {
+            let (x, y) = *point;
+            point_value(
+                ui,
+                &item,
                 egui::RichText::new(format!("({:.3}, {:.3})", x, y))
                     .monospace()
                     .strong(),
+                point,
+                client_size,
             );
         } else {
             ui.label(
-                egui::RichText::new("Not set")
+                egui::RichText::new(tr!("common.not_set"))
                     .color(egui::Color32::from_rgb(150, 150, 150))
                     .italics(),
             );
@@ -340,17 +604,30 @@ fn render_button_calibration(
 
         if is_this_calibrating {
             if ui
-                .button(egui::RichText::new("Stop").color(egui::Color32::from_rgb(255, 100, 100)))
+                .button(
+                    egui::RichText::new(tr!("common.stop"))
+                        .color(egui::Color32::from_rgb(255, 100, 100)),
+                )
                 .clicked()
             {
                 action = Some(UiAction::CancelCalibration);
             }
-            ui.label(egui::RichText::new("Click Button...").color(egui::Color32::YELLOW));
+            let label = if calibration.is_adjusting() {
+                tr!("calibration.nudge_hint")
+            } else {
+                tr!("calibration.click_button")
+            };
+            ui.label(egui::RichText::new(label).color(egui::Color32::YELLOW));
         } else {
-            if ui.button("Set").clicked() {
+            if ui.button(tr!("common.set")).clicked() {
                 action = Some(UiAction::StartCalibration(item.clone(), false));
             }
-            if current.is_some() && ui.button("Clear").on_hover_text("Clear").clicked() {
+            if current.is_some()
+                && ui
+                    .button(tr!("common.clear"))
+                    .on_hover_text(tr!("common.clear"))
+                    .clicked()
+            {
                 action = Some(UiAction::ClearCalibration(item));
             }
         }
@@ -396,6 +673,12 @@ pub fn apply_calibration_result(
         (CalibrationItem::ArrowRightButton, CalibrationResult::Point(x, y)) => {
             settings.arrow_right_pos = Some((x, y))
         }
+        (CalibrationItem::RedDotColor, CalibrationResult::Pixel(_, _, r, g, b)) => {
+            // Leave headroom for the dot's anti-aliased edge pixels
+            settings.min_red = r - r / 5;
+            settings.red_dominance = (r.saturating_sub(g.max(b)) / 2).max(10);
+            settings.color_detection.color = [r, g, b];
+        }
         _ => {}
     }
 }
@@ -413,5 +696,40 @@ pub fn clear_calibration(item: CalibrationItem, settings: &mut CollectionFillerS
         CalibrationItem::Page3Button => settings.page_3_pos = None,
         CalibrationItem::Page4Button => settings.page_4_pos = None,
         CalibrationItem::ArrowRightButton => settings.arrow_right_pos = None,
+        CalibrationItem::RedDotColor => {
+            let defaults = CollectionFillerSettings::default();
+            settings.min_red = defaults.min_red;
+            settings.red_dominance = defaults.red_dominance;
+            settings.color_detection.color = defaults.color_detection.color;
+        }
+    }
+}
+
+/// Copy one item back from an earlier copy of the settings (undo of a clear)
+pub fn restore_calibration(
+    item: CalibrationItem,
+    from: &CollectionFillerSettings,
+    settings: &mut CollectionFillerSettings,
+) {
+    match item {
+        CalibrationItem::CollectionTabsArea => {
+            settings.collection_tabs_area = from.collection_tabs_area
+        }
+        CalibrationItem::DungeonListArea => settings.dungeon_list_area = from.dungeon_list_area,
+        CalibrationItem::CollectionItemsArea => {
+            settings.collection_items_area = from.collection_items_area
+        }
+        CalibrationItem::AutoRefillButton => settings.auto_refill_pos = from.auto_refill_pos,
+        CalibrationItem::RegisterButton => settings.register_pos = from.register_pos,
+        CalibrationItem::YesButton => settings.yes_pos = from.yes_pos,
+        CalibrationItem::Page2Button => settings.page_2_pos = from.page_2_pos,
+        CalibrationItem::Page3Button => settings.page_3_pos = from.page_3_pos,
+        CalibrationItem::Page4Button => settings.page_4_pos = from.page_4_pos,
+        CalibrationItem::ArrowRightButton => settings.arrow_right_pos = from.arrow_right_pos,
+        CalibrationItem::RedDotColor => {
+            settings.min_red = from.min_red;
+            settings.red_dominance = from.red_dominance;
+            settings.color_detection.color = from.color_detection.color;
+        }
     }
 }
@@ -0,0 +1,15 @@
+use eframe::egui;
+
+/// Small filled square showing a sampled color, with its RGB value on hover
+pub fn color_swatch(ui: &mut egui::Ui, color: (u8, u8, u8)) -> egui::Response {
+    let (r, g, b) = color;
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(14.0, 14.0), egui::Sense::hover());
+    ui.painter()
+        .rect_filled(rect, 2.0, egui::Color32::from_rgb(r, g, b));
+    ui.painter().rect_stroke(
+        rect,
+        2.0,
+        egui::Stroke::new(1.0, egui::Color32::from_rgb(90, 90, 90)),
+    );
+    response.on_hover_text(format!("RGB({}, {}, {})", r, g, b))
+}
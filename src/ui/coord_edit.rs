@@ -0,0 +1,128 @@
+use crate::settings::{NormPoint, NormRect};
+use eframe::egui;
+use std::hash::Hash;
+
+/// Clickable monospace label that toggles the pixel editor below it
+fn editable_label(ui: &mut egui::Ui, id: egui::Id, text: egui::RichText) -> bool {
+    let mut editing = ui.data(|d| d.get_temp::<bool>(id).unwrap_or(false));
+    let response = ui
+        .add(egui::Label::new(text).sense(egui::Sense::click()))
+        .on_hover_text("Click to edit in pixels");
+    if response.clicked() {
+        editing = !editing;
+        ui.data_mut(|d| d.insert_temp(id, editing));
+    }
+    editing
+}
+
+fn valid_client_size(client_size: Option<(i32, i32)>) -> Option<(i32, i32)> {
+    client_size.filter(|(w, h)| *w > 0 && *h > 0)
+}
+
+/// Calibrated point display; clicking it edits the value in client pixels
+pub fn point_value(
+    ui: &mut egui::Ui,
+    id_source: impl Hash,
+    text: egui::RichText,
+    point: &mut NormPoint,
+    client_size: Option<(i32, i32)>,
+) {
+    let id = ui.make_persistent_id(("coord_edit_point", id_source));
+    if !editable_label(ui, id, text) {
+        return;
+    }
+    let Some((width, height)) = valid_client_size(client_size) else {
+        return;
+    };
+
+    // Same scale as denormalize_point so edited values round-trip exactly
+    let max_x = (width - 1).max(1) as f32;
+    let max_y = (height - 1).max(1) as f32;
+    let mut px = (point.0.clamp(0.0, 1.0) * max_x).round() as i32;
+    let mut py = (point.1.clamp(0.0, 1.0) * max_y).round() as i32;
+
+    let x_changed = ui
+        .add(
+            egui::DragValue::new(&mut px)
+                .prefix("x: ")
+                .clamp_range(0..=width - 1),
+        )
+        .changed();
+    let y_changed = ui
+        .add(
+            egui::DragValue::new(&mut py)
+                .prefix("y: ")
+                .clamp_range(0..=height - 1),
+        )
+        .changed();
+
+    if x_changed || y_changed {
+        *point = (px as f32 / max_x, py as f32 / max_y);
+    }
+}
+
+/// Calibrated area display; clicking it edits the value in client pixels
+pub fn rect_value(
+    ui: &mut egui::Ui,
+    id_source: impl Hash,
+    text: egui::RichText,
+    rect: &mut NormRect,
+    client_size: Option<(i32, i32)>,
+) {
+    let id = ui.make_persistent_id(("coord_edit_rect", id_source));
+    if !editable_label(ui, id, text) {
+        return;
+    }
+    let Some((width, height)) = valid_client_size(client_size) else {
+        return;
+    };
+
+    // Same scale as denormalize_rect
+    let (client_w, client_h) = (width as f32, height as f32);
+    let mut left = (rect.0.clamp(0.0, 1.0) * client_w).round() as i32;
+    let mut top = (rect.1.clamp(0.0, 1.0) * client_h).round() as i32;
+    let mut w = (rect.2.clamp(0.0, 1.0) * client_w).round() as i32;
+    let mut h = (rect.3.clamp(0.0, 1.0) * client_h).round() as i32;
+
+    let mut changed = false;
+    changed |= ui
+        .add(
+            egui::DragValue::new(&mut left)
+                .prefix("x: ")
+                .clamp_range(0..=width - 1),
+        )
+        .changed();
+    changed |= ui
+        .add(
+            egui::DragValue::new(&mut top)
+                .prefix("y: ")
+                .clamp_range(0..=height - 1),
+        )
+        .changed();
+    changed |= ui
+        .add(
+            egui::DragValue::new(&mut w)
+                .prefix("w: ")
+                .clamp_range(1..=(width - left).max(1)),
+        )
+        .changed();
+    changed |= ui
+        .add(
+            egui::DragValue::new(&mut h)
+                .prefix("h: ")
+                .clamp_range(1..=(height - top).max(1)),
+        )
+        .changed();
+
+    if changed {
+        // Keep the area inside the client even if the corner moved
+        let w = w.min(width - left).max(1);
+        let h = h.min(height - top).max(1);
+        *rect = (
+            left as f32 / client_w,
+            top as f32 / client_h,
+            w as f32 / client_w,
+            h as f32 / client_h,
+        );
+    }
+}
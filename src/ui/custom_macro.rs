@@ -1,18 +1,39 @@
+use crate::calibration::MagnifierFrame;
+use crate::core::macro_dsl::{parse_script, to_script};
+use crate::core::worker::WorkerStats;
 use crate::settings::{
-    ComparisonMode, MacroAction, MouseButton, NamedMacro, OcrAltTarget, OcrDecodeMode,
-    OcrNameMatchMode,
+    ComparisonMode, HotkeyKey, MacroAction, MouseButton, NamedMacro, OcrAltTarget, OcrDecodeMode,
+    OcrNameMatchMode, SPEED_MULTIPLIER_RANGE,
 };
+use crate::ui::anti_afk::render_key_row;
+use crate::ui::coord_edit::{point_value, rect_value};
+use crate::ui::image_path_field::image_path_field;
+use crate::ui::magnifier::render_magnifier;
+use crate::ui::reset_button::reset_to_defaults_button;
 use eframe::egui;
+use windows::Win32::Foundation::HWND;
+
+/// A game client found by "Find clients", ticked to run the macro on it
+pub struct ClientTarget {
+    pub hwnd: HWND,
+    pub title: String,
+    pub selected: bool,
+}
 
 #[derive(Debug)]
 pub enum CustomMacroUiAction {
     StartCalibration(usize), // Click action index
     CancelCalibration,
-    StartOcrRegionCalibration(usize), // OCR action index
+    StartOcrRegionCalibration(usize), // OCR or wait-for-image action index
     CancelOcrRegionCalibration,
     StartMacro,
     StopMacro,
+    TogglePause,
     DeleteMacro,
+    DeleteAction(usize),
+    ResetToDefaults,
+    FindClients,
+    ImportScript(Vec<MacroAction>), // Replaces the macro's actions
     None,
 }
 
@@ -80,31 +101,128 @@ fn apply_ocr_preprocess_preset(
     }
 }
 
+/// "Import from script…" paste box (or file) and "Export as script" for the whole action list
+fn render_script_panel(
+    ui: &mut egui::Ui,
+    named_macro: &NamedMacro,
+    client_size: Option<(i32, i32)>,
+) -> Option<CustomMacroUiAction> {
+    let open_id = ui.make_persistent_id(("macro_script_open", &named_macro.name));
+    let text_id = ui.make_persistent_id(("macro_script_text", &named_macro.name));
+    let error_id = ui.make_persistent_id(("macro_script_error", &named_macro.name));
+    let mut open = ui.data(|d| d.get_temp::<bool>(open_id).unwrap_or(false));
+    let mut text = ui.data(|d| d.get_temp::<String>(text_id).unwrap_or_default());
+    let mut error = ui.data(|d| d.get_temp::<Option<String>>(error_id).flatten());
+    let mut result = None;
+
+    ui.horizontal(|ui| {
+        if ui
+            .button(tr!("custom_macro.import_script"))
+            .on_hover_text(tr!("custom_macro.import_script_hint"))
+            .clicked()
+        {
+            open = !open;
+            error = None;
+        }
+        if ui.button(tr!("custom_macro.export_script")).clicked() {
+            error = export_script(named_macro, client_size).err();
+        }
+    });
+
+    if open {
+        ui.add(
+            egui::TextEdit::multiline(&mut text)
+                .code_editor()
+                .desired_rows(6)
+                .desired_width(f32::INFINITY)
+                .hint_text("click 512 340 right\ndelay 200\ntype \"hello\""),
+        );
+        ui.horizontal(|ui| {
+            if ui.button(tr!("custom_macro.script_load_file")).clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Macro script", &["txt"])
+                    .pick_file()
+                {
+                    match std::fs::read_to_string(&path) {
+                        Ok(contents) => text = contents,
+                        Err(err) => error = Some(format!("{}: {}", path.display(), err)),
+                    }
+                }
+            }
+            if ui.button(tr!("custom_macro.script_import")).clicked() {
+                match client_size
+                    .ok_or_else(|| tr!("common.connect_first").to_string())
+                    .and_then(|size| parse_script(&text, size))
+                {
+                    Ok(actions) => {
+                        result = Some(CustomMacroUiAction::ImportScript(actions));
+                        open = false;
+                        error = None;
+                    }
+                    Err(err) => error = Some(err),
+                }
+            }
+            if ui.button(tr!("custom_macro.script_cancel")).clicked() {
+                open = false;
+                error = None;
+            }
+        });
+    }
+    if let Some(err) = &error {
+        ui.colored_label(egui::Color32::from_rgb(255, 100, 100), err);
+    }
+
+    ui.data_mut(|d| {
+        d.insert_temp(open_id, open);
+        d.insert_temp(text_id, text);
+        d.insert_temp(error_id, error);
+    });
+    result
+}
+
+/// Save the actions as a script file chosen by the user
+fn export_script(named_macro: &NamedMacro, client_size: Option<(i32, i32)>) -> Result<(), String> {
+    let client_size = client_size.ok_or_else(|| tr!("common.connect_first").to_string())?;
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Macro script", &["txt"])
+        .set_file_name(format!("{}.txt", named_macro.name))
+        .save_file()
+    else {
+        return Ok(());
+    };
+    std::fs::write(&path, to_script(&named_macro.settings.actions, client_size))
+        .map_err(|err| format!("{}: {}", path.display(), err))
+}
+
 /// Render the Custom Macro Builder UI
 pub fn render_ui(
     ui: &mut egui::Ui,
     named_macro: &mut NamedMacro,
+    recent_image_paths: &mut Vec<String>,
     click_calibrating_action_index: Option<usize>,
     ocr_calibrating_action_index: Option<usize>,
+    magnifier: Option<&MagnifierFrame>,
+    show_regions: &mut bool,
     is_running: bool,
+    is_paused: bool,
     status: &str,
+    stats: &WorkerStats,
     game_connected: bool,
+    client_size: Option<(i32, i32)>,
     can_delete: bool, // Can this macro be deleted?
+    clients: &mut [ClientTarget],
     hotkey_error: Option<&str>,
 ) -> CustomMacroUiAction {
     let mut action = CustomMacroUiAction::None;
 
     if !game_connected {
-        ui.colored_label(
-            egui::Color32::RED,
-            "Please connect to game first (top left)",
-        );
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
         return CustomMacroUiAction::None;
     }
 
     // 1. Header Section (Clean)
     ui.horizontal(|ui| {
-        ui.label(egui::RichText::new("Macro Name:").strong());
+        ui.label(egui::RichText::new(tr!("custom_macro.name")).strong());
         ui.text_edit_singleline(&mut named_macro.name);
 
         // Spacer to push delete button to the right
@@ -112,14 +230,23 @@ pub fn render_ui(
             if can_delete {
                 if ui
                     .button(
-                        egui::RichText::new("Delete").color(egui::Color32::from_rgb(255, 100, 100)),
+                        egui::RichText::new(tr!("custom_macro.delete"))
+                            .color(egui::Color32::from_rgb(255, 100, 100)),
                     )
                     .clicked()
                 {
                     action = CustomMacroUiAction::DeleteMacro;
                 }
             }
-            ui.checkbox(&mut named_macro.show_in_overlay, "Show in Overlay");
+            if reset_to_defaults_button(ui, ("custom_macro_reset", &named_macro.name)) {
+                action = CustomMacroUiAction::ResetToDefaults;
+            }
+            ui.checkbox(
+                &mut named_macro.show_in_overlay,
+                tr!("common.show_in_overlay"),
+            );
+            ui.checkbox(show_regions, tr!("common.show_regions"))
+                .on_hover_text(tr!("custom_macro.show_regions_hint"));
         });
     });
 
@@ -133,7 +260,7 @@ pub fn render_ui(
         .show(ui, |ui| {
             ui.horizontal(|ui| {
                 ui.label(
-                    egui::RichText::new("Add Action:")
+                    egui::RichText::new(tr!("custom_macro.add_action"))
                         .strong()
                         .color(egui::Color32::LIGHT_GRAY),
                 );
@@ -148,7 +275,7 @@ pub fn render_ui(
 
                 let toolbar_color = egui::Color32::WHITE;
 
-                if toolbar_button(ui, "+ Click", toolbar_color).clicked() {
+                if toolbar_button(ui, tr!("custom_macro.add_click"), toolbar_color).clicked() {
                     named_macro.settings.actions.push(MacroAction::Click {
                         coordinate: None,
                         button: MouseButton::Left,
@@ -156,18 +283,26 @@ pub fn render_ui(
                         use_mouse_movement: false,
                     });
                 }
-                if toolbar_button(ui, "+ Type", toolbar_color).clicked() {
+                if toolbar_button(ui, tr!("custom_macro.add_key"), toolbar_color)
+                    .on_hover_text(tr!("custom_macro.add_key_hint"))
+                    .clicked()
+                {
+                    named_macro.settings.actions.push(MacroAction::PressKey {
+                        key: HotkeyKey::Digit1,
+                    });
+                }
+                if toolbar_button(ui, tr!("custom_macro.add_type"), toolbar_color).clicked() {
                     named_macro.settings.actions.push(MacroAction::TypeText {
                         text: String::new(),
                     });
                 }
-                if toolbar_button(ui, "+ Delay", toolbar_color).clicked() {
+                if toolbar_button(ui, tr!("custom_macro.add_delay"), toolbar_color).clicked() {
                     named_macro
                         .settings
                         .actions
                         .push(MacroAction::Delay { milliseconds: 100 });
                 }
-                if toolbar_button(ui, "+ OCR", toolbar_color).clicked() {
+                if toolbar_button(ui, tr!("custom_macro.add_ocr"), toolbar_color).clicked() {
                     named_macro.settings.actions.push(MacroAction::OcrSearch {
                         ocr_region: None,
                         scale_factor: 2,
@@ -182,19 +317,40 @@ pub fn render_ui(
                         alt_targets: Vec::new(),
                     });
                 }
+                if toolbar_button(ui, tr!("custom_macro.add_wait_gone"), toolbar_color)
+                    .on_hover_text(tr!("custom_macro.add_wait_gone_hint"))
+                    .clicked()
+                {
+                    named_macro
+                        .settings
+                        .actions
+                        .push(MacroAction::WaitForImageGone {
+                            image_path: String::new(),
+                            region: None,
+                            min_confidence: 0.85,
+                            timeout_ms: 5000,
+                        });
+                }
             });
         });
 
+    ui.add_space(4.0);
+    if let Some(script_action) = render_script_panel(ui, named_macro, client_size) {
+        action = script_action;
+    }
+
     ui.add_space(12.0);
 
     // 2. Actions List Section
-    ui.heading(egui::RichText::new("Actions").size(16.0).strong());
+    ui.heading(
+        egui::RichText::new(tr!("custom_macro.actions"))
+            .size(16.0)
+            .strong(),
+    );
     ui.add_space(4.0);
 
     if named_macro.settings.actions.is_empty() {
-        ui.label(
-            egui::RichText::new("No actions yet. Add some using the buttons above!").italics(),
-        );
+        ui.label(egui::RichText::new(tr!("custom_macro.no_actions")).italics());
     } else {
         let mut to_remove: Option<usize> = None;
         let mut to_move_up: Option<usize> = None;
@@ -220,7 +376,7 @@ pub fn render_ui(
                             };
 
                             if idx > 0 {
-                                if arrow_btn(ui, "⬆").on_hover_text("Move Up").clicked() {
+                                if arrow_btn(ui, "⬆").on_hover_text(tr!("custom_macro.move_up")).clicked() {
                                     to_move_up = Some(idx);
                                 }
                             } else {
@@ -228,7 +384,7 @@ pub fn render_ui(
                             }
 
                             if idx < actions_len - 1 {
-                                if arrow_btn(ui, "⬇").on_hover_text("Move Down").clicked() {
+                                if arrow_btn(ui, "⬇").on_hover_text(tr!("custom_macro.move_down")).clicked() {
                                     to_move_down = Some(idx);
                                 }
                             }
@@ -249,10 +405,12 @@ pub fn render_ui(
                             // Header Row: Type | Index | Delete
                             ui.horizontal(|ui| {
                                 let (title, color) = match macro_action {
-                                    MacroAction::Click { .. } => ("CLICK", egui::Color32::from_rgb(100, 149, 237)),
-                                    MacroAction::TypeText { .. } => ("TYPE", egui::Color32::from_rgb(200, 200, 200)),
-                                    MacroAction::Delay { .. } => ("DELAY", egui::Color32::from_rgb(255, 215, 0)),
-                                    MacroAction::OcrSearch { .. } => ("OCR", egui::Color32::from_rgb(218, 112, 214)),
+                                    MacroAction::Click { .. } => (tr!("custom_macro.badge_click"), egui::Color32::from_rgb(100, 149, 237)),
+                                    MacroAction::PressKey { .. } => (tr!("custom_macro.badge_key"), egui::Color32::from_rgb(240, 160, 80)),
+                                    MacroAction::TypeText { .. } => (tr!("custom_macro.badge_type"), egui::Color32::from_rgb(200, 200, 200)),
+                                    MacroAction::Delay { .. } => (tr!("custom_macro.badge_delay"), egui::Color32::from_rgb(255, 215, 0)),
+                                    MacroAction::OcrSearch { .. } => (tr!("custom_macro.badge_ocr"), egui::Color32::from_rgb(218, 112, 214)),
+                                    MacroAction::WaitForImageGone { .. } => (tr!("custom_macro.badge_wait_gone"), egui::Color32::from_rgb(120, 200, 160)),
                                 };
 
                                 // Removed colored indicator bar as requested
@@ -273,7 +431,7 @@ pub fn render_ui(
                                                 egui::RichText::new("✖") // Cross mark
                                                     .color(egui::Color32::from_rgb(150, 60, 60)),
                                             ).frame(false))
-                                            .on_hover_text("Remove Action")
+                                            .on_hover_text(tr!("custom_macro.remove_action"))
                                             .clicked()
                                         {
                                             to_remove = Some(idx);
@@ -296,10 +454,17 @@ pub fn render_ui(
                                             use_mouse_movement: _,
                                         } => {
                                             ui.horizontal(|ui| {
-                                                if let Some((x, y)) = coordinate {
-                                                     ui.label(egui::RichText::new(format!("at ({:.0}, {:.0})", x, y)).monospace());
+                                                if let Some(point) = coordinate.as_mut() {
+                                                    let (x, y) = *point;
+                                                    point_value(
+                                                        ui,
+                                                        ("click", idx),
+                                                        egui::RichText::new(tr!("custom_macro.click_at", position = format!("({:.3}, {:.3})", x, y))).monospace(),
+                                                        point,
+                                                        client_size,
+                                                    );
                                                 } else {
-                                                     ui.label(egui::RichText::new("Position not set").color(egui::Color32::RED));
+                                                     ui.label(egui::RichText::new(tr!("custom_macro.position_unset")).color(egui::Color32::RED));
                                                 }
 
                                                 // Inline calibration button
@@ -307,12 +472,12 @@ pub fn render_ui(
                                                     click_calibrating_action_index == Some(idx);
 
                                                 if is_this_calibrating {
-                                                    if ui.button(egui::RichText::new("CANCEL").size(10.0).color(egui::Color32::WHITE).strong()).clicked() {
+                                                    if ui.button(egui::RichText::new(tr!("custom_macro.cancel")).size(10.0).color(egui::Color32::WHITE).strong()).clicked() {
                                                         action = CustomMacroUiAction::CancelCalibration;
                                                     }
                                                     ui.spinner();
                                                 } else {
-                                                    let btn_text = if coordinate.is_none() { "SET POS" } else { "SET" };
+                                                    let btn_text = if coordinate.is_none() { tr!("custom_macro.set_position") } else { tr!("custom_macro.set") };
                                                     if ui.button(egui::RichText::new(btn_text).size(10.0)).clicked() {
                                                          action = CustomMacroUiAction::StartCalibration(idx);
                                                     }
@@ -320,35 +485,108 @@ pub fn render_ui(
 
                                                 ui.separator();
 
-                                                ui.selectable_value(button, MouseButton::Left, "Left");
-                                                ui.selectable_value(button, MouseButton::Right, "Right");
-                                                ui.selectable_value(button, MouseButton::Middle, "Middle");
+                                                ui.selectable_value(button, MouseButton::Left, tr!("custom_macro.button_left"));
+                                                ui.selectable_value(button, MouseButton::Right, tr!("custom_macro.button_right"));
+                                                ui.selectable_value(button, MouseButton::Middle, tr!("custom_macro.button_middle"));
 
                                                 ui.separator();
 
                                                 egui::ComboBox::from_id_source(format!("method_{}", idx))
                                                     .selected_text(match click_method {
-                                                        crate::settings::ClickMethod::SendMessage => "Direct",
-                                                        crate::settings::ClickMethod::MouseMovement => "Mouse",
+                                                        crate::settings::ClickMethod::SendMessage => tr!("custom_macro.method_direct"),
+                                                        crate::settings::ClickMethod::MouseMovement => tr!("custom_macro.method_mouse"),
                                                     })
                                                     .show_ui(ui, |ui| {
-                                                        ui.selectable_value(click_method, crate::settings::ClickMethod::SendMessage, "Direct (Backgr.)");
-                                                        ui.selectable_value(click_method, crate::settings::ClickMethod::MouseMovement, "Physical Mouse");
+                                                        ui.selectable_value(click_method, crate::settings::ClickMethod::SendMessage, tr!("custom_macro.method_direct_long"));
+                                                        ui.selectable_value(click_method, crate::settings::ClickMethod::MouseMovement, tr!("custom_macro.method_mouse_long"));
                                                     });
                                             });
+
+                                            if click_calibrating_action_index == Some(idx) {
+                                                if let Some(frame) = magnifier {
+                                                    render_magnifier(ui, frame);
+                                                }
+                                            }
+                                        }
+                                        MacroAction::PressKey { key } => {
+                                            render_key_row(
+                                                ui,
+                                                &format!("macro_key_capture_{}", idx),
+                                                key,
+                                                tr!("custom_macro.key_hint"),
+                                            );
                                         }
                                         MacroAction::TypeText { text } => {
                                             ui.horizontal(|ui| {
-                                                ui.label("Text:");
-                                                ui.add(egui::TextEdit::singleline(text).hint_text("Enter text to type..."));
+                                                ui.label(tr!("custom_macro.text"));
+                                                ui.add(egui::TextEdit::singleline(text).hint_text(tr!("custom_macro.text_hint")));
                                             });
                                         }
                                         MacroAction::Delay { milliseconds } => {
                                             ui.horizontal(|ui| {
-                                                ui.label("Wait");
+                                                ui.label(tr!("custom_macro.wait"));
                                                 ui.add(egui::DragValue::new(milliseconds).suffix(" ms").speed(10));
                                             });
                                         }
+                                        MacroAction::WaitForImageGone {
+                                            image_path,
+                                            region,
+                                            min_confidence,
+                                            timeout_ms,
+                                        } => {
+                                            ui.horizontal(|ui| {
+                                                ui.label(tr!("custom_macro.image"));
+                                                image_path_field(
+                                                    ui,
+                                                    image_path,
+                                                    recent_image_paths,
+                                                    tr!("custom_macro.select_image"),
+                                                );
+                                            });
+
+                                            ui.horizontal(|ui| {
+                                                if let Some(area) = region.as_mut() {
+                                                    let (l, t, w, h) = *area;
+                                                    rect_value(
+                                                        ui,
+                                                        ("wait_gone_region", idx),
+                                                        egui::RichText::new(tr!("custom_macro.ocr_region", region = format!("{:.3},{:.3} {:.3}x{:.3}", l, t, w, h))).monospace().size(11.0),
+                                                        area,
+                                                        client_size,
+                                                    );
+                                                } else {
+                                                    ui.label(egui::RichText::new(tr!("custom_macro.image_region_unset")).color(egui::Color32::GRAY).size(11.0));
+                                                }
+
+                                                if ocr_calibrating_action_index == Some(idx) {
+                                                    if ui.button(egui::RichText::new(tr!("custom_macro.cancel")).size(10.0)).clicked() {
+                                                        action = CustomMacroUiAction::CancelOcrRegionCalibration;
+                                                    }
+                                                    ui.spinner();
+                                                } else {
+                                                    if ui.button(egui::RichText::new(tr!("custom_macro.set_area")).size(10.0)).clicked() {
+                                                        action = CustomMacroUiAction::StartOcrRegionCalibration(idx);
+                                                    }
+                                                    if region.is_some() && ui.button(egui::RichText::new(tr!("custom_macro.clear_area")).size(10.0)).clicked() {
+                                                        *region = None;
+                                                    }
+                                                }
+                                            });
+
+                                            if ocr_calibrating_action_index == Some(idx) {
+                                                if let Some(frame) = magnifier {
+                                                    render_magnifier(ui, frame);
+                                                }
+                                            }
+
+                                            ui.horizontal(|ui| {
+                                                ui.label(tr!("custom_macro.confidence"));
+                                                ui.add(egui::Slider::new(min_confidence, 0.01..=0.99));
+                                                ui.label(tr!("custom_macro.timeout"));
+                                                ui.add(egui::DragValue::new(timeout_ms).suffix(" ms").speed(10))
+                                                    .on_hover_text(tr!("custom_macro.timeout_hint"));
+                                            });
+                                        }
                                         MacroAction::OcrSearch {
                                             ocr_region,
                                             scale_factor,
@@ -364,27 +602,40 @@ pub fn render_ui(
                                         } => {
                                             // Compact OCR UI
                                             ui.horizontal(|ui| {
-                                                if let Some((l, t, w, h)) = ocr_region {
-                                                    ui.label(egui::RichText::new(format!("Region: {:.0},{:.0} {:.0}x{:.0}", l, t, w, h)).monospace().size(11.0));
+                                                if let Some(region) = ocr_region.as_mut() {
+                                                    let (l, t, w, h) = *region;
+                                                    rect_value(
+                                                        ui,
+                                                        ("ocr_region", idx),
+                                                        egui::RichText::new(tr!("custom_macro.ocr_region", region = format!("{:.3},{:.3} {:.3}x{:.3}", l, t, w, h))).monospace().size(11.0),
+                                                        region,
+                                                        client_size,
+                                                    );
                                                 } else {
-                                                    ui.label(egui::RichText::new("Region: Not Set").color(egui::Color32::RED).size(11.0));
+                                                    ui.label(egui::RichText::new(tr!("custom_macro.ocr_region_unset")).color(egui::Color32::RED).size(11.0));
                                                 }
 
                                                 let is_this_calibrating = ocr_calibrating_action_index == Some(idx);
                                                 if is_this_calibrating {
-                                                    if ui.button(egui::RichText::new("CANCEL").size(10.0)).clicked() {
+                                                    if ui.button(egui::RichText::new(tr!("custom_macro.cancel")).size(10.0)).clicked() {
                                                         action = CustomMacroUiAction::CancelOcrRegionCalibration;
                                                     }
                                                     ui.spinner();
                                                 } else {
-                                                     if ui.button(egui::RichText::new("SET AREA").size(10.0)).clicked() {
+                                                     if ui.button(egui::RichText::new(tr!("custom_macro.set_area")).size(10.0)).clicked() {
                                                          action = CustomMacroUiAction::StartOcrRegionCalibration(idx);
                                                      }
                                                 }
                                             });
 
+                                            if ocr_calibrating_action_index == Some(idx) {
+                                                if let Some(frame) = magnifier {
+                                                    render_magnifier(ui, frame);
+                                                }
+                                            }
+
                                             ui.horizontal(|ui| {
-                                                ui.add(egui::TextEdit::singleline(target_stat).desired_width(100.0).hint_text("Stat Name"));
+                                                ui.add(egui::TextEdit::singleline(target_stat).desired_width(100.0).hint_text(tr!("custom_macro.stat_name")));
 
                                                 egui::ComboBox::from_id_source(format!("cmp_{}", idx))
                                                     .selected_text(match comparison {
@@ -402,8 +653,8 @@ pub fn render_ui(
                                                 ui.add(egui::DragValue::new(target_value).speed(1));
 
                                                 let match_label = match name_match_mode {
-                                                    OcrNameMatchMode::Exact => "Match: Exact",
-                                                    OcrNameMatchMode::Contains => "Match: Contains",
+                                                    OcrNameMatchMode::Exact => tr!("custom_macro.match_exact"),
+                                                    OcrNameMatchMode::Contains => tr!("custom_macro.match_contains"),
                                                 };
                                                 let match_combo = egui::ComboBox::from_id_source(
                                                     format!("match_inline_{}", idx),
@@ -415,20 +666,20 @@ pub fn render_ui(
                                                         ui.selectable_value(
                                                             name_match_mode,
                                                             OcrNameMatchMode::Exact,
-                                                            "Match: Exact",
+                                                            tr!("custom_macro.match_exact"),
                                                         );
                                                         ui.selectable_value(
                                                             name_match_mode,
                                                             OcrNameMatchMode::Contains,
-                                                            "Match: Contains",
+                                                            tr!("custom_macro.match_contains"),
                                                         );
                                                     });
                                                 match_response.response.on_hover_text(
-                                                    "Exact: name must match fully. Contains: partial match.",
+                                                    tr!("custom_macro.match_hint"),
                                                 );
                                             });
 
-                                            if ui.link("Add alternate target").clicked() {
+                                            if ui.link(tr!("custom_macro.add_alt_target")).clicked() {
                                                 alt_targets.push(OcrAltTarget {
                                                     target_stat: String::new(),
                                                     target_value: 0,
@@ -442,13 +693,13 @@ pub fn render_ui(
                                             for (alt_idx, alt) in alt_targets.iter_mut().enumerate()
                                             {
                                                 ui.horizontal(|ui| {
-                                                    ui.label(format!("Alt {}:", alt_idx + 1));
+                                                    ui.label(tr!("custom_macro.alt_target", number = alt_idx + 1));
                                                     ui.add(
                                                         egui::TextEdit::singleline(
                                                             &mut alt.target_stat,
                                                         )
                                                         .desired_width(100.0)
-                                                        .hint_text("Stat Name"),
+                                                        .hint_text(tr!("custom_macro.stat_name")),
                                                     );
 
                                                     egui::ComboBox::from_id_source(format!(
@@ -485,9 +736,9 @@ pub fn render_ui(
                                                     );
 
                                                     let alt_match_label = match alt.name_match_mode {
-                                                        OcrNameMatchMode::Exact => "Match: Exact",
+                                                        OcrNameMatchMode::Exact => tr!("custom_macro.match_exact"),
                                                         OcrNameMatchMode::Contains => {
-                                                            "Match: Contains"
+                                                            tr!("custom_macro.match_contains")
                                                         }
                                                     };
                                                     egui::ComboBox::from_id_source(format!(
@@ -500,23 +751,23 @@ pub fn render_ui(
                                                         ui.selectable_value(
                                                             &mut alt.name_match_mode,
                                                             OcrNameMatchMode::Exact,
-                                                            "Match: Exact",
+                                                            tr!("custom_macro.match_exact"),
                                                         );
                                                         ui.selectable_value(
                                                             &mut alt.name_match_mode,
                                                             OcrNameMatchMode::Contains,
-                                                            "Match: Contains",
+                                                            tr!("custom_macro.match_contains"),
                                                         );
                                                     });
 
-                                                    ui.label("Delay");
+                                                    ui.label(tr!("custom_macro.delay"));
                                                     ui.add(
                                                         egui::DragValue::new(&mut alt.delay_ms)
                                                             .suffix(" ms")
                                                             .speed(10),
                                                     );
 
-                                                    if ui.link("Remove").clicked() {
+                                                    if ui.link(tr!("custom_macro.remove")).clicked() {
                                                         remove_alt = Some(alt_idx);
                                                     }
                                                 });
@@ -525,12 +776,12 @@ pub fn render_ui(
                                                 alt_targets.remove(alt_idx);
                                             }
 
-                                            egui::CollapsingHeader::new("Advanced")
+                                            egui::CollapsingHeader::new(tr!("custom_macro.advanced"))
                                                 .id_source(format!("ocr_more_{}", idx))
                                                 .default_open(false)
                                                 .show(ui, |ui| {
                                                 ui.horizontal(|ui| {
-                                                    ui.label("Image preprocessing:");
+                                                    ui.label(tr!("custom_macro.preprocessing"));
                                                     let mut preset = infer_ocr_preprocess_preset(
                                                         *scale_factor,
                                                         *invert_colors,
@@ -538,11 +789,11 @@ pub fn render_ui(
                                                     );
                                                     let previous_preset = preset;
                                                     let preset_label = match preset {
-                                                        OcrPreprocessPreset::Default => "Default",
-                                                        OcrPreprocessPreset::HighContrast => "High-contrast",
-                                                        OcrPreprocessPreset::Invert => "Invert",
-                                                        OcrPreprocessPreset::Grayscale => "Grayscale",
-                                                        OcrPreprocessPreset::Custom => "Custom",
+                                                        OcrPreprocessPreset::Default => tr!("custom_macro.preset_default"),
+                                                        OcrPreprocessPreset::HighContrast => tr!("custom_macro.preset_high_contrast"),
+                                                        OcrPreprocessPreset::Invert => tr!("custom_macro.preset_invert"),
+                                                        OcrPreprocessPreset::Grayscale => tr!("custom_macro.preset_grayscale"),
+                                                        OcrPreprocessPreset::Custom => tr!("custom_macro.preset_custom"),
                                                     };
                                                     let preset_combo =
                                                         egui::ComboBox::from_id_source(format!(
@@ -554,28 +805,28 @@ pub fn render_ui(
                                                         ui.selectable_value(
                                                             &mut preset,
                                                             OcrPreprocessPreset::Default,
-                                                            "Default",
+                                                            tr!("custom_macro.preset_default"),
                                                         );
                                                         ui.selectable_value(
                                                             &mut preset,
                                                             OcrPreprocessPreset::HighContrast,
-                                                            "High-contrast",
+                                                            tr!("custom_macro.preset_high_contrast"),
                                                         );
                                                         ui.selectable_value(
                                                             &mut preset,
                                                             OcrPreprocessPreset::Invert,
-                                                            "Invert",
+                                                            tr!("custom_macro.preset_invert"),
                                                         );
                                                         ui.selectable_value(
                                                             &mut preset,
                                                             OcrPreprocessPreset::Grayscale,
-                                                            "Grayscale",
+                                                            tr!("custom_macro.preset_grayscale"),
                                                         );
                                                         if preset == OcrPreprocessPreset::Custom {
                                                             ui.selectable_value(
                                                                 &mut preset,
                                                                 OcrPreprocessPreset::Custom,
-                                                                "Custom",
+                                                                tr!("custom_macro.preset_custom"),
                                                             );
                                                         }
                                                     });
@@ -590,7 +841,7 @@ pub fn render_ui(
                                                 });
 
                                                 ui.horizontal(|ui| {
-                                                    ui.label("Accuracy vs speed:");
+                                                    ui.label(tr!("custom_macro.accuracy"));
                                                     let mut accuracy = if matches!(
                                                         decode_mode,
                                                         OcrDecodeMode::BeamSearch
@@ -605,19 +856,19 @@ pub fn render_ui(
                                                         idx
                                                     ))
                                                     .selected_text(match accuracy {
-                                                        OcrAccuracyMode::Fast => "Fast",
-                                                        OcrAccuracyMode::HighAccuracy => "High accuracy",
+                                                        OcrAccuracyMode::Fast => tr!("custom_macro.accuracy_fast"),
+                                                        OcrAccuracyMode::HighAccuracy => tr!("custom_macro.accuracy_high"),
                                                     })
                                                     .show_ui(ui, |ui| {
                                                         ui.selectable_value(
                                                             &mut accuracy,
                                                             OcrAccuracyMode::Fast,
-                                                            "Fast",
+                                                            tr!("custom_macro.accuracy_fast"),
                                                         );
                                                         ui.selectable_value(
                                                             &mut accuracy,
                                                             OcrAccuracyMode::HighAccuracy,
-                                                            "High accuracy",
+                                                            tr!("custom_macro.accuracy_high"),
                                                         );
                                                     });
                                                     if accuracy != previous_accuracy {
@@ -632,7 +883,7 @@ pub fn render_ui(
                                                     }
 
                                                     if matches!(accuracy, OcrAccuracyMode::HighAccuracy) {
-                                                        ui.label("Beam width:");
+                                                        ui.label(tr!("custom_macro.beam_width"));
                                                         ui.add(egui::DragValue::new(beam_width).clamp_range(2..=20));
                                                     }
                                                 });
@@ -649,7 +900,8 @@ pub fn render_ui(
         }
 
         if let Some(idx) = to_remove {
-            named_macro.settings.actions.remove(idx);
+            // Removed by the tool so it can be undone
+            action = CustomMacroUiAction::DeleteAction(idx);
         }
         if let Some(idx) = to_move_up {
             named_macro.settings.actions.swap(idx, idx - 1);
@@ -663,12 +915,16 @@ pub fn render_ui(
 
     // 3. Loop Settings Section
     ui.group(|ui| {
-        ui.heading(egui::RichText::new("Loop Settings").size(14.0).strong());
+        ui.heading(
+            egui::RichText::new(tr!("custom_macro.loop_settings"))
+                .size(14.0)
+                .strong(),
+        );
         ui.add_space(4.0);
 
         ui.horizontal(|ui| {
             ui.label(
-                egui::RichText::new("Don't forget to add delays between actions!")
+                egui::RichText::new(tr!("custom_macro.delay_reminder"))
                     .color(egui::Color32::from_rgb(255, 200, 100))
                     .size(12.0),
             );
@@ -677,14 +933,20 @@ pub fn render_ui(
         ui.add_space(8.0);
 
         ui.horizontal(|ui| {
-            ui.checkbox(&mut named_macro.settings.loop_enabled, "Enable Loop");
+            ui.checkbox(
+                &mut named_macro.settings.loop_enabled,
+                tr!("custom_macro.loop_enabled"),
+            );
 
             if named_macro.settings.loop_enabled {
                 ui.separator();
-                ui.checkbox(&mut named_macro.settings.infinite_loop, "Infinite");
+                ui.checkbox(
+                    &mut named_macro.settings.infinite_loop,
+                    tr!("custom_macro.loop_infinite"),
+                );
 
                 if !named_macro.settings.infinite_loop {
-                    ui.label("Repeat:");
+                    ui.label(tr!("custom_macro.repeat"));
                     let mut count_str = named_macro.settings.loop_count.to_string();
                     if ui
                         .add(egui::TextEdit::singleline(&mut count_str).desired_width(80.0))
@@ -694,10 +956,55 @@ pub fn render_ui(
                             named_macro.settings.loop_count = val.max(1);
                         }
                     }
-                    ui.label("times");
+                    ui.label(tr!("custom_macro.times"));
                 }
             }
         });
+
+        ui.horizontal(|ui| {
+            let speed = &mut named_macro.settings.speed_multiplier;
+            ui.label(tr!("custom_macro.speed"));
+            ui.add(
+                egui::Slider::new(speed, SPEED_MULTIPLIER_RANGE.0..=SPEED_MULTIPLIER_RANGE.1)
+                    .logarithmic(true)
+                    .suffix("x"),
+            )
+            .on_hover_text(tr!("custom_macro.speed_hint"));
+            if *speed != 1.0 && ui.small_button(tr!("custom_macro.speed_reset")).clicked() {
+                *speed = 1.0;
+            }
+        });
+
+        ui.checkbox(
+            &mut named_macro.settings.queue_one_shots,
+            tr!("custom_macro.queue_one_shots"),
+        )
+        .on_hover_text(tr!("custom_macro.queue_one_shots_hint"));
+
+        ui.add_space(4.0);
+
+        // Multi-client: the whole macro runs on each ticked client in turn
+        ui.horizontal(|ui| {
+            ui.label(tr!("custom_macro.clients"));
+            if ui
+                .add_enabled(
+                    !is_running,
+                    egui::Button::new(tr!("custom_macro.find_clients")),
+                )
+                .on_hover_text(tr!("custom_macro.find_clients_hint"))
+                .clicked()
+            {
+                action = CustomMacroUiAction::FindClients;
+            }
+        });
+        ui.add_enabled_ui(!is_running, |ui| {
+            for (idx, client) in clients.iter_mut().enumerate() {
+                ui.checkbox(
+                    &mut client.selected,
+                    format!("{}. {}", idx + 1, client.title),
+                );
+            }
+        });
     });
 
     ui.add_space(12.0);
@@ -705,9 +1012,9 @@ pub fn render_ui(
     // 4. Control Buttons
     ui.vertical_centered(|ui| {
         let (btn_text, btn_color) = if is_running {
-            ("Stop", egui::Color32::from_rgb(255, 100, 100))
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
         } else {
-            ("Start", egui::Color32::from_rgb(100, 255, 100))
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
         };
 
         let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
@@ -720,6 +1027,21 @@ pub fn render_ui(
                 CustomMacroUiAction::StartMacro
             };
         }
+
+        if is_running {
+            let pause_text = if is_paused {
+                tr!("custom_macro.resume")
+            } else {
+                tr!("custom_macro.pause")
+            };
+            if ui
+                .add(egui::Button::new(pause_text).min_size(egui::vec2(200.0, 28.0)))
+                .on_hover_text(tr!("custom_macro.pause_hint"))
+                .clicked()
+            {
+                action = CustomMacroUiAction::TogglePause;
+            }
+        }
     });
 
     ui.add_space(12.0);
@@ -727,7 +1049,7 @@ pub fn render_ui(
     ui.add_space(6.0);
 
     // 5. Status Section
-    crate::ui::status::render_status(ui, status, hotkey_error);
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
 
     action
 }
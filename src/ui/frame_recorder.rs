@@ -0,0 +1,80 @@
+use crate::settings::{
+    FrameRecorderSettings, FRAME_INTERVAL_RANGE_SECS, FRAME_MAX_FILES_RANGE,
+    FRAME_MAX_TOTAL_RANGE_MB, FRAME_SCALE_RANGE_PERCENT,
+};
+use eframe::egui;
+
+/// Collapsible "Frame recorder" section: the on/off switch, interval, scale and caps, and
+/// the last save error. Returns true when Open folder was clicked.
+pub fn render_frame_recorder_section(
+    ui: &mut egui::Ui,
+    settings: &mut FrameRecorderSettings,
+    error: Option<&str>,
+) -> bool {
+    let mut open_folder = false;
+
+    egui::CollapsingHeader::new(
+        egui::RichText::new(tr!("frame_recorder.title"))
+            .color(egui::Color32::from_rgb(180, 180, 180)),
+    )
+    .id_source("frame_recorder_section")
+    .show(ui, |ui| {
+        ui.label(
+            egui::RichText::new(tr!("frame_recorder.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut settings.enabled, tr!("frame_recorder.enabled"));
+            if ui
+                .button(tr!("frame_recorder.open_folder"))
+                .on_hover_text(tr!("frame_recorder.open_folder_hint"))
+                .clicked()
+            {
+                open_folder = true;
+            }
+        });
+
+        ui.add_enabled_ui(settings.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr!("frame_recorder.interval"));
+                ui.add(
+                    egui::DragValue::new(&mut settings.interval_secs)
+                        .clamp_range(FRAME_INTERVAL_RANGE_SECS.0..=FRAME_INTERVAL_RANGE_SECS.1)
+                        .suffix(" s"),
+                );
+                ui.label(tr!("frame_recorder.scale"));
+                ui.add(
+                    egui::DragValue::new(&mut settings.scale_percent)
+                        .clamp_range(FRAME_SCALE_RANGE_PERCENT.0..=FRAME_SCALE_RANGE_PERCENT.1)
+                        .suffix(" %"),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr!("frame_recorder.keep_at_most"));
+                ui.add(
+                    egui::DragValue::new(&mut settings.max_files)
+                        .clamp_range(FRAME_MAX_FILES_RANGE.0..=FRAME_MAX_FILES_RANGE.1)
+                        .suffix(tr!("frame_recorder.files_suffix")),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut settings.max_total_mb)
+                        .clamp_range(FRAME_MAX_TOTAL_RANGE_MB.0..=FRAME_MAX_TOTAL_RANGE_MB.1)
+                        .suffix(" MB"),
+                )
+                .on_hover_text(tr!("frame_recorder.caps_hint"));
+            });
+        });
+
+        if let Some(error) = error {
+            ui.label(
+                egui::RichText::new(error)
+                    .small()
+                    .color(egui::Color32::from_rgb(230, 100, 100)),
+            );
+        }
+    });
+
+    open_folder
+}
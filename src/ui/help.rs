@@ -1,53 +1,130 @@
 use crate::core::hotkey::hotkey_label;
-use crate::settings::AppSettings;
+use crate::settings::{AppSettings, HotkeyActionId};
 use eframe::egui;
 
 pub fn render_help(ui: &mut egui::Ui, settings: &AppSettings) {
-    ui.heading("Quick start");
-    ui.label("- Use the header Connect button to hunt for the Cabal D3D window; the green dot confirms a match.");
-    ui.label("- Pick a tool tab, fill the highlighted fields, then press Start (button turns Stop while running).");
-    ui.label("- Use the Log button to follow progress and the emergency hotkey (header) to halt a running tool.");
+    ui.heading(tr!("help.quick_start.title"));
+    ui.label(tr!("help.quick_start.1"));
+    ui.label(tr!("help.quick_start.2"));
+    ui.label(tr!("help.quick_start.3"));
+    ui.label(tr!("help.quick_start.anti_afk"));
+    ui.label(tr!("help.quick_start.pixel_trigger"));
+    ui.label(tr!("help.quick_start.rebuffer"));
+    ui.label(tr!("help.quick_start.rotation"));
+    ui.label(tr!("help.quick_start.auto_relog"));
+    ui.label(tr!("help.quick_start.loot_clicker"));
+    ui.label(tr!("help.quick_start.stat_logger"));
 
     ui.add_space(6.0);
-    ui.heading("Header controls");
-    ui.label("- Connect / Disconnect: finds or drops the game window and shows its current size.");
-    ui.label("- Overlay: switches to a compact toolbar; tools marked \"Show in overlay\" appear there.");
-    ui.label("- Log: opens the right-hand log panel that shows the latest lines while running and the complete trace after stop.");
-    ui.label("- ?: reopens this help panel when you need a refresher.");
-    ui.label("- Always on top: keeps the main window above other apps.");
-    ui.label(format!(
-        "- Emergency stop: click to set the hotkey ({}) or press the hotkey/Stop to immediately cancel automation.",
-        hotkey_label(&settings.emergency_stop_hotkey)
+    ui.heading(tr!("help.header.title"));
+    ui.label(tr!("help.header.1"));
+    ui.label(tr!("help.header.2"));
+    ui.label(tr!("help.header.3"));
+    ui.label(tr!("help.header.4"));
+    ui.label(tr!("help.header.5"));
+    ui.label(tr!("help.header.6"));
+    ui.label(tr!("help.header.7"));
+    ui.label(tr!("help.header.8"));
+    ui.label(tr!("help.header.command_line"));
+    ui.label(tr!("help.header.headless"));
+    ui.label(tr!("help.header.9"));
+    ui.label(tr!("help.header.10"));
+    ui.label(tr!("help.header.11"));
+    ui.label(tr!("help.header.12"));
+    ui.label(tr!("help.header.session_summary"));
+    ui.label(tr!("help.header.rate_limit"));
+    ui.label(tr!("help.header.13"));
+    ui.label(tr!("help.header.14"));
+    ui.label(tr!("help.header.15"));
+    ui.label(tr!("help.header.language"));
+    ui.label(tr!(
+        "help.header.16",
+        emergency_stop = hotkey_label(&settings.hotkey(HotkeyActionId::EmergencyStop)),
     ));
+    ui.label(tr!("help.header.17"));
+    ui.label(tr!("help.header.corner_failsafe"));
+    ui.label(tr!("help.header.sounds"));
+    ui.label(tr!("help.header.ocr_models"));
+    ui.label(tr!("help.header.frame_recorder"));
+    ui.label(tr!("help.header.remote_control"));
+    ui.label(tr!("help.header.macro_script"));
+    ui.label(tr!("help.header.schedule"));
+    ui.label(tr!("help.header.probe"));
+    ui.label(tr!("help.header.run_journal"));
+    ui.label(tr!(
+        "help.header.18",
+        toggle_image_clicker = hotkey_label(&settings.hotkey(HotkeyActionId::ToggleImageClicker)),
+        toggle_collection_filler =
+            hotkey_label(&settings.hotkey(HotkeyActionId::ToggleCollectionFiller)),
+    ));
+    ui.label(tr!(
+        "help.header.19",
+        toggle_overlay = hotkey_label(&settings.hotkey(HotkeyActionId::ToggleOverlay)),
+    ));
+    ui.label(tr!(
+        "help.header.20",
+        pause_resume = hotkey_label(&settings.hotkey(HotkeyActionId::PauseResume)),
+    ));
+    ui.label(tr!("help.header.21"));
+    ui.label(tr!("help.header.22"));
 
     ui.add_space(6.0);
-    ui.heading("Image Clicker (Accept Item)");
-    ui.label("- Image Path: the PNG/JPG the tool will scan for every cycle.");
-    ui.label("- Interval (ms): time between scans; lower values repeat faster.");
-    ui.label("- Confidence: how close the screenshot must match before clicking.");
-    ui.label("- Detection Area: optionally limit the search rectangle for better speed.");
-    ui.label("- Show in overlay: keeps this tool accessible from the overlay toolbar.");
+    ui.heading(tr!("help.image_clicker.title"));
+    ui.label(tr!("help.image_clicker.1"));
+    ui.label(tr!("help.image_clicker.2"));
+    ui.label(tr!("help.image_clicker.3"));
+    ui.label(tr!("help.image_clicker.match_mode"));
+    ui.label(tr!("help.image_clicker.4"));
+    ui.label(tr!("help.image_clicker.5"));
 
     ui.add_space(6.0);
-    ui.heading("Collection Filler");
-    ui.label("- Red Dot Image + Tolerance: defines what to look for when scanning tabs.");
-    ui.label("- Delay (ms): pause between automated clicks; keep it above 200 if the game feels unstable.");
-    ui.label("- Calibrate the Tabs, Dungeon List, and Items Areas before running.");
-    ui.label("- Calibrate the Auto Refill, Register, Yes, Page 2‑4, and Arrow Right buttons so clicks land correctly.");
-    ui.label("- Show in overlay: include this automation in the compact toolbar.");
+    ui.heading(tr!("help.collection_filler.title"));
+    ui.label(tr!("help.collection_filler.1"));
+    ui.label(tr!("help.collection_filler.2"));
+    ui.label(tr!("help.collection_filler.3"));
+    ui.label(tr!("help.collection_filler.scroll"));
+    ui.label(tr!("help.collection_filler.color"));
+    ui.label(tr!("help.collection_filler.4"));
+    ui.label(tr!("help.collection_filler.5"));
+    ui.label(tr!("help.collection_filler.6"));
+    ui.label(tr!("help.collection_filler.7"));
 
     ui.add_space(6.0);
-    ui.heading("Custom Macros");
-    ui.label("- Macro Name controls the tab text; \"Show in overlay\" makes it a quick toggle.");
-    ui.label("- Actions execute sequentially: Click (position/button/method), Type Text, Delay, and OCR Search.");
-    ui.label("- OCR Search: set a region by clicking top-left then bottom-right, enter the stat text, and the numeric value to compare.");
-    ui.label("- Alt target: optional backup stat/value pair that respects the same comparison mode.");
-    ui.label("- Comparison selects equals/≥/≤, and Name Match picks exact or contains.");
-    ui.label("- Advanced OCR tweaks (scale, grayscale, invert, beam search) improve accuracy for different fonts.");
+    ui.heading(tr!("help.custom_macros.title"));
+    ui.label(tr!("help.custom_macros.1"));
+    ui.label(tr!("help.custom_macros.2"));
+    ui.label(tr!("help.custom_macros.3"));
+    ui.label(tr!("help.custom_macros.4"));
+    ui.label(tr!("help.custom_macros.5"));
+    ui.label(tr!("help.custom_macros.6"));
+    ui.label(tr!("help.custom_macros.7"));
+    ui.label(tr!("help.custom_macros.clients"));
+    ui.label(tr!("help.custom_macros.8"));
+    ui.label(tr!("help.custom_macros.9"));
+    ui.label(tr!("help.custom_macros.10"));
+    ui.label(tr!("help.custom_macros.11"));
+    ui.label(tr!("help.custom_macros.12"));
+    ui.label(tr!("help.custom_macros.wait_gone"));
 
     ui.add_space(6.0);
-    ui.heading("Notes");
-    ui.label("- Recalibrate if the game window size or position changes.");
-    ui.label("- Settings auto-save whenever you make a change.");
-    ui.label("- If a tool shows an error, check the log (right panel) and stop before adjusting.");
+    ui.heading(tr!("help.notes.title"));
+    ui.label(tr!("help.notes.1"));
+    ui.label(tr!("help.notes.2"));
+    ui.label(tr!("help.notes.3"));
+    ui.label(tr!("help.notes.4"));
+    ui.label(tr!("help.notes.5"));
+    ui.label(tr!("help.notes.6"));
+    ui.label(tr!("help.notes.7"));
+    ui.label(tr!("help.notes.8"));
+    ui.label(tr!("help.notes.9"));
+    ui.label(tr!("help.notes.10"));
+    ui.label(tr!("help.notes.11"));
+    ui.label(tr!("help.notes.12"));
+    ui.label(tr!("help.notes.13"));
+    ui.label(tr!("help.notes.14"));
+    ui.label(tr!("help.notes.15"));
+    ui.label(tr!("help.notes.16"));
+    ui.label(tr!("help.notes.17"));
+    ui.label(tr!("help.notes.18"));
+    ui.label(tr!("help.notes.19"));
 }
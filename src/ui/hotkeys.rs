@@ -0,0 +1,123 @@
+use crate::core::hotkey::{hotkey_label, try_capture_hotkey, HotkeyProblem};
+use crate::settings::{
+    CornerFailsafe, HotkeyActionId, HotkeyConfig, ScreenCorner, FAILSAFE_THRESHOLD_RANGE_PX,
+};
+use eframe::egui;
+use std::collections::HashMap;
+
+/// Collapsible "Hotkeys" section: one row per bindable action (label, binding) with capture and
+/// Clear buttons, followed by a warning row when the binding is not active.
+/// Returns a binding the user captured or cleared.
+pub fn render_hotkeys_section(
+    ui: &mut egui::Ui,
+    hotkey_rows: &[(HotkeyActionId, String, HotkeyConfig)],
+    capturing: &mut Option<HotkeyActionId>,
+    hotkey_problems: &HashMap<HotkeyActionId, HotkeyProblem>,
+    emergency_stop_focus_only: &mut bool,
+    corner_failsafe: &mut CornerFailsafe,
+    hotkey_error: Option<&str>,
+) -> Option<(HotkeyActionId, HotkeyConfig)> {
+    let mut changed = None;
+
+    egui::CollapsingHeader::new(
+        egui::RichText::new(tr!("hotkeys.title")).color(egui::Color32::from_rgb(180, 180, 180)),
+    )
+    .id_source("hotkeys_section")
+    .show(ui, |ui| {
+        egui::Grid::new("hotkeys_grid")
+            .num_columns(3)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for (action, action_label, binding) in hotkey_rows {
+                    let action = *action;
+                    let is_capturing = *capturing == Some(action);
+
+                    ui.label(action_label);
+
+                    let label = if is_capturing {
+                        tr!("hotkeys.capturing").to_string()
+                    } else {
+                        hotkey_label(binding)
+                    };
+                    let button =
+                        egui::Button::new(egui::RichText::new(label).color(egui::Color32::WHITE))
+                            .min_size(egui::vec2(0.0, 24.0))
+                            .fill(if is_capturing {
+                                egui::Color32::from_rgb(90, 90, 120)
+                            } else {
+                                egui::Color32::from_white_alpha(10)
+                            });
+                    if ui.add(button).clicked() {
+                        *capturing = Some(action);
+                    }
+
+                    if ui
+                        .add(
+                            egui::Button::new(
+                                egui::RichText::new(tr!("common.clear"))
+                                    .color(egui::Color32::from_rgb(200, 160, 160)),
+                            )
+                            .fill(egui::Color32::from_white_alpha(10))
+                            .min_size(egui::vec2(0.0, 22.0)),
+                        )
+                        .clicked()
+                    {
+                        changed = Some((action, HotkeyConfig::disabled()));
+                    }
+                    ui.end_row();
+
+                    if let Some(problem) = hotkey_problems.get(&action) {
+                        let color = match problem {
+                            HotkeyProblem::Conflict(_) => egui::Color32::from_rgb(230, 200, 80),
+                            HotkeyProblem::Registration { .. } => {
+                                egui::Color32::from_rgb(200, 120, 120)
+                            }
+                        };
+                        ui.label("");
+                        ui.label(egui::RichText::new(problem.message()).color(color));
+                        ui.end_row();
+                    }
+                }
+            });
+
+        ui.checkbox(emergency_stop_focus_only, tr!("hotkeys.stop_focus_only"))
+            .on_hover_text(tr!("hotkeys.stop_focus_only_hint"));
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut corner_failsafe.enabled, tr!("hotkeys.corner_failsafe"))
+                .on_hover_text(tr!("hotkeys.corner_failsafe_hint"));
+            ui.add_enabled_ui(corner_failsafe.enabled, |ui| {
+                egui::ComboBox::from_id_source("failsafe_corner")
+                    .selected_text(corner_failsafe.corner.label())
+                    .show_ui(ui, |ui| {
+                        for corner in ScreenCorner::ALL {
+                            ui.selectable_value(
+                                &mut corner_failsafe.corner,
+                                corner,
+                                corner.label(),
+                            );
+                        }
+                    });
+                ui.add(
+                    egui::DragValue::new(&mut corner_failsafe.threshold_px)
+                        .clamp_range(FAILSAFE_THRESHOLD_RANGE_PX.0..=FAILSAFE_THRESHOLD_RANGE_PX.1)
+                        .suffix(" px"),
+                )
+                .on_hover_text(tr!("hotkeys.corner_failsafe_px_hint"));
+            });
+        });
+
+        if let Some(err) = hotkey_error {
+            ui.label(egui::RichText::new(err).color(egui::Color32::from_rgb(200, 120, 120)));
+        }
+    });
+
+    if let Some(action) = *capturing {
+        if let Some(new_hotkey) = try_capture_hotkey(ui.ctx()) {
+            changed = Some((action, new_hotkey));
+            *capturing = None;
+        }
+    }
+
+    changed
+}
@@ -1,3 +1,10 @@
+use crate::calibration::MagnifierFrame;
+use crate::core::worker::WorkerStats;
+use crate::settings::{NormRect, TemplateMatchMode};
+use crate::ui::coord_edit::rect_value;
+use crate::ui::image_path_field::image_path_field;
+use crate::ui::magnifier::render_magnifier;
+use crate::ui::reset_button::reset_to_defaults_button;
 use eframe::egui;
 
 #[derive(Debug)]
@@ -5,6 +12,7 @@ pub enum ImageUiAction {
     StartRegionCalibration,
     CancelCalibration,
     ClearRegion,
+    ResetToDefaults,
     Start,
     Stop,
     None,
@@ -14,96 +22,131 @@ pub enum ImageUiAction {
 pub fn render_ui(
     ui: &mut egui::Ui,
     image_path: &mut String,
+    recent_image_paths: &mut Vec<String>,
     interval_ms: &mut String,
     tolerance: &mut f32,
+    match_mode: &mut TemplateMatchMode,
+    max_runtime_minutes: &mut Option<u32>,
     show_in_overlay: &mut bool,
-    search_region: Option<(f32, f32, f32, f32)>,
+    search_region: &mut Option<NormRect>,
     is_calibrating: bool,
     is_waiting_for_second_click: bool,
+    magnifier: Option<&MagnifierFrame>,
     is_running: bool,
     status: &str,
+    stats: &WorkerStats,
     game_connected: bool,
+    client_size: Option<(i32, i32)>,
     hotkey_error: Option<&str>,
 ) -> ImageUiAction {
     let mut action = ImageUiAction::None;
 
     if !game_connected {
-        ui.colored_label(
-            egui::Color32::RED,
-            "Please connect to game first (top left)",
-        );
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
         return ImageUiAction::None;
     }
 
-    ui.checkbox(show_in_overlay, "Show in overlay");
+    ui.horizontal(|ui| {
+        ui.checkbox(show_in_overlay, tr!("common.show_in_overlay"));
+        if reset_to_defaults_button(ui, "image_clicker_reset") {
+            action = ImageUiAction::ResetToDefaults;
+        }
+    });
     ui.add_space(8.0);
 
     // 1. Settings Group
     ui.group(|ui| {
-        ui.heading(egui::RichText::new("Configuration").size(14.0).strong());
+        ui.heading(
+            egui::RichText::new(tr!("common.configuration"))
+                .size(14.0)
+                .strong(),
+        );
         ui.add_space(4.0);
 
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Image Path:").strong());
-            ui.text_edit_singleline(image_path);
-            if ui.button("Browse...").clicked() {
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("Image Files", &["png", "jpg", "jpeg", "bmp"])
-                    .set_title("Select Target Image")
-                    .set_directory(std::env::current_dir().unwrap_or_default())
-                    .pick_file()
-                {
-                    *image_path = path.display().to_string();
-                }
-            }
+            ui.label(egui::RichText::new(tr!("image_clicker.image_path")).strong());
+            image_path_field(
+                ui,
+                image_path,
+                recent_image_paths,
+                tr!("image_clicker.select_image"),
+            );
         });
 
         ui.add_space(4.0);
 
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Interval (ms):").strong());
+            ui.label(egui::RichText::new(tr!("image_clicker.interval")).strong());
             ui.add(egui::TextEdit::singleline(interval_ms).desired_width(80.0));
         });
 
         ui.add_space(4.0);
 
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Confidence:").strong());
+            ui.label(egui::RichText::new(tr!("image_clicker.confidence")).strong());
             ui.add(egui::Slider::new(tolerance, 0.01..=0.99));
         });
 
         ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("image_clicker.match_mode")).strong());
+            egui::ComboBox::from_id_source("image_clicker_match_mode")
+                .selected_text(match_mode.label())
+                .show_ui(ui, |ui| {
+                    for mode in TemplateMatchMode::ALL {
+                        ui.selectable_value(match_mode, mode, mode.label());
+                    }
+                })
+                .response
+                .on_hover_text(tr!("image_clicker.match_mode_hint"));
+        });
+
+        ui.add_space(4.0);
+
+        crate::ui::status::max_runtime_field(ui, max_runtime_minutes);
+
+        ui.add_space(4.0);
     });
 
     ui.add_space(12.0);
 
     // 2. Region Group
     ui.group(|ui| {
-        ui.heading(egui::RichText::new("Detection Area").size(14.0).strong());
+        ui.heading(
+            egui::RichText::new(tr!("image_clicker.detection_area"))
+                .size(14.0)
+                .strong(),
+        );
         ui.add_space(4.0);
 
         ui.label(
-            egui::RichText::new("Optional: Improve performance by limiting search area.")
+            egui::RichText::new(tr!("image_clicker.detection_area_hint"))
                 .small()
                 .color(egui::Color32::GRAY),
         );
         ui.add_space(4.0);
 
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new("Region:").strong());
+            ui.label(egui::RichText::new(tr!("image_clicker.region")).strong());
 
-            if let Some((left, top, width, height)) = search_region {
-                ui.label(
+            if let Some(region) = search_region.as_mut() {
+                let (left, top, width, height) = *region;
+                rect_value(
+                    ui,
+                    "image_clicker_region",
                     egui::RichText::new(format!(
                         "({:.3}, {:.3}, {:.3}x{:.3})",
                         left, top, width, height
                     ))
                     .monospace()
                     .strong(),
+                    region,
+                    client_size,
                 );
             } else {
                 ui.label(
-                    egui::RichText::new("Not set (Full Screen)")
+                    egui::RichText::new(tr!("image_clicker.region_unset"))
                         .color(egui::Color32::YELLOW)
                         .italics(),
                 );
@@ -114,29 +157,38 @@ pub fn render_ui(
             if is_calibrating {
                 if ui
                     .button(
-                        egui::RichText::new("Stop").color(egui::Color32::from_rgb(255, 100, 100)),
+                        egui::RichText::new(tr!("common.stop"))
+                            .color(egui::Color32::from_rgb(255, 100, 100)),
                     )
                     .clicked()
                 {
                     action = ImageUiAction::CancelCalibration;
                 }
                 let label = if is_waiting_for_second_click {
-                    "Click bottom-right..."
+                    tr!("calibration.click_bottom_right")
                 } else {
-                    "Click top-left..."
+                    tr!("calibration.click_top_left")
                 };
                 ui.label(egui::RichText::new(label).color(egui::Color32::YELLOW));
             } else {
-                if ui.button("Set Region").clicked() {
+                if ui.button(tr!("image_clicker.set_region")).clicked() {
                     action = ImageUiAction::StartRegionCalibration;
                 }
                 if search_region.is_some()
-                    && ui.button("Clear").on_hover_text("Clear Region").clicked()
+                    && ui
+                        .button(tr!("common.clear"))
+                        .on_hover_text(tr!("image_clicker.clear_region"))
+                        .clicked()
                 {
                     action = ImageUiAction::ClearRegion;
                 }
             }
         });
+
+        if let Some(frame) = magnifier {
+            ui.add_space(4.0);
+            render_magnifier(ui, frame);
+        }
     });
 
     ui.add_space(12.0);
@@ -144,9 +196,9 @@ pub fn render_ui(
     // 3. Controls
     ui.vertical_centered(|ui| {
         let (btn_text, btn_color) = if is_running {
-            ("Stop", egui::Color32::from_rgb(255, 100, 100))
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
         } else {
-            ("Start", egui::Color32::from_rgb(100, 255, 100))
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
         };
 
         let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
@@ -166,7 +218,8 @@ pub fn render_ui(
     ui.add_space(6.0);
 
     // 4. Status
-    crate::ui::status::render_status(ui, status, hotkey_error);
+    crate::ui::status::render_run_timer(ui, stats, *max_runtime_minutes);
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
 
     action
 }
@@ -0,0 +1,61 @@
+use crate::settings::remember_recent_path;
+use eframe::egui;
+use std::path::Path;
+
+/// Image path text field with Browse... and a dropdown of recently used paths
+/// Paths are remembered when browsed, picked, or typed and the file exists
+pub fn image_path_field(
+    ui: &mut egui::Ui,
+    path: &mut String,
+    recent_paths: &mut Vec<String>,
+    dialog_title: &str,
+) {
+    let response = ui.text_edit_singleline(path);
+    if response.lost_focus() && Path::new(path.trim()).is_file() {
+        remember_recent_path(recent_paths, path.trim());
+    }
+
+    if ui.button(tr!("image_path.browse")).clicked() {
+        if let Some(picked) = rfd::FileDialog::new()
+            .add_filter(tr!("image_path.filter"), &["png", "jpg", "jpeg", "bmp"])
+            .set_title(dialog_title)
+            .set_directory(std::env::current_dir().unwrap_or_default())
+            .pick_file()
+        {
+            *path = picked.display().to_string();
+            remember_recent_path(recent_paths, path);
+        }
+    }
+
+    ui.add_enabled_ui(!recent_paths.is_empty(), |ui| {
+        ui.menu_button("⏷", |ui| {
+            for entry in recent_paths.clone() {
+                let exists = Path::new(&entry).is_file();
+                let text = if exists {
+                    egui::RichText::new(&entry)
+                } else {
+                    egui::RichText::new(&entry)
+                        .color(egui::Color32::GRAY)
+                        .italics()
+                };
+                let button = ui.button(text);
+                let button = if exists {
+                    button
+                } else {
+                    button.on_hover_text(tr!("image_path.missing_hint"))
+                };
+                if button.clicked() {
+                    if exists {
+                        *path = entry.clone();
+                        remember_recent_path(recent_paths, &entry);
+                    } else {
+                        recent_paths.retain(|p| p != &entry);
+                    }
+                    ui.close_menu();
+                }
+            }
+        })
+        .response
+        .on_hover_text(tr!("image_path.recent_hint"));
+    });
+}
@@ -0,0 +1,175 @@
+use crate::calibration::MagnifierFrame;
+use crate::settings::{ClickMethod, HotkeyKey, MacroAction, MouseButton};
+use crate::ui::anti_afk::render_key_row;
+use crate::ui::coord_edit::point_value;
+use crate::ui::magnifier::render_magnifier;
+use eframe::egui;
+
+#[derive(Debug)]
+pub enum InputActionsUiAction {
+    StartCalibration(usize), // Click action index
+    CancelCalibration,
+}
+
+/// Editable list of click, key press and delay actions (Rebuffer, Auto-Relog), run
+/// through the custom macro executor. `id` keeps the widgets of each list apart.
+pub fn render_input_actions(
+    ui: &mut egui::Ui,
+    id: &str,
+    actions: &mut Vec<MacroAction>,
+    empty_hint: &str,
+    key_hint: &str,
+    calibrating_action: Option<usize>,
+    magnifier: Option<&MagnifierFrame>,
+    client_size: Option<(i32, i32)>,
+) -> Option<InputActionsUiAction> {
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("custom_macro.add_action")).strong());
+        if ui.button(tr!("custom_macro.add_click")).clicked() {
+            actions.push(MacroAction::Click {
+                coordinate: None,
+                button: MouseButton::Left,
+                click_method: ClickMethod::SendMessage,
+                use_mouse_movement: false,
+            });
+        }
+        if ui.button(tr!("custom_macro.add_key")).clicked() {
+            actions.push(MacroAction::PressKey {
+                key: HotkeyKey::Digit1,
+            });
+        }
+        if ui.button(tr!("custom_macro.add_delay")).clicked() {
+            actions.push(MacroAction::Delay { milliseconds: 500 });
+        }
+    });
+
+    if actions.is_empty() {
+        ui.label(
+            egui::RichText::new(empty_hint)
+                .color(egui::Color32::YELLOW)
+                .italics(),
+        );
+    }
+
+    let mut move_up = None;
+    let mut delete = None;
+    let count = actions.len();
+    for (idx, macro_action) in actions.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format!("{}.", idx + 1)).monospace());
+            match macro_action {
+                MacroAction::Click {
+                    coordinate, button, ..
+                } => {
+                    ui.label(egui::RichText::new(tr!("custom_macro.badge_click")).strong());
+                    if let Some(point) = coordinate.as_mut() {
+                        let (x, y) = *point;
+                        point_value(
+                            ui,
+                            (id, "click", idx),
+                            egui::RichText::new(format!("({:.3}, {:.3})", x, y)).monospace(),
+                            point,
+                            client_size,
+                        );
+                    } else {
+                        ui.label(
+                            egui::RichText::new(tr!("common.not_set"))
+                                .color(egui::Color32::YELLOW)
+                                .italics(),
+                        );
+                    }
+                    if calibrating_action == Some(idx) {
+                        if ui
+                            .button(
+                                egui::RichText::new(tr!("common.stop"))
+                                    .color(egui::Color32::from_rgb(255, 100, 100)),
+                            )
+                            .clicked()
+                        {
+                            action = Some(InputActionsUiAction::CancelCalibration);
+                        }
+                    } else if ui.button(tr!("common.set")).clicked() {
+                        action = Some(InputActionsUiAction::StartCalibration(idx));
+                    }
+                    egui::ComboBox::from_id_source((id, "button", idx))
+                        .selected_text(match button {
+                            MouseButton::Left => tr!("custom_macro.button_left"),
+                            MouseButton::Right => tr!("custom_macro.button_right"),
+                            MouseButton::Middle => tr!("custom_macro.button_middle"),
+                        })
+                        .width(70.0)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                button,
+                                MouseButton::Left,
+                                tr!("custom_macro.button_left"),
+                            );
+                            ui.selectable_value(
+                                button,
+                                MouseButton::Right,
+                                tr!("custom_macro.button_right"),
+                            );
+                            ui.selectable_value(
+                                button,
+                                MouseButton::Middle,
+                                tr!("custom_macro.button_middle"),
+                            );
+                        });
+                }
+                MacroAction::PressKey { key } => {
+                    render_key_row(ui, &format!("{}_key_capture_{}", id, idx), key, key_hint);
+                }
+                MacroAction::Delay { milliseconds } => {
+                    ui.label(egui::RichText::new(tr!("custom_macro.badge_delay")).strong());
+                    ui.add(egui::DragValue::new(milliseconds).suffix(" ms").speed(10));
+                }
+                // Dropped by the settings validation; nothing to edit
+                _ => {}
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .small_button("✖")
+                    .on_hover_text(tr!("custom_macro.delete"))
+                    .clicked()
+                {
+                    delete = Some(idx);
+                }
+                if idx + 1 < count
+                    && ui
+                        .small_button("⬇")
+                        .on_hover_text(tr!("custom_macro.move_down"))
+                        .clicked()
+                {
+                    move_up = Some(idx + 1);
+                }
+                if idx > 0
+                    && ui
+                        .small_button("⬆")
+                        .on_hover_text(tr!("custom_macro.move_up"))
+                        .clicked()
+                {
+                    move_up = Some(idx);
+                }
+            });
+        });
+    }
+    if let Some(idx) = move_up {
+        actions.swap(idx - 1, idx);
+    }
+    if let Some(idx) = delete {
+        actions.remove(idx);
+    }
+
+    if calibrating_action.is_some() {
+        ui.label(egui::RichText::new(tr!("calibration.click_button")).color(egui::Color32::YELLOW));
+        if let Some(frame) = magnifier {
+            ui.add_space(4.0);
+            render_magnifier(ui, frame);
+        }
+    }
+
+    action
+}
@@ -1,6 +1,55 @@
+use crate::core::clock::to_local;
+use crate::core::file_logger::file_logger;
+use crate::core::worker::{LogEntry, LogLevel};
+use crate::settings::LOG_RETENTION_RANGE_DAYS;
 use eframe::egui;
 
-pub fn render_log_panel(ctx: &egui::Context, log_snapshot: &[String], is_running: bool) {
+/// Context written at the top of a copied/saved log, so bug reports explain themselves
+pub struct LogReportContext {
+    // Connected game window: title and client size
+    pub window: Option<(String, i32, i32)>,
+    pub active_tool: String,
+}
+
+/// Log panel filter controls (kept in egui memory)
+#[derive(Clone, Default)]
+struct LogFilter {
+    // None = all sources
+    source: Option<String>,
+    min_level: Option<LogLevel>,
+    search: String,
+}
+
+impl LogFilter {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(source) = &self.source {
+            if entry.source != *source {
+                return false;
+            }
+        }
+        if let Some(level) = self.min_level {
+            if entry.level < level {
+                return false;
+            }
+        }
+        self.search.is_empty()
+            || entry
+                .text
+                .to_lowercase()
+                .contains(&self.search.to_lowercase())
+    }
+}
+
+/// `log_sources` lists the tool names (and "App") offered in the source filter
+pub fn render_log_panel(
+    ctx: &egui::Context,
+    log_snapshot: &[LogEntry],
+    log_sources: &[String],
+    report_context: &LogReportContext,
+    is_running: bool,
+    log_to_file: &mut bool,
+    log_retention_days: &mut u32,
+) {
     const RUNNING_LOG_LINES: usize = 5;
 
     egui::SidePanel::right("log_panel")
@@ -12,6 +61,15 @@ pub fn render_log_panel(ctx: &egui::Context, log_snapshot: &[String], is_running
                 .fill(egui::Color32::from_rgb(12, 12, 12))
                 .inner_margin(egui::Margin::same(8.0))
                 .show(ui, |ui| {
+                    let filter_id = ui.make_persistent_id("log_filter");
+                    let mut filter: LogFilter =
+                        ui.data(|d| d.get_temp(filter_id).unwrap_or_default());
+                    let visible: Vec<LogEntry> = log_snapshot
+                        .iter()
+                        .filter(|entry| filter.matches(entry))
+                        .cloned()
+                        .collect();
+
                     ui.horizontal(|ui| {
                         ui.label(
                             egui::RichText::new("Log")
@@ -19,15 +77,15 @@ pub fn render_log_panel(ctx: &egui::Context, log_snapshot: &[String], is_running
                                 .color(egui::Color32::LIGHT_GRAY),
                         );
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            let display_count = if is_running {
-                                log_snapshot.len().min(RUNNING_LOG_LINES)
+                            let count = if visible.len() == log_snapshot.len() {
+                                format!("{} lines", log_snapshot.len())
                             } else {
-                                log_snapshot.len()
+                                format!("{} of {} lines", visible.len(), log_snapshot.len())
                             };
                             let label = if is_running {
-                                format!("{} lines (last {})", log_snapshot.len(), display_count)
+                                format!("{} (last {})", count, visible.len().min(RUNNING_LOG_LINES))
                             } else {
-                                format!("{} lines", log_snapshot.len())
+                                count
                             };
                             ui.label(
                                 egui::RichText::new(label)
@@ -37,27 +95,123 @@ pub fn render_log_panel(ctx: &egui::Context, log_snapshot: &[String], is_running
                         });
                     });
 
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source("log_source_filter")
+                            .selected_text(filter.source.as_deref().unwrap_or("All tools"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut filter.source, None, "All tools");
+                                for source in log_sources {
+                                    ui.selectable_value(
+                                        &mut filter.source,
+                                        Some(source.clone()),
+                                        source,
+                                    );
+                                }
+                            });
+                        egui::ComboBox::from_id_source("log_level_filter")
+                            .selected_text(
+                                filter.min_level.map_or("All levels", |level| level.label()),
+                            )
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut filter.min_level, None, "All levels");
+                                for level in LogLevel::ALL {
+                                    ui.selectable_value(
+                                        &mut filter.min_level,
+                                        Some(level),
+                                        format!("{}+", level.label()),
+                                    );
+                                }
+                            });
+                        ui.add(
+                            egui::TextEdit::singleline(&mut filter.search)
+                                .hint_text("Search")
+                                .desired_width(90.0),
+                        );
+                    });
+                    ui.data_mut(|d| d.insert_temp(filter_id, filter.clone()));
+
+                    // Failures from Save... / Open log folder
+                    let error_id = ui.make_persistent_id("log_panel_error");
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button("Copy")
+                            .on_hover_text("Copy the filtered log, with app and window details")
+                            .clicked()
+                        {
+                            let report = format_report(report_context, &visible);
+                            ui.output_mut(|o| o.copied_text = report);
+                        }
+                        if ui.button("Save...").clicked() {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .set_title("Save Log")
+                                .set_file_name("cabalhelper-log.txt")
+                                .add_filter("Text", &["txt"])
+                                .save_file()
+                            {
+                                let report = format_report(report_context, &visible);
+                                let error = std::fs::write(&path, report)
+                                    .err()
+                                    .map(|err| format!("Can't write {}: {}", path.display(), err));
+                                ui.data_mut(|d| d.insert_temp(error_id, error));
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(log_to_file, "Write log to file");
+                        ui.add_enabled(
+                            *log_to_file,
+                            egui::DragValue::new(log_retention_days)
+                                .clamp_range(
+                                    LOG_RETENTION_RANGE_DAYS.0..=LOG_RETENTION_RANGE_DAYS.1,
+                                )
+                                .suffix(" days"),
+                        )
+                        .on_hover_text("Delete log files older than this");
+                        if ui.button("Open log folder").clicked() {
+                            let error = file_logger().open_dir().err();
+                            ui.data_mut(|d| d.insert_temp(error_id, error));
+                        }
+                    });
+                    let panel_error = ui.data(|d| d.get_temp::<Option<String>>(error_id).flatten());
+                    if let Some(err) = file_logger().error().or(panel_error) {
+                        ui.label(
+                            egui::RichText::new(err)
+                                .small()
+                                .color(egui::Color32::from_rgb(230, 100, 100)),
+                        );
+                    }
+
                     ui.add_space(6.0);
                     egui::ScrollArea::vertical()
                         .auto_shrink([false, false])
                         .show(ui, |ui| {
-                            if log_snapshot.is_empty() {
+                            if visible.is_empty() {
+                                let text = if log_snapshot.is_empty() {
+                                    "No log entries yet."
+                                } else {
+                                    "No entries match the filter."
+                                };
                                 ui.label(
-                                    egui::RichText::new("No log entries yet.")
+                                    egui::RichText::new(text)
                                         .italics()
                                         .color(egui::Color32::DARK_GRAY),
                                 );
                             } else {
                                 let start_idx = if is_running {
-                                    log_snapshot.len().saturating_sub(RUNNING_LOG_LINES)
+                                    visible.len().saturating_sub(RUNNING_LOG_LINES)
                                 } else {
                                     0
                                 };
-                                for line in &log_snapshot[start_idx..] {
+                                for entry in &visible[start_idx..] {
                                     ui.label(
-                                        egui::RichText::new(line)
-                                            .monospace()
-                                            .color(egui::Color32::from_rgb(200, 200, 200)),
+                                        egui::RichText::new(format!(
+                                            "{} [{}] {}",
+                                            entry_clock(entry),
+                                            entry.source,
+                                            entry.text
+                                        ))
+                                        .monospace()
+                                        .color(level_color(entry.level)),
                                     );
                                 }
                             }
@@ -65,3 +219,38 @@ pub fn render_log_panel(ctx: &egui::Context, log_snapshot: &[String], is_running
                 });
         });
 }
+
+fn level_color(level: LogLevel) -> egui::Color32 {
+    match level {
+        LogLevel::Info => egui::Color32::from_rgb(200, 200, 200),
+        LogLevel::Warn => egui::Color32::from_rgb(230, 180, 80),
+        LogLevel::Error => egui::Color32::from_rgb(230, 100, 100),
+    }
+}
+
+fn entry_clock(entry: &LogEntry) -> String {
+    to_local(entry.time)
+        .map(|time| time.clock())
+        .unwrap_or_else(|| "--:--:--".to_string())
+}
+
+fn format_report(context: &LogReportContext, entries: &[LogEntry]) -> String {
+    let mut report = format!("CabalHelper {}\n", env!("CARGO_PKG_VERSION"));
+    match &context.window {
+        Some((title, width, height)) => {
+            report.push_str(&format!("Window: {} ({}x{})\n", title, width, height))
+        }
+        None => report.push_str("Window: not connected\n"),
+    }
+    report.push_str(&format!("Tool: {}\n\n", context.active_tool));
+    for entry in entries {
+        report.push_str(&format!(
+            "{} {:<5} [{}] {}\n",
+            entry_clock(entry),
+            entry.level.label(),
+            entry.source,
+            entry.text
+        ));
+    }
+    report
+}
@@ -0,0 +1,260 @@
+use crate::calibration::MagnifierFrame;
+use crate::core::worker::WorkerStats;
+use crate::settings::{
+    LootClickerSettings, LootColor, LOOT_CLICK_DELAY_RANGE_MS, LOOT_DEDUPE_RANGE_PX,
+    LOOT_MAX_CLICKS_RANGE, LOOT_MERGE_RANGE_PX, LOOT_SWEEP_INTERVAL_RANGE_MS, MIN_BLOB_SIZE_RANGE,
+};
+use crate::ui::coord_edit::rect_value;
+use crate::ui::magnifier::render_magnifier;
+use crate::ui::reset_button::reset_to_defaults_button;
+use eframe::egui;
+
+#[derive(Debug)]
+pub enum LootClickerUiAction {
+    StartRegionCalibration,
+    CancelCalibration,
+    ResetToDefaults,
+    Start,
+    Stop,
+    None,
+}
+
+/// Render Loot Clicker UI
+pub fn render_ui(
+    ui: &mut egui::Ui,
+    settings: &mut LootClickerSettings,
+    show_regions: &mut bool,
+    is_calibrating: bool,
+    is_waiting_for_second_click: bool,
+    magnifier: Option<&MagnifierFrame>,
+    is_running: bool,
+    status: &str,
+    stats: &WorkerStats,
+    game_connected: bool,
+    client_size: Option<(i32, i32)>,
+    hotkey_error: Option<&str>,
+) -> LootClickerUiAction {
+    let mut action = LootClickerUiAction::None;
+
+    if !game_connected {
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
+        return LootClickerUiAction::None;
+    }
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.show_in_overlay, tr!("common.show_in_overlay"));
+        ui.checkbox(show_regions, tr!("common.show_regions"))
+            .on_hover_text(tr!("loot_clicker.show_regions_hint"));
+        if reset_to_defaults_button(ui, "loot_clicker_reset") {
+            action = LootClickerUiAction::ResetToDefaults;
+        }
+    });
+    ui.add_space(8.0);
+
+    // 1. Region Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("loot_clicker.loot_area"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        ui.label(
+            egui::RichText::new(tr!("loot_clicker.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("loot_clicker.region")).strong());
+
+            if let Some(region) = settings.region.as_mut() {
+                let (left, top, width, height) = *region;
+                rect_value(
+                    ui,
+                    "loot_clicker_region",
+                    egui::RichText::new(format!(
+                        "({:.3}, {:.3}, {:.3}x{:.3})",
+                        left, top, width, height
+                    ))
+                    .monospace()
+                    .strong(),
+                    region,
+                    client_size,
+                );
+            } else {
+                ui.label(
+                    egui::RichText::new(tr!("common.not_set"))
+                        .color(egui::Color32::YELLOW)
+                        .italics(),
+                );
+            }
+
+            ui.separator();
+
+            if is_calibrating {
+                if ui
+                    .button(
+                        egui::RichText::new(tr!("common.stop"))
+                            .color(egui::Color32::from_rgb(255, 100, 100)),
+                    )
+                    .clicked()
+                {
+                    action = LootClickerUiAction::CancelCalibration;
+                }
+                let label = if is_waiting_for_second_click {
+                    tr!("calibration.click_bottom_right")
+                } else {
+                    tr!("calibration.click_top_left")
+                };
+                ui.label(egui::RichText::new(label).color(egui::Color32::YELLOW));
+            } else if ui.button(tr!("image_clicker.set_region")).clicked() {
+                action = LootClickerUiAction::StartRegionCalibration;
+            }
+        });
+
+        if let Some(frame) = magnifier {
+            ui.add_space(4.0);
+            render_magnifier(ui, frame);
+        }
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 2. Detection Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("common.configuration"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        ui.label(egui::RichText::new(tr!("loot_clicker.colors")).strong());
+        if settings.colors.is_empty() {
+            ui.label(
+                egui::RichText::new(tr!("loot_clicker.no_colors"))
+                    .color(egui::Color32::YELLOW)
+                    .italics(),
+            );
+        }
+        let mut remove = None;
+        for (idx, loot) in settings.colors.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(format!("{}.", idx + 1)).monospace());
+                ui.color_edit_button_srgb(&mut loot.color)
+                    .on_hover_text(tr!("loot_clicker.color_hint"));
+                ui.label(tr!("collection_filler.color_tolerance"));
+                ui.add(egui::DragValue::new(&mut loot.tolerance));
+                if ui
+                    .small_button("✖")
+                    .on_hover_text(tr!("custom_macro.delete"))
+                    .clicked()
+                {
+                    remove = Some(idx);
+                }
+            });
+        }
+        if let Some(idx) = remove {
+            settings.colors.remove(idx);
+        }
+        if ui.button(tr!("loot_clicker.add_color")).clicked() {
+            settings.colors.push(LootColor {
+                color: [255, 255, 255],
+                tolerance: 20,
+            });
+        }
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("collection_filler.min_blob_size"));
+            ui.add(
+                egui::DragValue::new(&mut settings.min_blob_size)
+                    .clamp_range(MIN_BLOB_SIZE_RANGE.0..=MIN_BLOB_SIZE_RANGE.1)
+                    .suffix(" px"),
+            );
+            ui.label(tr!("loot_clicker.merge_distance"));
+            ui.add(
+                egui::DragValue::new(&mut settings.merge_distance_px)
+                    .clamp_range(LOOT_MERGE_RANGE_PX.0..=LOOT_MERGE_RANGE_PX.1)
+                    .suffix(" px"),
+            )
+            .on_hover_text(tr!("loot_clicker.merge_distance_hint"));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("loot_clicker.max_clicks"));
+            ui.add(
+                egui::DragValue::new(&mut settings.max_clicks_per_sweep)
+                    .clamp_range(LOOT_MAX_CLICKS_RANGE.0..=LOOT_MAX_CLICKS_RANGE.1),
+            );
+            ui.label(tr!("loot_clicker.dedupe_radius"));
+            ui.add(
+                egui::DragValue::new(&mut settings.dedupe_radius_px)
+                    .clamp_range(LOOT_DEDUPE_RANGE_PX.0..=LOOT_DEDUPE_RANGE_PX.1)
+                    .suffix(" px"),
+            )
+            .on_hover_text(tr!("loot_clicker.dedupe_radius_hint"));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("loot_clicker.click_delay"));
+            ui.add(
+                egui::DragValue::new(&mut settings.click_delay_ms)
+                    .clamp_range(LOOT_CLICK_DELAY_RANGE_MS.0..=LOOT_CLICK_DELAY_RANGE_MS.1)
+                    .speed(10)
+                    .suffix(" ms"),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut settings.repeat, tr!("loot_clicker.repeat"))
+                .on_hover_text(tr!("loot_clicker.repeat_hint"));
+            ui.add_enabled(
+                settings.repeat,
+                egui::DragValue::new(&mut settings.sweep_interval_ms)
+                    .clamp_range(LOOT_SWEEP_INTERVAL_RANGE_MS.0..=LOOT_SWEEP_INTERVAL_RANGE_MS.1)
+                    .speed(50)
+                    .suffix(" ms"),
+            );
+        });
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 3. Controls
+    ui.vertical_centered(|ui| {
+        let (btn_text, btn_color) = if is_running {
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
+        } else {
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
+        };
+
+        let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
+            .min_size(egui::vec2(200.0, 35.0));
+
+        if ui.add(button).clicked() {
+            action = if is_running {
+                LootClickerUiAction::Stop
+            } else {
+                LootClickerUiAction::Start
+            };
+        }
+    });
+
+    ui.add_space(12.0);
+    ui.separator();
+    ui.add_space(6.0);
+
+    // 4. Status
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
+
+    action
+}
@@ -0,0 +1,136 @@
+// Searchable macro list, shown instead of one tab per macro once there are many
+use crate::settings::MacroId;
+use eframe::egui;
+
+/// Up to this many macros get their own tabs; beyond it they move into the list
+pub const MACRO_TAB_LIMIT: usize = 6;
+
+const LIST_MAX_HEIGHT: f32 = 220.0;
+
+/// One row of the list
+pub struct MacroEntry<'a> {
+    pub id: MacroId,
+    pub macro_index: usize,
+    pub name: &'a str,
+    pub is_running: bool,
+    pub is_paused: bool,
+}
+
+pub enum MacroPickerAction {
+    None,
+    Select(usize),
+    Close,
+}
+
+/// Filter box plus the matching macros, most recently used first.
+/// Up/Down move the highlight, Enter selects it, Escape closes the list.
+pub fn render_macro_picker(
+    ui: &mut egui::Ui,
+    entries: &[MacroEntry],
+    recent: &[MacroId],
+    selected: Option<usize>,
+) -> MacroPickerAction {
+    let mut action = MacroPickerAction::None;
+    let filter_id = ui.id().with("macro_picker_filter");
+    let highlight_id = ui.id().with("macro_picker_highlight");
+    let mut filter = ui.data(|d| d.get_temp::<String>(filter_id).unwrap_or_default());
+    let mut highlighted = ui.data(|d| d.get_temp::<usize>(highlight_id).unwrap_or(0));
+
+    let filter_lower = filter.to_lowercase();
+    let mut matches: Vec<&MacroEntry> = entries
+        .iter()
+        .filter(|entry| entry.name.to_lowercase().contains(&filter_lower))
+        .collect();
+    // Recently used first (in order of use), the rest in tab order
+    matches.sort_by_key(|entry| {
+        (
+            recent
+                .iter()
+                .position(|&id| id == entry.id)
+                .unwrap_or(usize::MAX),
+            entry.macro_index,
+        )
+    });
+
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut filter)
+            .hint_text(tr!("macro_picker.filter"))
+            .desired_width(f32::INFINITY),
+    );
+    if !response.has_focus() && ui.memory(|m| m.focused().is_none()) {
+        response.request_focus();
+    }
+    if response.changed() {
+        highlighted = 0;
+    }
+
+    let down = ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown));
+    let up = ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp));
+    if down {
+        highlighted += 1;
+    }
+    if up {
+        highlighted = highlighted.saturating_sub(1);
+    }
+    highlighted = highlighted.min(matches.len().saturating_sub(1));
+
+    // Enter ends single-line editing, so the box has just lost focus when it is pressed
+    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+        if let Some(entry) = matches.get(highlighted) {
+            action = MacroPickerAction::Select(entry.macro_index);
+        }
+    }
+    if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+        action = MacroPickerAction::Close;
+    }
+
+    ui.add_space(4.0);
+    egui::ScrollArea::vertical()
+        .max_height(LIST_MAX_HEIGHT)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            if matches.is_empty() {
+                ui.label(
+                    egui::RichText::new(tr!("macro_picker.no_match")).color(egui::Color32::GRAY),
+                );
+            }
+            for (row, entry) in matches.iter().enumerate() {
+                let is_current = selected == Some(entry.macro_index);
+                let mut text = egui::RichText::new(entry.name);
+                if entry.is_paused {
+                    text = egui::RichText::new(format!("⏸ {}", entry.name))
+                        .color(egui::Color32::from_rgb(255, 165, 0));
+                } else if entry.is_running {
+                    text = text.color(egui::Color32::GREEN);
+                }
+                if is_current {
+                    text = text.strong();
+                }
+                let response = ui.add_sized(
+                    [ui.available_width(), 22.0],
+                    egui::SelectableLabel::new(row == highlighted, text),
+                );
+                if row == highlighted && (up || down) {
+                    response.scroll_to_me(None);
+                }
+                if response.clicked() {
+                    action = MacroPickerAction::Select(entry.macro_index);
+                }
+            }
+        });
+
+    if matches!(action, MacroPickerAction::None) {
+        ui.data_mut(|d| {
+            d.insert_temp(filter_id, filter);
+            d.insert_temp(highlight_id, highlighted);
+        });
+    } else {
+        // Start fresh the next time the list opens
+        ui.data_mut(|d| {
+            d.remove::<String>(filter_id);
+            d.remove::<usize>(highlight_id);
+        });
+    }
+
+    action
+}
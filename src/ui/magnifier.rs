@@ -0,0 +1,97 @@
+use crate::calibration::MagnifierFrame;
+use eframe::egui;
+
+const ZOOM: f32 = 6.0;
+
+/// Render the calibration magnifier: zoomed capture, crosshair on the exact pixel, client coords
+pub fn render_magnifier(ui: &mut egui::Ui, frame: &MagnifierFrame) {
+    let (width, height) = frame.image.dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(
+        [width as usize, height as usize],
+        frame.image.as_raw(),
+    );
+
+    // Reuse one texture for the whole session instead of allocating per frame
+    let texture_id = egui::Id::new("calibration_magnifier");
+    let texture = match ui.data(|d| d.get_temp::<egui::TextureHandle>(texture_id)) {
+        Some(mut texture) => {
+            texture.set(color_image, egui::TextureOptions::NEAREST);
+            texture
+        }
+        None => {
+            let texture = ui.ctx().load_texture(
+                "calibration_magnifier",
+                color_image,
+                egui::TextureOptions::NEAREST,
+            );
+            ui.data_mut(|d| d.insert_temp(texture_id, texture.clone()));
+            texture
+        }
+    };
+
+    ui.vertical(|ui| {
+        let size = egui::vec2(width as f32 * ZOOM, height as f32 * ZOOM);
+        let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+
+        painter.image(
+            texture.id(),
+            rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+
+        // Crosshair arms stop at the marked pixel so its color stays visible
+        let cell = egui::Rect::from_min_size(
+            rect.min + egui::vec2(frame.marker.0 as f32 * ZOOM, frame.marker.1 as f32 * ZOOM),
+            egui::vec2(ZOOM, ZOOM),
+        );
+        let stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 60, 60));
+        let center = cell.center();
+        painter.line_segment(
+            [
+                egui::pos2(rect.left(), center.y),
+                egui::pos2(cell.left(), center.y),
+            ],
+            stroke,
+        );
+        painter.line_segment(
+            [
+                egui::pos2(cell.right(), center.y),
+                egui::pos2(rect.right(), center.y),
+            ],
+            stroke,
+        );
+        painter.line_segment(
+            [
+                egui::pos2(center.x, rect.top()),
+                egui::pos2(center.x, cell.top()),
+            ],
+            stroke,
+        );
+        painter.line_segment(
+            [
+                egui::pos2(center.x, cell.bottom()),
+                egui::pos2(center.x, rect.bottom()),
+            ],
+            stroke,
+        );
+        painter.rect_stroke(cell, 0.0, stroke);
+        painter.rect_stroke(
+            rect,
+            0.0,
+            egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 60, 60)),
+        );
+
+        ui.label(
+            egui::RichText::new(format!("({}, {})", frame.center.0, frame.center.1))
+                .monospace()
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+    });
+}
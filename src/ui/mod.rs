@@ -1,8 +1,32 @@
 // UI module - all egui rendering logic
 pub mod collection_filler;
 pub mod image_clicker;
+pub mod anti_afk;
+pub mod pixel_trigger;
+pub mod rebuffer;
+pub mod rotation;
+pub mod auto_relog;
+pub mod loot_clicker;
+pub mod stat_logger;
 pub mod app_header;
 pub mod custom_macro;
+pub mod coord_edit;
 pub mod help;
+pub mod hotkeys;
+pub mod image_path_field;
+pub mod input_actions;
+pub mod macro_picker;
+pub mod statistics;
+pub mod sounds;
 pub mod status;
 pub mod log_panel;
+pub mod ocr_models;
+pub mod frame_recorder;
+pub mod remote_control;
+pub mod schedule;
+pub mod probe;
+pub mod overlay;
+pub mod running_panel;
+pub mod color_swatch;
+pub mod reset_button;
+pub mod magnifier;
@@ -0,0 +1,78 @@
+use crate::settings::OcrModelSettings;
+use eframe::egui;
+
+/// Collapsible "OCR models" section: a path field with Browse... per model, a Test load
+/// button and the result of the last test. Returns true when Test load was clicked.
+pub fn render_ocr_models_section(
+    ui: &mut egui::Ui,
+    ocr_models: &mut OcrModelSettings,
+    test_report: &[(bool, String)],
+) -> bool {
+    let mut test = false;
+
+    egui::CollapsingHeader::new(
+        egui::RichText::new(tr!("ocr_models.title")).color(egui::Color32::from_rgb(180, 180, 180)),
+    )
+    .id_source("ocr_models_section")
+    .show(ui, |ui| {
+        ui.label(
+            egui::RichText::new(tr!("ocr_models.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+
+        egui::Grid::new("ocr_models_grid")
+            .num_columns(3)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                model_path_row(
+                    ui,
+                    tr!("ocr_models.detection"),
+                    &mut ocr_models.detection_model_path,
+                );
+                model_path_row(
+                    ui,
+                    tr!("ocr_models.recognition"),
+                    &mut ocr_models.recognition_model_path,
+                );
+            });
+
+        if ui
+            .button(tr!("ocr_models.test_load"))
+            .on_hover_text(tr!("ocr_models.test_load_hint"))
+            .clicked()
+        {
+            test = true;
+        }
+
+        for (ok, line) in test_report {
+            let color = if *ok {
+                egui::Color32::from_rgb(140, 200, 140)
+            } else {
+                egui::Color32::from_rgb(200, 120, 120)
+            };
+            ui.label(egui::RichText::new(line).color(color));
+        }
+    });
+
+    test
+}
+
+fn model_path_row(ui: &mut egui::Ui, label: &str, path: &mut String) {
+    ui.label(label);
+    ui.add(
+        egui::TextEdit::singleline(path)
+            .hint_text(tr!("ocr_models.built_in"))
+            .desired_width(260.0),
+    );
+    if ui.button(tr!("image_path.browse")).clicked() {
+        if let Some(picked) = rfd::FileDialog::new()
+            .add_filter(tr!("ocr_models.filter"), &["rten"])
+            .set_title(label)
+            .pick_file()
+        {
+            *path = picked.display().to_string();
+        }
+    }
+    ui.end_row();
+}
@@ -0,0 +1,243 @@
+// Compact always-on-top toolbar shown in overlay mode
+use crate::settings::{OverlayAnchor, OverlayPlacement};
+use crate::ui::running_panel::{format_elapsed, RunningTool};
+use crate::ui::status::is_error_status;
+use eframe::egui;
+
+// Square tool buttons, followed by the narrower pin and settings buttons
+const BUTTON_SIZE: f32 = 36.0;
+const PIN_BUTTON_WIDTH: f32 = 20.0;
+const SETTINGS_BUTTON_WIDTH: f32 = 24.0;
+// Status line under the buttons, and each compact "running" row below it
+const STATUS_ROW_HEIGHT: f32 = 18.0;
+const RUNNING_ROW_HEIGHT: f32 = 18.0;
+
+/// One toolbar button: a tool or macro with "Show in overlay" enabled
+pub struct OverlayButton<'a> {
+    pub tool_index: usize,
+    pub name: &'a str,
+    pub is_running: bool,
+    pub is_paused: bool,
+    pub is_calibrating: bool,
+}
+
+/// What the status line shows: the running tool, or the selected one when idle
+pub struct OverlayStatus<'a> {
+    pub name: &'a str,
+    pub text: String,
+    pub is_running: bool,
+}
+
+pub enum OverlayAction {
+    None,
+    ToggleTool(usize),
+    StopTool(usize),
+    // Snap to the next anchor (or back to auto-snap after a drag)
+    CyclePlacement,
+    ExitOverlay,
+}
+
+/// Inner size of the toolbar for the given number of tool buttons and running rows
+pub fn overlay_size(button_count: usize, running_rows: usize) -> egui::Vec2 {
+    egui::vec2(
+        button_count as f32 * BUTTON_SIZE + PIN_BUTTON_WIDTH + SETTINGS_BUTTON_WIDTH,
+        BUTTON_SIZE + STATUS_ROW_HEIGHT + running_rows as f32 * RUNNING_ROW_HEIGHT,
+    )
+}
+
+/// Button text: initials of a multi-word name ("Image Clicker" -> "IC", "Macro 3" -> "M3"),
+/// otherwise its first two characters
+pub fn short_label(name: &str) -> String {
+    let words: Vec<&str> = name.split_whitespace().collect();
+    if words.len() > 1 {
+        words
+            .iter()
+            .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+            .take(3)
+            .collect::<String>()
+            .to_uppercase()
+    } else {
+        name.chars().take(2).collect()
+    }
+}
+
+pub fn render_overlay_toolbar(
+    ui: &mut egui::Ui,
+    buttons: &[OverlayButton],
+    status: &OverlayStatus,
+    running: &[RunningTool],
+    placement: OverlayPlacement,
+) -> OverlayAction {
+    let mut action = OverlayAction::None;
+    ui.spacing_mut().item_spacing = egui::vec2(0.0, 0.0);
+
+    ui.horizontal(|ui| {
+        ui.style_mut().spacing.item_spacing = egui::vec2(0.0, 0.0);
+
+        for button in buttons {
+            let text_color = if button.is_calibrating {
+                egui::Color32::YELLOW
+            } else if button.is_paused {
+                egui::Color32::from_rgb(255, 165, 0)
+            } else if button.is_running {
+                egui::Color32::GREEN
+            } else {
+                egui::Color32::WHITE
+            };
+            let btn = egui::Button::new(
+                egui::RichText::new(short_label(button.name))
+                    .size(16.0)
+                    .strong()
+                    .color(text_color),
+            )
+            .min_size(egui::vec2(BUTTON_SIZE, BUTTON_SIZE))
+            .stroke(egui::Stroke::new(
+                1.0,
+                if button.is_calibrating {
+                    egui::Color32::YELLOW
+                } else {
+                    egui::Color32::from_rgb(60, 60, 60)
+                },
+            ));
+
+            let state = if button.is_calibrating {
+                tr!("overlay.calibrating")
+            } else if button.is_paused {
+                tr!("overlay.paused")
+            } else if button.is_running {
+                tr!("overlay.running")
+            } else {
+                tr!("overlay.idle")
+            };
+            let response = ui
+                .add(btn)
+                .on_hover_text(format!("{}\n{}", button.name, state));
+            if response.clicked() {
+                action = OverlayAction::ToggleTool(button.tool_index);
+            }
+        }
+
+        // Pin button: auto-snap anchor, or manual placement after a drag
+        let (pin_text, pin_hover) = match placement {
+            OverlayPlacement::AutoSnap(anchor) => (
+                "📌",
+                tr!(
+                    "overlay.pin_snapped",
+                    anchor = anchor.label(),
+                    next = anchor.next().label(),
+                ),
+            ),
+            OverlayPlacement::Manual(..) => (
+                "✋",
+                tr!(
+                    "overlay.pin_manual",
+                    anchor = OverlayAnchor::TopCenter.label(),
+                ),
+            ),
+        };
+        let btn = egui::Button::new(
+            egui::RichText::new(pin_text)
+                .size(11.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+        )
+        .min_size(egui::vec2(PIN_BUTTON_WIDTH, BUTTON_SIZE))
+        .fill(egui::Color32::from_rgba_premultiplied(40, 40, 40, 180))
+        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 60, 60)));
+
+        if ui.add(btn).on_hover_text(pin_hover).clicked() {
+            action = OverlayAction::CyclePlacement;
+        }
+
+        // Settings button: back to the full window
+        let btn = egui::Button::new(
+            egui::RichText::new("⚙")
+                .size(12.0)
+                .color(egui::Color32::from_rgb(150, 150, 150)),
+        )
+        .min_size(egui::vec2(SETTINGS_BUTTON_WIDTH, BUTTON_SIZE))
+        .fill(egui::Color32::from_rgba_premultiplied(40, 40, 40, 180))
+        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 60, 60)));
+
+        if ui.add(btn).clicked() {
+            action = OverlayAction::ExitOverlay;
+        }
+    });
+
+    render_status_row(ui, status);
+    for tool in running {
+        if render_running_row(ui, tool) {
+            action = OverlayAction::StopTool(tool.tool_index);
+        }
+    }
+
+    action
+}
+
+/// Compact "what's running" row: name and elapsed time, with a stop button; true when clicked
+fn render_running_row(ui: &mut egui::Ui, tool: &RunningTool) -> bool {
+    let mut stop = false;
+    ui.horizontal(|ui| {
+        ui.set_height(RUNNING_ROW_HEIGHT);
+        let btn = egui::Button::new(
+            egui::RichText::new("⏹")
+                .size(10.0)
+                .color(egui::Color32::from_rgb(255, 100, 100)),
+        )
+        .min_size(egui::vec2(RUNNING_ROW_HEIGHT, RUNNING_ROW_HEIGHT))
+        .fill(egui::Color32::from_rgb(25, 25, 25));
+        if ui
+            .add(btn)
+            .on_hover_text(tr!("overlay.stop_tool", name = tool.name))
+            .clicked()
+        {
+            stop = true;
+        }
+        let color = if tool.is_paused {
+            egui::Color32::from_rgb(255, 165, 0)
+        } else {
+            egui::Color32::from_rgb(100, 255, 100)
+        };
+        let text = format!(" {} {}", format_elapsed(tool.elapsed), tool.name);
+        ui.add(
+            egui::Label::new(egui::RichText::new(&text).size(11.0).color(color))
+                .truncate(true)
+                .selectable(false),
+        )
+        .on_hover_text(&tool.status);
+    });
+    stop
+}
+
+/// One truncated line ("Name: status", full text on hover); errors flash the row red
+fn render_status_row(ui: &mut egui::Ui, status: &OverlayStatus) {
+    let is_error = is_error_status(&status.text);
+    let flash_on = is_error && (ui.input(|i| i.time) * 2.0) as i64 % 2 == 0;
+    let fill = if flash_on {
+        egui::Color32::from_rgb(120, 30, 30)
+    } else {
+        egui::Color32::from_rgb(25, 25, 25)
+    };
+    let text_color = if is_error {
+        egui::Color32::from_rgb(255, 100, 100)
+    } else if status.is_running {
+        egui::Color32::from_rgb(100, 255, 100)
+    } else {
+        egui::Color32::GRAY
+    };
+    let full = format!("{}: {}", status.name, status.text);
+
+    egui::Frame::none()
+        .fill(fill)
+        .inner_margin(egui::Margin::symmetric(4.0, 1.0))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.set_height(STATUS_ROW_HEIGHT - 2.0);
+            // Not selectable, so dragging the row still moves the overlay
+            ui.add(
+                egui::Label::new(egui::RichText::new(&full).size(11.0).color(text_color))
+                    .truncate(true)
+                    .selectable(false),
+            )
+            .on_hover_text(&full);
+        });
+}
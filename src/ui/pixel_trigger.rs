@@ -0,0 +1,252 @@
+use crate::calibration::MagnifierFrame;
+use crate::core::worker::WorkerStats;
+use crate::settings::{
+    NormPoint, PixelTriggerResponse, PixelTriggerSettings, PIXEL_COOLDOWN_RANGE_MS,
+    PIXEL_POLL_RANGE_MS,
+};
+use crate::ui::anti_afk::render_key_row;
+use crate::ui::color_swatch::color_swatch;
+use crate::ui::coord_edit::point_value;
+use crate::ui::magnifier::render_magnifier;
+use crate::ui::reset_button::reset_to_defaults_button;
+use eframe::egui;
+
+/// Spot being calibrated on the game window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelTriggerTarget {
+    // Watched pixel, sampled together with its color
+    Pixel,
+    ClickPosition,
+}
+
+#[derive(Debug)]
+pub enum PixelTriggerUiAction {
+    StartCalibration(PixelTriggerTarget),
+    CancelCalibration,
+    ResetToDefaults,
+    Start,
+    Stop,
+    None,
+}
+
+/// Render Pixel Trigger UI
+pub fn render_ui(
+    ui: &mut egui::Ui,
+    settings: &mut PixelTriggerSettings,
+    show_regions: &mut bool,
+    calibrating: Option<PixelTriggerTarget>,
+    magnifier: Option<&MagnifierFrame>,
+    is_running: bool,
+    status: &str,
+    stats: &WorkerStats,
+    game_connected: bool,
+    client_size: Option<(i32, i32)>,
+    hotkey_error: Option<&str>,
+) -> PixelTriggerUiAction {
+    let mut action = PixelTriggerUiAction::None;
+
+    if !game_connected {
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
+        return PixelTriggerUiAction::None;
+    }
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.show_in_overlay, tr!("common.show_in_overlay"));
+        ui.checkbox(show_regions, tr!("common.show_regions"))
+            .on_hover_text(tr!("pixel_trigger.show_regions_hint"));
+        if reset_to_defaults_button(ui, "pixel_trigger_reset") {
+            action = PixelTriggerUiAction::ResetToDefaults;
+        }
+    });
+    ui.add_space(8.0);
+
+    // 1. Settings Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("common.configuration"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        ui.label(
+            egui::RichText::new(tr!("pixel_trigger.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        ui.add_space(4.0);
+
+        let expected_color = settings.expected_color;
+        if let Some(clicked) = render_point_row(
+            ui,
+            PixelTriggerTarget::Pixel,
+            tr!("pixel_trigger.pixel"),
+            tr!("pixel_trigger.pixel_hint"),
+            &mut settings.pixel,
+            Some(expected_color),
+            calibrating,
+            client_size,
+        ) {
+            action = clicked;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("pixel_trigger.tolerance")).strong());
+            ui.add(egui::DragValue::new(&mut settings.tolerance).clamp_range(0..=255))
+                .on_hover_text(tr!("pixel_trigger.tolerance_hint"));
+        });
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("pixel_trigger.response")).strong());
+            for response in PixelTriggerResponse::ALL {
+                ui.radio_value(&mut settings.response, response, response.label());
+            }
+        });
+
+        match settings.response {
+            PixelTriggerResponse::Key => render_key_row(
+                ui,
+                "pixel_trigger_key_capture",
+                &mut settings.key,
+                tr!("pixel_trigger.key_hint"),
+            ),
+            PixelTriggerResponse::Click => {
+                if let Some(clicked) = render_point_row(
+                    ui,
+                    PixelTriggerTarget::ClickPosition,
+                    tr!("pixel_trigger.click_position"),
+                    tr!("pixel_trigger.click_position_hint"),
+                    &mut settings.click_position,
+                    None,
+                    calibrating,
+                    client_size,
+                ) {
+                    action = clicked;
+                }
+            }
+        }
+
+        if let Some(frame) = magnifier {
+            ui.add_space(4.0);
+            render_magnifier(ui, frame);
+        }
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("pixel_trigger.cooldown")).strong());
+            ui.add(
+                egui::DragValue::new(&mut settings.cooldown_ms)
+                    .clamp_range(PIXEL_COOLDOWN_RANGE_MS.0..=PIXEL_COOLDOWN_RANGE_MS.1)
+                    .speed(50)
+                    .suffix(" ms"),
+            )
+            .on_hover_text(tr!("pixel_trigger.cooldown_hint"));
+            ui.separator();
+            ui.label(egui::RichText::new(tr!("pixel_trigger.poll_interval")).strong());
+            ui.add(
+                egui::DragValue::new(&mut settings.poll_interval_ms)
+                    .clamp_range(PIXEL_POLL_RANGE_MS.0..=PIXEL_POLL_RANGE_MS.1)
+                    .speed(10)
+                    .suffix(" ms"),
+            );
+        });
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 2. Controls
+    ui.vertical_centered(|ui| {
+        let (btn_text, btn_color) = if is_running {
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
+        } else {
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
+        };
+
+        let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
+            .min_size(egui::vec2(200.0, 35.0));
+
+        if ui.add(button).clicked() {
+            action = if is_running {
+                PixelTriggerUiAction::Stop
+            } else {
+                PixelTriggerUiAction::Start
+            };
+        }
+    });
+
+    ui.add_space(12.0);
+    ui.separator();
+    ui.add_space(6.0);
+
+    // 3. Status
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
+
+    action
+}
+
+/// Calibrated point with its Set button (Stop while this point is being calibrated)
+fn render_point_row(
+    ui: &mut egui::Ui,
+    target: PixelTriggerTarget,
+    label: &str,
+    hint: &str,
+    point: &mut Option<NormPoint>,
+    color: Option<[u8; 3]>,
+    calibrating: Option<PixelTriggerTarget>,
+    client_size: Option<(i32, i32)>,
+) -> Option<PixelTriggerUiAction> {
+    let mut action = None;
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(label).strong());
+
+        if let Some(point) = point.as_mut() {
+            let (x, y) = *point;
+            point_value(
+                ui,
+                match target {
+                    PixelTriggerTarget::Pixel => "pixel_trigger_pixel",
+                    PixelTriggerTarget::ClickPosition => "pixel_trigger_click",
+                },
+                egui::RichText::new(format!("({:.3}, {:.3})", x, y))
+                    .monospace()
+                    .strong(),
+                point,
+                client_size,
+            );
+            if let Some([r, g, b]) = color {
+                color_swatch(ui, (r, g, b));
+            }
+        } else {
+            ui.label(
+                egui::RichText::new(tr!("common.not_set"))
+                    .color(egui::Color32::YELLOW)
+                    .italics(),
+            );
+        }
+
+        ui.separator();
+
+        if calibrating == Some(target) {
+            if ui
+                .button(
+                    egui::RichText::new(tr!("common.stop"))
+                        .color(egui::Color32::from_rgb(255, 100, 100)),
+                )
+                .clicked()
+            {
+                action = Some(PixelTriggerUiAction::CancelCalibration);
+            }
+            ui.label(
+                egui::RichText::new(tr!("calibration.click_button")).color(egui::Color32::YELLOW),
+            );
+        } else if ui.button(tr!("common.set")).on_hover_text(hint).clicked() {
+            action = Some(PixelTriggerUiAction::StartCalibration(target));
+        }
+    });
+    action
+}
@@ -0,0 +1,83 @@
+use crate::core::coords::normalize_point;
+use crate::core::window::{get_cursor_pos, get_pixel_color, screen_to_window_coords};
+use crate::ui::color_swatch::color_swatch;
+use eframe::egui;
+use std::time::Duration;
+use windows::Win32::Foundation::HWND;
+
+// Readout refresh while the section is open (collapsed, nothing polls or repaints)
+const PROBE_REFRESH: Duration = Duration::from_millis(100);
+
+/// Collapsible "Probe" section: where the cursor is on screen, in the game client and
+/// normalized, and the color under it
+pub fn render_probe_section(ui: &mut egui::Ui, game_hwnd: Option<HWND>) {
+    egui::CollapsingHeader::new(
+        egui::RichText::new(tr!("probe.title")).color(egui::Color32::from_rgb(180, 180, 180)),
+    )
+    .id_source("probe_section")
+    .show(ui, |ui| {
+        ui.ctx().request_repaint_after(PROBE_REFRESH);
+
+        let Some((screen_x, screen_y)) = get_cursor_pos() else {
+            ui.label(tr!("probe.no_cursor"));
+            return;
+        };
+        egui::Grid::new("probe_grid")
+            .num_columns(2)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(tr!("probe.screen"));
+                ui.monospace(format!("({}, {})", screen_x, screen_y));
+                ui.end_row();
+
+                let client =
+                    game_hwnd.and_then(|hwnd| screen_to_window_coords(hwnd, screen_x, screen_y));
+                ui.label(tr!("probe.client"));
+                match client {
+                    Some((x, y)) => {
+                        ui.horizontal(|ui| {
+                            ui.monospace(format!("({}, {})", x, y));
+                            if ui
+                                .small_button(tr!("probe.copy"))
+                                .on_hover_text(tr!("probe.copy_hint"))
+                                .clicked()
+                            {
+                                ui.output_mut(|o| o.copied_text = format!("({}, {})", x, y));
+                            }
+                        });
+                    }
+                    None => {
+                        ui.label(
+                            egui::RichText::new(tr!("probe.not_connected"))
+                                .color(egui::Color32::GRAY),
+                        );
+                    }
+                }
+                ui.end_row();
+
+                ui.label(tr!("probe.normalized"));
+                match game_hwnd
+                    .zip(client)
+                    .and_then(|(hwnd, (x, y))| normalize_point(hwnd, x, y))
+                {
+                    Some((x, y)) => ui.monospace(format!("({:.4}, {:.4})", x, y)),
+                    None => ui.label(egui::RichText::new("-").color(egui::Color32::GRAY)),
+                };
+                ui.end_row();
+
+                ui.label(tr!("probe.color"));
+                match get_pixel_color(screen_x, screen_y) {
+                    Some(color) => {
+                        ui.horizontal(|ui| {
+                            color_swatch(ui, color);
+                            ui.monospace(format!("RGB({}, {}, {})", color.0, color.1, color.2));
+                        });
+                    }
+                    None => {
+                        ui.label(egui::RichText::new("-").color(egui::Color32::GRAY));
+                    }
+                }
+                ui.end_row();
+            });
+    });
+}
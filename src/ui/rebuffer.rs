@@ -0,0 +1,133 @@
+use crate::calibration::MagnifierFrame;
+use crate::core::worker::WorkerStats;
+use crate::settings::{RebufferSettings, REBUFFER_INTERVAL_RANGE_MIN};
+use crate::ui::input_actions::{render_input_actions, InputActionsUiAction};
+use crate::ui::reset_button::reset_to_defaults_button;
+use eframe::egui;
+
+#[derive(Debug)]
+pub enum RebufferUiAction {
+    StartCalibration(usize), // Click action index
+    CancelCalibration,
+    ResetToDefaults,
+    Start,
+    Stop,
+    None,
+}
+
+/// Render Rebuffer UI
+pub fn render_ui(
+    ui: &mut egui::Ui,
+    settings: &mut RebufferSettings,
+    show_regions: &mut bool,
+    calibrating_action: Option<usize>,
+    magnifier: Option<&MagnifierFrame>,
+    is_running: bool,
+    status: &str,
+    stats: &WorkerStats,
+    game_connected: bool,
+    client_size: Option<(i32, i32)>,
+    hotkey_error: Option<&str>,
+) -> RebufferUiAction {
+    let mut action = RebufferUiAction::None;
+
+    if !game_connected {
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
+        return RebufferUiAction::None;
+    }
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.show_in_overlay, tr!("common.show_in_overlay"));
+        ui.checkbox(show_regions, tr!("common.show_regions"))
+            .on_hover_text(tr!("rebuffer.show_regions_hint"));
+        if reset_to_defaults_button(ui, "rebuffer_reset") {
+            action = RebufferUiAction::ResetToDefaults;
+        }
+    });
+    ui.add_space(8.0);
+
+    // 1. Settings Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("common.configuration"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        ui.label(
+            egui::RichText::new(tr!("rebuffer.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("rebuffer.interval")).strong());
+            ui.add(
+                egui::DragValue::new(&mut settings.interval_minutes)
+                    .clamp_range(REBUFFER_INTERVAL_RANGE_MIN.0..=REBUFFER_INTERVAL_RANGE_MIN.1)
+                    .suffix(" min"),
+            );
+            ui.checkbox(
+                &mut settings.run_immediately_on_start,
+                tr!("rebuffer.run_immediately"),
+            )
+            .on_hover_text(tr!("rebuffer.run_immediately_hint"));
+        });
+
+        ui.add_space(4.0);
+
+        match render_input_actions(
+            ui,
+            "rebuffer",
+            &mut settings.actions,
+            tr!("rebuffer.no_actions"),
+            tr!("rebuffer.key_hint"),
+            calibrating_action,
+            magnifier,
+            client_size,
+        ) {
+            Some(InputActionsUiAction::StartCalibration(idx)) => {
+                action = RebufferUiAction::StartCalibration(idx);
+            }
+            Some(InputActionsUiAction::CancelCalibration) => {
+                action = RebufferUiAction::CancelCalibration;
+            }
+            None => {}
+        }
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 2. Controls
+    ui.vertical_centered(|ui| {
+        let (btn_text, btn_color) = if is_running {
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
+        } else {
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
+        };
+
+        let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
+            .min_size(egui::vec2(200.0, 35.0));
+
+        if ui.add(button).clicked() {
+            action = if is_running {
+                RebufferUiAction::Stop
+            } else {
+                RebufferUiAction::Start
+            };
+        }
+    });
+
+    ui.add_space(12.0);
+    ui.separator();
+    ui.add_space(6.0);
+
+    // 3. Status
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
+
+    action
+}
@@ -0,0 +1,81 @@
+use crate::core::remote_control::new_token;
+use crate::settings::{RemoteControlSettings, REMOTE_PORT_RANGE};
+use eframe::egui;
+
+/// Collapsible "Remote control" section: on/off switch, port and token, the example URL
+/// and whether the listener is up
+pub fn render_remote_control_section(
+    ui: &mut egui::Ui,
+    settings: &mut RemoteControlSettings,
+    listening: bool,
+    error: Option<&str>,
+) {
+    egui::CollapsingHeader::new(
+        egui::RichText::new(tr!("remote_control.title"))
+            .color(egui::Color32::from_rgb(180, 180, 180)),
+    )
+    .id_source("remote_control_section")
+    .show(ui, |ui| {
+        ui.label(
+            egui::RichText::new(tr!("remote_control.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut settings.enabled, tr!("remote_control.enabled"));
+            ui.label(tr!("remote_control.port"));
+            ui.add(
+                egui::DragValue::new(&mut settings.port)
+                    .clamp_range(REMOTE_PORT_RANGE.0..=REMOTE_PORT_RANGE.1),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("remote_control.token"));
+            ui.add(
+                egui::TextEdit::singleline(&mut settings.token)
+                    .password(true)
+                    .desired_width(200.0),
+            );
+            if ui
+                .button(tr!("remote_control.new_token"))
+                .on_hover_text(tr!("remote_control.new_token_hint"))
+                .clicked()
+            {
+                if let Some(token) = new_token() {
+                    settings.token = token;
+                }
+            }
+        });
+
+        let url = format!(
+            "http://127.0.0.1:{}/status?token={}",
+            settings.port,
+            settings.token.trim()
+        );
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(&url).monospace().small());
+            if ui.small_button(tr!("remote_control.copy_url")).clicked() {
+                ui.output_mut(|o| o.copied_text = url.clone());
+            }
+        });
+
+        let (text, color) = if let Some(error) = error {
+            (error.to_string(), egui::Color32::from_rgb(230, 100, 100))
+        } else if listening {
+            (
+                tr!("remote_control.listening").to_string(),
+                egui::Color32::from_rgb(100, 200, 100),
+            )
+        } else if settings.enabled && settings.token.trim().is_empty() {
+            (
+                tr!("remote_control.needs_token").to_string(),
+                egui::Color32::YELLOW,
+            )
+        } else {
+            (tr!("remote_control.off").to_string(), egui::Color32::GRAY)
+        };
+        ui.label(egui::RichText::new(text).small().color(color));
+    });
+}
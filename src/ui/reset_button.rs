@@ -0,0 +1,23 @@
+use eframe::egui;
+
+/// Two-step "Reset to defaults" button (the second click confirms)
+/// Returns true once the reset is confirmed
+pub fn reset_to_defaults_button(ui: &mut egui::Ui, id_source: impl std::hash::Hash) -> bool {
+    let confirm_id = ui.make_persistent_id(id_source);
+    let confirming = ui.data(|d| d.get_temp::<bool>(confirm_id).unwrap_or(false));
+    let text = if confirming {
+        tr!("reset.confirm")
+    } else {
+        tr!("reset.button")
+    };
+
+    let response = ui.button(text).on_hover_text(tr!("reset.hint"));
+    if response.clicked() {
+        ui.data_mut(|d| d.insert_temp(confirm_id, !confirming));
+        return confirming;
+    }
+    if confirming && response.clicked_elsewhere() {
+        ui.data_mut(|d| d.insert_temp(confirm_id, false));
+    }
+    false
+}
@@ -0,0 +1,187 @@
+use crate::core::hotkey::hotkey_key_label;
+use crate::core::worker::WorkerStats;
+use crate::settings::{
+    RotationEntry, RotationSettings, ROTATION_COOLDOWN_RANGE_MS, ROTATION_GCD_RANGE_MS,
+    ROTATION_PRIORITY_RANGE,
+};
+use crate::ui::anti_afk::render_key_row;
+use crate::ui::reset_button::reset_to_defaults_button;
+use eframe::egui;
+
+#[derive(Debug)]
+pub enum RotationUiAction {
+    ResetToDefaults,
+    Start,
+    Stop,
+    None,
+}
+
+/// Render Skill Rotation UI. `cooldowns` (share of each key's cooldown left) is set
+/// while the rotation runs; the list is shown as cooldown bars then instead of editors.
+pub fn render_ui(
+    ui: &mut egui::Ui,
+    settings: &mut RotationSettings,
+    cooldowns: Option<&[f32]>,
+    is_running: bool,
+    status: &str,
+    stats: &WorkerStats,
+    game_connected: bool,
+    hotkey_error: Option<&str>,
+) -> RotationUiAction {
+    let mut action = RotationUiAction::None;
+
+    if !game_connected {
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
+        return RotationUiAction::None;
+    }
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.show_in_overlay, tr!("common.show_in_overlay"));
+        if reset_to_defaults_button(ui, "rotation_reset") {
+            action = RotationUiAction::ResetToDefaults;
+        }
+    });
+    ui.add_space(8.0);
+
+    // 1. Settings Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("common.configuration"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        ui.label(
+            egui::RichText::new(tr!("rotation.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        ui.add_space(4.0);
+
+        if let Some(cooldowns) = cooldowns {
+            render_cooldown_bars(ui, &settings.entries, cooldowns);
+        } else {
+            render_entry_list(ui, settings);
+        }
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 2. Controls
+    ui.vertical_centered(|ui| {
+        let (btn_text, btn_color) = if is_running {
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
+        } else {
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
+        };
+
+        let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
+            .min_size(egui::vec2(200.0, 35.0));
+
+        if ui.add(button).clicked() {
+            action = if is_running {
+                RotationUiAction::Stop
+            } else {
+                RotationUiAction::Start
+            };
+        }
+    });
+
+    ui.add_space(12.0);
+    ui.separator();
+    ui.add_space(6.0);
+
+    // 3. Status
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
+
+    action
+}
+
+/// Editable key list with the global cooldown
+fn render_entry_list(ui: &mut egui::Ui, settings: &mut RotationSettings) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("rotation.gcd")).strong());
+        ui.add(
+            egui::DragValue::new(&mut settings.global_gcd_ms)
+                .clamp_range(ROTATION_GCD_RANGE_MS.0..=ROTATION_GCD_RANGE_MS.1)
+                .speed(10)
+                .suffix(" ms"),
+        )
+        .on_hover_text(tr!("rotation.gcd_hint"));
+    });
+
+    ui.add_space(4.0);
+
+    if settings.entries.is_empty() {
+        ui.label(
+            egui::RichText::new(tr!("rotation.no_entries"))
+                .color(egui::Color32::YELLOW)
+                .italics(),
+        );
+    }
+
+    let mut remove = None;
+    for (idx, entry) in settings.entries.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format!("{}.", idx + 1)).monospace());
+            render_key_row(
+                ui,
+                &format!("rotation_key_capture_{}", idx),
+                &mut entry.key,
+                tr!("rotation.key_hint"),
+            );
+            ui.label(tr!("rotation.cooldown"));
+            ui.add(
+                egui::DragValue::new(&mut entry.cooldown_ms)
+                    .clamp_range(ROTATION_COOLDOWN_RANGE_MS.0..=ROTATION_COOLDOWN_RANGE_MS.1)
+                    .speed(50)
+                    .suffix(" ms"),
+            );
+            ui.label(tr!("rotation.priority"));
+            ui.add(
+                egui::DragValue::new(&mut entry.priority)
+                    .clamp_range(ROTATION_PRIORITY_RANGE.0..=ROTATION_PRIORITY_RANGE.1),
+            )
+            .on_hover_text(tr!("rotation.priority_hint"));
+            if ui
+                .small_button("✖")
+                .on_hover_text(tr!("custom_macro.delete"))
+                .clicked()
+            {
+                remove = Some(idx);
+            }
+        });
+    }
+    if let Some(idx) = remove {
+        settings.entries.remove(idx);
+    }
+
+    if ui.button(tr!("rotation.add_key")).clicked() {
+        settings.entries.push(RotationEntry::default());
+    }
+}
+
+/// One bar per key: full right after its press, empty once it is ready again
+fn render_cooldown_bars(ui: &mut egui::Ui, entries: &[RotationEntry], cooldowns: &[f32]) {
+    for (entry, left) in entries.iter().zip(cooldowns) {
+        ui.horizontal(|ui| {
+            ui.add_sized(
+                [60.0, 18.0],
+                egui::Label::new(egui::RichText::new(hotkey_key_label(entry.key)).strong()),
+            );
+            let text = if *left > 0.0 {
+                format!("{:.1}s", *left * entry.cooldown_ms as f32 / 1000.0)
+            } else {
+                tr!("rotation.ready").to_string()
+            };
+            ui.add(
+                egui::ProgressBar::new(*left)
+                    .desired_width(220.0)
+                    .text(text),
+            );
+        });
+    }
+}
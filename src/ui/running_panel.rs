@@ -0,0 +1,75 @@
+// "What's running" strip: every active tool with its status, elapsed time and a Stop button
+use eframe::egui;
+use std::time::Duration;
+
+/// A tool whose worker is running right now
+pub struct RunningTool<'a> {
+    pub tool_index: usize,
+    pub name: &'a str,
+    pub status: String,
+    pub elapsed: Option<Duration>,
+    pub is_paused: bool,
+}
+
+pub enum RunningPanelAction {
+    None,
+    Stop(usize),
+}
+
+/// "1h 02m 03s", or "2m 03s" under an hour
+pub fn format_elapsed(elapsed: Option<Duration>) -> String {
+    let Some(elapsed) = elapsed else {
+        return "-".to_string();
+    };
+    let secs = elapsed.as_secs();
+    if secs >= 3600 {
+        format!(
+            "{}h {:02}m {:02}s",
+            secs / 3600,
+            (secs / 60) % 60,
+            secs % 60
+        )
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
+
+pub fn render_running_panel(ui: &mut egui::Ui, running: &[RunningTool]) -> RunningPanelAction {
+    let mut action = RunningPanelAction::None;
+
+    egui::CollapsingHeader::new(
+        egui::RichText::new(tr!("running.title", count = running.len())).strong(),
+    )
+    .id_source("running_panel")
+    .default_open(true)
+    .show(ui, |ui| {
+        egui::Grid::new("running_tools")
+            .num_columns(4)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for tool in running {
+                    let name = if tool.is_paused {
+                        egui::RichText::new(format!("⏸ {}", tool.name))
+                            .color(egui::Color32::from_rgb(255, 165, 0))
+                    } else {
+                        egui::RichText::new(tool.name).color(egui::Color32::GREEN)
+                    };
+                    ui.label(name.strong());
+                    ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(&tool.status).color(egui::Color32::GRAY),
+                        )
+                        .truncate(true),
+                    )
+                    .on_hover_text(&tool.status);
+                    ui.label(format_elapsed(tool.elapsed));
+                    if ui.button(tr!("running.stop")).clicked() {
+                        action = RunningPanelAction::Stop(tool.tool_index);
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+
+    action
+}
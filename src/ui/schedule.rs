@@ -0,0 +1,94 @@
+use crate::core::scheduler::WEEKDAY_KEYS;
+use crate::settings::{ScheduleAction, ScheduleEntry};
+use eframe::egui;
+
+/// Collapsible "Schedule" section: one row per entry (tool or macro, time, days, start/stop)
+pub fn render_schedule_section(
+    ui: &mut egui::Ui,
+    schedule: &mut Vec<ScheduleEntry>,
+    tool_names: &[String],
+) {
+    egui::CollapsingHeader::new(
+        egui::RichText::new(tr!("schedule.title")).color(egui::Color32::from_rgb(180, 180, 180)),
+    )
+    .id_source("schedule_section")
+    .show(ui, |ui| {
+        ui.label(
+            egui::RichText::new(tr!("schedule.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+
+        let mut to_remove = None;
+        for (idx, entry) in schedule.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut entry.enabled, "");
+
+                egui::ComboBox::from_id_source(("schedule_action", idx))
+                    .selected_text(entry.action.label())
+                    .width(70.0)
+                    .show_ui(ui, |ui| {
+                        for action in ScheduleAction::ALL {
+                            ui.selectable_value(&mut entry.action, action, action.label());
+                        }
+                    });
+
+                let target_known = tool_names
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(&entry.target));
+                let selected = if entry.target.is_empty() {
+                    tr!("schedule.pick_tool").to_string()
+                } else {
+                    entry.target.clone()
+                };
+                let combo = egui::ComboBox::from_id_source(("schedule_target", idx))
+                    .selected_text(selected)
+                    .width(150.0)
+                    .show_ui(ui, |ui| {
+                        for name in tool_names {
+                            if ui.selectable_label(*name == entry.target, name).clicked() {
+                                entry.target = name.clone();
+                                // Bound to the macro of that name (if any) on the next sync
+                                entry.macro_id = None;
+                            }
+                        }
+                    });
+                if !target_known && !entry.target.is_empty() {
+                    combo.response.on_hover_text(tr!("schedule.unknown_tool"));
+                    ui.colored_label(egui::Color32::YELLOW, "⚠");
+                }
+
+                ui.label(tr!("schedule.at"));
+                ui.add(
+                    egui::DragValue::new(&mut entry.hour)
+                        .clamp_range(0..=23)
+                        .custom_formatter(|value, _| format!("{:02}", value as u8)),
+                );
+                ui.label(":");
+                ui.add(
+                    egui::DragValue::new(&mut entry.minute)
+                        .clamp_range(0..=59)
+                        .custom_formatter(|value, _| format!("{:02}", value as u8)),
+                );
+
+                for (day, key) in WEEKDAY_KEYS.iter().enumerate() {
+                    ui.toggle_value(&mut entry.days[day], crate::i18n::text(*key));
+                }
+
+                if ui.small_button("🗑").clicked() {
+                    to_remove = Some(idx);
+                }
+            });
+        }
+        if let Some(idx) = to_remove {
+            schedule.remove(idx);
+        }
+
+        if ui.button(tr!("schedule.add")).clicked() {
+            schedule.push(ScheduleEntry {
+                target: tool_names.first().cloned().unwrap_or_default(),
+                ..Default::default()
+            });
+        }
+    });
+}
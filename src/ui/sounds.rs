@@ -0,0 +1,93 @@
+use crate::core::sounds::{BuiltInSound, Event, EventSound};
+use eframe::egui;
+use std::collections::BTreeMap;
+
+/// Collapsible "Sounds" section: one row per event with its sound, a file field for
+/// custom sounds and a test button. Returns the event whose test button was clicked.
+pub fn render_sounds_section(
+    ui: &mut egui::Ui,
+    event_sounds: &mut BTreeMap<Event, EventSound>,
+) -> Option<Event> {
+    let mut test = None;
+
+    egui::CollapsingHeader::new(
+        egui::RichText::new(tr!("sounds.title")).color(egui::Color32::from_rgb(180, 180, 180)),
+    )
+    .id_source("sounds_section")
+    .show(ui, |ui| {
+        egui::Grid::new("sounds_grid")
+            .num_columns(3)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for event in Event::ALL {
+                    let mut sound = event_sounds.get(&event).cloned().unwrap_or_default();
+
+                    ui.label(event.label());
+
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source(("event_sound", event))
+                            .selected_text(sound_label(&sound))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut sound, EventSound::Off, tr!("sounds.off"));
+                                for builtin in BuiltInSound::ALL {
+                                    ui.selectable_value(
+                                        &mut sound,
+                                        EventSound::BuiltIn(builtin),
+                                        builtin.label(),
+                                    );
+                                }
+                                let is_file = matches!(sound, EventSound::File(_));
+                                if ui.selectable_label(is_file, tr!("sounds.file")).clicked()
+                                    && !is_file
+                                {
+                                    sound = EventSound::File(String::new());
+                                }
+                            });
+
+                        if let EventSound::File(path) = &mut sound {
+                            ui.add(
+                                egui::TextEdit::singleline(path)
+                                    .hint_text(tr!("sounds.file_hint"))
+                                    .desired_width(180.0),
+                            );
+                            if ui.button(tr!("image_path.browse")).clicked() {
+                                if let Some(picked) = rfd::FileDialog::new()
+                                    .add_filter(tr!("sounds.file_filter"), &["wav"])
+                                    .set_title(event.label())
+                                    .pick_file()
+                                {
+                                    *path = picked.display().to_string();
+                                }
+                            }
+                        }
+                    });
+
+                    if ui
+                        .add_enabled(sound != EventSound::Off, egui::Button::new("▶"))
+                        .on_hover_text(tr!("sounds.test_hint"))
+                        .clicked()
+                    {
+                        test = Some(event);
+                    }
+                    ui.end_row();
+
+                    // Off is the default, so it isn't written to the settings file
+                    if sound == EventSound::Off {
+                        event_sounds.remove(&event);
+                    } else {
+                        event_sounds.insert(event, sound);
+                    }
+                }
+            });
+    });
+
+    test
+}
+
+fn sound_label(sound: &EventSound) -> &'static str {
+    match sound {
+        EventSound::Off => tr!("sounds.off"),
+        EventSound::BuiltIn(builtin) => builtin.label(),
+        EventSound::File(_) => tr!("sounds.file"),
+    }
+}
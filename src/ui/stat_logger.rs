@@ -0,0 +1,299 @@
+use crate::calibration::MagnifierFrame;
+use crate::core::stat_history::{StatHistory, StatSample};
+use crate::core::worker::WorkerStats;
+use crate::settings::{StatLoggerSettings, OCR_SCALE_RANGE, STAT_LOG_INTERVAL_RANGE_SECS};
+use crate::ui::coord_edit::rect_value;
+use crate::ui::magnifier::render_magnifier;
+use crate::ui::reset_button::reset_to_defaults_button;
+use eframe::egui;
+
+// Size of the history plot
+const PLOT_HEIGHT: f32 = 120.0;
+
+#[derive(Debug)]
+pub enum StatLoggerUiAction {
+    StartRegionCalibration,
+    CancelCalibration,
+    ResetToDefaults,
+    Start,
+    Stop,
+    None,
+}
+
+/// Render Stat Logger UI
+pub fn render_ui(
+    ui: &mut egui::Ui,
+    settings: &mut StatLoggerSettings,
+    history: &StatHistory,
+    show_regions: &mut bool,
+    is_calibrating: bool,
+    is_waiting_for_second_click: bool,
+    magnifier: Option<&MagnifierFrame>,
+    is_running: bool,
+    status: &str,
+    stats: &WorkerStats,
+    game_connected: bool,
+    client_size: Option<(i32, i32)>,
+    hotkey_error: Option<&str>,
+) -> StatLoggerUiAction {
+    let mut action = StatLoggerUiAction::None;
+
+    if !game_connected {
+        ui.colored_label(egui::Color32::RED, tr!("common.connect_first"));
+        return StatLoggerUiAction::None;
+    }
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.show_in_overlay, tr!("common.show_in_overlay"));
+        ui.checkbox(show_regions, tr!("common.show_regions"))
+            .on_hover_text(tr!("stat_logger.show_regions_hint"));
+        if reset_to_defaults_button(ui, "stat_logger_reset") {
+            action = StatLoggerUiAction::ResetToDefaults;
+        }
+    });
+    ui.add_space(8.0);
+
+    // 1. Settings Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("common.configuration"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        ui.label(
+            egui::RichText::new(tr!("stat_logger.hint"))
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("stat_logger.region")).strong());
+
+            if let Some(region) = settings.region.as_mut() {
+                let (left, top, width, height) = *region;
+                rect_value(
+                    ui,
+                    "stat_logger_region",
+                    egui::RichText::new(format!(
+                        "({:.3}, {:.3}, {:.3}x{:.3})",
+                        left, top, width, height
+                    ))
+                    .monospace()
+                    .strong(),
+                    region,
+                    client_size,
+                );
+            } else {
+                ui.label(
+                    egui::RichText::new(tr!("common.not_set"))
+                        .color(egui::Color32::YELLOW)
+                        .italics(),
+                );
+            }
+
+            ui.separator();
+
+            if is_calibrating {
+                if ui
+                    .button(
+                        egui::RichText::new(tr!("common.stop"))
+                            .color(egui::Color32::from_rgb(255, 100, 100)),
+                    )
+                    .clicked()
+                {
+                    action = StatLoggerUiAction::CancelCalibration;
+                }
+                let label = if is_waiting_for_second_click {
+                    tr!("calibration.click_bottom_right")
+                } else {
+                    tr!("calibration.click_top_left")
+                };
+                ui.label(egui::RichText::new(label).color(egui::Color32::YELLOW));
+            } else if ui.button(tr!("image_clicker.set_region")).clicked() {
+                action = StatLoggerUiAction::StartRegionCalibration;
+            }
+        });
+
+        if let Some(frame) = magnifier {
+            ui.add_space(4.0);
+            render_magnifier(ui, frame);
+        }
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(tr!("stat_logger.interval")).strong());
+            ui.add(
+                egui::DragValue::new(&mut settings.interval_secs)
+                    .clamp_range(STAT_LOG_INTERVAL_RANGE_SECS.0..=STAT_LOG_INTERVAL_RANGE_SECS.1)
+                    .suffix(" s"),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("stat_logger.scale"));
+            ui.add(
+                egui::DragValue::new(&mut settings.scale_factor)
+                    .clamp_range(OCR_SCALE_RANGE.0..=OCR_SCALE_RANGE.1)
+                    .suffix("x"),
+            );
+            ui.checkbox(
+                &mut settings.grayscale,
+                tr!("custom_macro.preset_grayscale"),
+            );
+            ui.checkbox(
+                &mut settings.invert_colors,
+                tr!("custom_macro.preset_invert"),
+            );
+        });
+
+        ui.checkbox(&mut settings.write_csv, tr!("stat_logger.write_csv"))
+            .on_hover_text(tr!("stat_logger.write_csv_hint"));
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 2. Session Group
+    ui.group(|ui| {
+        ui.heading(
+            egui::RichText::new(tr!("stat_logger.session"))
+                .size(14.0)
+                .strong(),
+        );
+        ui.add_space(4.0);
+
+        if let (Some(latest), Some(delta)) = (history.latest(), history.delta()) {
+            ui.horizontal(|ui| {
+                ui.label(tr!("stat_logger.latest"));
+                ui.label(
+                    egui::RichText::new(latest.value.to_string())
+                        .monospace()
+                        .strong(),
+                );
+                ui.separator();
+                ui.label(tr!("stat_logger.delta"));
+                let color = if delta < 0 {
+                    egui::Color32::from_rgb(255, 100, 100)
+                } else {
+                    egui::Color32::from_rgb(100, 255, 100)
+                };
+                ui.label(
+                    egui::RichText::new(format!("{:+}", delta))
+                        .monospace()
+                        .strong()
+                        .color(color),
+                );
+            });
+            ui.add_space(4.0);
+            render_history_plot(ui, history.samples());
+        } else {
+            ui.label(
+                egui::RichText::new(tr!("stat_logger.no_readings"))
+                    .color(egui::Color32::GRAY)
+                    .italics(),
+            );
+        }
+
+        ui.add_space(4.0);
+    });
+
+    ui.add_space(12.0);
+
+    // 3. Controls
+    ui.vertical_centered(|ui| {
+        let (btn_text, btn_color) = if is_running {
+            (tr!("common.stop"), egui::Color32::from_rgb(255, 100, 100))
+        } else {
+            (tr!("common.start"), egui::Color32::from_rgb(100, 255, 100))
+        };
+
+        let button = egui::Button::new(egui::RichText::new(btn_text).size(16.0).color(btn_color))
+            .min_size(egui::vec2(200.0, 35.0));
+
+        if ui.add(button).clicked() {
+            action = if is_running {
+                StatLoggerUiAction::Stop
+            } else {
+                StatLoggerUiAction::Start
+            };
+        }
+    });
+
+    ui.add_space(12.0);
+    ui.separator();
+    ui.add_space(6.0);
+
+    // 4. Status
+    crate::ui::status::render_status(ui, status, stats, hotkey_error);
+
+    action
+}
+
+/// Line of the readings over the run, scaled to their own range, with the lowest and
+/// highest value at the left edge
+fn render_history_plot(ui: &mut egui::Ui, samples: &[StatSample]) {
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), PLOT_HEIGHT),
+        egui::Sense::hover(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_stroke(
+        rect,
+        2.0,
+        egui::Stroke::new(1.0, egui::Color32::from_rgb(90, 90, 90)),
+    );
+
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+        return;
+    };
+    let low = samples.iter().map(|s| s.value).min().unwrap_or(0);
+    let high = samples.iter().map(|s| s.value).max().unwrap_or(0);
+    let span_secs = (last.elapsed_secs - first.elapsed_secs).max(f64::EPSILON);
+    let plot = rect.shrink(6.0);
+    let points: Vec<egui::Pos2> = samples
+        .iter()
+        .map(|sample| {
+            let x = (sample.elapsed_secs - first.elapsed_secs) / span_secs;
+            // A flat series is drawn through the middle
+            let y = if high > low {
+                (sample.value - low) as f64 / (high - low) as f64
+            } else {
+                0.5
+            };
+            egui::pos2(
+                plot.left() + x as f32 * plot.width(),
+                plot.bottom() - y as f32 * plot.height(),
+            )
+        })
+        .collect();
+
+    if points.len() > 1 {
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.5, egui::Color32::from_rgb(100, 200, 255)),
+        ));
+    } else {
+        painter.circle_filled(points[0], 2.5, egui::Color32::from_rgb(100, 200, 255));
+    }
+
+    let font = egui::FontId::monospace(10.0);
+    painter.text(
+        plot.left_top(),
+        egui::Align2::LEFT_TOP,
+        high.to_string(),
+        font.clone(),
+        egui::Color32::GRAY,
+    );
+    painter.text(
+        plot.left_bottom(),
+        egui::Align2::LEFT_BOTTOM,
+        low.to_string(),
+        font,
+        egui::Color32::GRAY,
+    );
+}
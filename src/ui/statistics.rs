@@ -0,0 +1,80 @@
+// Lifetime statistics window: read-only totals per tool
+use crate::settings::{items_label, ToolUsage, UsageStats};
+use eframe::egui;
+
+pub enum StatisticsAction {
+    None,
+    Reset(String),
+}
+
+/// One table per tool: current tools first (in tab order), then tools that no longer exist
+pub fn render_statistics(
+    ui: &mut egui::Ui,
+    usage: &UsageStats,
+    tool_names: &[String],
+) -> StatisticsAction {
+    let mut action = StatisticsAction::None;
+
+    ui.label(
+        egui::RichText::new("Totals over every finished run, kept across sessions.")
+            .color(egui::Color32::GRAY),
+    );
+    ui.add_space(6.0);
+
+    let empty = ToolUsage::default();
+    let former: Vec<&String> = usage
+        .tools
+        .keys()
+        .filter(|name| !tool_names.contains(name))
+        .collect();
+
+    for name in tool_names.iter().chain(former) {
+        let totals = usage.tools.get(name).unwrap_or(&empty);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(name).strong());
+            if !tool_names.contains(name) {
+                ui.label(egui::RichText::new("(removed or renamed)").color(egui::Color32::GRAY));
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let reset =
+                    ui.add_enabled(usage.tools.contains_key(name), egui::Button::new("Reset"));
+                if reset.clicked() {
+                    action = StatisticsAction::Reset(name.clone());
+                }
+            });
+        });
+
+        egui::Grid::new(("usage_stats", name))
+            .num_columns(2)
+            .spacing([24.0, 2.0])
+            .show(ui, |ui| {
+                ui.label("Runs");
+                ui.label(totals.runs.to_string());
+                ui.end_row();
+                ui.label("Runtime");
+                ui.label(format_runtime(totals.runtime_secs));
+                ui.end_row();
+                ui.label("Loops");
+                ui.label(totals.iterations.to_string());
+                ui.end_row();
+                ui.label(items_label(name));
+                ui.label(totals.items.to_string());
+                ui.end_row();
+                ui.label("Runs with errors");
+                ui.label(totals.runs_with_errors.to_string());
+                ui.end_row();
+            });
+        ui.separator();
+    }
+
+    action
+}
+
+fn format_runtime(secs: u64) -> String {
+    format!(
+        "{}h {:02}m {:02}s",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
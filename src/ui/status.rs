@@ -1,12 +1,60 @@
+use crate::core::clock::format_hms;
+use crate::core::worker::WorkerStats;
+use crate::settings::MAX_RUNTIME_RANGE_MIN;
 use eframe::egui;
+use std::time::Duration;
 
-pub fn render_status(ui: &mut egui::Ui, status: &str, hotkey_error: Option<&str>) {
+/// Status lines reporting a problem (shown in red)
+pub fn is_error_status(status: &str) -> bool {
+    status.contains("Error") || status.contains("Failed")
+}
+
+/// "Running for 00:42:13" (and the time limit, when set) while a run is going
+pub fn render_run_timer(ui: &mut egui::Ui, stats: &WorkerStats, max_runtime_minutes: Option<u32>) {
+    let Some(elapsed) = stats.elapsed().filter(|_| stats.finished_at.is_none()) else {
+        return;
+    };
+    let mut text = tr!("status.running_for", time = format_hms(elapsed));
+    if let Some(minutes) = max_runtime_minutes {
+        let limit = Duration::from_secs(u64::from(minutes) * 60);
+        text.push_str(&tr!("status.time_limit_of", time = format_hms(limit)));
+    }
+    ui.label(egui::RichText::new(text).monospace());
+}
+
+/// "Stop after [N] min" checkbox and minutes field for a tool's max runtime
+pub fn max_runtime_field(ui: &mut egui::Ui, max_runtime_minutes: &mut Option<u32>) {
     ui.horizontal(|ui| {
-        ui.label(egui::RichText::new("Status:").strong());
+        let mut enabled = max_runtime_minutes.is_some();
+        if ui
+            .checkbox(&mut enabled, tr!("common.max_runtime"))
+            .on_hover_text(tr!("common.max_runtime_hint"))
+            .changed()
+        {
+            *max_runtime_minutes = enabled.then_some(60);
+        }
+        if let Some(minutes) = max_runtime_minutes {
+            ui.add(
+                egui::DragValue::new(minutes)
+                    .clamp_range(MAX_RUNTIME_RANGE_MIN.0..=MAX_RUNTIME_RANGE_MIN.1)
+                    .suffix(" min"),
+            );
+        }
+    });
+}
+
+pub fn render_status(
+    ui: &mut egui::Ui,
+    status: &str,
+    stats: &WorkerStats,
+    hotkey_error: Option<&str>,
+) {
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr!("status.label")).strong());
 
         let status_color = if status.contains("Running") || status.contains("Active") {
             egui::Color32::from_rgb(100, 255, 100)
-        } else if status.contains("Error") || status.contains("Failed") {
+        } else if is_error_status(status) {
             egui::Color32::from_rgb(255, 100, 100)
         } else {
             egui::Color32::GRAY
@@ -15,16 +63,54 @@ pub fn render_status(ui: &mut egui::Ui, status: &str, hotkey_error: Option<&str>
         ui.label(egui::RichText::new(status).color(status_color));
     });
 
+    // Compact run statistics, once the tool has been started
+    if let Some(elapsed) = stats.elapsed() {
+        let secs = elapsed.as_secs();
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(tr!(
+                    "status.run_stats",
+                    minutes = secs / 60,
+                    seconds = format!("{:02}", secs % 60),
+                    iterations = stats.iterations,
+                    items = stats.items_processed,
+                ))
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            if stats.skipped_clicks > 0 {
+                ui.label(
+                    egui::RichText::new(tr!("status.skipped_clicks", count = stats.skipped_clicks))
+                        .small()
+                        .color(egui::Color32::YELLOW),
+                )
+                .on_hover_text(tr!("status.skipped_clicks_hint"));
+            }
+            if let Some(err) = &stats.last_error {
+                let label = ui.label(
+                    egui::RichText::new(tr!("status.last_error", error = err))
+                        .small()
+                        .color(egui::Color32::from_rgb(255, 100, 100)),
+                );
+                if let Some(error) = &stats.helper_error {
+                    label.on_hover_text(error.hint());
+                }
+            }
+        });
+    }
+
     if let Some(err) = hotkey_error {
-        let full = format!("Hotkey error: {}", err);
-        let shortened = if full.len() > 80 {
-            format!("{}...", &full[..77])
+        let full = tr!("status.hotkey_error", error = err);
+        // Counted in characters: translations and OS messages aren't ASCII
+        let too_long = full.chars().count() > 80;
+        let shortened = if too_long {
+            format!("{}...", full.chars().take(77).collect::<String>())
         } else {
             full.clone()
         };
         let label = egui::RichText::new(shortened).color(egui::Color32::from_rgb(200, 120, 120));
         let response = ui.label(label);
-        if full.len() > 80 {
+        if too_long {
             response.on_hover_text(full);
         }
     }